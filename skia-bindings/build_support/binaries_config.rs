@@ -14,6 +14,8 @@ pub mod lib {
     pub const SK_PARAGRAPH: &str = "skparagraph";
     pub const SVG: &str = "svg";
     pub const SK_RESOURCES: &str = "skresources";
+    pub const SKOTTIE: &str = "skottie";
+    pub const SK_SG: &str = "sksg";
     pub const SK_UNICODE: &str = "skunicode";
 }
 
@@ -58,7 +60,7 @@ impl BinariesConfiguration {
         let feature_ids = features.ids();
 
         if features.text_layout {
-            if target.is_windows() {
+            if target.is_windows() && !features.unicode_client {
                 additional_files.push(ICUDTL_DAT.into());
             }
             ninja_built_libraries.push(lib::SK_PARAGRAPH.into());
@@ -68,6 +70,12 @@ impl BinariesConfiguration {
         }
         if features.svg {
             ninja_built_libraries.push(lib::SVG.into());
+        }
+        if features.skottie {
+            ninja_built_libraries.push(lib::SKOTTIE.into());
+            ninja_built_libraries.push(lib::SK_SG.into());
+        }
+        if features.resources() {
             ninja_built_libraries.push(lib::SK_RESOURCES.into());
         }
 
@@ -23,21 +23,39 @@ pub struct Features {
     /// Build with Direct3D support?
     pub d3d: bool,
 
+    /// Build with Dawn (WebGPU) support?
+    pub dawn: bool,
+
     /// Features related to text layout. Modules skshaper and skparagraph.
     pub text_layout: bool,
 
+    /// Use break positions supplied by the client instead of ICU for text layout.
+    pub unicode_client: bool,
+
     /// Support for rendering SVG.
     pub svg: bool,
 
+    /// Support for rendering Lottie animations with Skottie.
+    pub skottie: bool,
+
     /// Support the encoding of bitmap data to the WEBP image format.
     pub webp_encode: bool,
 
     /// Support the decoding of the WEBP image format to bitmap data.
     pub webp_decode: bool,
 
+    /// Support the decoding of the JPEG XL image format to bitmap data.
+    pub jpegxl_decode: bool,
+
+    /// Decode HEIF images with the platform's decoders on Android and Windows.
+    pub platform_codecs: bool,
+
     /// Build with FreeType embedded.
     pub embed_freetype: bool,
 
+    /// Bindings that depend on Skia's private headers.
+    pub unstable: bool,
+
     /// Build with animation support (yet unsupported, no wrappers).
     pub animation: bool,
 
@@ -59,11 +77,17 @@ impl Default for Features {
             vulkan: cfg!(feature = "vulkan"),
             metal: cfg!(feature = "metal"),
             d3d: cfg!(feature = "d3d"),
+            dawn: cfg!(feature = "dawn"),
             text_layout: cfg!(feature = "textlayout"),
+            unicode_client: cfg!(feature = "unicode-client"),
             svg: cfg!(feature = "svg"),
+            skottie: cfg!(feature = "skottie"),
             webp_encode: cfg!(feature = "webp-encode"),
             webp_decode: cfg!(feature = "webp-decode"),
+            jpegxl_decode: cfg!(feature = "jpegxl-decode"),
+            platform_codecs: cfg!(feature = "platform-codecs"),
             embed_freetype: cfg!(feature = "embed-freetype"),
+            unstable: cfg!(feature = "unstable"),
             animation: false,
             dng: false,
             particles: false,
@@ -73,7 +97,12 @@ impl Default for Features {
 
 impl Features {
     pub fn gpu(&self) -> bool {
-        self.gl || self.vulkan || self.metal || self.d3d
+        self.gl || self.vulkan || self.metal || self.d3d || self.dawn
+    }
+
+    /// The skresources module is used by `svg` and `skottie`.
+    pub fn resources(&self) -> bool {
+        self.svg || self.skottie
     }
 
     /// Feature Ids used to look up prebuilt binaries.
@@ -101,21 +130,39 @@ impl Features {
         if self.d3d {
             feature_ids.push(feature_id::D3D);
         }
+        if self.dawn {
+            feature_ids.push(feature_id::DAWN);
+        }
         if self.text_layout {
             feature_ids.push(feature_id::TEXTLAYOUT);
         }
+        if self.unicode_client {
+            feature_ids.push(feature_id::UNICODE_CLIENT);
+        }
         if self.svg {
             feature_ids.push(feature_id::SVG);
         }
+        if self.skottie {
+            feature_ids.push(feature_id::SKOTTIE);
+        }
         if self.webp_encode {
             feature_ids.push(feature_id::WEBPE);
         }
         if self.webp_decode {
             feature_ids.push(feature_id::WEBPD);
         }
+        if self.jpegxl_decode {
+            feature_ids.push(feature_id::JPEGXLD);
+        }
+        if self.platform_codecs {
+            feature_ids.push(feature_id::PLATFORM_CODECS);
+        }
         if self.embed_freetype {
             feature_ids.push(feature_id::EMBED_FREETYPE);
         }
+        if self.unstable {
+            feature_ids.push(feature_id::UNSTABLE);
+        }
 
         feature_ids.into_iter().collect()
     }
@@ -127,12 +174,18 @@ mod feature_id {
     pub const VULKAN: &str = "vulkan";
     pub const METAL: &str = "metal";
     pub const D3D: &str = "d3d";
+    pub const DAWN: &str = "dawn";
     pub const TEXTLAYOUT: &str = "textlayout";
+    pub const UNICODE_CLIENT: &str = "unicodeclient";
     pub const SVG: &str = "svg";
+    pub const SKOTTIE: &str = "skottie";
     pub const WEBPE: &str = "webpe";
     pub const WEBPD: &str = "webpd";
+    pub const JPEGXLD: &str = "jpegxld";
+    pub const PLATFORM_CODECS: &str = "platformcodecs";
     pub const EGL: &str = "egl";
     pub const X11: &str = "x11";
     pub const WAYLAND: &str = "wayland";
     pub const EMBED_FREETYPE: &str = "freetype";
+    pub const UNSTABLE: &str = "unstable";
 }
@@ -23,9 +23,13 @@ pub fn gn_args(config: &BuildConfiguration, mut builder: GnArgsBuilder) -> Vec<(
     builder.into_gn_args()
 }
 
-pub fn bindgen_and_cc_args(target: &Target, sysroot: Option<&str>) -> (Vec<String>, Vec<String>) {
+pub fn bindgen_and_cc_args(
+    target: &Target,
+    features: &Features,
+    sysroot: Option<&str>,
+) -> (Vec<String>, Vec<String>) {
     let mut builder = BindgenArgsBuilder::new(sysroot);
-    details(target).bindgen_args(target, &mut builder);
+    details(target).bindgen_args(target, features, &mut builder);
     builder.into_bindgen_and_cc_args()
 }
 
@@ -35,7 +39,13 @@ pub fn link_libraries(features: &Features, target: &Target) -> Vec<String> {
 
 pub trait PlatformDetails {
     fn gn_args(&self, config: &BuildConfiguration, builder: &mut GnArgsBuilder);
-    fn bindgen_args(&self, _target: &Target, _builder: &mut BindgenArgsBuilder) {}
+    fn bindgen_args(
+        &self,
+        _target: &Target,
+        _features: &Features,
+        _builder: &mut BindgenArgsBuilder,
+    ) {
+    }
     fn link_libraries(&self, features: &Features) -> Vec<String>;
 }
 
@@ -10,7 +10,12 @@ impl PlatformDetails for Musl {
         builder.cflags(flags(target));
     }
 
-    fn bindgen_args(&self, target: &Target, builder: &mut BindgenArgsBuilder) {
+    fn bindgen_args(
+        &self,
+        target: &Target,
+        _features: &Features,
+        builder: &mut BindgenArgsBuilder,
+    ) {
         builder.args(flags(target))
     }
 
@@ -8,18 +8,35 @@ pub struct Android;
 /// API level Android 8, Oreo (the first one with full Vulkan support)
 const API_LEVEL: &str = "26";
 
+/// API level Android 11, the first one with `AImageDecoder`, which is required by the
+/// `platform-codecs` feature.
+const PLATFORM_CODECS_API_LEVEL: &str = "30";
+
+fn api_level(features: &Features) -> &'static str {
+    if features.platform_codecs {
+        PLATFORM_CODECS_API_LEVEL
+    } else {
+        API_LEVEL
+    }
+}
+
 impl PlatformDetails for Android {
     fn gn_args(&self, config: &BuildConfiguration, builder: &mut GnArgsBuilder) {
         // TODO: this may belong into BuildConfiguration
         let (arch, _) = config.target.arch_abi();
         let ndk = ndk();
+        let api_level = api_level(&config.features);
 
         builder
             .arg("ndk", quote(&ndk))
-            .arg("ndk_api", API_LEVEL)
+            .arg("ndk_api", api_level)
             .arg("target_cpu", quote(clang::target_arch(arch)))
             .arg("skia_enable_fontmgr_android", yes());
 
+        if config.features.platform_codecs {
+            builder.arg("skia_use_ndk_images", yes());
+        }
+
         if !config.features.embed_freetype {
             builder.arg(
                 "skia_use_system_freetype2",
@@ -28,7 +45,7 @@ impl PlatformDetails for Android {
         }
 
         let major = ndk_major_version(Path::new(&ndk));
-        let mut extra_skia_cflags = extra_skia_cflags();
+        let mut extra_skia_cflags = extra_skia_cflags(api_level);
 
         // Version 23 is the first version using llvm 12
         // https://github.com/android/ndk/wiki/Changelog-r23#r23b
@@ -40,10 +57,11 @@ impl PlatformDetails for Android {
         builder.cflags(extra_skia_cflags);
     }
 
-    fn bindgen_args(&self, target: &Target, builder: &mut BindgenArgsBuilder) {
+    fn bindgen_args(&self, target: &Target, features: &Features, builder: &mut BindgenArgsBuilder) {
         builder.args(additional_clang_args(
             &target.to_string(),
             &target.architecture,
+            api_level(features),
         ));
     }
 
@@ -92,7 +110,7 @@ fn ndk_major_version(ndk_dir: &Path) -> u32 {
     captures[1].parse().expect("could not parse major version")
 }
 
-pub fn additional_clang_args(target: &str, target_arch: &str) -> Vec<String> {
+pub fn additional_clang_args(target: &str, target_arch: &str, api_level: &str) -> Vec<String> {
     let mut args: Vec<String> = Vec::new();
 
     match target_arch {
@@ -119,12 +137,12 @@ pub fn additional_clang_args(target: &str, target_arch: &str) -> Vec<String> {
     args.push(format!("-I{ndk}/sources/android/cpufeatures"));
 
     args.push(format!("--target={target}"));
-    args.extend(extra_skia_cflags());
+    args.extend(extra_skia_cflags(api_level));
     args
 }
 
-pub fn extra_skia_cflags() -> Vec<String> {
-    vec![format!("-D__ANDROID_API__={API_LEVEL}")]
+pub fn extra_skia_cflags(api_level: &str) -> Vec<String> {
+    vec![format!("-D__ANDROID_API__={api_level}")]
 }
 
 pub fn link_libraries(features: &Features) -> Vec<&str> {
@@ -132,5 +150,8 @@ pub fn link_libraries(features: &Features) -> Vec<&str> {
     if features.gl {
         libs.extend(vec!["EGL", "GLESv2"])
     };
+    if features.platform_codecs {
+        libs.push("jnigraphics");
+    }
     libs
 }
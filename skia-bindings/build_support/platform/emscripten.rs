@@ -24,7 +24,12 @@ impl PlatformDetails for Emscripten {
             .arg("skia_enable_fontmgr_custom_empty", yes());
     }
 
-    fn bindgen_args(&self, _target: &cargo::Target, builder: &mut BindgenArgsBuilder) {
+    fn bindgen_args(
+        &self,
+        _target: &cargo::Target,
+        _features: &Features,
+        builder: &mut BindgenArgsBuilder,
+    ) {
         builder.arg("-nobuiltininc");
 
         // visibility=default, otherwise some types may be missing:
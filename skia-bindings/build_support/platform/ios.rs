@@ -26,7 +26,12 @@ impl PlatformDetails for Ios {
         }
     }
 
-    fn bindgen_args(&self, target: &Target, builder: &mut BindgenArgsBuilder) {
+    fn bindgen_args(
+        &self,
+        target: &Target,
+        _features: &Features,
+        builder: &mut BindgenArgsBuilder,
+    ) {
         builder.args(additional_clang_args(
             &target.architecture,
             target.abi.as_deref(),
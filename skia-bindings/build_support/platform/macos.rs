@@ -21,7 +21,12 @@ impl PlatformDetails for MacOs {
         builder.cflags(flags());
     }
 
-    fn bindgen_args(&self, _target: &Target, builder: &mut BindgenArgsBuilder) {
+    fn bindgen_args(
+        &self,
+        _target: &Target,
+        _features: &Features,
+        builder: &mut BindgenArgsBuilder,
+    ) {
         // macOS uses `-isysroot/path/to/sysroot`, but this doesn't appear
         // to work for other targets. `--sysroot=` works for all targets,
         // to my knowledge, but doesn't seem to be idiomatic for macOS
@@ -57,6 +62,17 @@ impl PlatformDetails for MacOs {
             libs.push("framework=MetalKit");
             libs.push("framework=Foundation");
         }
+        // Dawn uses Metal on macOS.
+        if features.dawn {
+            if !features.metal {
+                libs.extend(["framework=Metal", "framework=Foundation"]);
+            }
+            libs.extend([
+                "framework=QuartzCore",
+                "framework=IOSurface",
+                "framework=IOKit",
+            ]);
+        }
 
         libs.iter().map(|l| l.to_string()).collect()
     }
@@ -75,9 +75,13 @@ fn generic_link_libraries(features: &Features) -> Vec<String> {
     if features.gl {
         libs.push("opengl32");
     }
-    if features.d3d {
+    // Dawn uses Direct3D 12 on Windows.
+    if features.d3d || features.dawn {
         libs.extend(["d3d12", "dxgi", "d3dcompiler"]);
     }
+    if features.platform_codecs {
+        libs.push("windowscodecs");
+    }
 
     libs.iter().map(|l| l.to_string()).collect()
 }
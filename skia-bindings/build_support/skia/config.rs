@@ -117,7 +117,7 @@ impl FinalBuildConfiguration {
                 .arg("is_debug", yes_if(build.skia_debug))
                 .arg("skia_enable_svg", yes_if(features.svg))
                 .arg("skia_enable_gpu", yes_if(features.gpu()))
-                .arg("skia_enable_skottie", no());
+                .arg("skia_enable_skottie", yes_if(features.skottie));
 
             // Always enable PDF document support, because it gets switched off for WASM builds.
             // See <https://github.com/rust-skia/rust-skia/issues/694>
@@ -129,6 +129,7 @@ impl FinalBuildConfiguration {
                 .arg("skia_use_system_libpng", yes_if(use_system_libraries))
                 .arg("skia_use_libwebp_encode", yes_if(features.webp_encode))
                 .arg("skia_use_libwebp_decode", yes_if(features.webp_decode))
+                .arg("skia_use_libjxl_decode", yes_if(features.jpegxl_decode))
                 .arg("skia_use_system_zlib", yes_if(use_system_libraries))
                 .arg("skia_use_xps", no())
                 .arg("skia_use_dng_sdk", yes_if(features.dng))
@@ -149,6 +150,10 @@ impl FinalBuildConfiguration {
                 builder.arg("skia_use_direct3d", yes());
             }
 
+            if features.dawn {
+                builder.arg("skia_use_dawn", yes());
+            }
+
             // further flags that limit the components of Skia debug builds.
             if build.skia_debug {
                 builder
@@ -162,8 +167,9 @@ impl FinalBuildConfiguration {
             if features.text_layout {
                 builder
                     .arg("skia_enable_skshaper", yes())
-                    .arg("skia_use_icu", yes())
+                    .arg("skia_use_icu", yes_if(!features.unicode_client))
                     .arg("skia_use_system_icu", yes_if(use_system_libraries))
+                    .arg("skia_use_client_icu", yes_if(features.unicode_client))
                     .arg("skia_use_harfbuzz", yes())
                     .arg("skia_pdf_subset_harfbuzz", yes())
                     .arg("skia_use_system_harfbuzz", yes_if(use_system_libraries))
@@ -22,6 +22,12 @@ pub struct Configuration {
 
     /// Further definitions needed for build consistency.
     pub definitions: Definitions,
+
+    /// Add the Dawn headers, some of which are generated, to the include paths.
+    pub dawn: bool,
+
+    /// The features the bindings are built with.
+    pub features: features::Features,
 }
 
 impl Configuration {
@@ -44,15 +50,33 @@ impl Configuration {
             if features.d3d {
                 sources.push("src/d3d.cpp".into());
             }
+            if features.dawn {
+                sources.push("src/dawn.cpp".into());
+            }
             if features.gpu() {
                 sources.push("src/gpu.cpp".into());
             }
             if features.text_layout {
                 sources.extend(vec!["src/shaper.cpp".into(), "src/paragraph.cpp".into()]);
             }
+            if features.resources() {
+                sources.push("src/resources.cpp".into());
+            }
             if features.svg {
                 sources.push("src/svg.cpp".into());
             }
+            if features.skottie {
+                sources.push("src/skottie.cpp".into());
+            }
+            if features.webp_encode {
+                sources.push("src/webp.cpp".into());
+            }
+            if features.platform_codecs {
+                sources.push("src/platform_codecs.cpp".into());
+            }
+            if features.unstable {
+                sources.push("src/unstable.cpp".into());
+            }
             sources
         };
 
@@ -60,6 +84,8 @@ impl Configuration {
             skia_source_dir: skia_source_dir.into(),
             binding_sources,
             definitions,
+            dawn: features.dawn,
+            features: features.clone(),
         }
     }
 }
@@ -180,6 +206,16 @@ pub fn generate_bindings(
     bindgen_args.push(format!("-I{}", include_path.display()));
     cc_build.include(include_path);
 
+    if build.dawn {
+        for dawn_include_path in [
+            include_path.join("third_party/externals/dawn/include"),
+            output_directory.join("gen/third_party/externals/dawn/include"),
+        ] {
+            bindgen_args.push(format!("-I{}", dawn_include_path.display()));
+            cc_build.include(dawn_include_path);
+        }
+    }
+
     for (name, value) in &build.definitions {
         match value {
             Some(value) => {
@@ -211,7 +247,7 @@ pub fn generate_bindings(
 
     // Platform specific arguments and flags.
     {
-        let (bindgen, cc) = platform::bindgen_and_cc_args(&target, sysroot);
+        let (bindgen, cc) = platform::bindgen_and_cc_args(&target, &build.features, sysroot);
         bindgen_args.extend(bindgen);
         cc_args.extend(cc);
     }
@@ -362,6 +398,7 @@ const OPAQUE_TYPES: &[&str] = &[
     "SkTypeface_LocalizedStrings",
     "SkWStream",
     "GrVkMemoryAllocator",
+    "GrVkSecondaryCBDrawContext",
     "SkShaper",
     "SkShaper_BiDiRunIterator",
     "SkShaper_FontRunIterator",
@@ -406,6 +443,10 @@ const OPAQUE_TYPES: &[&str] = &[
     // Feature `svg`:
     "SkSVGNode",
     "skresources::ResourceProvider",
+    // Feature `skottie`:
+    "skottie::Animation",
+    "skottie::PropertyHandle",
+    "skottie::.*PropertyHandle",
     // m107 (layout failure)
     "skgpu::VulkanMemoryAllocator",
     // m109 (ParagraphPainter::SkPaintOrID)
@@ -521,6 +562,8 @@ const ENUM_TABLE: &[EnumEntry] = &[
     ("BitDepth", rewrite::k_xxx),
     ("CachingHint", rewrite::k_xxx_name),
     ("CompressionType", rewrite::k_xxx),
+    ("RescaleGamma", rewrite::k_xxx),
+    ("RescaleMode", rewrite::k_xxx),
     // SkImageFilter_MapDirection
     ("MapDirection", rewrite::k_xxx_name),
     // SkCodec_Result
@@ -547,6 +590,8 @@ const ENUM_TABLE: &[EnumEntry] = &[
     // SkTextUtils_Align
     // We need name_opt to cover SkSVGPreserveAspectRatio_Align
     ("Align", rewrite::k_xxx_name_opt),
+    // SkTraceMemoryDump_LevelOfDetail
+    ("LevelOfDetail", rewrite::k_xxx_name),
     // SkTrimPathEffect_Mode
     ("Mode", rewrite::k_xxx),
     // SkTypeface_SerializeBehavior
@@ -585,6 +630,11 @@ const ENUM_TABLE: &[EnumEntry] = &[
     ("TextDecorationMode", rewrite::k_xxx),
     ("StyleType", rewrite::k_xxx),
     //
+    // SkUnicode.h
+    //
+    ("BreakType", rewrite::k_xxx),
+    ("LineBreakType", rewrite::k_xxx),
+    //
     // Vk*
     //
     ("VkChromaLocation", rewrite::vk),
@@ -630,13 +680,20 @@ const ENUM_TABLE: &[EnumEntry] = &[
     ("ColorSpace", rewrite::k_xxx),
     // m109: SkGradientShader::Interpolation::HueMethod
     ("HueMethod", rewrite::k_xxx),
+    // skgpu::VulkanMemoryAllocator::BufferUsage
+    ("BufferUsage", rewrite::k_xxx),
     // SkCodecAnimation
     ("DisposalMethod", rewrite::k_xxx),
     ("Blend", rewrite::k_xxx),
+    // modules/svg
+    ("SkSVGTag", rewrite::k_xxx),
+    // modules/skottie
+    // skottie::PropertyObserver::NodeType
+    ("NodeType", rewrite::upper_camel_case),
 ];
 
 pub(crate) mod rewrite {
-    use heck::ToShoutySnakeCase;
+    use heck::{ToShoutySnakeCase, ToUpperCamelCase};
     use regex::Regex;
 
     pub fn k_xxx_uppercase(name: &str, variant: &str) -> String {
@@ -670,6 +727,10 @@ pub(crate) mod rewrite {
         capture(name, variant, &format!("k(.*)_{name}"))
     }
 
+    pub fn upper_camel_case(_name: &str, variant: &str) -> String {
+        variant.to_upper_camel_case()
+    }
+
     pub fn vk(name: &str, variant: &str) -> String {
         let prefix = name.to_shouty_snake_case();
         capture(name, variant, &format!("{prefix}_(.*)"))
@@ -769,6 +830,9 @@ pub(crate) mod definitions {
         if features.svg {
             files.push("obj/modules/svg/svg.ninja".into());
         }
+        if features.skottie {
+            files.push("obj/modules/skottie/skottie.ninja".into());
+        }
         files
     }
 
@@ -58,11 +58,16 @@
 #include "include/core/SkSurfaceCharacterization.h"
 #include "include/core/SkSwizzle.h"
 #include "include/core/SkTextBlob.h"
+#include "include/core/SkTraceMemoryDump.h"
 #include "include/core/SkTypeface.h"
 #include "include/core/SkTypes.h"
 #include "include/core/SkVertices.h"
 // docs/
 #include "include/docs/SkPDFDocument.h"
+
+#include "include/encode/SkICC.h"
+#include "include/encode/SkJpegEncoder.h"
+#include "include/encode/SkPngEncoder.h"
 // effects/
 #include "include/effects/Sk1DPathEffect.h"
 #include "include/effects/Sk2DPathEffect.h"
@@ -91,6 +96,7 @@
 // pathops/
 #include "include/pathops/SkPathOps.h"
 
+
 // svg/
 #include "include/svg/SkSVGCanvas.h"
 
@@ -103,6 +109,12 @@
 #include "include/utils/SkShadowUtils.h"
 #include "include/utils/SkTextUtils.h"
 
+
+#if SK_SUPPORT_GPU
+#include "src/gpu/ganesh/GrSurfaceProxyView.h"
+#include "src/image/SkImage_Base.h"
+#endif
+
 //
 // codec/SkCodec.h
 //
@@ -111,6 +123,10 @@ extern "C" SkCodec* C_SkCodec_MakeFromData(SkData* data) {
     return SkCodec::MakeFromData(sp(data)).release();
 }
 
+extern "C" SkCodec* C_SkCodec_MakeFromStream(SkStream* stream, SkCodec::Result* result) {
+    return SkCodec::MakeFromStream(std::unique_ptr<SkStream>(stream), result).release();
+}
+
 extern "C" void C_SkCodec_delete(SkCodec* self) {
     delete self;
 }
@@ -409,6 +425,65 @@ extern "C" SkImage* C_SkImage_reinterpretColorSpace(const SkImage* self, SkColor
     return self->reinterpretColorSpace(sp(newColorSpace)).release();
 }
 
+namespace AsyncReadPixels {
+    // The receiver takes ownership of `result`, which is `nullptr` if the read failed.
+    extern "C" typedef void (*Callback)(void* context, const SkImage::AsyncReadResult* result);
+
+    struct Context {
+        Callback callback;
+        void* context;
+    };
+
+    void callback(SkImage::ReadPixelsContext context, std::unique_ptr<const SkImage::AsyncReadResult> result) {
+        auto c = std::unique_ptr<Context>(static_cast<Context*>(context));
+        c->callback(c->context, result.release());
+    }
+}
+
+extern "C" void C_SkImage_asyncRescaleAndReadPixels(
+        const SkImage* self,
+        const SkImageInfo* info,
+        const SkIRect* srcRect,
+        SkImage::RescaleGamma rescaleGamma,
+        SkImage::RescaleMode rescaleMode,
+        AsyncReadPixels::Callback callback,
+        void* context) {
+    self->asyncRescaleAndReadPixels(
+        *info, *srcRect, rescaleGamma, rescaleMode,
+        AsyncReadPixels::callback, new AsyncReadPixels::Context { callback, context });
+}
+
+extern "C" void C_SkImage_asyncRescaleAndReadPixelsYUV420(
+        const SkImage* self,
+        SkYUVColorSpace yuvColorSpace,
+        SkColorSpace* dstColorSpace,
+        const SkIRect* srcRect,
+        const SkISize* dstSize,
+        SkImage::RescaleGamma rescaleGamma,
+        SkImage::RescaleMode rescaleMode,
+        AsyncReadPixels::Callback callback,
+        void* context) {
+    self->asyncRescaleAndReadPixelsYUV420(
+        yuvColorSpace, sp(dstColorSpace), *srcRect, *dstSize, rescaleGamma, rescaleMode,
+        AsyncReadPixels::callback, new AsyncReadPixels::Context { callback, context });
+}
+
+extern "C" void C_SkImage_AsyncReadResult_delete(const SkImage::AsyncReadResult* self) {
+    delete self;
+}
+
+extern "C" int C_SkImage_AsyncReadResult_count(const SkImage::AsyncReadResult* self) {
+    return self->count();
+}
+
+extern "C" const void* C_SkImage_AsyncReadResult_data(const SkImage::AsyncReadResult* self, int i) {
+    return self->data(i);
+}
+
+extern "C" size_t C_SkImage_AsyncReadResult_rowBytes(const SkImage::AsyncReadResult* self, int i) {
+    return self->rowBytes(i);
+}
+
 //
 // core/SkImageEncoder.h
 //
@@ -421,6 +496,37 @@ extern "C" SkData *C_SkEncodeBitmap(const SkBitmap *src, SkEncodedImageFormat fo
     return SkEncodeBitmap(*src, format, quality).release();
 }
 
+//
+// encode/SkJpegEncoder.h
+//
+
+extern "C" bool C_SkJpegEncoder_Encode(
+    SkWStream* dst, const SkPixmap* src,
+    int quality, SkJpegEncoder::Downsample downsample, SkJpegEncoder::AlphaOption alphaOption) {
+    SkJpegEncoder::Options options;
+    options.fQuality = quality;
+    options.fDownsample = downsample;
+    options.fAlphaOption = alphaOption;
+    return SkJpegEncoder::Encode(dst, *src, options);
+}
+
+//
+// encode/SkPngEncoder.h
+//
+
+extern "C" bool C_SkPngEncoder_Encode(
+    SkWStream* dst, const SkPixmap* src,
+    int filterFlags, int zLibLevel,
+    const void* const* comments, const size_t* commentSizes, int commentCount) {
+    SkPngEncoder::Options options;
+    options.fFilterFlags = static_cast<SkPngEncoder::FilterFlag>(filterFlags);
+    options.fZLibLevel = zLibLevel;
+    if (commentCount) {
+        options.fComments = SkDataTable::MakeCopyArrays(comments, commentSizes, commentCount);
+    }
+    return SkPngEncoder::Encode(dst, *src, options);
+}
+
 //
 // core/SkData.h
 //
@@ -678,6 +784,15 @@ extern "C" void C_SkPathMeasure_destruct(const SkPathMeasure* self) {
     self->~SkPathMeasure();
 }
 
+extern "C" bool C_SkPathMeasure_getPosTans(SkPathMeasure* self, const SkScalar* distances, size_t count, SkPoint* positions, SkVector* tangents) {
+    for (size_t i = 0; i < count; ++i) {
+        if (!self->getPosTan(distances[i], &positions[i], &tangents[i])) {
+            return false;
+        }
+    }
+    return true;
+}
+
 //
 // core/SkPathTypes.h
 //
@@ -693,6 +808,10 @@ extern "C" bool C_PathUtils_FillPathWithPaint(const SkPath* src, const SkPaint*
     return skpathutils::FillPathWithPaint(*src, *paint, dst, cullRect, *matrix);
 }
 
+extern "C" bool C_PathUtils_FillPathWithPaintResScale(const SkPath* src, const SkPaint* paint, SkPath* dst, const SkRect* cullRect, SkScalar resScale) {
+    return skpathutils::FillPathWithPaint(*src, *paint, dst, cullRect, resScale);
+}
+
 //
 // core/SkCanvas.h
 // Note: bindgen layout is broken, so we are forced to allocate Canvas instances on the heap only.
@@ -915,6 +1034,100 @@ extern "C" SkColorSpace* C_SkColorSpace_Deserialize(const void* data, size_t len
     return SkColorSpace::Deserialize(data, length).release();
 }
 
+extern "C" SkColorSpace* C_SkColorSpace_MakeRGB(const skcms_TransferFunction* transferFn, const skcms_Matrix3x3* toXYZ) {
+    return SkColorSpace::MakeRGB(*transferFn, *toXYZ).release();
+}
+
+extern "C" bool C_SkColorSpace_isNumericalTransferFn(const SkColorSpace* self) {
+    skcms_TransferFunction fn;
+    return self->isNumericalTransferFn(&fn);
+}
+
+extern "C" void C_SkColorSpace_transferFn(const SkColorSpace* self, skcms_TransferFunction* fn) {
+    self->transferFn(fn);
+}
+
+extern "C" void C_SkColorSpace_invTransferFn(const SkColorSpace* self, skcms_TransferFunction* fn) {
+    self->invTransferFn(fn);
+}
+
+extern "C" bool C_SkColorSpace_toXYZD50(const SkColorSpace* self, skcms_Matrix3x3* toXYZD50) {
+    return self->toXYZD50(toXYZD50);
+}
+
+extern "C" bool C_SkColorSpacePrimaries_toXYZD50(const SkColorSpacePrimaries* self, skcms_Matrix3x3* toXYZD50) {
+    return self->toXYZD50(toXYZD50);
+}
+
+//
+// Pixel conversion
+//
+
+extern "C" bool C_SkConvertPixels(
+    const SkImageInfo* dstInfo, void* dstPixels, size_t dstRowBytes,
+    const SkImageInfo* srcInfo, const void* srcPixels, size_t srcRowBytes) {
+    if (dstInfo->dimensions() != srcInfo->dimensions()) {
+        return false;
+    }
+    return SkPixmap(*srcInfo, srcPixels, srcRowBytes).readPixels(*dstInfo, dstPixels, dstRowBytes);
+}
+
+//
+// third_party/skcms/skcms.h, encode/SkICC.h
+//
+
+struct IccProfileInfo {
+    uint32_t dataColorSpace;
+    uint32_t pcs;
+    uint32_t tagCount;
+    bool hasTRC;
+    // 0 if the curve is parametric.
+    uint32_t trcTableEntries[3];
+    skcms_TransferFunction trc[3];
+    bool hasToXYZD50;
+    skcms_Matrix3x3 toXYZD50;
+    bool hasA2B;
+    bool hasB2A;
+};
+
+extern "C" bool C_skcms_Parse(const void* data, size_t length, IccProfileInfo* info) {
+    skcms_ICCProfile profile;
+    if (!skcms_Parse(data, length, &profile)) {
+        return false;
+    }
+    info->dataColorSpace = profile.data_color_space;
+    info->pcs = profile.pcs;
+    info->tagCount = profile.tag_count;
+    info->hasTRC = profile.has_trc;
+    for (int i = 0; i < 3; ++i) {
+        info->trcTableEntries[i] = profile.trc[i].table_entries;
+        info->trc[i] = profile.trc[i].parametric;
+    }
+    info->hasToXYZD50 = profile.has_toXYZD50;
+    info->toXYZD50 = profile.toXYZD50;
+    info->hasA2B = profile.has_A2B;
+    info->hasB2A = profile.has_B2A;
+    return true;
+}
+
+extern "C" SkColorSpace* C_SkColorSpace_MakeFromICC(const void* data, size_t length) {
+    skcms_ICCProfile profile;
+    if (!skcms_Parse(data, length, &profile)) {
+        return nullptr;
+    }
+    return SkColorSpace::Make(profile).release();
+}
+
+extern "C" SkData* C_SkColorSpace_writeICCProfile(const SkColorSpace* self) {
+    skcms_TransferFunction fn;
+    skcms_Matrix3x3 toXYZD50;
+    self->transferFn(&fn);
+    if (!self->toXYZD50(&toXYZD50)) {
+        return nullptr;
+    }
+    return SkWriteICCProfile(fn, toXYZD50).release();
+}
+
 //
 // SkM44
 //
@@ -1073,10 +1286,20 @@ extern "C" SkPicture* C_SkPicture_MakeFromData2(const void* data, size_t size) {
     return SkPicture::MakeFromData(data, size).release();
 }
 
+extern "C" SkPicture* C_SkPicture_MakeFromDataWithProcs(const void* data, size_t size, const RustDeserialProcs* procs) {
+    auto deserialProcs = toDeserialProcs(procs);
+    return SkPicture::MakeFromData(data, size, &deserialProcs).release();
+}
+
 extern "C" SkData* C_SkPicture_serialize(const SkPicture* self) {
     return self->serialize().release();
 }
 
+extern "C" SkData* C_SkPicture_serializeWithProcs(const SkPicture* self, const RustSerialProcs* procs) {
+    auto serialProcs = toSerialProcs(procs);
+    return self->serialize(&serialProcs).release();
+}
+
 extern "C" SkPicture* C_SkPicture_MakePlaceholder(const SkRect& cull) {
     return SkPicture::MakePlaceholder(cull).release();
 }
@@ -1243,6 +1466,14 @@ extern "C" SkTextBlob *C_SkTextBlob_MakeFromRSXform(const void *text, size_t byt
     return SkTextBlob::MakeFromRSXform(text, byteLength, xform, *font, encoding).release();
 }
 
+extern "C" SkData* C_SkTextBlob_serialize(const SkTextBlob* self, const RustSerialProcs* procs) {
+    return self->serialize(toSerialProcs(procs)).release();
+}
+
+extern "C" SkTextBlob* C_SkTextBlob_Deserialize(const void* data, size_t size, const RustDeserialProcs* procs) {
+    return SkTextBlob::Deserialize(data, size, toDeserialProcs(procs)).release();
+}
+
 extern "C" void C_SkTextBlob_Iter_destruct(SkTextBlob::Iter* self) {
     self->~Iter();
 }
@@ -1628,6 +1859,10 @@ extern "C" SkColor4f C_SkColorFilter_filterColor4f(
     return self->filterColor4f(*srcColor, srcCS, dstCS);
 }
 
+extern "C" SkColorFilter* C_SkColorFilter_makeWithWorkingColorSpace(const SkColorFilter* self, SkColorSpace* workingSpace) {
+    return self->makeWithWorkingColorSpace(sp(workingSpace)).release();
+}
+
 //
 // SkColorFilters
 //
@@ -1705,6 +1940,15 @@ extern "C" bool C_SkContourMeasure_isClosed(const SkContourMeasure* self) {
     return self->isClosed();
 }
 
+extern "C" bool C_SkContourMeasure_getPosTans(const SkContourMeasure* self, const SkScalar* distances, size_t count, SkPoint* positions, SkVector* tangents) {
+    for (size_t i = 0; i < count; ++i) {
+        if (!self->getPosTan(distances[i], &positions[i], &tangents[i])) {
+            return false;
+        }
+    }
+    return true;
+}
+
 //
 // core/SkDataTable.h
 //
@@ -1839,6 +2083,76 @@ extern "C" SkImageGenerator *C_SkImageGenerator_MakeFromPicture(
         ).release();
 }
 
+namespace RustImageGenerator {
+    extern "C" typedef SkData* (*RefEncodedData)(TraitObject);
+    extern "C" typedef bool (*GetPixels)(TraitObject, const SkImageInfo*, void*, size_t);
+    extern "C" typedef SkImage* (*GenerateTexture)(TraitObject, GrRecordingContext*, const SkImageInfo*, GrMipmapped);
+    extern "C" typedef void (*Drop)(TraitObject);
+
+    struct Param {
+        TraitObject trait;
+        RefEncodedData refEncodedData;
+        GetPixels getPixels;
+        // `nullptr` if the generator does not generate textures.
+        GenerateTexture generateTexture;
+        Drop drop;
+    };
+
+    class Generator: public SkImageGenerator {
+    public:
+        Generator(const SkImageInfo& info, const Param& param)
+        : SkImageGenerator(info), _param(param) {
+        }
+
+        ~Generator() override {
+            _param.drop(_param.trait);
+        }
+
+    protected:
+        sk_sp<SkData> onRefEncodedData() override {
+            return sp(_param.refEncodedData(_param.trait));
+        }
+
+        bool onGetPixels(const SkImageInfo& info, void* pixels, size_t rowBytes, const Options&) override {
+            return _param.getPixels(_param.trait, &info, pixels, rowBytes);
+        }
+
+#if SK_SUPPORT_GPU
+        GrSurfaceProxyView onGenerateTexture(
+                GrRecordingContext* context,
+                const SkImageInfo& info,
+                GrMipmapped mipmapped,
+                GrImageTexGenPolicy policy) override {
+            if (_param.generateTexture) {
+                auto image = sk_sp<SkImage>(_param.generateTexture(_param.trait, context, &info, mipmapped));
+                if (image) {
+                    if (image->width() != info.width() || image->height() != info.height()
+                        || image->colorType() != info.colorType()) {
+                        return {};
+                    }
+                    // Uploads raster images and moves images of other contexts to `context`.
+                    if (auto direct = context->asDirectContext()) {
+                        image = image->makeTextureImage(direct, mipmapped);
+                        if (!image) {
+                            return {};
+                        }
+                    }
+                    return std::get<0>(as_IB(image)->asView(context, mipmapped, policy));
+                }
+            }
+            return SkImageGenerator::onGenerateTexture(context, info, mipmapped, policy);
+        }
+#endif
+
+    private:
+        Param _param;
+    };
+}
+
+extern "C" SkImageGenerator* C_RustImageGenerator_new(const SkImageInfo* info, const RustImageGenerator::Param* param) {
+    return new RustImageGenerator::Generator(*info, *param);
+}
+
 //
 // core/SkString.h
 //
@@ -2039,6 +2353,10 @@ extern "C" bool C_SkWStream_write(SkWStream* self, const void* buffer, size_t si
     return self->write(buffer, size);
 }
 
+extern "C" void C_SkWStream_flush(SkWStream* self) {
+    self->flush();
+}
+
 extern "C" SkMemoryStream* C_SkMemoryStream_MakeDirect(const void* data, size_t length) {
     return SkMemoryStream::MakeDirect(data, length).release();
 }
@@ -2055,6 +2373,74 @@ extern "C" SkStreamAsset* C_SkDynamicMemoryWStream_detachAsStream(SkDynamicMemor
     return self->detachAsStream().release();
 }
 
+//
+// core/SkTraceMemoryDump.h
+//
+
+namespace TraceMemoryDump {
+    extern "C" typedef void (*DumpNumericValue)(TraitObject, const char*, const char*, const char*, uint64_t);
+    extern "C" typedef void (*DumpStringValue)(TraitObject, const char*, const char*, const char*);
+    extern "C" typedef void (*SetMemoryBacking)(TraitObject, const char*, const char*, const char*);
+    extern "C" typedef SkTraceMemoryDump::LevelOfDetail (*GetRequestedDetails)(TraitObject);
+    extern "C" typedef bool (*ShouldDumpWrappedObjects)(TraitObject);
+    extern "C" typedef void (*DumpWrappedState)(TraitObject, const char*, bool);
+}
+
+class RustTraceMemoryDump: public SkTraceMemoryDump {
+public:
+    struct Param {
+        TraitObject trait;
+        ::TraceMemoryDump::DumpNumericValue dumpNumericValue;
+        ::TraceMemoryDump::DumpStringValue dumpStringValue;
+        ::TraceMemoryDump::SetMemoryBacking setMemoryBacking;
+        ::TraceMemoryDump::GetRequestedDetails getRequestedDetails;
+        ::TraceMemoryDump::ShouldDumpWrappedObjects shouldDumpWrappedObjects;
+        ::TraceMemoryDump::DumpWrappedState dumpWrappedState;
+    };
+
+    explicit RustTraceMemoryDump(const Param& param)
+    : _param(param) {
+    }
+
+    void dumpNumericValue(const char* dumpName, const char* valueName, const char* units, uint64_t value) override {
+        _param.dumpNumericValue(_param.trait, dumpName, valueName, units, value);
+    }
+
+    void dumpStringValue(const char* dumpName, const char* valueName, const char* value) override {
+        _param.dumpStringValue(_param.trait, dumpName, valueName, value);
+    }
+
+    void setMemoryBacking(const char* dumpName, const char* backingType, const char* backingObjectId) override {
+        _param.setMemoryBacking(_param.trait, dumpName, backingType, backingObjectId);
+    }
+
+    void setDiscardableMemoryBacking(const char*, const SkDiscardableMemory&) override {
+    }
+
+    LevelOfDetail getRequestedDetails() const override {
+        return _param.getRequestedDetails(_param.trait);
+    }
+
+    bool shouldDumpWrappedObjects() const override {
+        return _param.shouldDumpWrappedObjects(_param.trait);
+    }
+
+    void dumpWrappedState(const char* dumpName, bool isWrappedObject) override {
+        _param.dumpWrappedState(_param.trait, dumpName, isWrappedObject);
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" void C_RustTraceMemoryDump_construct(RustTraceMemoryDump* uninitialized, const RustTraceMemoryDump::Param* param) {
+    new(uninitialized) RustTraceMemoryDump(*param);
+}
+
+extern "C" void C_SkGraphics_DumpMemoryStatistics(SkTraceMemoryDump* dump) {
+    SkGraphics::DumpMemoryStatistics(dump);
+}
+
 //
 // core/SkYUVAInfo.h
 //
@@ -2496,6 +2882,20 @@ bool C_SkRuntimeEffect_allowBlender(const SkRuntimeEffect* self) {
     return self->allowBlender();
 }
 
+// Converts the unpremultiplied `color` from `src` to `dst`.
+// The profiles of SkColorSpaces are always accepted by skcms_Transform(), so this can not fail.
+void C_SkRuntimeEffect_transformColor(const SkColorSpace* src, const SkColorSpace* dst, SkColor4f* color) {
+    skcms_ICCProfile srcProfile, dstProfile;
+    src->toProfile(&srcProfile);
+    dst->toProfile(&dstProfile);
+    SkColor4f converted = *color;
+    if (skcms_Transform(color->vec(), skcms_PixelFormat_RGBA_ffff, skcms_AlphaFormat_Unpremul, &srcProfile,
+                        converted.vec(), skcms_PixelFormat_RGBA_ffff, skcms_AlphaFormat_Unpremul, &dstProfile,
+                        1)) {
+        *color = converted;
+    }
+}
+
 }
 
 //
@@ -2638,6 +3038,37 @@ SkImageFilter *C_SkImageFilters_Picture(SkPicture *pic, const SkRect *targetRect
     return SkImageFilters::Picture(sp(pic), *targetRect).release();
 }
 
+SkImageFilter *C_SkImageFilters_RuntimeShader(const SkRuntimeEffect *effect, const SkData *uniforms,
+                                              const SkRuntimeEffect::ChildPtr *children, size_t childCount,
+                                              const SkString *childShaderNames,
+                                              SkImageFilter *const inputs[], size_t inputCount)
+{
+    SkRuntimeShaderBuilder builder(sk_ref_sp(effect));
+    auto bytes = static_cast<const uint8_t*>(uniforms->data());
+    for (const auto& uniform : effect->uniforms()) {
+        builder.uniform(uniform.name).set(bytes + uniform.offset, (int)uniform.sizeInBytes());
+    }
+    auto effectChildren = effect->children();
+    for (size_t i = 0; i < childCount && i < effectChildren.size(); ++i) {
+        auto child = builder.child(effectChildren[i].name);
+        if (auto shader = children[i].shader()) {
+            child = sk_ref_sp(shader);
+        } else if (auto colorFilter = children[i].colorFilter()) {
+            child = sk_ref_sp(colorFilter);
+        } else if (auto blender = children[i].blender()) {
+            child = sk_ref_sp(blender);
+        }
+    }
+
+    std::vector<std::string_view> names;
+    std::vector<sk_sp<SkImageFilter>> inputFilters;
+    for (size_t i = 0; i < inputCount; ++i) {
+        names.emplace_back(childShaderNames[i].c_str(), childShaderNames[i].size());
+        inputFilters.push_back(sp(inputs[i]));
+    }
+    return SkImageFilters::RuntimeShader(builder, names.data(), inputFilters.data(), (int)inputCount).release();
+}
+
 SkImageFilter *C_SkImageFilters_Shader(SkShader *shader,
                                        SkImageFilters::Dither dither,
                                        const SkImageFilters::CropRect *cropRect)
@@ -2753,18 +3184,24 @@ extern "C" void C_SkPDF_StructElementNode_appendChild(SkPDF::StructureElementNod
     self->fChildVector.push_back(std::unique_ptr<SkPDF::StructureElementNode>(node));
 }
 
-extern "C" size_t C_SkPDF_StructureElementNode_getChildVector(const SkPDF::StructureElementNode *self, SkPDF::StructureElementNode **nodes)
+extern "C" size_t C_SkPDF_StructureElementNode_getChildVector(const SkPDF::StructureElementNode *self, SkPDF::StructureElementNode* const** nodes)
 {
-    if (self->fChildVector.empty())
-    {
-        *nodes = nullptr;
-        return 0;
-    }
-    else
-    {
-        *nodes = &*self->fChildVector.front();
-        return self->fChildVector.size();
-    }
+    // `std::unique_ptr` has the same layout as a raw pointer.
+    *nodes = reinterpret_cast<SkPDF::StructureElementNode* const*>(self->fChildVector.data());
+    return self->fChildVector.size();
+}
+
+extern "C" void C_SkPDF_StructureElementNode_setAdditionalNodeIds(SkPDF::StructureElementNode *self, const int *ids, size_t len) {
+    self->fAdditionalNodeIds = std::vector<int>(ids, ids + len);
+}
+
+extern "C" size_t C_SkPDF_StructureElementNode_getAdditionalNodeIds(const SkPDF::StructureElementNode *self, const int **ids) {
+    *ids = self->fAdditionalNodeIds.data();
+    return self->fAdditionalNodeIds.size();
+}
+
+extern "C" void C_SkPDF_SetNodeId(SkCanvas* canvas, int nodeId) {
+    SkPDF::SetNodeId(canvas, nodeId);
 }
 
 extern "C" void C_SkPDF_Metadata_Construct(SkPDF::Metadata* uninitialized) {
@@ -2849,6 +3286,88 @@ extern "C" void C_SkOrderedFontMgr_append(SkOrderedFontMgr* self, SkFontMgr* fon
     self->append(sp(fontMgr));
 }
 
+namespace RustFontFallback {
+    extern "C" typedef SkTypeface* (*MatchFamilyStyleCharacter)(TraitObject, const char familyName[], const SkFontStyle*, const char* bcp47[], int bcp47Count, SkUnichar character);
+    extern "C" typedef void (*Drop)(TraitObject);
+
+    struct Param {
+        TraitObject trait;
+        MatchFamilyStyleCharacter matchFamilyStyleCharacter;
+        Drop drop;
+    };
+
+    // Forwards everything to the base font manager, but asks Rust first when a fallback for a
+    // character is needed.
+    class FontMgr: public SkFontMgr {
+    public:
+        FontMgr(sk_sp<SkFontMgr> base, const Param& param) : _base(std::move(base)), _param(param) {
+        }
+
+        ~FontMgr() override {
+            _param.drop(_param.trait);
+        }
+
+    protected:
+        int onCountFamilies() const override {
+            return _base->countFamilies();
+        }
+
+        void onGetFamilyName(int index, SkString* familyName) const override {
+            _base->getFamilyName(index, familyName);
+        }
+
+        SkFontStyleSet* onCreateStyleSet(int index) const override {
+            return _base->createStyleSet(index);
+        }
+
+        SkFontStyleSet* onMatchFamily(const char familyName[]) const override {
+            return _base->matchFamily(familyName);
+        }
+
+        SkTypeface* onMatchFamilyStyle(const char familyName[], const SkFontStyle& style) const override {
+            return _base->matchFamilyStyle(familyName, style);
+        }
+
+        SkTypeface* onMatchFamilyStyleCharacter(const char familyName[], const SkFontStyle& style,
+                                                const char* bcp47[], int bcp47Count,
+                                                SkUnichar character) const override {
+            auto typeface = _param.matchFamilyStyleCharacter(_param.trait, familyName, &style, bcp47, bcp47Count, character);
+            if (typeface) {
+                return typeface;
+            }
+            return _base->matchFamilyStyleCharacter(familyName, style, bcp47, bcp47Count, character);
+        }
+
+        sk_sp<SkTypeface> onMakeFromData(sk_sp<SkData> data, int ttcIndex) const override {
+            return _base->makeFromData(std::move(data), ttcIndex);
+        }
+
+        sk_sp<SkTypeface> onMakeFromStreamIndex(std::unique_ptr<SkStreamAsset> stream, int ttcIndex) const override {
+            return _base->makeFromStream(std::move(stream), ttcIndex);
+        }
+
+        sk_sp<SkTypeface> onMakeFromStreamArgs(std::unique_ptr<SkStreamAsset> stream, const SkFontArguments& args) const override {
+            return _base->makeFromStream(std::move(stream), args);
+        }
+
+        sk_sp<SkTypeface> onMakeFromFile(const char path[], int ttcIndex) const override {
+            return _base->makeFromFile(path, ttcIndex);
+        }
+
+        sk_sp<SkTypeface> onLegacyMakeTypeface(const char familyName[], SkFontStyle style) const override {
+            return _base->legacyMakeTypeface(familyName, style);
+        }
+
+    private:
+        sk_sp<SkFontMgr> _base;
+        Param _param;
+    };
+}
+
+extern "C" SkFontMgr* C_RustFontFallback_new(SkFontMgr* base, const RustFontFallback::Param* param) {
+    return new RustFontFallback::FontMgr(sp(base), *param);
+}
+
 extern "C" void C_SkParsePath_ToSVGString(const SkPath* self, SkString* uninitialized, SkParsePath::PathEncoding encoding) {
     new (uninitialized) SkString(SkParsePath::ToSVGString(*self, encoding));
 }
@@ -2858,6 +3377,7 @@ extern "C" void C_SkParsePath_ToSVGString(const SkPath* self, SkString* uninitia
 //
 
 class RustStream : public SkStream {
+protected:
     void *m_data;
     size_t m_length;
     bool m_isEof;
@@ -2966,3 +3486,94 @@ extern "C" void C_RustStream_destruct(
 ) {
     stream->~RustStream();
 }
+
+// A RustStream that owns its Rust counterpart and drops it when it gets deleted.
+//
+// Reaching the end is not final: codecs that decode incrementally read again when more data
+// may have arrived.
+class RustOwnedStream : public RustStream {
+    void *m_owned;
+    void (*m_drop)(void *);
+
+public:
+    RustOwnedStream(
+            void *data,
+            size_t length,
+            size_t (*read)(void *, void *, size_t),
+            bool (*seekAbsolute)(void *, size_t),
+            bool (*seekRelative)(void *, long),
+            void (*drop)(void *)
+    ) : RustStream(data, length, read, seekAbsolute, seekRelative), m_owned(data), m_drop(drop) {}
+
+    ~RustOwnedStream() override {
+        (this->m_drop)(this->m_owned);
+    }
+
+    size_t read(void *buffer, size_t count) override {
+        size_t out = (this->m_read)(this->m_data, buffer, count);
+        this->m_isEof = !out;
+        return out;
+    }
+};
+
+extern "C" SkStream* C_RustStream_newOwned(
+        void *data,
+        size_t length,
+        size_t (*read)(void *, void *, size_t),
+        bool (*seekAbsolute)(void *, size_t),
+        bool (*seekRelative)(void *, long),
+        void (*drop)(void *)
+) {
+    return new RustOwnedStream(data, length, read, seekAbsolute, seekRelative, drop);
+}
+
+//
+// SkWStream <-> RustWStream interop
+//
+
+class RustWStream : public SkWStream {
+    void *m_data;
+    size_t m_bytesWritten;
+
+    bool (*m_write)(void *, const void *, size_t);
+
+    void (*m_flush)(void *);
+
+public:
+    RustWStream(
+            void *data,
+            bool (*write)(void *, const void *, size_t),
+            void (*flush)(void *)
+    ) : m_data(data), m_bytesWritten(0), m_write(write), m_flush(flush) {}
+
+    bool write(const void *buffer, size_t size) override {
+        if (!(this->m_write)(this->m_data, buffer, size)) {
+            return false;
+        }
+        this->m_bytesWritten += size;
+        return true;
+    }
+
+    void flush() override {
+        (this->m_flush)(this->m_data);
+    }
+
+    size_t bytesWritten() const override {
+        return this->m_bytesWritten;
+    }
+};
+
+extern "C" void C_RustWStream_construct(
+        RustWStream *out,
+        void *data,
+        bool (*write)(void *, const void *, size_t),
+        void (*flush)(void *)
+) {
+    new(out) RustWStream(data, write, flush);
+}
+
+extern "C" void C_RustWStream_destruct(
+    RustWStream *stream
+) {
+    stream->~RustWStream();
+}
@@ -3,8 +3,14 @@
 
 #include <vector>
 #include <optional>
+#include "include/core/SkData.h"
+#include "include/core/SkImage.h"
+#include "include/core/SkPicture.h"
 #include "include/core/SkRefCnt.h"
+#include "include/core/SkSerialProcs.h"
+#include "include/core/SkStream.h"
 #include "include/core/SkString.h"
+#include "include/core/SkTypeface.h"
 
 template<typename T>
 inline sk_sp<T> spFromConst(const T* pt) {
@@ -44,8 +50,98 @@ template<typename T> struct VecSink {
     }
 };
 
+/// A VecWriter is passed from C++ to Rust for copying a slice of values into a `std::vector`.
+template<typename T> struct VecWriter {
+    void* target;
+    void (*write_fn)(void*, const T*, size_t);
+
+    static VecWriter<T> of(std::vector<T>& v) {
+        return VecWriter<T> { &v, [](void* target, const T* ptr, size_t len) {
+            auto v = static_cast<std::vector<T>*>(target);
+            v->insert(v->end(), ptr, ptr + len);
+        }};
+    }
+};
+
 struct SkStrings {
     std::vector<SkString> strings;
 };
 
+/// Serial procs implemented in Rust, see `SerialProcs` and `DeserialProcs` in skia-safe.
+struct RustSerialProcs {
+    void* context;
+    SkData* (*picture)(void* context, SkPicture* picture);
+    SkData* (*image)(void* context, SkImage* image);
+    SkData* (*typeface)(void* context, SkTypeface* typeface);
+};
+
+struct RustDeserialProcs {
+    void* context;
+    SkPicture* (*picture)(void* context, const void* data, size_t length);
+    SkImage* (*image)(void* context, const void* data, size_t length);
+    SkTypeface* (*typeface)(void* context, const void* data, size_t length);
+};
+
+inline SkSerialProcs toSerialProcs(const RustSerialProcs* rust) {
+    SkSerialProcs procs;
+    auto ctx = const_cast<RustSerialProcs*>(rust);
+    if (rust->picture) {
+        procs.fPictureProc = [](SkPicture* picture, void* ctx) {
+            auto rust = static_cast<RustSerialProcs*>(ctx);
+            return sk_sp<SkData>(rust->picture(rust->context, picture));
+        };
+        procs.fPictureCtx = ctx;
+    }
+    if (rust->image) {
+        procs.fImageProc = [](SkImage* image, void* ctx) {
+            auto rust = static_cast<RustSerialProcs*>(ctx);
+            return sk_sp<SkData>(rust->image(rust->context, image));
+        };
+        procs.fImageCtx = ctx;
+    }
+    if (rust->typeface) {
+        // Without data, text blobs would lose their typefaces, so fall back to the default
+        // serialization, which `toDeserialProcs()` handles.
+        procs.fTypefaceProc = [](SkTypeface* typeface, void* ctx) {
+            auto rust = static_cast<RustSerialProcs*>(ctx);
+            auto data = sk_sp<SkData>(rust->typeface(rust->context, typeface));
+            return data ? data : typeface->serialize();
+        };
+        procs.fTypefaceCtx = ctx;
+    }
+    return procs;
+}
+
+inline SkDeserialProcs toDeserialProcs(const RustDeserialProcs* rust) {
+    SkDeserialProcs procs;
+    auto ctx = const_cast<RustDeserialProcs*>(rust);
+    if (rust->picture) {
+        procs.fPictureProc = [](const void* data, size_t length, void* ctx) {
+            auto rust = static_cast<RustDeserialProcs*>(ctx);
+            return sk_sp<SkPicture>(rust->picture(rust->context, data, length));
+        };
+        procs.fPictureCtx = ctx;
+    }
+    if (rust->image) {
+        procs.fImageProc = [](const void* data, size_t length, void* ctx) {
+            auto rust = static_cast<RustDeserialProcs*>(ctx);
+            return sk_sp<SkImage>(rust->image(rust->context, data, length));
+        };
+        procs.fImageCtx = ctx;
+    }
+    if (rust->typeface) {
+        procs.fTypefaceProc = [](const void* data, size_t length, void* ctx) {
+            auto rust = static_cast<RustDeserialProcs*>(ctx);
+            auto typeface = sk_sp<SkTypeface>(rust->typeface(rust->context, data, length));
+            if (!typeface) {
+                SkMemoryStream stream(data, length, false);
+                typeface = SkTypeface::MakeDeserialize(&stream);
+            }
+            return typeface;
+        };
+        procs.fTypefaceCtx = ctx;
+    }
+    return procs;
+}
+
 #endif //SKIA_BINDINGS_BINDINGS_H
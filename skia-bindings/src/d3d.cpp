@@ -6,6 +6,7 @@
     #define SK_DIRECT3D
 #endif
 
+#include "include/gpu/GrBackendSemaphore.h"
 #include "include/gpu/GrBackendSurface.h"
 #include "include/gpu/GrDirectContext.h"
 #include "include/gpu/d3d/GrD3DBackendContext.h"
@@ -21,6 +22,18 @@ extern "C" void C_GrD3DTextureResourceInfo_Construct(GrD3DTextureResourceInfo* u
     new(uninitialized) GrD3DTextureResourceInfo();
 }
 
+//
+// gpu/GrBackendSemaphore.h
+//
+
+extern "C" void C_GrBackendSemaphore_initDirect3D(GrBackendSemaphore* self, const GrD3DFenceInfo* info) {
+    self->initDirect3D(*info);
+}
+
+extern "C" bool C_GrBackendSemaphore_getD3DFenceInfo(const GrBackendSemaphore* self, GrD3DFenceInfo* outInfo) {
+    return self->getD3DFenceInfo(outInfo);
+}
+
 //
 // gpu/GrBackendSurface.h
 //
@@ -0,0 +1,68 @@
+#include "bindings.h"
+
+// for VSCode
+// TODO: remove that and add proper CMake support for VSCode
+#ifndef SK_DAWN
+    #define SK_DAWN
+#endif
+
+#include "include/gpu/GrBackendSurface.h"
+#include "include/gpu/GrDirectContext.h"
+#include "include/gpu/dawn/GrDawnTypes.h"
+
+// The Dawn objects are passed as their C handles. The C++ wrappers reference them on
+// construction and release them when they go out of scope.
+
+//
+// gpu/GrBackendSurface.h
+//
+
+extern "C" GrBackendTexture* C_GrBackendTexture_NewDawn(
+    int width, int height,
+    WGPUTexture texture, uint32_t format, uint32_t levelCount,
+    const char* label,
+    size_t labelCount) {
+    GrDawnTextureInfo info;
+    info.fTexture = wgpu::Texture(texture);
+    info.fFormat = static_cast<wgpu::TextureFormat>(format);
+    info.fLevelCount = levelCount;
+    return new GrBackendTexture(width, height, info, std::string_view(label, labelCount));
+}
+
+extern "C" bool C_GrBackendTexture_getDawnTextureInfo(
+    const GrBackendTexture* self,
+    WGPUTexture* texture, uint32_t* format, uint32_t* levelCount) {
+    GrDawnTextureInfo info;
+    if (!self->getDawnTextureInfo(&info)) {
+        return false;
+    }
+    // Borrowed, the backend texture keeps a reference.
+    *texture = info.fTexture.Get();
+    *format = static_cast<uint32_t>(info.fFormat);
+    *levelCount = info.fLevelCount;
+    return true;
+}
+
+extern "C" void C_GrBackendRenderTarget_ConstructDawn(
+    GrBackendRenderTarget* uninitialized,
+    int width, int height, int sampleCnt, int stencilBits,
+    WGPUTextureView textureView, uint32_t format, uint32_t levelCount) {
+    GrDawnRenderTargetInfo info;
+    info.fTextureView = wgpu::TextureView(textureView);
+    info.fFormat = static_cast<wgpu::TextureFormat>(format);
+    info.fLevelCount = levelCount;
+    new(uninitialized)GrBackendRenderTarget(width, height, sampleCnt, stencilBits, info);
+}
+
+//
+// gpu/GrDirectContext.h
+//
+
+extern "C" GrDirectContext* C_GrDirectContext_MakeDawn(
+    WGPUDevice device,
+    const GrContextOptions* options) {
+    if (options) {
+        return GrDirectContext::MakeDawn(wgpu::Device(device), *options).release();
+    }
+    return GrDirectContext::MakeDawn(wgpu::Device(device)).release();
+}
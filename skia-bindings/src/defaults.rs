@@ -2,8 +2,9 @@
 #![allow(clippy::derivable_impls)]
 
 use crate::{
-    SkBlendMode, SkBlurStyle, SkCanvas_Lattice_RectType, SkClipOp, SkPaint_Cap, SkPaint_Join,
-    SkParsePath_PathEncoding, SkPathDirection, SkTileMode, SkYUVColorSpace,
+    SkBlendMode, SkBlurStyle, SkCanvas_Lattice_RectType, SkClipOp, SkPDF_Metadata_Outline,
+    SkPaint_Cap, SkPaint_Join, SkParsePath_PathEncoding, SkPathDirection, SkTileMode,
+    SkYUVColorSpace,
 };
 
 impl Default for SkBlendMode {
@@ -67,6 +68,12 @@ impl Default for SkParsePath_PathEncoding {
     }
 }
 
+impl Default for SkPDF_Metadata_Outline {
+    fn default() -> Self {
+        SkPDF_Metadata_Outline::None
+    }
+}
+
 #[cfg(feature = "textlayout")]
 pub mod textlayout {
     impl Default for crate::skia_textlayout_Affinity {
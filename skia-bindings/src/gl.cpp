@@ -7,6 +7,7 @@
 #endif
 
 #include "include/core/SkSurfaceCharacterization.h"
+#include "include/gpu/GrBackendSemaphore.h"
 #include "include/gpu/GrDirectContext.h"
 #include "include/gpu/gl/GrGLExtensions.h"
 #include "include/gpu/gl/GrGLInterface.h"
@@ -142,6 +143,69 @@ extern "C" GrGLExtensions* C_GrGLInterface_extensions(GrGLInterface* self) {
     return &self->fExtensions;
 }
 
+// Queries the parameters of the framebuffer that is currently bound to the GL context.
+// Tries the legacy queries first (GLES, WebGL, compatibility profiles) and falls back to
+// attachment queries for core profiles.
+extern "C" bool C_GrGLInterface_getBoundFramebuffer(
+    const GrGLInterface* self,
+    GrGLint* fboid, GrGLint viewport[4], GrGLint* samples, GrGLint* stencilBits, GrGLint colorBits[4]) {
+    const auto& gl = self->fFunctions;
+    if (!gl.fGetIntegerv || !gl.fGetError) {
+        return false;
+    }
+
+    // Clear previous errors, but don't loop forever on a lost context.
+    for (int i = 0; i < 16 && gl.fGetError() != GR_GL_NO_ERROR; ++i) {}
+
+    gl.fGetIntegerv(GR_GL_FRAMEBUFFER_BINDING, fboid);
+    gl.fGetIntegerv(GR_GL_VIEWPORT, viewport);
+    gl.fGetIntegerv(GR_GL_SAMPLES, samples);
+    if (gl.fGetError() != GR_GL_NO_ERROR) {
+        return false;
+    }
+
+    gl.fGetIntegerv(GR_GL_STENCIL_BITS, stencilBits);
+    gl.fGetIntegerv(GR_GL_RED_BITS, &colorBits[0]);
+    gl.fGetIntegerv(GR_GL_GREEN_BITS, &colorBits[1]);
+    gl.fGetIntegerv(GR_GL_BLUE_BITS, &colorBits[2]);
+    gl.fGetIntegerv(GR_GL_ALPHA_BITS, &colorBits[3]);
+    if (gl.fGetError() == GR_GL_NO_ERROR) {
+        return true;
+    }
+
+    if (!gl.fGetFramebufferAttachmentParameteriv) {
+        return false;
+    }
+
+    const GrGLenum kBackLeft = 0x0402;
+    const GrGLenum kStencil = 0x1802;
+    const GrGLenum kObjectType = 0x8CD0;
+    const GrGLenum kRedSize = 0x2212;
+    const GrGLenum kGreenSize = 0x2213;
+    const GrGLenum kBlueSize = 0x2214;
+    const GrGLenum kAlphaSize = 0x2215;
+    const GrGLenum kStencilSize = 0x2217;
+
+    auto query = [&](GrGLenum attachment, GrGLenum pname) {
+        GrGLint type = GR_GL_NONE;
+        gl.fGetFramebufferAttachmentParameteriv(GR_GL_FRAMEBUFFER, attachment, kObjectType, &type);
+        GrGLint value = 0;
+        if (type != GR_GL_NONE) {
+            gl.fGetFramebufferAttachmentParameteriv(GR_GL_FRAMEBUFFER, attachment, pname, &value);
+        }
+        return value;
+    };
+
+    GrGLenum color = *fboid ? GR_GL_COLOR_ATTACHMENT0 : kBackLeft;
+    GrGLenum stencil = *fboid ? GR_GL_STENCIL_ATTACHMENT : kStencil;
+    *stencilBits = query(stencil, kStencilSize);
+    colorBits[0] = query(color, kRedSize);
+    colorBits[1] = query(color, kGreenSize);
+    colorBits[2] = query(color, kBlueSize);
+    colorBits[3] = query(color, kAlphaSize);
+    return gl.fGetError() == GR_GL_NO_ERROR;
+}
+
 //
 // gpu/gl/GrGLAssembleInterface.h
 //
@@ -185,3 +249,15 @@ extern "C" GrBackendTexture* C_GrBackendTexture_NewGL(
 extern "C" void C_GrBackendRenderTarget_ConstructGL(GrBackendRenderTarget* uninitialized, int width, int height, int sampleCnt, int stencilBits, const GrGLFramebufferInfo* glInfo) {
     new(uninitialized)GrBackendRenderTarget(width, height, sampleCnt, stencilBits, *glInfo);
 }
+
+//
+// gpu/GrBackendSemaphore.h
+//
+
+extern "C" void C_GrBackendSemaphore_initGL(GrBackendSemaphore* self, GrGLsync sync) {
+    self->initGL(sync);
+}
+
+extern "C" GrGLsync C_GrBackendSemaphore_glSync(const GrBackendSemaphore* self) {
+    return self->glSync();
+}
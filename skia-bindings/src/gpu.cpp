@@ -1,12 +1,17 @@
 #include "bindings.h"
 #include "include/gpu/GrDirectContext.h"
 #include "include/gpu/GrBackendDrawableInfo.h"
+#include "include/gpu/GrBackendSemaphore.h"
+#include "include/gpu/GrContextThreadSafeProxy.h"
 #include "include/gpu/GrYUVABackendTextures.h"
+#include "include/gpu/mock/GrMockTypes.h"
 #include "include/core/SkCanvas.h"
 #include "include/core/SkDrawable.h"
 #include "include/core/SkSurface.h"
 #include "include/core/SkSurfaceCharacterization.h"
+#include "include/core/SkTraceMemoryDump.h"
 #include "include/core/SkImageGenerator.h"
+#include "include/core/SkPromiseImageTexture.h"
 
 //
 // core/SkSurface.h
@@ -79,6 +84,10 @@ extern "C" GrBackendTexture* C_SkSurface_getBackendTexture(
     return new GrBackendTexture(self->getBackendTexture(handleAccess));
 }
 
+extern "C" bool C_SkSurface_wait(SkSurface* self, const GrBackendSemaphore* waitSemaphores, size_t count, bool deleteSemaphoresAfterWait) {
+    return self->wait(count, waitSemaphores, deleteSemaphoresAfterWait);
+}
+
 extern "C" void C_SkSurface_getBackendRenderTarget(
         SkSurface* self,
         SkSurface::BackendHandleAccess handleAccess,
@@ -107,6 +116,34 @@ extern "C" const SkImageInfo* C_SkSurfaceCharacterization_imageInfo(const SkSurf
     return &self->imageInfo();
 }
 
+extern "C" GrContextThreadSafeProxy* C_SkSurfaceCharacterization_refContextInfo(const SkSurfaceCharacterization* self) {
+    return self->refContextInfo().release();
+}
+
+//
+// core/SkPromiseImageTexture.h
+//
+
+extern "C" SkPromiseImageTexture* C_SkPromiseImageTexture_Make(const GrBackendTexture* backendTexture) {
+    return SkPromiseImageTexture::Make(*backendTexture).release();
+}
+
+extern "C" void C_SkPromiseImageTexture_ref(const SkPromiseImageTexture* self) {
+    self->ref();
+}
+
+extern "C" void C_SkPromiseImageTexture_unref(const SkPromiseImageTexture* self) {
+    self->unref();
+}
+
+extern "C" bool C_SkPromiseImageTexture_unique(const SkPromiseImageTexture* self) {
+    return self->unique();
+}
+
+extern "C" const GrBackendTexture* C_SkPromiseImageTexture_backendTexture(const SkPromiseImageTexture* self) {
+    return &self->backendTexture();
+}
+
 //
 // core/SkImageGenerator.h
 //
@@ -228,6 +265,11 @@ extern "C" void C_GrRecordingContext_compressedBackendFormat(const GrRecordingCo
     *backendFormat = self->compressedBackendFormat(compressionType);
 }
 
+// GrContext_Base.h
+extern "C" GrContextThreadSafeProxy* C_GrRecordingContext_threadSafeProxy(GrRecordingContext* self) {
+    return self->threadSafeProxy().release();
+}
+
 extern "C" bool C_GrRecordingContext_abandoned(GrRecordingContext* self) {
     return self->abandoned();
 }
@@ -240,6 +282,11 @@ extern "C" int C_GrRecordingContext_maxSurfaceSampleCountForColorType(const GrRe
 // gpu/GrDirectContext.h
 //
 
+extern "C" GrDirectContext* C_GrDirectContext_MakeMock(const GrContextOptions* options) {
+    GrMockOptions mockOptions;
+    return GrDirectContext::MakeMock(&mockOptions, *options).release();
+}
+
 extern "C" void C_GrDirectContext_flushAndSubmit(GrDirectContext* self) {
     self->flushAndSubmit();
 }
@@ -248,14 +295,168 @@ extern "C" void C_GrDirectContext_compressedBackendFormat(const GrDirectContext*
     *result = self->compressedBackendFormat(compression);
 }
 
+extern "C" GrBackendTexture* C_GrDirectContext_createBackendTexture(
+        GrDirectContext* self,
+        int width, int height,
+        const GrBackendFormat* backendFormat,
+        GrMipmapped mipmapped,
+        GrRenderable renderable,
+        GrProtected isProtected) {
+    return new GrBackendTexture(self->createBackendTexture(
+        width, height, *backendFormat, mipmapped, renderable, isProtected));
+}
+
+extern "C" GrBackendTexture* C_GrDirectContext_createBackendTexture2(
+        GrDirectContext* self,
+        int width, int height,
+        SkColorType colorType,
+        GrMipmapped mipmapped,
+        GrRenderable renderable,
+        GrProtected isProtected) {
+    return new GrBackendTexture(self->createBackendTexture(
+        width, height, colorType, mipmapped, renderable, isProtected));
+}
+
+extern "C" GrBackendTexture* C_GrDirectContext_createBackendTexture3(
+        GrDirectContext* self,
+        const SkPixmap* srcData, int numLevels,
+        GrSurfaceOrigin origin,
+        GrRenderable renderable,
+        GrProtected isProtected) {
+    return new GrBackendTexture(self->createBackendTexture(
+        srcData, numLevels, origin, renderable, isProtected));
+}
+
+extern "C" bool C_GrDirectContext_updateBackendTexture(
+        GrDirectContext* self,
+        const GrBackendTexture* backendTexture,
+        const SkColor4f* color) {
+    return self->updateBackendTexture(*backendTexture, *color, nullptr, nullptr);
+}
+
+extern "C" bool C_GrDirectContext_updateBackendTexture2(
+        GrDirectContext* self,
+        const GrBackendTexture* backendTexture,
+        const SkPixmap* srcData, int numLevels,
+        GrSurfaceOrigin origin) {
+    return self->updateBackendTexture(*backendTexture, srcData, numLevels, origin, nullptr, nullptr);
+}
+
+extern "C" GrBackendTexture* C_GrDirectContext_createCompressedBackendTexture(
+        GrDirectContext* self,
+        int width, int height,
+        SkImage::CompressionType compression,
+        const void* data, size_t dataSize,
+        GrMipmapped mipmapped,
+        GrProtected isProtected) {
+    return new GrBackendTexture(self->createCompressedBackendTexture(
+        width, height, compression, data, dataSize, mipmapped, isProtected));
+}
+
+extern "C" GrBackendTexture* C_GrDirectContext_createCompressedBackendTexture2(
+        GrDirectContext* self,
+        int width, int height,
+        const GrBackendFormat* backendFormat,
+        const void* data, size_t dataSize,
+        GrMipmapped mipmapped,
+        GrProtected isProtected) {
+    return new GrBackendTexture(self->createCompressedBackendTexture(
+        width, height, *backendFormat, data, dataSize, mipmapped, isProtected));
+}
+
+extern "C" bool C_GrDirectContext_updateCompressedBackendTexture(
+        GrDirectContext* self,
+        const GrBackendTexture* backendTexture,
+        const void* data, size_t dataSize) {
+    return self->updateCompressedBackendTexture(*backendTexture, data, dataSize, nullptr, nullptr);
+}
+
+extern "C" void C_GrDirectContext_deleteBackendTexture(GrDirectContext* self, const GrBackendTexture* backendTexture) {
+    self->deleteBackendTexture(*backendTexture);
+}
+
 extern "C" void C_GrDirectContext_directContextId(const GrDirectContext* self, GrDirectContext::DirectContextID* r) {
     *r = self->directContextID();
 }
 
+extern "C" void C_GrDirectContext_dumpMemoryStatistics(const GrDirectContext* self, SkTraceMemoryDump* traceMemoryDump) {
+    self->dumpMemoryStatistics(traceMemoryDump);
+}
+
 extern "C" void C_GrDirectContext_performDeferredCleanup(GrDirectContext* self, long msNotUsed, bool scratchResourcesOnly) {
     self->performDeferredCleanup(std::chrono::milliseconds(msNotUsed), scratchResourcesOnly);
 }
 
+extern "C" bool C_GrDirectContext_wait(GrDirectContext* self, const GrBackendSemaphore* waitSemaphores, size_t count, bool deleteSemaphoresAfterWait) {
+    return self->wait(count, waitSemaphores, deleteSemaphoresAfterWait);
+}
+
+//
+// gpu/GrContextThreadSafeProxy.h
+//
+
+extern "C" void C_GrContextThreadSafeProxy_ref(const GrContextThreadSafeProxy* self) {
+    self->ref();
+}
+
+extern "C" void C_GrContextThreadSafeProxy_unref(const GrContextThreadSafeProxy* self) {
+    self->unref();
+}
+
+extern "C" bool C_GrContextThreadSafeProxy_unique(const GrContextThreadSafeProxy* self) {
+    return self->unique();
+}
+
+extern "C" bool C_GrContextThreadSafeProxy_isValid(const GrContextThreadSafeProxy* self) {
+    return self->isValid();
+}
+
+extern "C" void C_GrContextThreadSafeProxy_createCharacterization(
+        GrContextThreadSafeProxy* self,
+        size_t cacheMaxResourceBytes,
+        const SkImageInfo* ii,
+        const GrBackendFormat* backendFormat,
+        int sampleCount,
+        GrSurfaceOrigin origin,
+        const SkSurfaceProps* surfaceProps,
+        bool isMipMapped,
+        bool willUseGLFBO0,
+        bool isTextureable,
+        GrProtected isProtected,
+        SkSurfaceCharacterization* uninitialized) {
+    new(uninitialized) SkSurfaceCharacterization(self->createCharacterization(
+        cacheMaxResourceBytes, *ii, *backendFormat, sampleCount, origin, *surfaceProps,
+        isMipMapped, willUseGLFBO0, isTextureable, isProtected));
+}
+
+extern "C" void C_GrContextThreadSafeProxy_defaultBackendFormat(const GrContextThreadSafeProxy* self, SkColorType ct, GrRenderable renderable, GrBackendFormat* result) {
+    *result = self->defaultBackendFormat(ct, renderable);
+}
+
+//
+// gpu/GrBackendSemaphore.h
+//
+
+extern "C" void C_GrBackendSemaphore_Construct(GrBackendSemaphore* uninitialized) {
+    new(uninitialized) GrBackendSemaphore();
+}
+
+extern "C" void C_GrBackendSemaphore_CopyConstruct(GrBackendSemaphore* uninitialized, const GrBackendSemaphore* semaphore) {
+    new(uninitialized) GrBackendSemaphore(*semaphore);
+}
+
+extern "C" void C_GrBackendSemaphore_destruct(GrBackendSemaphore* self) {
+    self->~GrBackendSemaphore();
+}
+
+extern "C" bool C_GrBackendSemaphore_isInitialized(const GrBackendSemaphore* self) {
+    return self->isInitialized();
+}
+
+extern "C" GrBackendApi C_GrBackendSemaphore_backend(const GrBackendSemaphore* self) {
+    return self->backend();
+}
+
 //
 // gpu/GrContextOptions.h
 //
@@ -264,6 +465,66 @@ extern "C" void C_GrContextOptions_Construct(GrContextOptions* uninitialized) {
     new(uninitialized) GrContextOptions();
 }
 
+namespace PersistentCache {
+    extern "C" typedef SkData* (*Load)(TraitObject, const SkData*);
+    extern "C" typedef void (*Store)(TraitObject, const SkData*, const SkData*, const SkString*);
+}
+
+class RustPersistentCache: public GrContextOptions::PersistentCache {
+public:
+    struct Param {
+        TraitObject trait;
+        ::PersistentCache::Load load;
+        ::PersistentCache::Store store;
+    };
+
+    explicit RustPersistentCache(const Param& param)
+    : _param(param) {
+    }
+
+    sk_sp<SkData> load(const SkData& key) override {
+        return sp(_param.load(_param.trait, &key));
+    }
+
+    void store(const SkData& key, const SkData& data, const SkString& description) override {
+        _param.store(_param.trait, &key, &data, &description);
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" GrContextOptions::PersistentCache* C_RustPersistentCache_new(const RustPersistentCache::Param* param) {
+    return new RustPersistentCache(*param);
+}
+
+namespace ShaderErrorHandler {
+    extern "C" typedef void (*CompileError)(TraitObject, const char*, const char*);
+}
+
+class RustShaderErrorHandler: public GrContextOptions::ShaderErrorHandler {
+public:
+    struct Param {
+        TraitObject trait;
+        ::ShaderErrorHandler::CompileError compileError;
+    };
+
+    explicit RustShaderErrorHandler(const Param& param)
+    : _param(param) {
+    }
+
+    void compileError(const char* shader, const char* errors) override {
+        _param.compileError(_param.trait, shader, errors);
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" GrContextOptions::ShaderErrorHandler* C_RustShaderErrorHandler_new(const RustShaderErrorHandler::Param* param) {
+    return new RustShaderErrorHandler(*param);
+}
+
 //
 // gpu/GrRecordingContext.h
 //
@@ -384,6 +645,48 @@ extern "C" SkImage* C_SkImage_MakeCrossContextFromPixmap(
     return SkImage::MakeCrossContextFromPixmap(context, *pixmap, buildMips, limitToMaxTextureSize).release();
 }
 
+namespace RustPromiseImageTexture {
+    extern "C" {
+        typedef SkPromiseImageTexture* (*Fulfill)(void* context);
+        typedef void (*Release)(void* context);
+    }
+
+    struct Context {
+        void* context;
+        Fulfill fulfill;
+        Release release;
+    };
+
+    sk_sp<SkPromiseImageTexture> fulfill(SkImage::PromiseImageTextureContext context) {
+        auto c = static_cast<Context*>(context);
+        return sp(c->fulfill(c->context));
+    }
+
+    void release(SkImage::PromiseImageTextureContext context) {
+        auto c = static_cast<Context*>(context);
+        c->release(c->context);
+        delete c;
+    }
+}
+
+extern "C" SkImage* C_SkImage_MakePromiseTexture(
+        GrContextThreadSafeProxy* gpuContextProxy,
+        const GrBackendFormat* backendFormat,
+        const SkISize* dimensions,
+        GrMipmapped mipmapped,
+        GrSurfaceOrigin origin,
+        SkColorType colorType,
+        SkAlphaType alphaType,
+        SkColorSpace* colorSpace,
+        RustPromiseImageTexture::Fulfill fulfill,
+        RustPromiseImageTexture::Release release,
+        void* context) {
+    auto c = new RustPromiseImageTexture::Context { context, fulfill, release };
+    return SkImage::MakePromiseTexture(
+            sp(gpuContextProxy), *backendFormat, *dimensions, mipmapped, origin, colorType, alphaType,
+            sp(colorSpace), RustPromiseImageTexture::fulfill, RustPromiseImageTexture::release, c).release();
+}
+
 extern "C" SkImage* C_SkImage_MakeFromAdoptedTexture(
         GrRecordingContext* context,
         const GrBackendTexture* backendTexture,
@@ -1,12 +1,23 @@
-#[cfg(windows)]
+use std::io;
+#[cfg(all(windows, not(feature = "unicode-client")))]
+use std::sync::Mutex;
+
+/// ICU data that was set by the application with [`set_data()`].
+#[cfg(all(windows, not(feature = "unicode-client")))]
+static ICU_DATA: Mutex<Option<&'static [u8]>> = Mutex::new(None);
+
+#[cfg(all(windows, not(feature = "unicode-client")))]
 pub fn init() {
     use std::env;
     static icudtl: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/skia/icudtl.dat"));
 
+    if let Some(data) = *ICU_DATA.lock().unwrap() {
+        unsafe { crate::C_SetICU(&data[0] as &'static u8 as *const u8 as _) };
+        return;
+    }
+
     #[cfg(feature = "embed-icudtl")]
     {
-        use std::sync::Mutex;
-
         lazy_static::lazy_static!(
             static ref MUTEX : Mutex<()> = Mutex::new(());
         );
@@ -36,5 +47,51 @@ pub fn init() {
     }
 }
 
-#[cfg(not(windows))]
+#[cfg(any(not(windows), feature = "unicode-client"))]
 pub fn init() {}
+
+/// Sets the contents of an `icudtl.dat` file as the ICU data. The data is copied, and ICU
+/// references the copy for the rest of the process' lifetime.
+///
+/// Subsequent calls to [`init()`] use this data instead of the embedded or the executable's
+/// `icudtl.dat` file.
+///
+/// Fails if ICU rejects the data, or if different data was set before. Setting the same data
+/// again succeeds without copying it.
+#[cfg(all(windows, not(feature = "unicode-client")))]
+pub fn set_data(data: &[u8]) -> io::Result<()> {
+    if data.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "ICU data must not be empty",
+        ));
+    }
+    let mut icu_data = ICU_DATA.lock().unwrap();
+    if let Some(existing) = *icu_data {
+        if existing == data {
+            return Ok(());
+        }
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "ICU was already initialized with different data",
+        ));
+    }
+    let copy: *mut [u8] = Box::into_raw(data.into());
+    let data: &'static [u8] = unsafe { &*copy };
+    if !unsafe { crate::C_SetICU(&data[0] as &'static u8 as *const u8 as _) } {
+        drop(unsafe { Box::from_raw(copy) });
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "ICU rejected the data",
+        ));
+    }
+    *icu_data = Some(data);
+    Ok(())
+}
+
+/// On platforms other than Windows, the ICU data is linked into the Skia library, and with the
+/// feature `unicode-client`, ICU is not used at all, so this function has no effect.
+#[cfg(any(not(windows), feature = "unicode-client"))]
+pub fn set_data(_data: &[u8]) -> io::Result<()> {
+    Ok(())
+}
@@ -7,8 +7,13 @@
 #include "include/core/SkColorSpace.h"
 #include "include/core/SkSurface.h"
 #include "include/gpu/mtl/GrMtlBackendContext.h"
+#include "include/gpu/GrBackendSemaphore.h"
 #include "include/gpu/GrDirectContext.h"
 
+#include <CoreFoundation/CoreFoundation.h>
+#include <objc/message.h>
+#include <objc/runtime.h>
+
 extern "C" void C_GrMtlTypes(GrMTLTextureUsage*, GrMtlSurfaceInfo *) {};
 
 //
@@ -38,6 +43,24 @@ extern "C" SkSurface *C_SkSurface_MakeFromMTKView(GrRecordingContext *context,
     ).release();
 }
 
+extern "C" void C_GrMTLHandle_release(GrMTLHandle handle) {
+    CFRelease(handle);
+}
+
+// Presents a CAMetalDrawable with a new command buffer of the MTLCommandQueue `queue`.
+extern "C" bool C_CAMetalDrawable_present(GrMTLHandle queue, GrMTLHandle drawable) {
+    auto commandBuffer = reinterpret_cast<id(*)(id, SEL)>(objc_msgSend)(
+        (id)queue, sel_registerName("commandBuffer"));
+    if (!commandBuffer) {
+        return false;
+    }
+    reinterpret_cast<void(*)(id, SEL, id)>(objc_msgSend)(
+        commandBuffer, sel_registerName("presentDrawable:"), (id)drawable);
+    reinterpret_cast<void(*)(id, SEL)>(objc_msgSend)(
+        commandBuffer, sel_registerName("commit"));
+    return true;
+}
+
 //
 // gpu/GrDirectContext.h
 //
@@ -108,3 +131,19 @@ extern "C" GrBackendTexture* C_GrBackendTexture_NewMtl(
 extern "C" void C_GrBackendRenderTarget_ConstructMtl(GrBackendRenderTarget* uninitialized, int width, int height, int sampleCnt, const GrMtlTextureInfo* mtlInfo) {
     new(uninitialized)GrBackendRenderTarget(width, height, sampleCnt, *mtlInfo);
 }
+
+//
+// gpu/GrBackendSemaphore.h
+//
+
+extern "C" void C_GrBackendSemaphore_initMetal(GrBackendSemaphore* self, GrMTLHandle event, uint64_t value) {
+    self->initMetal(event, value);
+}
+
+extern "C" GrMTLHandle C_GrBackendSemaphore_mtlSemaphore(const GrBackendSemaphore* self) {
+    return self->mtlSemaphore();
+}
+
+extern "C" uint64_t C_GrBackendSemaphore_mtlValue(const GrBackendSemaphore* self) {
+    return self->mtlValue();
+}
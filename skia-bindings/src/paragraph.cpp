@@ -12,10 +12,12 @@
 #include "modules/skparagraph/include/TextShadow.h"
 #include "modules/skparagraph/include/TextStyle.h"
 #include "modules/skparagraph/include/TypefaceFontProvider.h"
+#include "modules/skunicode/include/SkUnicode.h"
 
 // m84: needs definition of SkFontData
 #include "src/core/SkFontDescriptor.h"
 
+#include <limits>
 #include <optional>
 
 using namespace skia::textlayout;
@@ -273,6 +275,14 @@ extern "C" {
     int32_t C_Paragraph_unresolvedGlyphs(Paragraph* self) {
         return self->unresolvedGlyphs();
     }
+
+    typedef void (*ParagraphVisitor)(int lineNumber, const Paragraph::VisitorInfo* info, void* context);
+
+    void C_Paragraph_visit(Paragraph* self, ParagraphVisitor visitor, void* context) {
+        self->visit([&](int lineNumber, const Paragraph::VisitorInfo* info) {
+            visitor(lineNumber, info, context);
+        });
+    }
 }
 
 //
@@ -312,9 +322,25 @@ extern "C" {
         return self->Reset();
     }
 
+    void C_ParagraphBuilder_setWordsUtf8(ParagraphBuilder* self, const SkUnicode::Position* words, size_t count) {
+        self->setWordsUtf8(std::vector<SkUnicode::Position>(words, words + count));
+    }
+
+    void C_ParagraphBuilder_setGraphemeBreaksUtf8(ParagraphBuilder* self, const SkUnicode::Position* graphemeBreaks, size_t count) {
+        self->setGraphemeBreaksUtf8(std::vector<SkUnicode::Position>(graphemeBreaks, graphemeBreaks + count));
+    }
+
+    void C_ParagraphBuilder_setLineBreaksUtf8(ParagraphBuilder* self, const SkUnicode::LineBreakBefore* lineBreaks, size_t count) {
+        self->setLineBreaksUtf8(std::vector<SkUnicode::LineBreakBefore>(lineBreaks, lineBreaks + count));
+    }
+
     ParagraphBuilder* C_ParagraphBuilder_make(const ParagraphStyle* style, const FontCollection* fontCollection) {
         return ParagraphBuilder::make(*style, spFromConst(fontCollection)).release();
     }
+
+    ParagraphBuilder* C_ParagraphBuilder_makeWithUnicode(const ParagraphStyle* style, const FontCollection* fontCollection, SkUnicode* unicode) {
+        return ParagraphBuilder::make(*style, spFromConst(fontCollection), std::unique_ptr<SkUnicode>(unicode)).release();
+    }
 }
 
 //
@@ -445,3 +471,381 @@ extern "C" {
         return self->registerTypeface(sk_sp<SkTypeface>(typeface));
     }
 }
+
+//
+// SkUnicode.h
+//
+
+namespace RustUnicode {
+    extern "C" typedef bool (*IsCharacterClass)(TraitObject, SkUnichar);
+    extern "C" typedef void (*ToUpper)(TraitObject, const char*, size_t, VecWriter<char>*);
+    extern "C" typedef bool (*BidiLevels)(TraitObject, const char16_t*, size_t, SkBidiIterator::Direction, VecWriter<SkBidiIterator::Level>*);
+    extern "C" typedef bool (*Breaks)(TraitObject, const char*, size_t, SkUnicode::BreakType, const char*, VecWriter<SkBreakIterator::Position>*, VecWriter<SkBreakIterator::Status>*);
+    extern "C" typedef void (*ReorderVisual)(TraitObject, const SkUnicode::BidiLevel*, size_t, int32_t*);
+    extern "C" typedef TraitObject (*Clone)(TraitObject);
+    extern "C" typedef void (*Drop)(TraitObject);
+
+    struct Param {
+        TraitObject trait;
+        IsCharacterClass isControl;
+        IsCharacterClass isWhitespace;
+        IsCharacterClass isSpace;
+        IsCharacterClass isTabulation;
+        IsCharacterClass isHardBreak;
+        IsCharacterClass isIdeographic;
+        ToUpper toUpper;
+        BidiLevels bidiLevels;
+        Breaks breaks;
+        ReorderVisual reorderVisual;
+        Clone clone;
+        Drop drop;
+    };
+
+    /// Decodes the code point at `*ptr` and advances `*ptr` past it. On invalid UTF-8, advances
+    /// by one byte and returns -1.
+    SkUnichar nextUtf8(const char** ptr, const char* end) {
+        auto p = reinterpret_cast<const uint8_t*>(*ptr);
+        auto available = end - *ptr;
+        *ptr += 1;
+        uint8_t lead = p[0];
+        if (lead < 0x80) {
+            return lead;
+        }
+        int count;
+        SkUnichar unichar;
+        SkUnichar min;
+        if ((lead & 0xE0) == 0xC0) {
+            count = 1; unichar = lead & 0x1F; min = 0x80;
+        } else if ((lead & 0xF0) == 0xE0) {
+            count = 2; unichar = lead & 0x0F; min = 0x800;
+        } else if ((lead & 0xF8) == 0xF0) {
+            count = 3; unichar = lead & 0x07; min = 0x10000;
+        } else {
+            return -1;
+        }
+        if (available <= count) {
+            return -1;
+        }
+        for (int i = 1; i <= count; ++i) {
+            if ((p[i] & 0xC0) != 0x80) {
+                return -1;
+            }
+            unichar = (unichar << 6) | (p[i] & 0x3F);
+        }
+        if (unichar < min || unichar > 0x10FFFF || (unichar >= 0xD800 && unichar <= 0xDFFF)) {
+            return -1;
+        }
+        *ptr += count;
+        return unichar;
+    }
+
+    /// Encodes a valid code point as one or two UTF-16 code units.
+    size_t encodeUtf16(SkUnichar unichar, uint16_t units[2]) {
+        if (unichar < 0x10000) {
+            units[0] = static_cast<uint16_t>(unichar);
+            return 1;
+        }
+        unichar -= 0x10000;
+        units[0] = static_cast<uint16_t>(0xD800 | (unichar >> 10));
+        units[1] = static_cast<uint16_t>(0xDC00 | (unichar & 0x3FF));
+        return 2;
+    }
+
+    /// Converts UTF-8 to UTF-16 and records the UTF-8 offset of each UTF-16 code unit.
+    bool toUtf16(const char* utf8, int utf8Units, std::u16string* utf16, std::vector<size_t>* utf8Offsets) {
+        const char* current = utf8;
+        const char* end = utf8 + utf8Units;
+        while (current < end) {
+            auto offset = current - utf8;
+            SkUnichar unichar = nextUtf8(&current, end);
+            if (unichar < 0) {
+                return false;
+            }
+            uint16_t units[2];
+            auto count = encodeUtf16(unichar, units);
+            for (size_t i = 0; i < count; ++i) {
+                utf16->push_back(units[i]);
+                utf8Offsets->push_back(offset);
+            }
+        }
+        return true;
+    }
+
+    class BidiIterator : public SkBidiIterator {
+    public:
+        explicit BidiIterator(std::vector<Level> levels)
+        : _levels(std::move(levels)) {
+        }
+
+        Position getLength() override {
+            return static_cast<Position>(_levels.size());
+        }
+
+        Level getLevelAt(Position position) override {
+            return _levels[position];
+        }
+
+    private:
+        std::vector<Level> _levels;
+    };
+
+    class BreakIterator : public SkBreakIterator {
+    public:
+        BreakIterator(const Param& param, SkUnicode::BreakType breakType, const char* locale)
+        : _param(param), _breakType(breakType), _locale(locale ? locale : ""), _current(0) {
+        }
+
+        Position first() override {
+            _current = 0;
+            return current();
+        }
+
+        Position current() override {
+            return _current < _positions.size() ? _positions[_current] : -1;
+        }
+
+        Position next() override {
+            if (_current < _positions.size()) {
+                ++_current;
+            }
+            return current();
+        }
+
+        Status status() override {
+            return _current < _statuses.size() ? _statuses[_current] : 0;
+        }
+
+        bool isDone() override {
+            return _current >= _positions.size();
+        }
+
+        bool setText(const char utftext8[], int utf8Units) override {
+            _positions.clear();
+            _statuses.clear();
+            _current = 0;
+            auto positions = VecWriter<Position>::of(_positions);
+            auto statuses = VecWriter<Status>::of(_statuses);
+            return _param.breaks(
+                _param.trait, utftext8, utf8Units, _breakType,
+                _locale.isEmpty() ? nullptr : _locale.c_str(),
+                &positions, &statuses);
+        }
+
+        bool setText(const char16_t utftext16[], int utf16Units) override {
+            auto utf8 = SkUnicode::convertUtf16ToUtf8(std::u16string(utftext16, utf16Units));
+            if (utf8.size() > static_cast<size_t>(std::numeric_limits<int>::max())) {
+                return false;
+            }
+            return setText(utf8.c_str(), static_cast<int>(utf8.size()));
+        }
+
+    private:
+        Param _param;
+        SkUnicode::BreakType _breakType;
+        SkString _locale;
+        std::vector<Position> _positions;
+        std::vector<Status> _statuses;
+        size_t _current;
+    };
+
+    class Unicode : public SkUnicode {
+    public:
+        explicit Unicode(const Param& param)
+        : _param(param) {
+        }
+
+        ~Unicode() override {
+            _param.drop(_param.trait);
+        }
+
+        bool isControl(SkUnichar utf8) override {
+            return _param.isControl(_param.trait, utf8);
+        }
+
+        bool isWhitespace(SkUnichar utf8) override {
+            return _param.isWhitespace(_param.trait, utf8);
+        }
+
+        bool isSpace(SkUnichar utf8) override {
+            return _param.isSpace(_param.trait, utf8);
+        }
+
+        bool isTabulation(SkUnichar utf8) override {
+            return _param.isTabulation(_param.trait, utf8);
+        }
+
+        bool isHardBreak(SkUnichar utf8) override {
+            return _param.isHardBreak(_param.trait, utf8);
+        }
+
+        bool isIdeographic(SkUnichar utf8) override {
+            return _param.isIdeographic(_param.trait, utf8);
+        }
+
+        SkString toUpper(const SkString& str) override {
+            std::vector<char> upper;
+            auto writer = VecWriter<char>::of(upper);
+            _param.toUpper(_param.trait, str.c_str(), str.size(), &writer);
+            return SkString(upper.data(), upper.size());
+        }
+
+        bool getBidiRegions(const char utf8[], int utf8Units, TextDirection dir, std::vector<BidiRegion>* results) override {
+            std::u16string utf16;
+            std::vector<size_t> utf8Offsets;
+            if (!toUtf16(utf8, utf8Units, &utf16, &utf8Offsets)) {
+                return false;
+            }
+            std::vector<BidiLevel> levels;
+            if (!bidiLevels(utf16.data(), utf16.size(), dir == TextDirection::kLTR ? SkBidiIterator::kLTR : SkBidiIterator::kRTL, &levels)) {
+                return false;
+            }
+            size_t start = 0;
+            for (size_t i = 1; i <= levels.size(); ++i) {
+                if (i == levels.size() || levels[i] != levels[start]) {
+                    auto end = i == levels.size() ? utf8Units : utf8Offsets[i];
+                    results->emplace_back(utf8Offsets[start], end, levels[start]);
+                    start = i;
+                }
+            }
+            return true;
+        }
+
+        bool getWords(const char utf8[], int utf8Units, const char* locale, std::vector<Position>* results) override {
+            std::vector<SkBreakIterator::Position> positions;
+            std::vector<SkBreakIterator::Status> statuses;
+            auto positionsWriter = VecWriter<SkBreakIterator::Position>::of(positions);
+            auto statusesWriter = VecWriter<SkBreakIterator::Status>::of(statuses);
+            if (!_param.breaks(_param.trait, utf8, utf8Units, BreakType::kWords, locale, &positionsWriter, &statusesWriter)) {
+                return false;
+            }
+            results->insert(results->end(), positions.begin(), positions.end());
+            return true;
+        }
+
+        bool computeCodeUnitFlags(char utf8[], int utf8Units, bool replaceTabs,
+                                  SkTArray<SkUnicode::CodeUnitFlags, true>* results) override {
+            results->reset();
+            results->push_back_n(utf8Units + 1, CodeUnitFlags::kNoCodeUnitFlag);
+
+            std::vector<SkBreakIterator::Position> positions;
+            std::vector<SkBreakIterator::Status> statuses;
+
+            auto positionsWriter = VecWriter<SkBreakIterator::Position>::of(positions);
+            auto statusesWriter = VecWriter<SkBreakIterator::Status>::of(statuses);
+            if (!_param.breaks(_param.trait, utf8, utf8Units, BreakType::kLines, nullptr, &positionsWriter, &statusesWriter)) {
+                return false;
+            }
+            for (size_t i = 0; i < positions.size(); ++i) {
+                auto position = positions[i];
+                if (position < 0 || position > utf8Units) {
+                    continue;
+                }
+                auto status = i < statuses.size() ? statuses[i] : 0;
+                (*results)[position] |= status == static_cast<SkBreakIterator::Status>(LineBreakType::kHardLineBreak)
+                    ? CodeUnitFlags::kHardLineBreakBefore
+                    : CodeUnitFlags::kSoftLineBreakBefore;
+            }
+
+            positions.clear();
+            statuses.clear();
+            if (!_param.breaks(_param.trait, utf8, utf8Units, BreakType::kGraphemes, nullptr, &positionsWriter, &statusesWriter)) {
+                return false;
+            }
+            for (auto position : positions) {
+                if (position >= 0 && position <= utf8Units) {
+                    (*results)[position] |= CodeUnitFlags::kGraphemeStart;
+                }
+            }
+
+            const char* current = utf8;
+            const char* end = utf8 + utf8Units;
+            while (current < end) {
+                auto before = current - utf8;
+                SkUnichar unichar = nextUtf8(&current, end);
+                if (unichar < 0) {
+                    unichar = 0xFFFD;
+                }
+                auto after = current - utf8;
+                if (replaceTabs && isTabulation(unichar)) {
+                    (*results)[before] |= CodeUnitFlags::kTabulation;
+                    unichar = ' ';
+                    utf8[before] = ' ';
+                }
+                for (auto i = before; i < after; ++i) {
+                    if (isSpace(unichar)) {
+                        (*results)[i] |= CodeUnitFlags::kPartOfIntraWordBreak;
+                    }
+                    if (isWhitespace(unichar)) {
+                        (*results)[i] |= CodeUnitFlags::kPartOfWhiteSpaceBreak;
+                    }
+                    if (isControl(unichar)) {
+                        (*results)[i] |= CodeUnitFlags::kControl;
+                    }
+                    if (isIdeographic(unichar)) {
+                        (*results)[i] |= CodeUnitFlags::kIdeographic;
+                    }
+                }
+            }
+            return true;
+        }
+
+        bool computeCodeUnitFlags(char16_t utf16[], int utf16Units, bool replaceTabs,
+                                  SkTArray<SkUnicode::CodeUnitFlags, true>* results) override {
+            // Only UTF-8 text is used by skparagraph.
+            return false;
+        }
+
+        std::unique_ptr<SkBidiIterator> makeBidiIterator(const uint16_t text[], int count,
+                                                         SkBidiIterator::Direction direction) override {
+            std::vector<BidiLevel> levels;
+            if (!bidiLevels(reinterpret_cast<const char16_t*>(text), count, direction, &levels)) {
+                return nullptr;
+            }
+            return std::make_unique<BidiIterator>(std::move(levels));
+        }
+
+        std::unique_ptr<SkBidiIterator> makeBidiIterator(const char text[], int count,
+                                                         SkBidiIterator::Direction direction) override {
+            std::u16string utf16;
+            std::vector<size_t> utf8Offsets;
+            if (!toUtf16(text, count, &utf16, &utf8Offsets)
+                || utf16.size() > static_cast<size_t>(std::numeric_limits<int>::max())) {
+                return nullptr;
+            }
+            return makeBidiIterator(reinterpret_cast<const uint16_t*>(utf16.data()), static_cast<int>(utf16.size()), direction);
+        }
+
+        std::unique_ptr<SkBreakIterator> makeBreakIterator(const char locale[], BreakType breakType) override {
+            return std::make_unique<BreakIterator>(_param, breakType, locale);
+        }
+
+        std::unique_ptr<SkBreakIterator> makeBreakIterator(BreakType breakType) override {
+            return std::make_unique<BreakIterator>(_param, breakType, nullptr);
+        }
+
+        void reorderVisual(const BidiLevel runLevels[], int levelsCount, int32_t logicalFromVisual[]) override {
+            _param.reorderVisual(_param.trait, runLevels, levelsCount, logicalFromVisual);
+        }
+
+        std::unique_ptr<SkUnicode> copy() override {
+            Param param = _param;
+            param.trait = _param.clone(_param.trait);
+            return std::make_unique<Unicode>(param);
+        }
+
+    private:
+        bool bidiLevels(const char16_t* utf16, size_t utf16Units, SkBidiIterator::Direction direction, std::vector<BidiLevel>* levels) {
+            auto writer = VecWriter<BidiLevel>::of(*levels);
+            return _param.bidiLevels(_param.trait, utf16, utf16Units, direction, &writer)
+                && levels->size() == utf16Units;
+        }
+
+        Param _param;
+    };
+}
+
+extern "C" {
+    SkUnicode* C_RustUnicode_new(const RustUnicode::Param* param) {
+        return new RustUnicode::Unicode(*param);
+    }
+}
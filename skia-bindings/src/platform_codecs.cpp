@@ -0,0 +1,101 @@
+#include "bindings.h"
+
+#include <cstring>
+
+#include "include/codec/SkCodec.h"
+#include "include/core/SkData.h"
+#include "include/core/SkImageGenerator.h"
+#include "include/core/SkStream.h"
+#include "include/private/SkEncodedInfo.h"
+
+#if defined(SK_BUILD_FOR_WIN)
+    #include "include/ports/SkImageGeneratorWIC.h"
+#elif defined(SK_ENABLE_NDK_IMAGES)
+    #include "include/ports/SkImageGeneratorNDK.h"
+#endif
+
+namespace {
+
+std::unique_ptr<SkImageGenerator> makePlatformGenerator(sk_sp<SkData> data) {
+#if defined(SK_BUILD_FOR_WIN)
+    return SkImageGeneratorWIC::MakeFromEncodedWIC(std::move(data));
+#elif defined(SK_ENABLE_NDK_IMAGES)
+    return SkImageGeneratorNDK::MakeFromEncodedNDK(std::move(data));
+#else
+    (void)data;
+    return nullptr;
+#endif
+}
+
+sk_sp<SkData> readToEnd(SkStream* stream) {
+    SkDynamicMemoryWStream buffer;
+    char chunk[4096];
+    for (;;) {
+        size_t read = stream->read(chunk, sizeof(chunk));
+        if (!read) {
+            break;
+        }
+        buffer.write(chunk, read);
+    }
+    return buffer.detachAsData();
+}
+
+// An SkCodec that decodes HEIF images with the platform's image decoder.
+class PlatformHeifCodec : public SkCodec {
+public:
+    // ISO base media files start with a `ftyp` box, which lists the major brand first.
+    static bool IsHeif(const void* buffer, size_t bytesRead) {
+        if (bytesRead < 12) {
+            return false;
+        }
+        auto bytes = static_cast<const char*>(buffer);
+        if (memcmp(bytes + 4, "ftyp", 4)) {
+            return false;
+        }
+        const char* brand = bytes + 8;
+        for (const char* heifBrand : {"heic", "heix", "hevc", "hevx", "heim", "heis", "mif1", "msf1"}) {
+            if (!memcmp(brand, heifBrand, 4)) {
+                return true;
+            }
+        }
+        return false;
+    }
+
+    static std::unique_ptr<SkCodec> Make(std::unique_ptr<SkStream> stream, Result* result) {
+        auto generator = makePlatformGenerator(readToEnd(stream.get()));
+        if (!generator) {
+            *result = kUnimplemented;
+            return nullptr;
+        }
+        const SkImageInfo& info = generator->getInfo();
+        auto encodedInfo = SkEncodedInfo::Make(
+            info.width(), info.height(),
+            SkEncodedInfo::kRGBA_Color,
+            info.isOpaque() ? SkEncodedInfo::kOpaque_Alpha : SkEncodedInfo::kUnpremul_Alpha,
+            8);
+        *result = kSuccess;
+        return std::unique_ptr<SkCodec>(
+            new PlatformHeifCodec(std::move(encodedInfo), std::move(stream), std::move(generator)));
+    }
+
+protected:
+    SkEncodedImageFormat onGetEncodedFormat() const override {
+        return SkEncodedImageFormat::kHEIF;
+    }
+
+    Result onGetPixels(const SkImageInfo& dstInfo, void* dst, size_t rowBytes, const Options&, int*) override {
+        return fGenerator->getPixels(dstInfo, dst, rowBytes) ? kSuccess : kInvalidConversion;
+    }
+
+private:
+    PlatformHeifCodec(SkEncodedInfo&& info, std::unique_ptr<SkStream> stream, std::unique_ptr<SkImageGenerator> generator)
+        : SkCodec(std::move(info), skcms_PixelFormat_RGBA_8888, std::move(stream)), fGenerator(std::move(generator)) {}
+
+    std::unique_ptr<SkImageGenerator> fGenerator;
+};
+
+}
+
+extern "C" void C_SkCodec_RegisterPlatformDecoders() {
+    SkCodec::Register(PlatformHeifCodec::IsHeif, PlatformHeifCodec::Make);
+}
@@ -0,0 +1,112 @@
+#include "bindings.h"
+
+#include "include/core/SkData.h"
+#include "include/core/SkImage.h"
+#include "include/core/SkTypeface.h"
+#include "modules/skresources/include/SkResources.h"
+
+namespace RustExternalTrack {
+    extern "C" typedef void (*Seek)(TraitObject, float);
+    extern "C" typedef void (*Drop)(TraitObject);
+
+    struct Param {
+        TraitObject trait;
+        Seek seek;
+        Drop drop;
+    };
+
+    class Track final : public skresources::ExternalTrackAsset {
+    public:
+        explicit Track(const Param& param) : _param(param) {
+        }
+
+        ~Track() override {
+            _param.drop(_param.trait);
+        }
+
+        void seek(float t) override {
+            _param.seek(_param.trait, t);
+        }
+
+    private:
+        Param _param;
+    };
+}
+
+namespace RustResourceProvider {
+    extern "C" typedef SkData* (*Load)(TraitObject, const char*, const char*);
+    extern "C" typedef SkImage* (*LoadImage)(TraitObject, const char*, const char*, const char*);
+    extern "C" typedef SkTypeface* (*LoadTypeface)(TraitObject, const char*, const char*);
+    // Returns `false` if there is no audio track, otherwise initializes `track`.
+    extern "C" typedef bool (*LoadAudio)(TraitObject, const char*, const char*, const char*, RustExternalTrack::Param* track);
+    extern "C" typedef void (*Drop)(TraitObject);
+
+    struct Param {
+        TraitObject trait;
+        Load load;
+        LoadImage loadImage;
+        LoadTypeface loadTypeface;
+        LoadAudio loadAudio;
+        Drop drop;
+    };
+
+    class StaticImageAsset final : public skresources::ImageAsset {
+    public:
+        explicit StaticImageAsset(sk_sp<SkImage> image) : _image(std::move(image)) {
+        }
+
+        bool isMultiFrame() override {
+            return false;
+        }
+
+        sk_sp<SkImage> getFrame(float) override {
+            return _image;
+        }
+
+    private:
+        sk_sp<SkImage> _image;
+    };
+
+    class Provider final : public skresources::ResourceProvider {
+    public:
+        explicit Provider(const Param& param) : _param(param) {
+        }
+
+        ~Provider() override {
+            _param.drop(_param.trait);
+        }
+
+        sk_sp<SkData> load(const char path[], const char name[]) const override {
+            return sp(_param.load(_param.trait, path ? path : "", name ? name : ""));
+        }
+
+        sk_sp<skresources::ImageAsset> loadImageAsset(const char path[], const char name[], const char id[]) const override {
+            auto image = sp(_param.loadImage(_param.trait, path ? path : "", name ? name : "", id ? id : ""));
+            if (!image) {
+                return nullptr;
+            }
+            return sk_make_sp<StaticImageAsset>(std::move(image));
+        }
+
+        sk_sp<SkTypeface> loadTypeface(const char name[], const char url[]) const override {
+            return sp(_param.loadTypeface(_param.trait, name ? name : "", url ? url : ""));
+        }
+
+        sk_sp<skresources::ExternalTrackAsset> loadAudioAsset(const char path[], const char name[], const char id[]) override {
+            RustExternalTrack::Param track;
+            if (!_param.loadAudio(_param.trait, path ? path : "", name ? name : "", id ? id : "", &track)) {
+                return nullptr;
+            }
+            return sk_make_sp<RustExternalTrack::Track>(track);
+        }
+
+    private:
+        Param _param;
+    };
+}
+
+extern "C" skresources::ResourceProvider* C_RustResourceProvider_new(const RustResourceProvider::Param* param) {
+    // Images embedded as data URIs are decoded without asking the Rust provider.
+    auto provider = sk_make_sp<RustResourceProvider::Provider>(*param);
+    return skresources::DataURIResourceProviderProxy::Make(std::move(provider)).release();
+}
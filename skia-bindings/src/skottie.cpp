@@ -0,0 +1,246 @@
+#include "bindings.h"
+
+#include "include/core/SkCanvas.h"
+#include "include/core/SkTypeface.h"
+#include "modules/skottie/include/Skottie.h"
+#include "modules/skottie/include/SkottieProperty.h"
+#include "modules/skresources/include/SkResources.h"
+
+//
+// modules/skottie/include/SkottieProperty.h
+//
+
+namespace RustPropertyObserver {
+    extern "C" typedef void (*OnColorProperty)(TraitObject, const char*, skottie::ColorPropertyHandle*);
+    extern "C" typedef void (*OnOpacityProperty)(TraitObject, const char*, skottie::OpacityPropertyHandle*);
+    extern "C" typedef void (*OnTextProperty)(TraitObject, const char*, skottie::TextPropertyHandle*);
+    extern "C" typedef void (*OnTransformProperty)(TraitObject, const char*, skottie::TransformPropertyHandle*);
+    extern "C" typedef void (*OnNode)(TraitObject, const char*, skottie::PropertyObserver::NodeType);
+
+    struct Param {
+        TraitObject trait;
+        OnColorProperty onColorProperty;
+        OnOpacityProperty onOpacityProperty;
+        OnTextProperty onTextProperty;
+        OnTransformProperty onTransformProperty;
+        OnNode onEnterNode;
+        OnNode onLeavingNode;
+    };
+
+    // The Rust observer is borrowed and only called while the animation is built.
+    class Observer final : public skottie::PropertyObserver {
+    public:
+        explicit Observer(const Param& param) : _param(param) {
+        }
+
+        void onColorProperty(const char nodeName[], const LazyHandle<skottie::ColorPropertyHandle>& lh) override {
+            _param.onColorProperty(_param.trait, nodeName ? nodeName : "", lh().release());
+        }
+
+        void onOpacityProperty(const char nodeName[], const LazyHandle<skottie::OpacityPropertyHandle>& lh) override {
+            _param.onOpacityProperty(_param.trait, nodeName ? nodeName : "", lh().release());
+        }
+
+        void onTextProperty(const char nodeName[], const LazyHandle<skottie::TextPropertyHandle>& lh) override {
+            _param.onTextProperty(_param.trait, nodeName ? nodeName : "", lh().release());
+        }
+
+        void onTransformProperty(const char nodeName[], const LazyHandle<skottie::TransformPropertyHandle>& lh) override {
+            _param.onTransformProperty(_param.trait, nodeName ? nodeName : "", lh().release());
+        }
+
+        void onEnterNode(const char nodeName[], NodeType nodeType) override {
+            _param.onEnterNode(_param.trait, nodeName ? nodeName : "", nodeType);
+        }
+
+        void onLeavingNode(const char nodeName[], NodeType nodeType) override {
+            _param.onLeavingNode(_param.trait, nodeName ? nodeName : "", nodeType);
+        }
+
+    private:
+        Param _param;
+    };
+}
+
+extern "C" void C_skottie_ColorPropertyHandle_delete(skottie::ColorPropertyHandle* self) {
+    delete self;
+}
+
+extern "C" SkColor C_skottie_ColorPropertyHandle_get(const skottie::ColorPropertyHandle* self) {
+    return self->get();
+}
+
+extern "C" void C_skottie_ColorPropertyHandle_set(skottie::ColorPropertyHandle* self, SkColor color) {
+    self->set(color);
+}
+
+extern "C" void C_skottie_OpacityPropertyHandle_delete(skottie::OpacityPropertyHandle* self) {
+    delete self;
+}
+
+extern "C" float C_skottie_OpacityPropertyHandle_get(const skottie::OpacityPropertyHandle* self) {
+    return self->get();
+}
+
+extern "C" void C_skottie_OpacityPropertyHandle_set(skottie::OpacityPropertyHandle* self, float opacity) {
+    self->set(opacity);
+}
+
+extern "C" void C_skottie_TransformPropertyHandle_delete(skottie::TransformPropertyHandle* self) {
+    delete self;
+}
+
+extern "C" void C_skottie_TransformPropertyHandle_get(const skottie::TransformPropertyHandle* self, skottie::TransformPropertyValue* value) {
+    *value = self->get();
+}
+
+extern "C" void C_skottie_TransformPropertyHandle_set(skottie::TransformPropertyHandle* self, const skottie::TransformPropertyValue* value) {
+    self->set(*value);
+}
+
+extern "C" void C_skottie_TextPropertyHandle_delete(skottie::TextPropertyHandle* self) {
+    delete self;
+}
+
+extern "C" void C_skottie_TextPropertyHandle_getText(const skottie::TextPropertyHandle* self, SkString* text) {
+    *text = self->get().fText;
+}
+
+extern "C" void C_skottie_TextPropertyHandle_setText(skottie::TextPropertyHandle* self, const char* text, size_t len) {
+    auto value = self->get();
+    value.fText = SkString(text, len);
+    self->set(value);
+}
+
+extern "C" float C_skottie_TextPropertyHandle_getTextSize(const skottie::TextPropertyHandle* self) {
+    return self->get().fTextSize;
+}
+
+extern "C" void C_skottie_TextPropertyHandle_setTextSize(skottie::TextPropertyHandle* self, float size) {
+    auto value = self->get();
+    value.fTextSize = size;
+    self->set(value);
+}
+
+extern "C" SkColor C_skottie_TextPropertyHandle_getFillColor(const skottie::TextPropertyHandle* self) {
+    return self->get().fFillColor;
+}
+
+extern "C" void C_skottie_TextPropertyHandle_setFillColor(skottie::TextPropertyHandle* self, SkColor color) {
+    auto value = self->get();
+    value.fFillColor = color;
+    self->set(value);
+}
+
+extern "C" SkColor C_skottie_TextPropertyHandle_getStrokeColor(const skottie::TextPropertyHandle* self) {
+    return self->get().fStrokeColor;
+}
+
+extern "C" void C_skottie_TextPropertyHandle_setStrokeColor(skottie::TextPropertyHandle* self, SkColor color) {
+    auto value = self->get();
+    value.fStrokeColor = color;
+    self->set(value);
+}
+
+extern "C" SkTypeface* C_skottie_TextPropertyHandle_getTypeface(const skottie::TextPropertyHandle* self) {
+    return self->get().fTypeface.release();
+}
+
+extern "C" void C_skottie_TextPropertyHandle_setTypeface(skottie::TextPropertyHandle* self, SkTypeface* typeface) {
+    auto value = self->get();
+    value.fTypeface = sp(typeface);
+    self->set(value);
+}
+
+//
+// modules/skottie/include/Skottie.h
+//
+
+namespace RustMarkerObserver {
+    extern "C" typedef void (*OnMarker)(TraitObject, const char*, float, float);
+
+    struct Param {
+        TraitObject trait;
+        OnMarker onMarker;
+    };
+
+    // The Rust observer is borrowed and only called while the animation is built.
+    class Observer final : public skottie::MarkerObserver {
+    public:
+        explicit Observer(const Param& param) : _param(param) {
+        }
+
+        void onMarker(const char name[], const float& t0, const float& t1) override {
+            _param.onMarker(_param.trait, name ? name : "", t0, t1);
+        }
+
+    private:
+        Param _param;
+    };
+}
+
+extern "C" skottie::Animation* C_skottie_Animation_Make(
+        const char* data, size_t length,
+        skresources::ResourceProvider* resourceProvider,
+        const RustPropertyObserver::Param* propertyObserver,
+        const RustMarkerObserver::Param* markerObserver) {
+    skottie::Animation::Builder builder;
+    if (resourceProvider) {
+        builder.setResourceProvider(sp(resourceProvider));
+    }
+    if (propertyObserver) {
+        builder.setPropertyObserver(sk_make_sp<RustPropertyObserver::Observer>(*propertyObserver));
+    }
+    if (markerObserver) {
+        builder.setMarkerObserver(sk_make_sp<RustMarkerObserver::Observer>(*markerObserver));
+    }
+    return builder.make(data, length).release();
+}
+
+extern "C" void C_skottie_Animation_ref(const skottie::Animation* self) {
+    self->ref();
+}
+
+extern "C" void C_skottie_Animation_unref(const skottie::Animation* self) {
+    self->unref();
+}
+
+extern "C" bool C_skottie_Animation_unique(const skottie::Animation* self) {
+    return self->unique();
+}
+
+extern "C" void C_skottie_Animation_version(const skottie::Animation* self, SkString* version) {
+    *version = self->version();
+}
+
+extern "C" double C_skottie_Animation_duration(const skottie::Animation* self) {
+    return self->duration();
+}
+
+extern "C" double C_skottie_Animation_fps(const skottie::Animation* self) {
+    return self->fps();
+}
+
+extern "C" double C_skottie_Animation_inPoint(const skottie::Animation* self) {
+    return self->inPoint();
+}
+
+extern "C" double C_skottie_Animation_outPoint(const skottie::Animation* self) {
+    return self->outPoint();
+}
+
+extern "C" void C_skottie_Animation_size(const skottie::Animation* self, SkSize* size) {
+    *size = self->size();
+}
+
+extern "C" void C_skottie_Animation_seekFrame(skottie::Animation* self, double t) {
+    self->seekFrame(t);
+}
+
+extern "C" void C_skottie_Animation_seekFrameTime(skottie::Animation* self, double t) {
+    self->seekFrameTime(t);
+}
+
+extern "C" void C_skottie_Animation_render(const skottie::Animation* self, SkCanvas* canvas, const SkRect* dst) {
+    self->render(canvas, dst);
+}
@@ -5,6 +5,8 @@
 
 #include "modules/svg/include/SkSVGDOM.h"
 #include "modules/svg/include/SkSVGNode.h"
+#include "modules/svg/include/SkSVGSVG.h"
+#include "modules/svg/include/SkSVGText.h"
 #include "modules/skresources/include/SkResources.h"
 
 #include "include/core/SkStream.h"
@@ -51,6 +53,10 @@ public:
 };
 
 
+//
+// modules/svg/include/SkSVGDOM.h
+//
+
 extern "C" SkSVGDOM* C_SkSVGDOM_MakeFromStream(SkStream& stream, loadSkData loadCb, loadSkTypeface loadTfCb) {
     auto provider = sk_make_sp<ImageResourceProvider>(loadCb, loadTfCb);
     auto builder = SkSVGDOM::Builder();
@@ -58,6 +64,12 @@ extern "C" SkSVGDOM* C_SkSVGDOM_MakeFromStream(SkStream& stream, loadSkData load
     return builder.make(stream).release();
 }
 
+extern "C" SkSVGDOM* C_SkSVGDOM_MakeFromStreamWithResourceProvider(SkStream& stream, skresources::ResourceProvider* provider) {
+    auto builder = SkSVGDOM::Builder();
+    builder.setResourceProvider(sp(provider));
+    return builder.make(stream).release();
+}
+
 extern "C" void C_SkSVGDOM_ref(const SkSVGDOM* self) {
     self->ref();
 }
@@ -73,3 +85,62 @@ extern "C" bool C_SkSVGDOM_unique(const SkSVGDOM* self) {
 extern "C" void C_SkSVGDOM_setContainerSize(SkSVGDOM* self, const SkSize& size){
     self->setContainerSize(size);
 }
+
+extern "C" SkSVGNode* C_SkSVGDOM_getRoot(const SkSVGDOM* self) {
+    return SkSafeRef(self->getRoot());
+}
+
+extern "C" SkSVGNode* C_SkSVGDOM_findNodeById(SkSVGDOM* self, const char* id) {
+    auto node = self->findNodeById(id);
+    return node ? SkSafeRef(node->get()) : nullptr;
+}
+
+//
+// modules/svg/include/SkSVGNode.h
+//
+
+extern "C" void C_SkSVGNode_ref(const SkSVGNode* self) {
+    self->ref();
+}
+
+extern "C" void C_SkSVGNode_unref(const SkSVGNode* self) {
+    self->unref();
+}
+
+extern "C" bool C_SkSVGNode_unique(const SkSVGNode* self) {
+    return self->unique();
+}
+
+extern "C" SkSVGTag C_SkSVGNode_tag(const SkSVGNode* self) {
+    return self->tag();
+}
+
+extern "C" bool C_SkSVGNode_setAttribute(SkSVGNode* self, const char* name, const char* value) {
+    return self->setAttribute(name, value);
+}
+
+extern "C" void C_SkSVGNode_appendChild(SkSVGNode* self, SkSVGNode* child) {
+    self->appendChild(sk_sp<SkSVGNode>(child));
+}
+
+//
+// modules/svg/include/SkSVGText.h
+//
+
+extern "C" SkSVGNode* C_SkSVGText_Make() {
+    return SkSVGText::Make().release();
+}
+
+extern "C" SkSVGNode* C_SkSVGTextLiteral_Make(const char* text) {
+    auto literal = SkSVGTextLiteral::Make();
+    literal->setText(SkString(text));
+    return literal.release();
+}
+
+extern "C" void C_SkSVGTextLiteral_getText(const SkSVGNode* self, SkString* text) {
+    *text = static_cast<const SkSVGTextLiteral*>(self)->getText();
+}
+
+extern "C" void C_SkSVGTextLiteral_setText(SkSVGNode* self, const char* text) {
+    static_cast<SkSVGTextLiteral*>(self)->setText(SkString(text));
+}
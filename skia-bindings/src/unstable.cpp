@@ -0,0 +1,180 @@
+#include "bindings.h"
+
+// Bindings that depend on Skia's private headers. Their API may change with any Skia update, which
+// is why they are only available with the `unstable` feature.
+
+#include "include/codec/SkAndroidCodec.h"
+#include "include/core/SkMatrix.h"
+#include "include/core/SkPath.h"
+#include "include/core/SkRect.h"
+#include "include/core/SkStrokeRec.h"
+#include "include/docs/SkDocument.h"
+#include "include/private/SkGainmapInfo.h"
+#include "include/private/SkGainmapShader.h"
+#include "src/core/SkPathEffectBase.h"
+#include "src/utils/SkMultiPictureDocument.h"
+
+//
+// codec/SkAndroidCodec.h
+//
+
+extern "C" SkData* C_SkAndroidCodec_getGainmap(SkData* data, SkGainmapInfo* info) {
+    auto codec = SkAndroidCodec::MakeFromData(sp(data));
+    if (!codec) {
+        return nullptr;
+    }
+    std::unique_ptr<SkStream> gainmapStream;
+    if (!codec->getAndroidGainmap(info, &gainmapStream) || !gainmapStream || !gainmapStream->hasLength()) {
+        return nullptr;
+    }
+    return SkData::MakeFromStream(gainmapStream.get(), gainmapStream->getLength()).release();
+}
+
+//
+// include/private/SkGainmapShader.h
+//
+
+extern "C" SkShader* C_SkGainmapShader_Make(
+    SkImage* baseImage, const SkRect* baseRect, const SkSamplingOptions* baseSamplingOptions,
+    SkImage* gainmapImage, const SkRect* gainmapRect, const SkSamplingOptions* gainmapSamplingOptions,
+    const SkGainmapInfo* gainmapInfo, const SkRect* dstRect, float dstHdrRatio, SkColorSpace* dstColorSpace) {
+    return SkGainmapShader::Make(
+        sp(baseImage), *baseRect, *baseSamplingOptions,
+        sp(gainmapImage), *gainmapRect, *gainmapSamplingOptions,
+        *gainmapInfo, *dstRect, dstHdrRatio, sp(dstColorSpace)).release();
+}
+
+//
+// SkPathEffect
+//
+
+namespace RustPathEffect {
+    extern "C" typedef bool (*FilterPath)(TraitObject, SkPath*, const SkPath*, SkStrokeRec*, const SkRect*, const SkMatrix*);
+    extern "C" typedef bool (*ComputeFastBounds)(TraitObject, SkRect*);
+    extern "C" typedef void (*Drop)(TraitObject);
+
+    struct Param {
+        TraitObject trait;
+        FilterPath filterPath;
+        ComputeFastBounds computeFastBounds;
+        bool needsCTM;
+        Drop drop;
+    };
+
+    class PathEffect: public SkPathEffectBase {
+    public:
+        explicit PathEffect(const Param& param) : _param(param) {
+        }
+
+        ~PathEffect() override {
+            _param.drop(_param.trait);
+        }
+
+        bool computeFastBounds(SkRect* bounds) const override {
+            return _param.computeFastBounds(_param.trait, bounds);
+        }
+
+        // Path effects implemented in Rust can not be deserialized.
+        Factory getFactory() const override {
+            return nullptr;
+        }
+
+        const char* getTypeName() const override {
+            return "RustPathEffect";
+        }
+
+    protected:
+        bool onFilterPath(SkPath* dst, const SkPath& src, SkStrokeRec* rec, const SkRect* cullR,
+                          const SkMatrix& ctm) const override {
+            return _param.filterPath(_param.trait, dst, &src, rec, cullR, &ctm);
+        }
+
+        bool onNeedsCTM() const override {
+            return _param.needsCTM;
+        }
+
+    private:
+        Param _param;
+    };
+}
+
+extern "C" SkPathEffect* C_RustPathEffect_new(const RustPathEffect::Param* param) {
+    return new RustPathEffect::PathEffect(*param);
+}
+
+//
+// utils/SkMultiPictureDocument.h
+//
+
+extern "C" SkDocument* C_SkMultiPictureDocument_Make(SkWStream* stream, const RustSerialProcs* procs) {
+    if (!procs) {
+        return SkMakeMultiPictureDocument(stream).release();
+    }
+    // The pages are serialized when the document closes, so the end page callback keeps a copy
+    // of the procs alive as long as the document.
+    auto rustProcs = std::make_shared<RustSerialProcs>(*procs);
+    auto serialProcs = toSerialProcs(rustProcs.get());
+    return SkMakeMultiPictureDocument(stream, &serialProcs, [rustProcs](const SkPicture*) {}).release();
+}
+
+extern "C" int C_SkMultiPictureDocument_ReadPageCount(const void* data, size_t length) {
+    SkMemoryStream stream(data, length, false);
+    return SkMultiPictureDocumentReadPageCount(&stream);
+}
+
+extern "C" bool C_SkMultiPictureDocument_Read(
+    const void* data, size_t length,
+    SkPicture** pictures, SkSize* sizes, int count,
+    const RustDeserialProcs* procs) {
+    SkMemoryStream stream(data, length, false);
+    std::vector<SkDocumentPage> pages(count);
+    SkDeserialProcs deserialProcs;
+    if (procs) {
+        deserialProcs = toDeserialProcs(procs);
+    }
+    if (!SkMultiPictureDocumentRead(&stream, pages.data(), count, procs ? &deserialProcs : nullptr)) {
+        return false;
+    }
+    for (int i = 0; i < count; ++i) {
+        pictures[i] = pages[i].fPicture.release();
+        sizes[i] = pages[i].fSize;
+    }
+    return true;
+}
+
+#if SK_SUPPORT_GPU
+
+#include "src/gpu/ganesh/GrEagerVertexAllocator.h"
+#include "src/gpu/ganesh/geometry/GrTriangulator.h"
+
+//
+// src/gpu/ganesh/geometry/GrTriangulator.h
+//
+
+namespace {
+    class PointVertexAllocator : public GrEagerVertexAllocator {
+    public:
+        void* lock(size_t stride, int eagerCount) override {
+            SkASSERT(stride == sizeof(SkPoint));
+            fPoints.resize(eagerCount);
+            return fPoints.data();
+        }
+
+        void unlock(int actualCount) override {
+            fPoints.resize(actualCount);
+        }
+
+        std::vector<SkPoint> fPoints;
+    };
+}
+
+extern "C" void C_GrTriangulator_PathToTriangles(
+        const SkPath* path, SkScalar tolerance, const SkRect* clipBounds,
+        VecSink<SkPoint>* triangles, bool* isLinear) {
+    PointVertexAllocator allocator;
+    int count = GrTriangulator::PathToTriangles(*path, tolerance, *clipBounds, &allocator, isLinear);
+    allocator.fPoints.resize(count);
+    triangles->set(allocator.fPoints);
+}
+
+#endif
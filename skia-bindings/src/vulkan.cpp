@@ -2,12 +2,18 @@
     #define SK_VULKAN
 #endif
 
+#include "bindings.h"
 #include "include/gpu/GrBackendDrawableInfo.h"
+#include "include/gpu/GrBackendSemaphore.h"
 #include "include/gpu/GrBackendSurface.h"
 #include "include/gpu/GrDirectContext.h"
 #include "include/gpu/vk/GrVkTypes.h"
 #include "include/gpu/vk/GrVkBackendContext.h"
 #include "include/gpu/vk/GrVkExtensions.h"
+#include "include/gpu/vk/VulkanMemoryAllocator.h"
+#include "include/private/chromium/GrVkSecondaryCBDrawContext.h"
+#include "include/core/SkDeferredDisplayList.h"
+#include "include/core/SkSurfaceCharacterization.h"
 
 // Additional types not yet referenced.
 extern "C" void C_GrVkTypes(GrVkSurfaceInfo *) {};
@@ -20,6 +26,10 @@ extern "C" void C_GrBackendFormat_ConstructVk2(GrBackendFormat* uninitialized, c
     new(uninitialized)GrBackendFormat(GrBackendFormat::MakeVk(*ycbcrInfo, willUseDRMFormatModifiers));
 }
 
+extern "C" const GrVkYcbcrConversionInfo* C_GrBackendFormat_getVkYcbcrConversionInfo(const GrBackendFormat* self) {
+    return self->getVkYcbcrConversionInfo();
+}
+
 extern "C" GrBackendTexture* C_GrBackendTexture_NewVk(
     int width, int height,
     const GrVkImageInfo* vkInfo,
@@ -36,7 +46,7 @@ extern "C" bool C_GrBackendDrawableInfo_getVkDrawableInfo(const GrBackendDrawabl
     return self->getVkDrawableInfo(info);
 }
 
-extern "C" void C_GPU_VK_Types(GrVkExtensionFlags *, GrVkFeatureFlags *, VkBuffer *) {}
+extern "C" void C_GPU_VK_Types(GrVkExtensionFlags *, GrVkFeatureFlags *, VkBuffer *, skgpu::VulkanMemoryAllocator::AllocationPropertyFlags *) {}
 
 typedef PFN_vkVoidFunction (*GetProcFn)(const char* name, VkInstance instance, VkDevice device);
 typedef const void* (*GetProcFnVoidPtr)(const char* name, VkInstance instance, VkDevice device);
@@ -83,10 +93,175 @@ extern "C" void C_GrVkBackendContext_setProtectedContext(GrVkBackendContext *sel
     self->fProtectedContext = protectedContext;
 }
 
+extern "C" void C_GrVkBackendContext_setDeviceFeatures2(GrVkBackendContext *self, const VkPhysicalDeviceFeatures2* features) {
+    self->fDeviceFeatures2 = features;
+}
+
+extern "C" void C_GrVkBackendContext_setMemoryAllocator(GrVkBackendContext *self, skgpu::VulkanMemoryAllocator* allocator) {
+    self->fMemoryAllocator = sp(allocator);
+}
+
 extern "C" void C_GrVkBackendContext_setMaxAPIVersion(GrVkBackendContext *self, uint32_t maxAPIVersion) {
     self->fMaxAPIVersion = maxAPIVersion;
 }
 
+//
+// gpu/vk/VulkanMemoryAllocator.h
+//
+
+namespace RustMemoryAllocator {
+    extern "C" typedef bool (*AllocateImageMemory)(TraitObject, VkImage, uint32_t, skgpu::VulkanBackendMemory*);
+    extern "C" typedef bool (*AllocateBufferMemory)(TraitObject, VkBuffer, skgpu::VulkanMemoryAllocator::BufferUsage, uint32_t, skgpu::VulkanBackendMemory*);
+    extern "C" typedef void (*GetAllocInfo)(TraitObject, skgpu::VulkanBackendMemory, skgpu::VulkanAlloc*);
+    extern "C" typedef void* (*MapMemory)(TraitObject, skgpu::VulkanBackendMemory);
+    extern "C" typedef void (*UnmapMemory)(TraitObject, skgpu::VulkanBackendMemory);
+    extern "C" typedef bool (*FlushMemory)(TraitObject, skgpu::VulkanBackendMemory, VkDeviceSize, VkDeviceSize);
+    extern "C" typedef void (*FreeMemory)(TraitObject, skgpu::VulkanBackendMemory);
+    extern "C" typedef void (*TotalAllocatedAndUsedMemory)(TraitObject, uint64_t*, uint64_t*);
+    extern "C" typedef void (*Drop)(TraitObject);
+
+    struct Param {
+        TraitObject trait;
+        AllocateImageMemory allocateImageMemory;
+        AllocateBufferMemory allocateBufferMemory;
+        GetAllocInfo getAllocInfo;
+        MapMemory mapMemory;
+        UnmapMemory unmapMemory;
+        FlushMemory flushMemory;
+        FlushMemory invalidateMemory;
+        FreeMemory freeMemory;
+        TotalAllocatedAndUsedMemory totalAllocatedAndUsedMemory;
+        Drop drop;
+    };
+
+    class MemoryAllocator : public skgpu::VulkanMemoryAllocator {
+    public:
+        explicit MemoryAllocator(const Param& param)
+        : _param(param) {
+        }
+
+        ~MemoryAllocator() override {
+            _param.drop(_param.trait);
+        }
+
+        VkResult allocateImageMemory(VkImage image, uint32_t allocationPropertyFlags, skgpu::VulkanBackendMemory* memory) override {
+            return _param.allocateImageMemory(_param.trait, image, allocationPropertyFlags, memory)
+                ? VK_SUCCESS
+                : VK_ERROR_OUT_OF_DEVICE_MEMORY;
+        }
+
+        VkResult allocateBufferMemory(VkBuffer buffer, BufferUsage usage, uint32_t allocationPropertyFlags, skgpu::VulkanBackendMemory* memory) override {
+            return _param.allocateBufferMemory(_param.trait, buffer, usage, allocationPropertyFlags, memory)
+                ? VK_SUCCESS
+                : VK_ERROR_OUT_OF_DEVICE_MEMORY;
+        }
+
+        void getAllocInfo(const skgpu::VulkanBackendMemory& memory, skgpu::VulkanAlloc* alloc) const override {
+            _param.getAllocInfo(_param.trait, memory, alloc);
+        }
+
+        VkResult mapMemory(const skgpu::VulkanBackendMemory& memory, void** data) override {
+            *data = _param.mapMemory(_param.trait, memory);
+            return *data ? VK_SUCCESS : VK_ERROR_MEMORY_MAP_FAILED;
+        }
+
+        void unmapMemory(const skgpu::VulkanBackendMemory& memory) override {
+            _param.unmapMemory(_param.trait, memory);
+        }
+
+        VkResult flushMemory(const skgpu::VulkanBackendMemory& memory, VkDeviceSize offset, VkDeviceSize size) override {
+            return _param.flushMemory(_param.trait, memory, offset, size)
+                ? VK_SUCCESS
+                : VK_ERROR_OUT_OF_HOST_MEMORY;
+        }
+
+        VkResult invalidateMemory(const skgpu::VulkanBackendMemory& memory, VkDeviceSize offset, VkDeviceSize size) override {
+            return _param.invalidateMemory(_param.trait, memory, offset, size)
+                ? VK_SUCCESS
+                : VK_ERROR_OUT_OF_HOST_MEMORY;
+        }
+
+        void freeMemory(const skgpu::VulkanBackendMemory& memory) override {
+            _param.freeMemory(_param.trait, memory);
+        }
+
+        std::pair<uint64_t, uint64_t> totalAllocatedAndUsedMemory() const override {
+            uint64_t allocated = 0;
+            uint64_t used = 0;
+            _param.totalAllocatedAndUsedMemory(_param.trait, &allocated, &used);
+            return std::make_pair(allocated, used);
+        }
+
+    private:
+        Param _param;
+    };
+}
+
+extern "C" skgpu::VulkanMemoryAllocator* C_RustMemoryAllocator_new(const RustMemoryAllocator::Param* param) {
+    return new RustMemoryAllocator::MemoryAllocator(*param);
+}
+
+//
+// private/chromium/GrVkSecondaryCBDrawContext.h
+//
+
+extern "C" GrVkSecondaryCBDrawContext* C_GrVkSecondaryCBDrawContext_Make(
+    GrRecordingContext* context,
+    const SkImageInfo* imageInfo,
+    const GrVkDrawableInfo* vkInfo,
+    const SkSurfaceProps* props) {
+    return GrVkSecondaryCBDrawContext::Make(context, *imageInfo, *vkInfo, props).release();
+}
+
+extern "C" void C_GrVkSecondaryCBDrawContext_ref(const GrVkSecondaryCBDrawContext* self) {
+    self->ref();
+}
+
+extern "C" void C_GrVkSecondaryCBDrawContext_unref(const GrVkSecondaryCBDrawContext* self) {
+    // Skia asserts that the resources are released before the context is destroyed, and
+    // releasing them twice is harmless.
+    if (self->unique()) {
+        const_cast<GrVkSecondaryCBDrawContext*>(self)->releaseResources();
+    }
+    self->unref();
+}
+
+extern "C" bool C_GrVkSecondaryCBDrawContext_unique(const GrVkSecondaryCBDrawContext* self) {
+    return self->unique();
+}
+
+extern "C" SkCanvas* C_GrVkSecondaryCBDrawContext_getCanvas(GrVkSecondaryCBDrawContext* self) {
+    return self->getCanvas();
+}
+
+extern "C" void C_GrVkSecondaryCBDrawContext_flush(GrVkSecondaryCBDrawContext* self) {
+    self->flush();
+}
+
+extern "C" bool C_GrVkSecondaryCBDrawContext_wait(GrVkSecondaryCBDrawContext* self, const GrBackendSemaphore* waitSemaphores, size_t count, bool deleteSemaphoresAfterWait) {
+    return self->wait(count, waitSemaphores, deleteSemaphoresAfterWait);
+}
+
+extern "C" void C_GrVkSecondaryCBDrawContext_releaseResources(GrVkSecondaryCBDrawContext* self) {
+    self->releaseResources();
+}
+
+extern "C" const SkSurfaceProps* C_GrVkSecondaryCBDrawContext_props(const GrVkSecondaryCBDrawContext* self) {
+    return &self->props();
+}
+
+extern "C" bool C_GrVkSecondaryCBDrawContext_characterize(const GrVkSecondaryCBDrawContext* self, SkSurfaceCharacterization* characterization) {
+    return self->characterize(characterization);
+}
+
+extern "C" bool C_GrVkSecondaryCBDrawContext_draw(GrVkSecondaryCBDrawContext* self, const SkDeferredDisplayList* displayList) {
+    return self->draw(sp(displayList));
+}
+
+extern "C" bool C_GrVkSecondaryCBDrawContext_isCompatible(const GrVkSecondaryCBDrawContext* self, const SkSurfaceCharacterization* characterization) {
+    return self->isCompatible(*characterization);
+}
+
 extern "C" GrDirectContext* C_GrDirectContext_MakeVulkan(
     const GrVkBackendContext* vkBackendContext,
     const GrContextOptions* options) {
@@ -131,3 +306,15 @@ extern "C" VkImageLayout C_MutableTextureState_getVkImageLayout(const skgpu::Mut
 extern "C" uint32_t C_MutableTextureState_getQueueFamilyIndex(const skgpu::MutableTextureState* self) {
     return self->getQueueFamilyIndex();
 }
+
+//
+// gpu/GrBackendSemaphore.h
+//
+
+extern "C" void C_GrBackendSemaphore_initVulkan(GrBackendSemaphore* self, VkSemaphore semaphore) {
+    self->initVulkan(semaphore);
+}
+
+extern "C" VkSemaphore C_GrBackendSemaphore_vkSemaphore(const GrBackendSemaphore* self) {
+    return self->vkSemaphore();
+}
@@ -0,0 +1,18 @@
+#include "bindings.h"
+
+#include "include/core/SkPixmap.h"
+#include "include/core/SkStream.h"
+#include "include/encode/SkWebpEncoder.h"
+
+//
+// encode/SkWebpEncoder.h
+//
+
+extern "C" bool C_SkWebpEncoder_Encode(
+    SkWStream* dst, const SkPixmap* src,
+    SkWebpEncoder::Compression compression, float quality) {
+    SkWebpEncoder::Options options;
+    options.fCompression = compression;
+    options.fQuality = quality;
+    return SkWebpEncoder::Encode(dst, *src, options);
+}
@@ -0,0 +1,186 @@
+//! Derive macros for `skia-safe`. Use them through the `derive` feature of `skia-safe`, which
+//! re-exports them.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, LitStr, Path, Result,
+    Type,
+};
+
+/// Implements `skia_safe::runtime_effect::SkslUniforms` for a `#[repr(C)]` struct whose fields
+/// mirror the uniforms of a runtime effect.
+///
+/// Each field type must implement `skia_safe::runtime_effect::UniformValue`. Field attributes:
+///
+/// - `#[sksl(name = "...")]` sets the name of the uniform, which defaults to the field name.
+/// - `#[sksl(array)]` marks a Rust array as an SkSL array. Without it, `[f32; 2]` is a `float2`,
+///   with it, a `float[2]`.
+///
+/// If `skia-safe` is renamed in `Cargo.toml` or re-exported by another crate, set the path to it
+/// with `#[sksl(crate = "...")]` on the struct, for example `#[sksl(crate = "my_crate::skia")]`.
+///
+/// The layout is checked at compile time: fields must not be padded.
+#[proc_macro_derive(SkslUniforms, attributes(sksl))]
+pub fn derive_sksl_uniforms(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    sksl_uniforms(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+struct Field {
+    name: String,
+    ty: Type,
+    count: TokenStream2,
+}
+
+fn sksl_uniforms(input: DeriveInput) -> Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "SkslUniforms can not be derived for generic types",
+        ));
+    }
+    if !is_repr_c(&input)? {
+        return Err(Error::new(
+            input.ident.span(),
+            "SkslUniforms requires #[repr(C)]",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "SkslUniforms requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "SkslUniforms can only be derived for structs",
+            ))
+        }
+    };
+
+    let fields = fields
+        .iter()
+        .map(|field| {
+            let mut name = field.ident.as_ref().unwrap().to_string();
+            let mut array = false;
+            for attr in field.attrs.iter().filter(|a| a.path().is_ident("sksl")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("name") {
+                        name = meta.value()?.parse::<LitStr>()?.value();
+                        Ok(())
+                    } else if meta.path.is_ident("array") {
+                        array = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `name = \"...\"` or `array`"))
+                    }
+                })?;
+            }
+            if !array {
+                return Ok(Field {
+                    name,
+                    ty: field.ty.clone(),
+                    count: quote!(1usize),
+                });
+            }
+            match &field.ty {
+                Type::Array(array) => {
+                    let len = &array.len;
+                    Ok(Field {
+                        name,
+                        ty: (*array.elem).clone(),
+                        count: quote!((#len) as usize),
+                    })
+                }
+                ty => Err(Error::new(
+                    ty.span(),
+                    "#[sksl(array)] requires an array type",
+                )),
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let ident = &input.ident;
+    let krate = crate_path(&input)?;
+    let rt = quote!(#krate::runtime_effect);
+
+    let mut offset = quote!(0usize);
+    let mut descriptors = Vec::new();
+    let mut checks = Vec::new();
+    for Field { name, ty, count } in &fields {
+        descriptors.push(quote! {
+            #rt::UniformField {
+                name: #name,
+                ty: <#ty as #rt::UniformValue>::TYPE,
+                count: #count,
+                offset: #offset,
+            }
+        });
+        checks.push(quote! {
+            assert!(
+                ::core::mem::size_of::<#ty>()
+                    == <#ty as #rt::UniformValue>::TYPE.component_count() * 4,
+                concat!("the type of uniform `", #name, "` has an invalid size"),
+            );
+        });
+        offset = quote!(#offset + ::core::mem::size_of::<#ty>() * #count);
+    }
+
+    Ok(quote! {
+        unsafe impl #rt::SkslUniforms for #ident {
+            const FIELDS: &'static [#rt::UniformField] = &[#(#descriptors),*];
+        }
+
+        const _: () = {
+            #(#checks)*
+            assert!(
+                ::core::mem::size_of::<#ident>() == #offset,
+                concat!("`", stringify!(#ident), "` must not contain padding"),
+            );
+        };
+    })
+}
+
+/// Returns the path of the `skia-safe` crate, `::skia_safe` unless set with
+/// `#[sksl(crate = "...")]`.
+fn crate_path(input: &DeriveInput) -> Result<Path> {
+    let mut path = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("sksl")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                path = Some(meta.value()?.parse::<LitStr>()?.parse::<Path>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `crate = \"...\"`"))
+            }
+        })?;
+    }
+    Ok(path.unwrap_or_else(|| syn::parse_quote!(::skia_safe)))
+}
+
+fn is_repr_c(input: &DeriveInput) -> Result<bool> {
+    let mut repr_c = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") {
+                repr_c = true;
+            }
+            // Skip arguments like in `align(4)`.
+            if meta.input.peek(syn::token::Paren) {
+                let _args;
+                syn::parenthesized!(_args in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+    Ok(repr_c)
+}
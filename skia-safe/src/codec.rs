@@ -1,4 +1,4 @@
-// TODO: wrap SkAndroidCodec.h, SkCodecAnimation.h
+// TODO: wrap SkAndroidCodec.h (only the gainmap is exposed), SkCodecAnimation.h
 
 mod _codec;
 pub mod codec_animation;
@@ -6,3 +6,19 @@ pub use _codec::*;
 
 mod encoded_origin;
 pub use encoded_origin::*;
+
+mod frame_decoder;
+pub use frame_decoder::*;
+
+#[cfg(feature = "unstable")]
+mod gainmap;
+#[cfg(feature = "unstable")]
+pub use gainmap::*;
+
+mod partial_data;
+pub use partial_data::*;
+
+#[cfg(feature = "platform-codecs")]
+mod platform_codecs;
+#[cfg(feature = "platform-codecs")]
+pub use platform_codecs::*;
@@ -1,11 +1,12 @@
 use super::codec_animation;
 use crate::{
-    prelude::*, yuva_pixmap_info::SupportedDataTypes, AlphaType, Data, EncodedImageFormat,
-    EncodedOrigin, IRect, ISize, Image, ImageInfo, Pixmap, YUVAPixmapInfo, YUVAPixmaps,
+    interop::RustStream, prelude::*, yuva_pixmap_info::SupportedDataTypes, AlphaType, Data,
+    EncodedImageFormat, EncodedOrigin, IRect, ISize, Image, ImageInfo, Pixmap, YUVAPixmapInfo,
+    YUVAPixmaps,
 };
 use ffi::CStr;
 use skia_bindings::{self as sb, SkCodec, SkCodec_FrameInfo, SkCodec_Options};
-use std::{ffi, fmt, mem, ptr};
+use std::{ffi, fmt, io, mem, ptr};
 
 pub use sb::SkCodec_Result as Result;
 variant_name!(Result::IncompleteInput);
@@ -79,13 +80,27 @@ impl fmt::Debug for Codec {
 }
 
 impl Codec {
-    // TODO: wrap MakeFromStream
     // TODO: wrap from_data with SkPngChunkReader
 
     pub fn from_data(data: impl Into<Data>) -> Option<Codec> {
         Codec::from_ptr(unsafe { sb::C_SkCodec_MakeFromData(data.into().into_ptr()) })
     }
 
+    /// Creates a codec that reads the encoded image from `reader` while decoding.
+    ///
+    /// Only the header is read here. With [`Self::start_incremental_decode()`] and
+    /// [`Self::incremental_decode()`], or with the scanline decoder, an image can be decoded
+    /// while more data arrives: when `reader` runs out of data, [`Result::IncompleteInput`] is
+    /// returned and decoding can be continued later. [`PartialData`](super::PartialData) is a
+    /// reader for data that arrives in parts.
+    ///
+    /// `reader` can not be rewound, so each frame can only be decoded once.
+    pub fn from_stream(reader: impl io::Read + 'static) -> std::result::Result<Codec, Result> {
+        let stream = RustStream::new_owned(reader);
+        let mut result = Result::InternalError;
+        Codec::from_ptr(unsafe { sb::C_SkCodec_MakeFromStream(stream, &mut result) }).ok_or(result)
+    }
+
     pub fn info(&self) -> ImageInfo {
         let mut info = ImageInfo::default();
         unsafe { sb::C_SkCodec_getInfo(self.native(), info.native_mut()) };
@@ -318,6 +333,13 @@ impl Codec {
         .then_some(info)
     }
 
+    /// The [`FrameInfo`] of every frame. Empty for still images.
+    pub fn frame_infos(&mut self) -> Vec<FrameInfo> {
+        (0..self.get_frame_count())
+            .filter_map(|index| self.get_frame_info(index))
+            .collect()
+    }
+
     pub fn get_repetition_count(&mut self) -> Option<usize> {
         const REPETITION_COUNT_INFINITE: i32 = -1;
         let count = unsafe { sb::C_SkCodec_getRepetitionCount(self.native_mut()) };
@@ -330,3 +352,17 @@ impl Codec {
 
     // TODO: Register
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Codec, Result};
+
+    #[test]
+    fn from_stream_rejects_unknown_formats() {
+        let reader: &'static [u8] = &[0u8; 32];
+        assert_eq!(
+            Codec::from_stream(reader).err(),
+            Some(Result::Unimplemented)
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use super::{codec_animation::DisposalMethod, Codec, FrameInfo, Options, Result, ZeroInitialized};
+use crate::{AlphaType, Bitmap, ColorType, Image};
+
+/// Decodes the frames of an animated image (GIF, WebP, APNG) into a single bitmap.
+///
+/// Frames usually only contain the pixels that changed since a previous frame, their
+/// _required frame_. [`FrameDecoder::decode_frame()`] reuses the frame that is already in the
+/// bitmap whenever the codec can continue from it, so playing an animation in order decodes
+/// every frame only once. Seeking to an unrelated frame lets the codec decode the required
+/// frames first.
+#[derive(Debug)]
+pub struct FrameDecoder {
+    codec: Codec,
+    frame_infos: Vec<FrameInfo>,
+    bitmap: Bitmap,
+    current_frame: Option<usize>,
+}
+
+impl FrameDecoder {
+    /// Creates a frame decoder that decodes into an N32 premultiplied bitmap of the codec's
+    /// dimensions.
+    ///
+    /// Returns `None` if the bitmap can not be allocated.
+    pub fn new(mut codec: Codec) -> Option<Self> {
+        let frame_infos = codec.frame_infos();
+        let info = codec
+            .info()
+            .with_color_type(ColorType::N32)
+            .with_alpha_type(AlphaType::Premul);
+        let mut bitmap = Bitmap::new();
+        if !bitmap.try_alloc_pixels_flags(&info) {
+            return None;
+        }
+        Some(Self {
+            codec,
+            frame_infos,
+            bitmap,
+            current_frame: None,
+        })
+    }
+
+    pub fn codec(&self) -> &Codec {
+        &self.codec
+    }
+
+    /// The number of frames, `1` for still images.
+    pub fn frame_count(&self) -> usize {
+        self.frame_infos.len().max(1)
+    }
+
+    pub fn frame_info(&self, index: usize) -> Option<&FrameInfo> {
+        self.frame_infos.get(index)
+    }
+
+    pub fn frame_infos(&self) -> &[FrameInfo] {
+        &self.frame_infos
+    }
+
+    /// The number of times the animation repeats after the first time it was shown, or `None`
+    /// if it repeats forever.
+    pub fn repetition_count(&mut self) -> Option<usize> {
+        self.codec.get_repetition_count()
+    }
+
+    /// The index of the frame that is currently in [`Self::bitmap()`].
+    pub fn current_frame(&self) -> Option<usize> {
+        self.current_frame
+    }
+
+    pub fn bitmap(&self) -> &Bitmap {
+        &self.bitmap
+    }
+
+    /// Decodes frame `index` into the bitmap and returns it.
+    ///
+    /// [`Result::IncompleteInput`] is treated as success, the missing pixels are left
+    /// transparent.
+    pub fn decode_frame(&mut self, index: usize) -> std::result::Result<&Bitmap, Result> {
+        if index >= self.frame_count() {
+            return Err(Result::InvalidParameters);
+        }
+        if self.current_frame == Some(index) {
+            return Ok(&self.bitmap);
+        }
+
+        let prior_frame = self
+            .current_frame
+            .filter(|&current| self.can_continue_from(current, index));
+        let options = Options {
+            zero_initialized: ZeroInitialized::No,
+            subset: None,
+            frame_index: index,
+            prior_frame,
+        };
+
+        self.current_frame = None;
+        let result = unsafe {
+            self.codec
+                .get_pixels_to_pixmap(self.bitmap.pixmap(), Some(&options))
+        };
+        match result {
+            Result::Success | Result::IncompleteInput => {
+                self.bitmap.notify_pixels_changed();
+                self.current_frame = Some(index);
+                Ok(&self.bitmap)
+            }
+            result => Err(result),
+        }
+    }
+
+    /// Decodes frame `index` and returns a copy of it as an [`Image`].
+    pub fn frame_image(&mut self, index: usize) -> std::result::Result<Image, Result> {
+        self.decode_frame(index).map(|bitmap| bitmap.as_image())
+    }
+
+    /// Returns `true` if the codec can draw frame `index` on top of frame `current`.
+    fn can_continue_from(&self, current: usize, index: usize) -> bool {
+        let (current_info, info) = match (self.frame_info(current), self.frame_info(index)) {
+            (Some(current_info), Some(info)) => (current_info, info),
+            _ => return false,
+        };
+        let required_frame = match usize::try_from(info.required_frame) {
+            Ok(required_frame) => required_frame,
+            Err(_) => return false,
+        };
+        current < index
+            && current >= required_frame
+            && current_info.disposal_method != DisposalMethod::RestorePrevious
+    }
+}
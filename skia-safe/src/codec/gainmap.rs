@@ -0,0 +1,260 @@
+use crate::{encode::jpeg_encoder, prelude::*, scalar, Color4f, Data, Image, Pixmap};
+use skia_bindings::{self as sb, SkGainmapInfo};
+use std::fmt::Write;
+
+pub use sb::SkGainmapInfo_Type as GainmapType;
+variant_name!(GainmapType::MultiPicture);
+
+/// Describes how a gainmap is applied to a base image to recover an HDR rendition of it.
+///
+/// Gainmaps require the `unstable` feature, because Skia declares them in private headers.
+///
+/// The gainmap is applied fully on displays with a HDR ratio of at least
+/// `display_ratio_hdr`, not at all on displays with a ratio of at most `display_ratio_sdr`,
+/// and partially in between.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GainmapInfo {
+    pub gainmap_ratio_min: Color4f,
+    pub gainmap_ratio_max: Color4f,
+    pub gainmap_gamma: Color4f,
+    pub epsilon_sdr: Color4f,
+    pub epsilon_hdr: Color4f,
+    pub display_ratio_sdr: scalar,
+    pub display_ratio_hdr: scalar,
+    pub ty: GainmapType,
+}
+
+native_transmutable!(SkGainmapInfo, GainmapInfo, gainmap_info_layout);
+
+impl Default for GainmapInfo {
+    fn default() -> Self {
+        Self {
+            gainmap_ratio_min: Color4f::new(1.0, 1.0, 1.0, 1.0),
+            gainmap_ratio_max: Color4f::new(2.0, 2.0, 2.0, 1.0),
+            gainmap_gamma: Color4f::new(1.0, 1.0, 1.0, 1.0),
+            epsilon_sdr: Color4f::new(0.0, 0.0, 0.0, 1.0),
+            epsilon_hdr: Color4f::new(0.0, 0.0, 0.0, 1.0),
+            display_ratio_sdr: 1.0,
+            display_ratio_hdr: 2.0,
+            ty: GainmapType::Unknown,
+        }
+    }
+}
+
+/// The gainmap of an Ultra HDR (JPEG-R) or other HDR photo.
+///
+/// Draw the base image with the gainmap applied with [`crate::shaders::gainmap()`].
+#[derive(Clone, Debug)]
+pub struct Gainmap {
+    pub info: GainmapInfo,
+    pub image: Image,
+}
+
+impl Gainmap {
+    /// Extracts and decodes the gainmap of the encoded image `data`. The base image is decoded
+    /// as usual, for example with [`Image::from_encoded()`].
+    ///
+    /// Returns `None` if the image has no gainmap, or Skia was built without JPEG gainmap
+    /// support.
+    pub fn from_encoded(data: impl Into<Data>) -> Option<Self> {
+        let mut info = GainmapInfo::default();
+        let gainmap_data = Data::from_ptr(unsafe {
+            sb::C_SkAndroidCodec_getGainmap(data.into().into_ptr(), info.native_mut())
+        })?;
+        let image = Image::from_encoded(gainmap_data)?;
+        Some(Self { info, image })
+    }
+
+    /// Encodes `base` as a JPEG that carries this gainmap as described by Adobe's gain map
+    /// specification: the gainmap is appended as a second JPEG, which the Multi-Picture Format
+    /// (MPF) segment of the base image refers to, and [`Self::info`] is stored in its XMP
+    /// metadata.
+    ///
+    /// The base image can be decoded with [`Image::from_encoded()`], and the gainmap with
+    /// [`Self::from_encoded()`].
+    ///
+    /// Returns `None` if one of the images can not be encoded.
+    pub fn encode_jpeg(&self, base: &Pixmap, options: &jpeg_encoder::Options) -> Option<Data> {
+        let gainmap_image = self.image.to_raster_image(None)?;
+        let gainmap = jpeg_encoder::encode_to_data(&gainmap_image.peek_pixels()?, options)?;
+        let base = jpeg_encoder::encode_to_data(base, options)?;
+
+        let primary_xmp = xmp_segment(&hdrgm_xmp(None));
+        let gainmap_xmp = xmp_segment(&hdrgm_xmp(Some(&self.info)));
+        let base = base.as_bytes().strip_prefix(&JPEG_SOI)?;
+        let gainmap = gainmap.as_bytes().strip_prefix(&JPEG_SOI)?;
+
+        let primary_size = JPEG_SOI.len() + primary_xmp.len() + MPF_SEGMENT_SIZE + base.len();
+        let gainmap_size = JPEG_SOI.len() + gainmap_xmp.len() + gainmap.len();
+        // MPF offsets are relative to the byte order mark behind the "MPF\0" identifier.
+        let mpf_origin = JPEG_SOI.len() + primary_xmp.len() + 8;
+        let mpf = mpf_segment(primary_size, gainmap_size, primary_size - mpf_origin)?;
+
+        let mut jpeg = Vec::with_capacity(primary_size + gainmap_size);
+        jpeg.extend(JPEG_SOI);
+        jpeg.extend(primary_xmp);
+        jpeg.extend(mpf);
+        jpeg.extend(base);
+        jpeg.extend(JPEG_SOI);
+        jpeg.extend(gainmap_xmp);
+        jpeg.extend(gainmap);
+        Some(Data::new_copy(&jpeg))
+    }
+}
+
+const JPEG_SOI: [u8; 2] = [0xff, 0xd8];
+
+/// The size of the APP2 segment [`mpf_segment()`] creates, including its marker.
+const MPF_SEGMENT_SIZE: usize = 4 + 4 + 8 + 2 + 3 * 12 + 4 + 2 * 16;
+
+/// Returns the XMP packet with the `hdrgm` properties of `info`, or with only the version of the
+/// specification for the base image.
+fn hdrgm_xmp(info: Option<&GainmapInfo>) -> String {
+    let mut attributes = String::from(r#"hdrgm:Version="1.0""#);
+    let mut elements = String::new();
+    if let Some(info) = info {
+        // Skia's gamma is the exponent applied when the gainmap is decoded, the specification
+        // stores the one used for encoding.
+        let log2 = |c: Color4f| [c.r.log2(), c.g.log2(), c.b.log2()];
+        let inverse = |c: Color4f| [1.0 / c.r, 1.0 / c.g, 1.0 / c.b];
+        let rgb = |c: Color4f| [c.r, c.g, c.b];
+        let channels = [
+            ("GainMapMin", log2(info.gainmap_ratio_min)),
+            ("GainMapMax", log2(info.gainmap_ratio_max)),
+            ("Gamma", inverse(info.gainmap_gamma)),
+            ("OffsetSDR", rgb(info.epsilon_sdr)),
+            ("OffsetHDR", rgb(info.epsilon_hdr)),
+        ];
+        for (name, [r, g, b]) in channels {
+            // Properties that are equal for all channels are stored as a single value.
+            if r == g && g == b {
+                write!(attributes, r#" hdrgm:{name}="{r}""#).unwrap();
+            } else {
+                write!(
+                    elements,
+                    "<hdrgm:{name}><rdf:Seq><rdf:li>{r}</rdf:li><rdf:li>{g}</rdf:li>\
+                     <rdf:li>{b}</rdf:li></rdf:Seq></hdrgm:{name}>"
+                )
+                .unwrap();
+            }
+        }
+        write!(
+            attributes,
+            r#" hdrgm:HDRCapacityMin="{}" hdrgm:HDRCapacityMax="{}" hdrgm:BaseRenditionIsHDR="False""#,
+            info.display_ratio_sdr.log2(),
+            info.display_ratio_hdr.log2()
+        )
+        .unwrap();
+    }
+    format!(
+        r##"<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"><rdf:Description rdf:about="" xmlns:hdrgm="http://ns.adobe.com/hdr-gain-map/1.0/" {attributes}>{elements}</rdf:Description></rdf:RDF></x:xmpmeta>"##
+    )
+}
+
+/// Wraps the XMP `packet` in an APP1 segment.
+fn xmp_segment(packet: &str) -> Vec<u8> {
+    const SIGNATURE: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+    let length = 2 + SIGNATURE.len() + packet.len();
+    let mut segment = vec![0xff, 0xe1];
+    segment.extend((length as u16).to_be_bytes());
+    segment.extend(SIGNATURE);
+    segment.extend(packet.as_bytes());
+    segment
+}
+
+/// Creates the APP2 segment that lists the primary image and the gainmap at `gainmap_offset`.
+///
+/// Returns `None` if a size or offset does not fit into 32 bits.
+fn mpf_segment(primary_size: usize, gainmap_size: usize, gainmap_offset: usize) -> Option<Vec<u8>> {
+    const UNDEFINED: u16 = 7;
+    const LONG: u16 = 4;
+    // Representative image flag and the "Baseline MP Primary Image" type.
+    const PRIMARY_ATTRIBUTES: u32 = 0x2003_0000;
+
+    let mut segment = vec![0xff, 0xe2];
+    segment.extend(((MPF_SEGMENT_SIZE - 2) as u16).to_be_bytes());
+    segment.extend(b"MPF\0");
+    // Big endian TIFF header, the index IFD follows directly.
+    segment.extend(b"MM\0\x2a");
+    segment.extend(8u32.to_be_bytes());
+    segment.extend(3u16.to_be_bytes());
+    let mut entry = |tag: u16, ty: u16, count: u32, value: [u8; 4]| {
+        segment.extend(tag.to_be_bytes());
+        segment.extend(ty.to_be_bytes());
+        segment.extend(count.to_be_bytes());
+        segment.extend(value);
+    };
+    // MPFVersion, NumberOfImages, and MPEntry, which points behind the IFD.
+    entry(0xb000, UNDEFINED, 4, *b"0100");
+    entry(0xb001, LONG, 1, 2u32.to_be_bytes());
+    entry(
+        0xb002,
+        UNDEFINED,
+        2 * 16,
+        (8 + 2 + 3 * 12 + 4u32).to_be_bytes(),
+    );
+    segment.extend(0u32.to_be_bytes());
+    for (attributes, size, offset) in [
+        (PRIMARY_ATTRIBUTES, primary_size, 0),
+        (0, gainmap_size, gainmap_offset),
+    ] {
+        segment.extend(attributes.to_be_bytes());
+        segment.extend(u32::try_from(size).ok()?.to_be_bytes());
+        segment.extend(u32::try_from(offset).ok()?.to_be_bytes());
+        segment.extend([0; 4]);
+    }
+    debug_assert_eq!(segment.len(), MPF_SEGMENT_SIZE);
+    Some(segment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Gainmap, GainmapInfo};
+    use crate::{encode::jpeg_encoder, Color, Color4f, Data, Image, Surface};
+
+    #[test]
+    fn encode_and_decode_gainmap_jpeg() {
+        let mut base = Surface::new_raster_n32_premul((16, 8)).unwrap();
+        base.canvas().clear(Color::GRAY);
+        let mut gainmap_surface = Surface::new_raster_n32_premul((4, 2)).unwrap();
+        gainmap_surface.canvas().clear(Color::WHITE);
+        let gainmap = Gainmap {
+            info: GainmapInfo {
+                gainmap_ratio_max: Color4f::new(4.0, 4.0, 2.0, 1.0),
+                gainmap_gamma: Color4f::new(0.5, 0.5, 0.5, 1.0),
+                display_ratio_hdr: 4.0,
+                ..GainmapInfo::default()
+            },
+            image: gainmap_surface.image_snapshot(),
+        };
+
+        let data = gainmap
+            .encode_jpeg(
+                &base.peek_pixels().unwrap(),
+                &jpeg_encoder::Options::default(),
+            )
+            .unwrap();
+        let bytes = data.as_bytes();
+        let decoded = Image::from_encoded(data.clone()).unwrap();
+        assert_eq!(decoded.dimensions(), (16, 8).into());
+
+        // The second MP entry points to the gainmap JPEG.
+        let mpf = bytes.windows(4).position(|w| w == b"MPF\0").unwrap() + 4;
+        let entry = mpf + 8 + 2 + 3 * 12 + 4 + 16;
+        let offset = u32::from_be_bytes(bytes[entry + 8..entry + 12].try_into().unwrap());
+        let gainmap_jpeg = &bytes[mpf + offset as usize..];
+        assert_eq!(gainmap_jpeg[..2], [0xff, 0xd8]);
+        let decoded_gainmap = Image::from_encoded(Data::new_copy(gainmap_jpeg)).unwrap();
+        assert_eq!(decoded_gainmap.dimensions(), (4, 2).into());
+
+        // Skia may be built without gainmap support.
+        if let Some(decoded) = Gainmap::from_encoded(data) {
+            assert_eq!(decoded.image.dimensions(), (4, 2).into());
+            let info = decoded.info;
+            assert!((info.gainmap_ratio_max.b - 2.0).abs() < 0.001);
+            assert!((info.gainmap_gamma.r - 0.5).abs() < 0.001);
+            assert!((info.display_ratio_hdr - 4.0).abs() < 0.001);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+/// A buffer for encoded data that arrives in parts, for example from the network.
+///
+/// Clones share the same buffer: one clone is passed to [`Codec::from_stream()`], the other one
+/// receives the data with [`PartialData::append()`]. Whenever the codec runs out of data, it
+/// returns [`Result::IncompleteInput`] together with the number of rows that were decoded so
+/// far, and continues from there when it is called again after more data was appended:
+///
+/// ```no_run
+/// # use skia_safe::codec::{self, Codec, PartialData};
+/// # fn receive() -> Option<Vec<u8>> { None }
+/// let data = PartialData::new();
+/// data.append(&receive().unwrap());
+/// let mut codec = Codec::from_stream(data.clone()).unwrap();
+/// let info = codec.info();
+/// let row_bytes = info.min_row_bytes();
+/// let mut pixels = vec![0; info.compute_byte_size(row_bytes)];
+/// if codec.start_incremental_decode(&info, &mut pixels, row_bytes, None) == codec::Result::Success {
+///     while let (codec::Result::IncompleteInput, Some(rows_decoded)) = codec.incremental_decode() {
+///         // Show the first `rows_decoded` rows, then wait for more data.
+///         match receive() {
+///             Some(bytes) => data.append(&bytes),
+///             None => break,
+///         }
+///     }
+/// }
+/// ```
+///
+/// Incremental decoding is supported for PNG and GIF images. JPEG images have to be decoded
+/// again from all the data that is available with [`Codec::get_image()`] or
+/// [`Codec::get_pixels_with_options()`], which return [`Result::IncompleteInput`] and fill the
+/// missing rows. Skia's JPEG decoder does not expose the intermediate passes of progressive
+/// JPEGs.
+///
+/// [`Codec::from_stream()`]: super::Codec::from_stream()
+/// [`Codec::get_image()`]: super::Codec::get_image()
+/// [`Codec::get_pixels_with_options()`]: super::Codec::get_pixels_with_options()
+/// [`Result::IncompleteInput`]: super::Result::IncompleteInput
+#[derive(Clone, Default, Debug)]
+pub struct PartialData(Arc<Mutex<Buffer>>);
+
+#[derive(Default, Debug)]
+struct Buffer {
+    bytes: Vec<u8>,
+    position: usize,
+    complete: bool,
+}
+
+impl PartialData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `bytes` to the data. Bytes that were read are not kept.
+    pub fn append(&self, bytes: &[u8]) {
+        let mut buffer = self.0.lock().unwrap();
+        let position = buffer.position;
+        buffer.bytes.drain(..position);
+        buffer.position = 0;
+        buffer.bytes.extend_from_slice(bytes);
+    }
+
+    /// Marks the data as complete. Only informative, see [`Self::is_complete()`].
+    pub fn finish(&self) {
+        self.0.lock().unwrap().complete = true;
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.0.lock().unwrap().complete
+    }
+
+    /// The number of bytes that were appended but not read yet.
+    pub fn available(&self) -> usize {
+        let buffer = self.0.lock().unwrap();
+        buffer.bytes.len() - buffer.position
+    }
+}
+
+impl io::Read for PartialData {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut buffer = self.0.lock().unwrap();
+        let available = &buffer.bytes[buffer.position..];
+        let count = available.len().min(buf.len());
+        buf[..count].copy_from_slice(&available[..count]);
+        buffer.position += count;
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PartialData;
+    use std::io::Read;
+
+    #[test]
+    fn read_what_was_appended_so_far() {
+        let data = PartialData::new();
+        let mut reader = data.clone();
+        let mut buf = [0u8; 4];
+
+        data.append(&[1, 2]);
+        assert_eq!(reader.read(&mut buf).unwrap(), 2);
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+
+        data.append(&[3, 4, 5]);
+        assert_eq!(data.available(), 3);
+        assert_eq!(reader.read(&mut buf).unwrap(), 3);
+        assert_eq!(&buf[..3], &[3, 4, 5]);
+    }
+}
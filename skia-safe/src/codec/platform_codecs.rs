@@ -0,0 +1,19 @@
+use skia_bindings as sb;
+use std::sync::Once;
+
+/// Registers the platform's image decoders with [`Codec`](super::Codec), so that HEIF (HEIC)
+/// images can be decoded with [`Codec::from_data()`](super::Codec::from_data()),
+/// [`Image::from_encoded()`](crate::Image::from_encoded()), and the other codec APIs.
+///
+/// The images are decoded with the Windows Imaging Component on Windows, which requires the
+/// HEIF and HEVC extensions from the Microsoft Store, and with `AImageDecoder` on Android 11
+/// and later. On other platforms, HEIF images are still detected, but fail to decode with
+/// [`Result::Unimplemented`](super::Result::Unimplemented).
+///
+/// This function needs to be called once before the first HEIF image is decoded, calling it
+/// again has no effect. The platform decoders do not support incremental or scanline decoding
+/// and decode the whole image at once.
+pub fn register_platform_decoders() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| unsafe { sb::C_SkCodec_RegisterPlatformDecoders() });
+}
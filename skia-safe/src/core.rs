@@ -11,18 +11,20 @@ mod blend_mode;
 mod blender;
 mod blur_types;
 pub mod canvas;
+pub mod cicp;
 mod clip_op;
 mod color;
 pub mod color_filter;
 mod color_space;
 mod color_type;
 pub mod contour_measure;
+mod convert_pixels;
 mod coverage_mode;
 mod cubic_map;
 mod data;
 mod data_table;
 mod deferred_display_list;
-mod deferred_display_list_recorder;
+pub mod deferred_display_list_recorder;
 pub mod document;
 pub mod drawable;
 mod encoded_image_format;
@@ -35,11 +37,14 @@ pub mod font_parameters;
 pub mod font_style;
 mod font_types;
 pub mod graphics;
+pub mod icc;
 pub mod image;
 mod image_encoder;
 pub mod image_filter;
 mod image_generator;
 mod image_info;
+#[cfg(feature = "image")]
+mod image_interop;
 mod m44;
 mod mask_filter;
 pub mod matrix;
@@ -57,6 +62,7 @@ mod pixel_ref;
 mod pixmap;
 mod point;
 mod point3;
+#[cfg(feature = "gpu")]
 mod promise_image_texture;
 mod raster_handle_allocator;
 mod rect;
@@ -65,6 +71,9 @@ pub mod rrect;
 mod rsxform;
 pub mod sampling_options;
 mod scalar_;
+#[cfg(feature = "serde")]
+mod serde_interop;
+mod serial_procs;
 pub mod shader;
 mod size;
 pub mod stroke_rec;
@@ -75,7 +84,7 @@ mod swizzle;
 mod text_blob;
 mod tile_mode;
 mod time;
-mod trace_memory_dump;
+pub mod trace_memory_dump;
 pub mod typeface;
 mod types;
 mod un_pre_multiply;
@@ -97,12 +106,13 @@ pub use color_filter::{color_filters, ColorFilter};
 pub use color_space::*;
 pub use color_type::*;
 pub use contour_measure::{ContourMeasure, ContourMeasureIter};
+pub use convert_pixels::*;
 pub use coverage_mode::*;
 pub use cubic_map::*;
 pub use data::*;
 pub use data_table::*;
 pub use deferred_display_list::*;
-pub use deferred_display_list_recorder::*;
+pub use deferred_display_list_recorder::DeferredDisplayListRecorder;
 pub use document::Document;
 pub use drawable::Drawable;
 pub use encoded_image_format::*;
@@ -118,6 +128,8 @@ pub use image_encoder::*;
 pub use image_filter::ImageFilter;
 pub use image_generator::*;
 pub use image_info::*;
+#[cfg(feature = "image")]
+pub use image_interop::*;
 pub use m44::*;
 pub use mask_filter::*;
 pub use matrix::Matrix;
@@ -130,6 +142,8 @@ pub use paint::Join as PaintJoin;
 pub use paint::Style as PaintStyle;
 pub use path::Path;
 pub use path_builder::PathBuilder;
+#[cfg(feature = "unstable")]
+pub use path_effect::CustomPathEffect;
 pub use path_effect::PathEffect;
 pub use path_measure::PathMeasure;
 pub use path_types::*;
@@ -139,6 +153,7 @@ pub use pixel_ref::*;
 pub use pixmap::*;
 pub use point::*;
 pub use point3::*;
+#[cfg(feature = "gpu")]
 pub use promise_image_texture::*;
 pub use raster_handle_allocator::*;
 pub use rect::*;
@@ -150,6 +165,7 @@ pub use sampling_options::{
     CubicResampler, FilterMode, FilterOptions, MipmapMode, SamplingMode, SamplingOptions,
 };
 pub use scalar_::*;
+pub use serial_procs::*;
 pub use shader::{shaders, Shader};
 pub use size::*;
 pub use stroke_rec::StrokeRec;
@@ -159,7 +175,7 @@ pub use surface_props::*;
 pub use swizzle::*;
 pub use text_blob::*;
 pub use time::*;
-pub use trace_memory_dump::*;
+pub use trace_memory_dump::TraceMemoryDump;
 pub use typeface::Typeface;
 pub use types::*;
 pub use un_pre_multiply::*;
@@ -0,0 +1,534 @@
+//! Coding-independent code points (CICP) as defined by ITU-T H.273.
+//!
+//! Video formats, AVIF, and PNG (`cICP` chunk) describe colors with CICP instead of ICC profiles.
+//! [`ColorSpace::from_cicp()`] creates the color space of such content and
+//! [`ColorSpace::to_cicp()`] finds the code points that describe a color space. ICC profiles may
+//! contain CICP in a `cicp` tag, see [`crate::icc::Profile::cicp`] and [`with_cicp()`].
+//!
+//! PNG `cICP` chunks are read by [`decode()`] and [`read_png_chunk()`], and written by the
+//! [`crate::encode::png_encoder`] when [`crate::encode::png_encoder::Options::cicp`] is set.
+//! Other image formats, AVIF among them, are decoded without their CICP.
+use crate::{
+    named_gamut, named_transfer_fn, ColorSpace, ColorSpacePrimaries, ColorSpaceTransferFn, Data,
+    FourByteTag, Image,
+};
+
+/// A color description with the code points of ITU-T H.273.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Cicp {
+    pub color_primaries: u8,
+    pub transfer_characteristics: u8,
+    /// The matrix that converts RGB to YUV, `0` for RGB content.
+    pub matrix_coefficients: u8,
+    /// Whether the values use the full range, instead of the narrow video range.
+    pub full_range: bool,
+}
+
+impl Cicp {
+    pub const SRGB: Self = Self::new_rgb(primaries::BT709, transfer::SRGB);
+    pub const DISPLAY_P3: Self = Self::new_rgb(primaries::DISPLAY_P3, transfer::SRGB);
+    pub const BT2100_PQ: Self = Self::new_rgb(primaries::BT2020, transfer::PQ);
+    pub const BT2100_HLG: Self = Self::new_rgb(primaries::BT2020, transfer::HLG);
+
+    /// Describes full range RGB content.
+    pub const fn new_rgb(color_primaries: u8, transfer_characteristics: u8) -> Self {
+        Self {
+            color_primaries,
+            transfer_characteristics,
+            matrix_coefficients: 0,
+            full_range: true,
+        }
+    }
+}
+
+/// The color primaries code points that are supported by [`ColorSpace::from_cicp()`].
+pub mod primaries {
+    pub const BT709: u8 = 1;
+    pub const BT470M: u8 = 4;
+    pub const BT470BG: u8 = 5;
+    pub const BT601: u8 = 6;
+    pub const SMPTE240: u8 = 7;
+    pub const GENERIC_FILM: u8 = 8;
+    pub const BT2020: u8 = 9;
+    pub const XYZ: u8 = 10;
+    pub const DCI_P3: u8 = 11;
+    pub const DISPLAY_P3: u8 = 12;
+    pub const EBU3213: u8 = 22;
+}
+
+/// The transfer characteristics code points that are supported by [`ColorSpace::from_cicp()`].
+pub mod transfer {
+    pub const BT709: u8 = 1;
+    pub const GAMMA22: u8 = 4;
+    pub const GAMMA28: u8 = 5;
+    pub const BT601: u8 = 6;
+    pub const LINEAR: u8 = 8;
+    pub const SRGB: u8 = 13;
+    pub const BT2020_10BIT: u8 = 14;
+    pub const BT2020_12BIT: u8 = 15;
+    pub const PQ: u8 = 16;
+    pub const HLG: u8 = 18;
+}
+
+// The code points `to_cicp()` prefers come first.
+const PRIMARIES: [u8; 11] = [
+    primaries::BT709,
+    primaries::DISPLAY_P3,
+    primaries::BT2020,
+    primaries::DCI_P3,
+    primaries::XYZ,
+    primaries::BT601,
+    primaries::SMPTE240,
+    primaries::BT470M,
+    primaries::BT470BG,
+    primaries::GENERIC_FILM,
+    primaries::EBU3213,
+];
+
+const TRANSFERS: [u8; 10] = [
+    transfer::SRGB,
+    transfer::BT709,
+    transfer::LINEAR,
+    transfer::PQ,
+    transfer::HLG,
+    transfer::GAMMA22,
+    transfer::GAMMA28,
+    transfer::BT601,
+    transfer::BT2020_10BIT,
+    transfer::BT2020_12BIT,
+];
+
+/// Returns the matrix to XYZ D50 of the color primaries code point `code`.
+pub fn gamut(code: u8) -> Option<[[f32; 3]; 3]> {
+    const D65: (f32, f32) = (0.3127, 0.3290);
+    const C: (f32, f32) = (0.310, 0.316);
+    let ((rx, ry), (gx, gy), (bx, by), (wx, wy)) = match code {
+        primaries::BT709 => ((0.64, 0.33), (0.30, 0.60), (0.15, 0.06), D65),
+        primaries::BT470M => ((0.67, 0.33), (0.21, 0.71), (0.14, 0.08), C),
+        primaries::BT470BG => ((0.64, 0.33), (0.29, 0.60), (0.15, 0.06), D65),
+        primaries::BT601 | primaries::SMPTE240 => {
+            ((0.630, 0.340), (0.310, 0.595), (0.155, 0.070), D65)
+        }
+        primaries::GENERIC_FILM => ((0.681, 0.319), (0.243, 0.692), (0.145, 0.049), C),
+        primaries::BT2020 => ((0.708, 0.292), (0.170, 0.797), (0.131, 0.046), D65),
+        primaries::XYZ => return Some(named_gamut::XYZ),
+        primaries::DCI_P3 => (
+            (0.680, 0.320),
+            (0.265, 0.690),
+            (0.150, 0.060),
+            (0.314, 0.351),
+        ),
+        primaries::DISPLAY_P3 => ((0.680, 0.320), (0.265, 0.690), (0.150, 0.060), D65),
+        primaries::EBU3213 => ((0.630, 0.340), (0.295, 0.605), (0.155, 0.077), D65),
+        _ => return None,
+    };
+    ColorSpacePrimaries {
+        rx,
+        ry,
+        gx,
+        gy,
+        bx,
+        by,
+        wx,
+        wy,
+    }
+    .to_xyzd50()
+}
+
+/// Returns the transfer function of the transfer characteristics code point `code`.
+pub fn transfer_fn(code: u8) -> Option<ColorSpaceTransferFn> {
+    Some(match code {
+        // BT.709 and BT.2020 share the same transfer function.
+        transfer::BT709 | transfer::BT601 | transfer::BT2020_10BIT | transfer::BT2020_12BIT => {
+            named_transfer_fn::REC2020
+        }
+        transfer::GAMMA22 => named_transfer_fn::DOT22,
+        transfer::GAMMA28 => ColorSpaceTransferFn {
+            g: 2.8,
+            ..named_transfer_fn::LINEAR
+        },
+        transfer::LINEAR => named_transfer_fn::LINEAR,
+        transfer::SRGB => named_transfer_fn::SRGB,
+        transfer::PQ => named_transfer_fn::PQ,
+        transfer::HLG => named_transfer_fn::HLG,
+        _ => return None,
+    })
+}
+
+impl ColorSpace {
+    /// Creates the color space of RGB values described by `cicp`.
+    ///
+    /// The matrix coefficients and the range are not part of the color space, YUV content must
+    /// be converted to full range RGB before. PQ and HLG content decodes to the values of
+    /// [`named_transfer_fn::PQ`] and [`named_transfer_fn::HLG`], see [`crate::hdr`] for color
+    /// spaces that decode SDR reference white to `1.0`.
+    ///
+    /// Returns `None` if a code point is not supported.
+    pub fn from_cicp(cicp: &Cicp) -> Option<Self> {
+        Self::new_rgb(
+            &transfer_fn(cicp.transfer_characteristics)?,
+            &gamut(cicp.color_primaries)?,
+        )
+    }
+
+    /// Returns full range RGB code points that describe the color space, or `None` if the
+    /// transfer function or the gamut has no code point.
+    pub fn to_cicp(&self) -> Option<Cicp> {
+        let tf = self.transfer_fn();
+        let transfer_characteristics = *TRANSFERS.iter().find(|&&code| {
+            let candidate = transfer_fn(code).unwrap();
+            [
+                (tf.g, candidate.g),
+                (tf.a, candidate.a),
+                (tf.b, candidate.b),
+                (tf.c, candidate.c),
+                (tf.d, candidate.d),
+                (tf.e, candidate.e),
+                (tf.f, candidate.f),
+            ]
+            .iter()
+            .all(|(v, c)| (v - c).abs() < 0.001)
+        })?;
+
+        let to_xyzd50 = self.to_xyzd50()?;
+        let color_primaries = *PRIMARIES.iter().find(|&&code| match gamut(code) {
+            Some(candidate) => to_xyzd50
+                .iter()
+                .flatten()
+                .zip(candidate.iter().flatten())
+                .all(|(v, c)| (v - c).abs() < 0.001),
+            None => false,
+        })?;
+
+        Some(Cicp::new_rgb(color_primaries, transfer_characteristics))
+    }
+}
+
+const CICP_TAG: FourByteTag = FourByteTag::from_chars('c', 'i', 'c', 'p');
+const HEADER_SIZE: usize = 128;
+const TAG_ENTRY_SIZE: usize = 12;
+const CICP_TAG_SIZE: usize = 12;
+
+/// Reads the `cicp` tag of the ICC profile `icc`.
+pub(crate) fn read_icc_tag(icc: &[u8]) -> Option<Cicp> {
+    let (offset, size) = tag_entries(icc)?
+        .find(|(tag, _, _)| *tag == CICP_TAG)
+        .map(|(_, offset, size)| (offset, size))?;
+    let data = icc.get(offset..offset.checked_add(size)?)?;
+    if data.len() < CICP_TAG_SIZE || !data.starts_with(b"cicp") {
+        return None;
+    }
+    Some(Cicp {
+        color_primaries: data[8],
+        transfer_characteristics: data[9],
+        matrix_coefficients: data[10],
+        full_range: data[11] != 0,
+    })
+}
+
+/// Returns a copy of the ICC profile `icc` with its `cicp` tag set to `cicp`.
+///
+/// Returns `None` if `icc` is not a valid ICC profile.
+pub fn with_cicp(icc: &[u8], cicp: &Cicp) -> Option<Vec<u8>> {
+    let entries: Vec<_> = tag_entries(icc)?.collect();
+    let table_end = HEADER_SIZE + 4 + entries.len() * TAG_ENTRY_SIZE;
+    let size: usize = read_u32(icc, 0)?.try_into().ok()?;
+    // The header and the tag table must be part of the profile, and the tag data must follow them.
+    if size < table_end
+        || entries.iter().any(|&(_, offset, tag_size)| {
+            offset < table_end || offset.saturating_add(tag_size) > size
+        })
+    {
+        return None;
+    }
+    let mut profile = icc.get(..size)?.to_vec();
+
+    let data = [
+        b'c',
+        b'i',
+        b'c',
+        b'p',
+        0,
+        0,
+        0,
+        0,
+        cicp.color_primaries,
+        cicp.transfer_characteristics,
+        cicp.matrix_coefficients,
+        cicp.full_range as u8,
+    ];
+
+    match entries.iter().find(|(tag, _, _)| *tag == CICP_TAG) {
+        Some(&(_, offset, tag_size)) if tag_size >= CICP_TAG_SIZE => {
+            profile
+                .get_mut(offset..offset + CICP_TAG_SIZE)?
+                .copy_from_slice(&data);
+        }
+        Some(_) => return None,
+        None => {
+            // Insert the tag entry at the end of the tag table, which moves all tag data by the
+            // size of an entry, and append the tag data 4 byte aligned.
+            for (i, (_, offset, _)) in entries.iter().enumerate() {
+                let pos = HEADER_SIZE + 4 + i * TAG_ENTRY_SIZE + 4;
+                let offset = u32::try_from(offset + TAG_ENTRY_SIZE).ok()?;
+                profile
+                    .get_mut(pos..pos + 4)?
+                    .copy_from_slice(&offset.to_be_bytes());
+            }
+            profile.resize((profile.len() + 3) & !3, 0);
+            let data_offset = u32::try_from(profile.len() + TAG_ENTRY_SIZE).ok()?;
+            let mut entry = Vec::with_capacity(TAG_ENTRY_SIZE);
+            entry.extend_from_slice(b"cicp");
+            entry.extend_from_slice(&data_offset.to_be_bytes());
+            entry.extend_from_slice(&(CICP_TAG_SIZE as u32).to_be_bytes());
+            profile.splice(table_end..table_end, entry);
+            profile.extend_from_slice(&data);
+
+            let count = u32::try_from(entries.len() + 1).ok()?;
+            profile
+                .get_mut(HEADER_SIZE..HEADER_SIZE + 4)?
+                .copy_from_slice(&count.to_be_bytes());
+        }
+    }
+
+    let size = u32::try_from(profile.len()).ok()?;
+    profile.get_mut(0..4)?.copy_from_slice(&size.to_be_bytes());
+    // The profile ID is an MD5 hash of the profile, zero means that it is not computed.
+    profile.get_mut(84..100)?.fill(0);
+    Some(profile)
+}
+
+/// Iterates over the signature, offset, and size of the tags in `icc`.
+fn tag_entries(icc: &[u8]) -> Option<impl Iterator<Item = (FourByteTag, usize, usize)> + '_> {
+    let count: usize = read_u32(icc, HEADER_SIZE)?.try_into().ok()?;
+    let table = icc.get(HEADER_SIZE + 4..HEADER_SIZE + 4 + count.checked_mul(TAG_ENTRY_SIZE)?)?;
+    Some(table.chunks_exact(TAG_ENTRY_SIZE).map(|entry| {
+        (
+            FourByteTag::new(read_u32(entry, 0).unwrap()),
+            read_u32(entry, 4).unwrap() as usize,
+            read_u32(entry, 8).unwrap() as usize,
+        )
+    }))
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+const PNG_SIGNATURE: &[u8; 8] = b"\x89PNG\r\n\x1a\n";
+const PNG_CICP: &[u8; 4] = b"cICP";
+
+/// Decodes the encoded image `data` like [`Image::from_encoded()`]. If `data` is a PNG with a
+/// `cICP` chunk that describes RGB content, the image gets the color space of the chunk, which
+/// takes precedence over an embedded ICC profile.
+pub fn decode(data: impl Into<Data>) -> Option<Image> {
+    let data = data.into();
+    let color_space = read_png_chunk(data.as_bytes())
+        .filter(|cicp| cicp.matrix_coefficients == 0 && cicp.full_range)
+        .and_then(|cicp| ColorSpace::from_cicp(&cicp));
+    let image = Image::from_encoded(data)?;
+    match color_space {
+        Some(color_space) => image.reinterpret_color_space(color_space),
+        None => Some(image),
+    }
+}
+
+/// Reads the `cICP` chunk of the PNG `png`.
+pub fn read_png_chunk(png: &[u8]) -> Option<Cicp> {
+    let (_, data) = png_chunks(png)?.find(|(ty, _)| ty == PNG_CICP)?;
+    match *data {
+        [color_primaries, transfer_characteristics, matrix_coefficients, full_range] => {
+            Some(Cicp {
+                color_primaries,
+                transfer_characteristics,
+                matrix_coefficients,
+                full_range: full_range != 0,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Returns a copy of the PNG `png` with its `cICP` chunk set to `cicp`.
+///
+/// Returns `None` if `png` is not a PNG.
+pub fn with_png_chunk(png: &[u8], cicp: &Cicp) -> Option<Vec<u8>> {
+    let chunks: Vec<_> = png_chunks(png)?.collect();
+    // `IHDR` must be the first chunk, and `cICP` must precede `PLTE` and `IDAT`.
+    if chunks.first().map(|(ty, _)| ty) != Some(b"IHDR") {
+        return None;
+    }
+
+    let mut result = Vec::with_capacity(png.len() + 16);
+    result.extend_from_slice(PNG_SIGNATURE);
+    for (i, (ty, data)) in chunks.iter().enumerate() {
+        if ty == PNG_CICP {
+            continue;
+        }
+        write_png_chunk(&mut result, ty, data);
+        if i == 0 {
+            write_png_chunk(
+                &mut result,
+                PNG_CICP,
+                &[
+                    cicp.color_primaries,
+                    cicp.transfer_characteristics,
+                    cicp.matrix_coefficients,
+                    cicp.full_range as u8,
+                ],
+            );
+        }
+    }
+    Some(result)
+}
+
+/// Iterates over the type and data of the chunks of `png`, up to and including `IEND`.
+fn png_chunks(png: &[u8]) -> Option<impl Iterator<Item = ([u8; 4], &[u8])> + '_> {
+    let mut rest = png.strip_prefix(PNG_SIGNATURE)?;
+    let mut ended = false;
+    Some(std::iter::from_fn(move || {
+        if ended {
+            return None;
+        }
+        let length: usize = read_u32(rest, 0)?.try_into().ok()?;
+        let ty: [u8; 4] = rest.get(4..8)?.try_into().ok()?;
+        let data = rest.get(8..8usize.checked_add(length)?)?;
+        rest = rest.get(8 + length + 4..)?;
+        ended = &ty == b"IEND";
+        Some((ty, data))
+    }))
+}
+
+fn write_png_chunk(png: &mut Vec<u8>, ty: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(ty);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// The CRC-32 of PNG chunks (ISO 3309).
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| {
+            if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        crc32, decode, primaries, read_png_chunk, transfer, with_cicp, with_png_chunk, Cicp,
+        HEADER_SIZE,
+    };
+    use crate::{
+        encode::png_encoder, icc::Profile, named_gamut, named_transfer_fn, ColorSpace, ImageInfo,
+        Pixmap,
+    };
+
+    #[test]
+    fn color_spaces_from_cicp() {
+        assert!(ColorSpace::from_cicp(&Cicp::SRGB).unwrap().is_srgb());
+
+        let p3 = ColorSpace::from_cicp(&Cicp::DISPLAY_P3).unwrap();
+        let to_xyzd50 = p3.to_xyzd50().unwrap();
+        for (v, e) in to_xyzd50
+            .iter()
+            .flatten()
+            .zip(named_gamut::DISPLAY_P3.iter().flatten())
+        {
+            assert!((v - e).abs() < 0.001);
+        }
+
+        let pq = ColorSpace::from_cicp(&Cicp::BT2100_PQ).unwrap();
+        assert_eq!(pq.transfer_fn(), named_transfer_fn::PQ);
+
+        assert!(ColorSpace::from_cicp(&Cicp::new_rgb(2, transfer::SRGB)).is_none());
+        assert!(ColorSpace::from_cicp(&Cicp::new_rgb(primaries::BT709, 2)).is_none());
+    }
+
+    #[test]
+    fn cicp_round_trip() {
+        for cicp in [
+            Cicp::SRGB,
+            Cicp::DISPLAY_P3,
+            Cicp::BT2100_PQ,
+            Cicp::BT2100_HLG,
+            Cicp::new_rgb(primaries::BT2020, transfer::LINEAR),
+        ] {
+            let color_space = ColorSpace::from_cicp(&cicp).unwrap();
+            assert_eq!(color_space.to_cicp(), Some(cicp));
+        }
+        let spin = ColorSpace::new_srgb().with_color_spin();
+        assert_eq!(spin.to_cicp(), None);
+    }
+
+    #[test]
+    fn icc_cicp_tag() {
+        let icc = ColorSpace::new_srgb().to_icc().unwrap();
+        assert_eq!(Profile::parse(&icc).unwrap().cicp, None);
+
+        let tagged = with_cicp(&icc, &Cicp::SRGB).unwrap();
+        let profile = Profile::parse(&tagged).unwrap();
+        assert_eq!(profile.cicp, Some(Cicp::SRGB));
+        assert_eq!(
+            profile.tag_count,
+            Profile::parse(&icc).unwrap().tag_count + 1
+        );
+        assert_eq!(ColorSpace::from_icc(&tagged), Some(ColorSpace::new_srgb()));
+
+        let retagged = with_cicp(&tagged, &Cicp::DISPLAY_P3).unwrap();
+        assert_eq!(retagged.len(), tagged.len());
+        assert_eq!(
+            Profile::parse(&retagged).unwrap().cicp,
+            Some(Cicp::DISPLAY_P3)
+        );
+
+        assert!(with_cicp(b"not a profile", &Cicp::SRGB).is_none());
+
+        // A header with a size of 0 and an empty tag table.
+        assert!(with_cicp(&[0; HEADER_SIZE + 4], &Cicp::SRGB).is_none());
+        let mut truncated = icc.to_vec();
+        truncated[0..4].copy_from_slice(&(HEADER_SIZE as u32 + 4).to_be_bytes());
+        assert!(with_cicp(&truncated, &Cicp::SRGB).is_none());
+    }
+
+    #[test]
+    fn png_cicp_chunk() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+
+        let info = ImageInfo::new_n32_premul((2, 2), None);
+        let pixels = [0xffu8; 16];
+        let pixmap = Pixmap::new(&info, &pixels, 8);
+        let png = png_encoder::encode_to_data(&pixmap, &png_encoder::Options::default()).unwrap();
+        assert_eq!(read_png_chunk(png.as_bytes()), None);
+
+        let options = png_encoder::Options {
+            cicp: Some(Cicp::DISPLAY_P3),
+            ..png_encoder::Options::default()
+        };
+        let tagged = png_encoder::encode_to_data(&pixmap, &options).unwrap();
+        assert_eq!(read_png_chunk(tagged.as_bytes()), Some(Cicp::DISPLAY_P3));
+        let mut written = Vec::new();
+        assert!(png_encoder::encode(&pixmap, &mut written, &options));
+        assert_eq!(written, tagged.as_bytes());
+
+        let retagged = with_png_chunk(tagged.as_bytes(), &Cicp::BT2100_PQ).unwrap();
+        assert_eq!(retagged.len(), tagged.len());
+        assert_eq!(read_png_chunk(&retagged), Some(Cicp::BT2100_PQ));
+        assert!(with_png_chunk(b"not a png", &Cicp::SRGB).is_none());
+
+        let image = decode(tagged).unwrap();
+        assert_eq!(
+            Some(image.color_space()),
+            ColorSpace::from_cicp(&Cicp::DISPLAY_P3)
+        );
+        assert!(decode(png).unwrap().color_space().is_srgb());
+    }
+}
@@ -11,6 +11,7 @@ use std::ops::{BitAnd, BitOr, Index, IndexMut, Mul};
 // endian dependent, so we can't expose it as (transmuted) individual
 // argb fields.
 #[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
 pub struct Color(SkColor);
 
@@ -210,6 +211,7 @@ bitflags! {
 // TODO: SkRGBA4f
 
 #[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Color4f {
     pub r: f32,
@@ -77,6 +77,20 @@ impl ColorFilter {
             sb::C_SkColorFilter_makeComposed(self.native(), inner.into().into_ptr())
         })
     }
+
+    /// Returns a filter that converts the colors from the destination to `color_space`, applies
+    /// `self`, and converts the result back. For example, pass
+    /// [`ColorSpace::new_srgb_linear()`] to filter linear values.
+    #[must_use]
+    pub fn with_working_color_space(&self, color_space: impl Into<ColorSpace>) -> Self {
+        ColorFilter::from_ptr(unsafe {
+            sb::C_SkColorFilter_makeWithWorkingColorSpace(
+                self.native(),
+                color_space.into().into_ptr(),
+            )
+        })
+        .unwrap()
+    }
 }
 
 pub mod color_filters {
@@ -1,20 +1,22 @@
 use super::Data;
 use crate::prelude::*;
 use sb::SkNVRefCnt;
-use skia_bindings::{self as sb, SkColorSpace, SkColorSpacePrimaries};
+use skia_bindings::{
+    self as sb, skcms_Matrix3x3, skcms_TransferFunction, SkColorSpace, SkColorSpacePrimaries,
+};
 use std::fmt;
 
 #[derive(Clone, PartialEq, Debug)]
 #[repr(C)]
 pub struct ColorSpacePrimaries {
-    rx: f32,
-    ry: f32,
-    gx: f32,
-    gy: f32,
-    bx: f32,
-    by: f32,
-    wx: f32,
-    wy: f32,
+    pub rx: f32,
+    pub ry: f32,
+    pub gx: f32,
+    pub gy: f32,
+    pub bx: f32,
+    pub by: f32,
+    pub wx: f32,
+    pub wy: f32,
 }
 
 native_transmutable!(
@@ -23,7 +25,25 @@ native_transmutable!(
     color_space_primaries_layout
 );
 
+impl ColorSpacePrimaries {
+    /// Computes the matrix that converts linear RGB with these primaries and white point to XYZ
+    /// relative to the D50 white point. Returns `None` if the primaries do not span a gamut.
+    pub fn to_xyzd50(&self) -> Option<[[f32; 3]; 3]> {
+        let mut m = skcms_Matrix3x3 {
+            vals: Default::default(),
+        };
+        unsafe { sb::C_SkColorSpacePrimaries_toXYZD50(self.native(), &mut m) }.if_true_some(m.vals)
+    }
+}
+
+/// The coefficients of a transfer function that converts encoded values to linear values.
+///
+/// For `g >= 0`, this is the piecewise function `(a * x + b)^g + e` for `x >= d`, and
+/// `c * x + f` otherwise. Negative values of `g` mark the HDR transfer functions
+/// [`named_transfer_fn::PQ`] and [`named_transfer_fn::HLG`], which interpret the other
+/// coefficients differently.
 #[derive(Clone, PartialEq, Debug)]
+#[repr(C)]
 pub struct ColorSpaceTransferFn {
     pub g: f32,
     pub a: f32,
@@ -34,6 +54,12 @@ pub struct ColorSpaceTransferFn {
     pub f: f32,
 }
 
+native_transmutable!(
+    skcms_TransferFunction,
+    ColorSpaceTransferFn,
+    color_space_transfer_fn_layout
+);
+
 // TODO: Make the binding generator provide all these constants.
 pub mod named_transfer_fn {
     use crate::ColorSpaceTransferFn;
@@ -100,6 +126,52 @@ pub mod named_transfer_fn {
     };
 }
 
+/// Matrices that convert linear RGB of common gamuts to XYZ relative to the D50 white point, in
+/// row-major order.
+pub mod named_gamut {
+    const fn fixed_to_float(x: i32) -> f32 {
+        x as f32 / 65536.0
+    }
+
+    pub const SRGB: [[f32; 3]; 3] = [
+        [
+            fixed_to_float(0x6FA2),
+            fixed_to_float(0x6299),
+            fixed_to_float(0x24A0),
+        ],
+        [
+            fixed_to_float(0x38F5),
+            fixed_to_float(0xB785),
+            fixed_to_float(0x0F84),
+        ],
+        [
+            fixed_to_float(0x0390),
+            fixed_to_float(0x18DA),
+            fixed_to_float(0xB6CF),
+        ],
+    ];
+
+    pub const ADOBE_RGB: [[f32; 3]; 3] = [
+        [0.60974, 0.20528, 0.14919],
+        [0.31111, 0.62567, 0.06322],
+        [0.01947, 0.06087, 0.74457],
+    ];
+
+    pub const DISPLAY_P3: [[f32; 3]; 3] = [
+        [0.515102, 0.291965, 0.157153],
+        [0.241182, 0.692236, 0.0665819],
+        [-0.00104941, 0.0418818, 0.784378],
+    ];
+
+    pub const REC2020: [[f32; 3]; 3] = [
+        [0.673459, 0.165661, 0.125100],
+        [0.279033, 0.675338, 0.0456288],
+        [-0.00193139, 0.0299794, 0.797162],
+    ];
+
+    pub const XYZ: [[f32; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+}
+
 pub type ColorSpace = RCHandle<SkColorSpace>;
 unsafe_send_sync!(ColorSpace);
 require_base_type!(SkColorSpace, SkNVRefCnt);
@@ -139,6 +211,64 @@ impl ColorSpace {
         Self::from_ptr(unsafe { sb::C_SkColorSpace_MakeSRGBLinear() }).unwrap()
     }
 
+    /// Creates a color space from a transfer function and a matrix that converts linear RGB to
+    /// XYZ relative to the D50 white point, for example one of [`named_gamut`].
+    ///
+    /// This also accepts the HDR transfer functions [`named_transfer_fn::PQ`] and
+    /// [`named_transfer_fn::HLG`]. Returns `None` if the transfer function is invalid.
+    pub fn new_rgb(transfer_fn: &ColorSpaceTransferFn, to_xyzd50: &[[f32; 3]; 3]) -> Option<Self> {
+        let to_xyzd50 = skcms_Matrix3x3 { vals: *to_xyzd50 };
+        Self::from_ptr(unsafe { sb::C_SkColorSpace_MakeRGB(transfer_fn.native(), &to_xyzd50) })
+    }
+
+    /// Creates a color space from a transfer function and the chromaticities of the primaries
+    /// and the white point.
+    ///
+    /// Returns `None` if the primaries do not span a gamut or the transfer function is invalid.
+    pub fn new_rgb_with_primaries(
+        transfer_fn: &ColorSpaceTransferFn,
+        primaries: &ColorSpacePrimaries,
+    ) -> Option<Self> {
+        Self::new_rgb(transfer_fn, &primaries.to_xyzd50()?)
+    }
+
+    /// Returns `true` if the transfer function is a piecewise function, and `false` for the HDR
+    /// transfer functions PQ and HLG.
+    pub fn is_numerical_transfer_fn(&self) -> bool {
+        unsafe { sb::C_SkColorSpace_isNumericalTransferFn(self.native()) }
+    }
+
+    /// The transfer function that converts encoded values to linear values.
+    pub fn transfer_fn(&self) -> ColorSpaceTransferFn {
+        let mut tf = named_transfer_fn::LINEAR;
+        unsafe { sb::C_SkColorSpace_transferFn(self.native(), tf.native_mut()) };
+        tf
+    }
+
+    /// The transfer function that converts linear values to encoded values.
+    pub fn inv_transfer_fn(&self) -> ColorSpaceTransferFn {
+        let mut tf = named_transfer_fn::LINEAR;
+        unsafe { sb::C_SkColorSpace_invTransferFn(self.native(), tf.native_mut()) };
+        tf
+    }
+
+    /// The matrix that converts linear RGB to XYZ relative to the D50 white point, in row-major
+    /// order.
+    pub fn to_xyzd50(&self) -> Option<[[f32; 3]; 3]> {
+        let mut m = skcms_Matrix3x3 {
+            vals: Default::default(),
+        };
+        unsafe { sb::C_SkColorSpace_toXYZD50(self.native(), &mut m) }.if_true_some(m.vals)
+    }
+
+    pub fn gamma_close_to_srgb(&self) -> bool {
+        unsafe { self.native().gammaCloseToSRGB() }
+    }
+
+    pub fn gamma_is_linear(&self) -> bool {
+        unsafe { self.native().gammaIsLinear() }
+    }
+
     pub fn to_xyzd50_hash(&self) -> XYZD50Hash {
         XYZD50Hash(self.native().fToXYZD50Hash)
     }
@@ -175,8 +305,6 @@ impl ColorSpace {
             .unwrap()
     }
 
-    // TODO: transferFn()
-    // TODO: invTransferFn()
     // TODO: gamutTransformTo()
     // TODO: transferFnHash()?
     // TODO: hash()?
@@ -214,3 +342,51 @@ pub fn serialize_and_deserialize() {
 
     assert!(original == deserialized);
 }
+
+#[test]
+pub fn custom_transfer_fn_and_gamut() {
+    let srgb = ColorSpace::new_rgb(&named_transfer_fn::SRGB, &named_gamut::SRGB).unwrap();
+    assert!(srgb.is_srgb());
+    assert!(srgb.gamma_close_to_srgb());
+
+    let pq = ColorSpace::new_rgb(&named_transfer_fn::PQ, &named_gamut::REC2020).unwrap();
+    assert!(!pq.is_numerical_transfer_fn());
+    assert_eq!(pq.transfer_fn(), named_transfer_fn::PQ);
+    assert_eq!(pq.to_xyzd50(), Some(named_gamut::REC2020));
+
+    let linear = ColorSpace::new_rgb(&named_transfer_fn::LINEAR, &named_gamut::DISPLAY_P3).unwrap();
+    assert!(linear.is_numerical_transfer_fn());
+    assert!(linear.gamma_is_linear());
+    assert_eq!(linear.inv_transfer_fn(), named_transfer_fn::LINEAR);
+
+    let invalid = ColorSpaceTransferFn {
+        g: f32::NAN,
+        ..named_transfer_fn::LINEAR
+    };
+    assert!(ColorSpace::new_rgb(&invalid, &named_gamut::SRGB).is_none());
+}
+
+#[test]
+pub fn gamut_from_primaries() {
+    let srgb = ColorSpacePrimaries {
+        rx: 0.64,
+        ry: 0.33,
+        gx: 0.30,
+        gy: 0.60,
+        bx: 0.15,
+        by: 0.06,
+        wx: 0.3127,
+        wy: 0.3290,
+    };
+    let m = srgb.to_xyzd50().unwrap();
+    for (row, expected) in m.iter().zip(named_gamut::SRGB.iter()) {
+        for (v, e) in row.iter().zip(expected) {
+            assert!((v - e).abs() < 0.001);
+        }
+    }
+    assert!(
+        ColorSpace::new_rgb_with_primaries(&named_transfer_fn::SRGB, &srgb)
+            .unwrap()
+            .is_srgb()
+    );
+}
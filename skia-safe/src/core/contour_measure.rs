@@ -48,6 +48,26 @@ impl ContourMeasure {
         .if_true_some((p, v))
     }
 
+    /// Returns the positions and tangents at each of the `distances` along the contour.
+    /// Distances are pinned to `0..=length()`.
+    ///
+    /// Equivalent to calling [`Self::pos_tan()`] for each distance, but crosses the FFI boundary
+    /// only once.
+    pub fn sample(&self, distances: &[scalar]) -> Option<Vec<(Point, Vector)>> {
+        let mut positions = vec![Point::default(); distances.len()];
+        let mut tangents = vec![Vector::default(); distances.len()];
+        unsafe {
+            sb::C_SkContourMeasure_getPosTans(
+                self.native(),
+                distances.as_ptr(),
+                distances.len(),
+                positions.native_mut().as_mut_ptr(),
+                tangents.native_mut().as_mut_ptr(),
+            )
+        }
+        .if_true_then_some(|| positions.into_iter().zip(tangents).collect())
+    }
+
     pub fn get_matrix(
         &self,
         distance: scalar,
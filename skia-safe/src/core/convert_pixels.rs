@@ -0,0 +1,68 @@
+use crate::{prelude::*, ImageInfo};
+use skia_bindings as sb;
+
+/// Converts the pixels `src` described by `src_info` to the color type, alpha type, and color
+/// space of `dst_info` and writes them to `dst`, without creating a surface. Both buffers must be
+/// tightly packed, with [`ImageInfo::min_row_bytes()`] bytes per row.
+///
+/// Returns `false` if the dimensions of the infos differ, a buffer is too small, or the
+/// conversion is not supported, for example from a color type with color channels to
+/// [`crate::ColorType::Alpha8`].
+pub fn convert_pixels<D, S>(
+    dst_info: &ImageInfo,
+    dst: &mut [D],
+    src_info: &ImageInfo,
+    src: &[S],
+) -> bool {
+    let dst_row_bytes = dst_info.min_row_bytes();
+    let src_row_bytes = src_info.min_row_bytes();
+    if !dst_info.valid_pixels(dst_row_bytes, dst) || !src_info.valid_pixels(src_row_bytes, src) {
+        return false;
+    }
+
+    unsafe {
+        sb::C_SkConvertPixels(
+            dst_info.native(),
+            dst.as_mut_ptr() as _,
+            dst_row_bytes,
+            src_info.native(),
+            src.as_ptr() as _,
+            src_row_bytes,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::convert_pixels;
+    use crate::{AlphaType, ColorSpace, ColorType, ImageInfo};
+
+    #[test]
+    fn linear_float_to_srgb_8888() {
+        let src_info = ImageInfo::new(
+            (2, 1),
+            ColorType::RGBAF32,
+            AlphaType::Unpremul,
+            ColorSpace::new_srgb_linear(),
+        );
+        let src = [[0.5f32, 0.0, 1.0, 1.0], [0.0, 1.0, 0.0, 1.0]];
+        let dst_info = ImageInfo::new(
+            (2, 1),
+            ColorType::RGBA8888,
+            AlphaType::Unpremul,
+            ColorSpace::new_srgb(),
+        );
+        let mut dst = [[0u8; 4]; 2];
+        assert!(convert_pixels(&dst_info, &mut dst, &src_info, &src));
+
+        let [r, g, b, a] = dst[0];
+        assert!((r as i32 - 188).abs() <= 1);
+        assert_eq!((g, b, a), (0, 255, 255));
+        assert_eq!(dst[1], [0, 255, 0, 255]);
+
+        let mut small = [[0u8; 4]; 1];
+        assert!(!convert_pixels(&dst_info, &mut small, &src_info, &src));
+        let other_size = dst_info.with_dimensions((1, 2));
+        assert!(!convert_pixels(&other_size, &mut dst, &src_info, &src));
+    }
+}
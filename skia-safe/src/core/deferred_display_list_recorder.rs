@@ -1,6 +1,13 @@
 use crate::{prelude::*, Canvas, DeferredDisplayList, SurfaceCharacterization};
+#[cfg(feature = "gpu")]
+use crate::{IRect, ISize};
 use skia_bindings::{self as sb, SkDeferredDisplayListRecorder};
 use std::fmt;
+#[cfg(feature = "gpu")]
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
 
 pub type DeferredDisplayListRecorder = Handle<SkDeferredDisplayListRecorder>;
 
@@ -33,3 +40,107 @@ impl DeferredDisplayListRecorder {
         })
     }
 }
+
+/// Records the content of a surface with the given `characterization` into one
+/// [`DeferredDisplayList`] per tile of `tile_size`, using `threads` worker threads.
+///
+/// `record` is called once per tile with a canvas that is translated so that drawing can be done
+/// in the coordinate space of the whole surface, and the bounds of the tile. The returned tiles
+/// can then be drawn with [`crate::Surface::draw_display_list_with_offset()`] with the tile's
+/// top left corner as offset.
+///
+/// Tiles that could not be recorded are omitted, and if `tile_size` is empty, no tiles are
+/// recorded.
+#[cfg(feature = "gpu")]
+pub fn record_tiles(
+    characterization: &SurfaceCharacterization,
+    tile_size: impl Into<ISize>,
+    threads: impl Into<Option<usize>>,
+    record: impl Fn(&mut Canvas, IRect) + Sync,
+) -> Vec<(IRect, DeferredDisplayList)> {
+    let tiles = tile_rects(characterization.dimensions(), tile_size.into());
+    let threads = threads
+        .into()
+        .or_else(|| thread::available_parallelism().ok().map(|n| n.get()))
+        .unwrap_or(1)
+        .clamp(1, tiles.len().max(1));
+
+    let next = AtomicUsize::new(0);
+    let record_tile = |tile: IRect| {
+        let mut recorder = DeferredDisplayListRecorder::new(&characterization.resized(tile.size()));
+        let canvas = recorder.canvas();
+        canvas.translate((-tile.left as f32, -tile.top as f32));
+        record(canvas, tile);
+        recorder.detach().map(|ddl| (tile, ddl))
+    };
+    let worker = || {
+        let mut recorded = Vec::new();
+        loop {
+            let i = next.fetch_add(1, Ordering::Relaxed);
+            match tiles.get(i) {
+                Some(tile) => recorded.extend(record_tile(*tile).map(|r| (i, r))),
+                None => return recorded,
+            }
+        }
+    };
+
+    let mut recorded: Vec<_> = thread::scope(|s| {
+        let workers: Vec<_> = (0..threads).map(|_| s.spawn(worker)).collect();
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap())
+            .collect()
+    });
+    recorded.sort_by_key(|(i, _)| *i);
+    recorded.into_iter().map(|(_, tile)| tile).collect()
+}
+
+/// Partitions a surface of `size` into tiles of at most `tile_size` in row-major order. Returns
+/// no tiles if `tile_size` is empty.
+#[cfg(feature = "gpu")]
+fn tile_rects(size: ISize, tile_size: ISize) -> Vec<IRect> {
+    if tile_size.width <= 0 || tile_size.height <= 0 {
+        return Vec::new();
+    }
+    let mut tiles = Vec::new();
+    for top in (0..size.height).step_by(tile_size.height as usize) {
+        for left in (0..size.width).step_by(tile_size.width as usize) {
+            tiles.push(IRect::new(
+                left,
+                top,
+                left.saturating_add(tile_size.width).min(size.width),
+                top.saturating_add(tile_size.height).min(size.height),
+            ));
+        }
+    }
+    tiles
+}
+
+#[cfg(all(test, feature = "gpu"))]
+mod tests {
+    use super::tile_rects;
+    use crate::{IRect, ISize};
+
+    #[test]
+    fn tiles_cover_the_surface() {
+        let tiles = tile_rects(ISize::new(250, 100), ISize::new(100, 64));
+        assert_eq!(
+            tiles,
+            vec![
+                IRect::new(0, 0, 100, 64),
+                IRect::new(100, 0, 200, 64),
+                IRect::new(200, 0, 250, 64),
+                IRect::new(0, 64, 100, 100),
+                IRect::new(100, 64, 200, 100),
+                IRect::new(200, 64, 250, 100),
+            ]
+        );
+        assert!(tile_rects(ISize::new(0, 0), ISize::new(16, 16)).is_empty());
+        assert!(tile_rects(ISize::new(100, 100), ISize::new(0, 16)).is_empty());
+        assert!(tile_rects(ISize::new(100, 100), ISize::new(16, -1)).is_empty());
+        assert_eq!(
+            tile_rects(ISize::new(100, 100), ISize::new(i32::MAX, i32::MAX)),
+            vec![IRect::new(0, 0, 100, 100)]
+        );
+    }
+}
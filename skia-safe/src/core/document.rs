@@ -1,24 +1,45 @@
-use crate::{interop::DynamicMemoryWStream, prelude::*, Canvas, Data, Rect, Size};
+use crate::{
+    interop::{DynamicMemoryWStream, RustWStream},
+    prelude::*,
+    Canvas, Data, Rect, Size,
+};
 use core::fmt;
-use skia_bindings::{self as sb, SkDocument, SkRefCntBase};
-use std::{pin::Pin, ptr};
+use skia_bindings::{self as sb, SkDocument, SkRefCntBase, SkWStream};
+use std::{io, pin::Pin, ptr};
 
-pub struct Document<State = state::Open> {
+/// A document that is either kept in memory until it is closed, for example one created by
+/// [`crate::pdf::new_document()`], or written to an [`std::io::Write`] while its pages are
+/// drawn, for example one created by [`crate::pdf::new_document_to_writer()`].
+pub struct Document<'a, State = state::Open> {
     // note: order matters here, first the document must be
     // dropped _and then_ the stream.
     document: RCHandle<SkDocument>,
-    stream: Pin<Box<DynamicMemoryWStream>>,
+    stream: Pin<Box<Output<'a>>>,
 
     state: State,
 }
 
+enum Output<'a> {
+    Writer(RustWStream<'a>),
+    Memory(DynamicMemoryWStream),
+}
+
+impl Output<'_> {
+    fn stream_mut(&mut self) -> &mut SkWStream {
+        match self {
+            Output::Writer(stream) => stream.stream_mut(),
+            Output::Memory(stream) => stream.native_mut().base_mut(),
+        }
+    }
+}
+
 require_type_equality!(sb::SkDocument_INHERITED, sb::SkRefCnt);
 
 impl NativeRefCountedBase for SkDocument {
     type Base = SkRefCntBase;
 }
 
-impl<State: fmt::Debug> fmt::Debug for Document<State> {
+impl<State: fmt::Debug> fmt::Debug for Document<'_, State> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Document")
             .field("state", &self.state)
@@ -56,23 +77,42 @@ pub mod state {
     }
 }
 
-impl<State> Document<State> {
+impl<State> Document<'_, State> {
     pub fn abort(mut self) {
         unsafe { self.document.native_mut().abort() }
         drop(self)
     }
 }
 
-impl Document<state::Open> {
+impl<'a> Document<'a, state::Open> {
+    /// Creates a document that writes to `stream`. `create` is called with the stream, which
+    /// does not move anymore.
     pub(crate) fn new(
-        stream: Pin<Box<DynamicMemoryWStream>>,
-        document: RCHandle<SkDocument>,
-    ) -> Self {
-        Document {
+        stream: RustWStream<'a>,
+        create: impl FnOnce(&mut SkWStream) -> *mut SkDocument,
+    ) -> Option<Self> {
+        Self::with_output(Output::Writer(stream), create)
+    }
+
+    /// Creates a document that collects its content in memory, see [`Self::close()`].
+    pub(crate) fn new_in_memory(
+        create: impl FnOnce(&mut SkWStream) -> *mut SkDocument,
+    ) -> Option<Self> {
+        Self::with_output(Output::Memory(DynamicMemoryWStream::new()), create)
+    }
+
+    fn with_output(
+        output: Output<'a>,
+        create: impl FnOnce(&mut SkWStream) -> *mut SkDocument,
+    ) -> Option<Self> {
+        // The stream can not move anymore as soon as the document refers to it.
+        let mut stream = Box::pin(output);
+        let document = RCHandle::from_ptr(create(stream.stream_mut()))?;
+        Some(Document {
             document,
             stream,
             state: state::Open { pages: 0 },
-        }
+        })
     }
 
     /// The number of pages in this document.
@@ -86,7 +126,7 @@ impl Document<state::Open> {
         mut self,
         size: impl Into<Size>,
         content: Option<&Rect>,
-    ) -> Document<state::OnPage> {
+    ) -> Document<'a, state::OnPage> {
         let size = size.into();
         let canvas = unsafe {
             self.document.native_mut().beginPage(
@@ -108,15 +148,39 @@ impl Document<state::Open> {
 
     /// Close the document and return the encoded representation.
     /// This function consumes and drops the document.
+    ///
+    /// Documents that are written to an [`io::Write`] return empty data, use
+    /// [`Self::close_to_writer()`] for them.
     pub fn close(mut self) -> Data {
+        self.close_stream();
+        match &mut *self.stream {
+            Output::Writer(_) => Data::new_empty(),
+            Output::Memory(stream) => stream.detach_as_data(),
+        }
+    }
+
+    /// Close the document and write the remaining parts of it to the [`io::Write`] it was
+    /// created with. This function consumes and drops the document.
+    ///
+    /// Returns the first error the writer returned while the document was written. Documents
+    /// that are kept in memory return `Ok(())`, use [`Self::close()`] for them.
+    pub fn close_to_writer(mut self) -> io::Result<()> {
+        self.close_stream();
+        match &mut *self.stream {
+            Output::Writer(stream) => stream.take_error().map_or(Ok(()), Err),
+            Output::Memory(_) => Ok(()),
+        }
+    }
+
+    fn close_stream(&mut self) {
         unsafe {
             self.document.native_mut().close();
         };
-        self.stream.detach_as_data()
+        unsafe { sb::C_SkWStream_flush(self.stream.stream_mut()) };
     }
 }
 
-impl Document<state::OnPage> {
+impl<'a> Document<'a, state::OnPage> {
     /// The current page we are currently drawing on.
     pub fn page(&self) -> usize {
         self.state.page
@@ -130,7 +194,7 @@ impl Document<state::OnPage> {
     /// Ends the page.
     /// This function consumes the document and returns a new open document that
     /// contains the pages drawn so far.
-    pub fn end_page(mut self) -> Document {
+    pub fn end_page(mut self) -> Document<'a> {
         unsafe {
             self.document.native_mut().endPage();
         }
@@ -1,9 +1,9 @@
 use crate::{
-    interop::VecSink, prelude::*, scalar, FontHinting, FontMetrics, GlyphId, Paint, Path, Point,
-    Rect, TextEncoding, Typeface, Unichar,
+    interop::VecSink, prelude::*, scalar, FontHinting, FontMetrics, GlyphId, Matrix, Paint, Path,
+    Point, Rect, TextEncoding, Typeface, Unichar,
 };
-use skia_bindings::{self as sb, SkFont, SkFont_PrivFlags};
-use std::{fmt, ptr};
+use skia_bindings::{self as sb, SkFont, SkFont_PrivFlags, SkMatrix, SkPath};
+use std::{ffi::c_void, fmt, ptr};
 
 pub use skia_bindings::SkFont_Edging as Edging;
 variant_name!(Edging::Alias);
@@ -416,7 +416,54 @@ impl Font {
         unsafe { self.native().getPath(glyph_id, path.native_mut()) }.if_true_some(path)
     }
 
-    // TODO: getPaths() (needs a function to be passed, but supports a context).
+    /// Calls `f` for each glyph with its path, or `None` if the glyph has no outline, and the
+    /// matrix that maps the path to the size and skew of the font. All paths are retrieved with
+    /// one call into Skia.
+    pub fn get_paths(
+        &self,
+        glyphs: &[GlyphId],
+        mut f: impl FnMut(GlyphId, Option<&Path>, &Matrix),
+    ) {
+        struct Context<'a> {
+            glyphs: std::slice::Iter<'a, GlyphId>,
+            f: &'a mut dyn FnMut(GlyphId, Option<&Path>, &Matrix),
+        }
+
+        unsafe extern "C" fn glyph_path(
+            path: *const SkPath,
+            matrix: *const SkMatrix,
+            context: *mut c_void,
+        ) {
+            let context = &mut *(context as *mut Context);
+            if let Some(glyph) = context.glyphs.next() {
+                let path = path.as_ref().map(Path::from_native_ref);
+                (context.f)(*glyph, path, Matrix::from_native_ref(&*matrix));
+            }
+        }
+
+        let mut context = Context {
+            glyphs: glyphs.iter(),
+            f: &mut f,
+        };
+        unsafe {
+            self.native().getPaths(
+                glyphs.as_ptr(),
+                glyphs.len().try_into().unwrap(),
+                Some(glyph_path),
+                &mut context as *mut Context as *mut c_void,
+            )
+        }
+    }
+
+    /// Returns the paths of `glyphs` at the size of the font, like [`Self::get_path()`] does for a
+    /// single glyph.
+    pub fn get_paths_transformed(&self, glyphs: &[GlyphId]) -> Vec<Option<Path>> {
+        let mut paths = Vec::with_capacity(glyphs.len());
+        self.get_paths(glyphs, |_, path, matrix| {
+            paths.push(path.map(|path| path.with_transform(matrix)))
+        });
+        paths
+    }
 
     pub fn metrics(&self) -> (scalar, FontMetrics) {
         let mut line_spacing = 0.0;
@@ -444,3 +491,17 @@ fn test_flags() {
     font.set_embolden(false);
     assert!(!font.is_embolden());
 }
+
+#[test]
+fn get_paths_of_multiple_glyphs() {
+    let font = Font::new(Typeface::default(), 24.0);
+    let glyphs = font.str_to_glyphs_vec("a .");
+    let paths = font.get_paths_transformed(&glyphs);
+    assert_eq!(paths.len(), glyphs.len());
+    for (glyph, path) in glyphs.iter().zip(&paths) {
+        assert_eq!(
+            path.as_ref().map(|p| *p.bounds()),
+            font.get_path(*glyph).map(|p| *p.bounds())
+        );
+    }
+}
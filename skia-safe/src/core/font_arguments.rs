@@ -26,9 +26,13 @@ pub mod variation_position {
     );
 }
 
+/// Selects the color palette of a font with a `CPAL` table, as used by `COLR` color fonts like
+/// emoji and icon fonts.
 #[derive(Clone, Debug)]
 pub struct Palette<'a> {
+    /// The index of the base palette, see [`crate::Typeface::palettes()`].
     pub index: i32,
+    /// Colors that replace entries of the base palette.
     pub overrides: &'a [palette::Override],
 }
 
@@ -36,6 +40,7 @@ pub mod palette {
     use crate::Color;
     use skia_bindings::SkFontArguments_Palette_Override;
 
+    /// Replaces the color of the palette entry `index`.
     #[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
     #[repr(C)]
     pub struct Override {
@@ -179,3 +184,18 @@ fn access_coordinates() {
     assert_eq!(args.variation_design_position().coordinates[0].value, 1.0);
     drop(args);
 }
+
+#[test]
+fn access_palette() {
+    let overrides = [palette::Override {
+        index: 2,
+        color: crate::Color::RED,
+    }];
+    let args = FontArguments::new().set_palette(Palette {
+        index: 1,
+        overrides: &overrides,
+    });
+    let palette = args.palette();
+    assert_eq!(palette.index, 1);
+    assert_eq!(palette.overrides, overrides);
+}
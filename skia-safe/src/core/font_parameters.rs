@@ -1,4 +1,5 @@
 pub use variation::Axis as VariationAxis;
+pub use variation::NamedInstance as VariationNamedInstance;
 
 pub mod variation {
     use crate::{prelude::*, FourByteTag};
@@ -39,4 +40,13 @@ pub mod variation {
             self
         }
     }
+
+    /// A predefined position in the design space of a variable font, like "Bold Condensed".
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct NamedInstance {
+        /// The subfamily name of the instance.
+        pub name: Option<String>,
+        pub postscript_name: Option<String>,
+        pub coordinates: Vec<crate::font_arguments::variation_position::Coordinate>,
+    }
 }
@@ -1,54 +1,74 @@
-use skia_bindings::SkGraphics;
+//! Global settings and the caches that are shared by all drawing.
+//!
+//! The font cache holds the glyph images and paths of the fonts that were drawn with, the resource
+//! cache holds decoded images and other intermediate results of raster drawing. GPU resources are
+//! managed by `gpu::DirectContext` instead.
+use crate::{trace_memory_dump, TraceMemoryDump};
+use skia_bindings::{self as sb, SkGraphics};
 use std::ffi::CString;
 
 pub fn init() {
     unsafe { SkGraphics::Init() };
 }
 
+/// Returns the maximum number of bytes the font cache may use.
 pub fn font_cache_limit() -> usize {
     unsafe { SkGraphics::GetFontCacheLimit() }
 }
 
+/// Sets the maximum number of bytes the font cache may use and returns the previous limit. If
+/// the cache uses more than the new limit, it is purged until it fits.
 pub fn set_font_cache_limit(bytes: usize) -> usize {
     unsafe { SkGraphics::SetFontCacheLimit(bytes) }
 }
 
+/// Returns the number of bytes the font cache currently uses.
 pub fn font_cache_used() -> usize {
     unsafe { SkGraphics::GetFontCacheUsed() }
 }
 
+/// Returns the number of entries in the font cache.
 pub fn font_cache_count_used() -> i32 {
     unsafe { SkGraphics::GetFontCacheCountUsed() }
 }
 
+/// Returns the maximum number of entries the font cache may hold.
 pub fn font_cache_count_limit() -> i32 {
     unsafe { SkGraphics::GetFontCacheCountLimit() }
 }
 
+/// Sets the maximum number of entries the font cache may hold and returns the previous limit.
 pub fn set_font_cache_count_limit(count: i32) -> i32 {
     unsafe { SkGraphics::SetFontCacheCountLimit(count) }
 }
 
+/// Removes all unused entries from the font cache.
 pub fn purge_font_cache() {
     unsafe { SkGraphics::PurgeFontCache() }
 }
 
+/// Returns the number of bytes the resource cache currently uses.
 pub fn resource_cache_total_bytes_used() -> usize {
     unsafe { SkGraphics::GetResourceCacheTotalBytesUsed() }
 }
 
+/// Returns the maximum number of bytes the resource cache may use.
 pub fn resource_cache_total_bytes_limit() -> usize {
     unsafe { SkGraphics::GetResourceCacheTotalByteLimit() }
 }
 
+/// Sets the maximum number of bytes the resource cache may use and returns the previous limit.
 pub fn set_resource_cache_total_bytes_limit(new_limit: usize) -> usize {
     unsafe { SkGraphics::SetResourceCacheTotalByteLimit(new_limit) }
 }
 
+/// Removes all unused entries from the resource cache.
 pub fn purge_resource_cache() {
     unsafe { SkGraphics::PurgeResourceCache() }
 }
 
+/// Returns the size above which single allocations are not cached, or `None` if there is no
+/// such limit.
 pub fn resource_cache_single_allocation_byte_limit() -> Option<usize> {
     let size = unsafe { SkGraphics::GetResourceCacheSingleAllocationByteLimit() };
     if size != 0 {
@@ -69,12 +89,48 @@ pub fn set_resource_cache_single_allocation_byte_limit(new_limit: Option<usize>)
     }
 }
 
-// TODO: dump_memory_statistics() (needs SkTraceMemoryDumpWrapper interop wrapper).
+/// Dumps memory usage statistics of the global caches to `dump`.
+pub fn dump_memory_statistics(dump: &mut dyn TraceMemoryDump) {
+    trace_memory_dump::with_native_trace_memory_dump(dump, |dump| unsafe {
+        sb::C_SkGraphics_DumpMemoryStatistics(dump)
+    })
+}
 
+/// Removes all unused entries from the font and the resource cache.
 pub fn purge_all_caches() {
     unsafe { SkGraphics::PurgeAllCaches() }
 }
 
+/// A snapshot of the memory used by the global caches.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct CacheUsage {
+    pub font_cache_bytes_used: usize,
+    pub font_cache_bytes_limit: usize,
+    pub font_cache_count_used: i32,
+    pub font_cache_count_limit: i32,
+    pub resource_cache_bytes_used: usize,
+    pub resource_cache_bytes_limit: usize,
+}
+
+impl CacheUsage {
+    /// The number of bytes used by both caches.
+    pub fn total_bytes_used(&self) -> usize {
+        self.font_cache_bytes_used + self.resource_cache_bytes_used
+    }
+}
+
+/// Returns the current memory usage and limits of the font and the resource cache.
+pub fn cache_usage() -> CacheUsage {
+    CacheUsage {
+        font_cache_bytes_used: font_cache_used(),
+        font_cache_bytes_limit: font_cache_limit(),
+        font_cache_count_used: font_cache_count_used(),
+        font_cache_count_limit: font_cache_count_limit(),
+        resource_cache_bytes_used: resource_cache_total_bytes_used(),
+        resource_cache_bytes_limit: resource_cache_total_bytes_limit(),
+    }
+}
+
 pub fn set_flags(flags: impl AsRef<str>) {
     let c_str = CString::new(flags.as_ref()).unwrap();
     unsafe { SkGraphics::SetFlags(c_str.as_ptr()) }
@@ -86,3 +142,27 @@ pub fn set_flags(flags: impl AsRef<str>) {
 pub fn allow_jit() {
     unsafe { SkGraphics::AllowJIT() }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Font, Paint, Surface, Typeface};
+
+    #[test]
+    #[serial_test::serial]
+    fn limit_and_purge_font_cache() {
+        let mut surface = Surface::new_raster_n32_premul((256, 64)).unwrap();
+        let font = Font::new(Typeface::default(), 24.0);
+        surface
+            .canvas()
+            .draw_str("Hello, World", (10, 40), &font, &Paint::default());
+
+        let previous = set_font_cache_limit(1 << 20);
+        let usage = cache_usage();
+        assert_eq!(usage.font_cache_bytes_limit, 1 << 20);
+        assert!(usage.font_cache_bytes_used <= usage.font_cache_bytes_limit);
+
+        purge_all_caches();
+        assert_eq!(set_font_cache_limit(previous), 1 << 20);
+    }
+}
@@ -0,0 +1,146 @@
+//! ICC color profiles, parsed with skcms.
+//!
+//! [`Profile::parse()`] reads the parts of a profile that skcms understands,
+//! [`ColorSpace::from_icc()`] converts a profile to the closest [`ColorSpace`], and
+//! [`ColorSpace::to_icc()`] writes a [`ColorSpace`] as a profile that can be embedded into
+//! encoded images and documents.
+use crate::{
+    cicp::{self, Cicp},
+    named_transfer_fn,
+    prelude::*,
+    ColorSpace, ColorSpaceTransferFn, Data, FourByteTag,
+};
+use skia_bindings::{self as sb, IccProfileInfo};
+
+/// The tone reproduction curve of a color channel.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Curve {
+    Parametric(ColorSpaceTransferFn),
+    /// A lookup table with the given number of entries.
+    Table(usize),
+}
+
+/// The structure of an ICC profile.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Profile {
+    /// The color space of the data, for example `RGB ` or `CMYK`.
+    pub data_color_space: FourByteTag,
+    /// The profile connection space, `XYZ ` or `Lab `.
+    pub pcs: FourByteTag,
+    pub tag_count: usize,
+    /// The curves of the red, green, and blue channel.
+    pub trc: Option<[Curve; 3]>,
+    /// The matrix that converts linear RGB to XYZ relative to the D50 white point, in row-major
+    /// order.
+    pub to_xyzd50: Option<[[f32; 3]; 3]>,
+    /// Whether the profile contains a transform from the device to the connection space based on
+    /// lookup tables.
+    pub has_a2b: bool,
+    /// Whether the profile contains a transform from the connection space to the device based on
+    /// lookup tables.
+    pub has_b2a: bool,
+    /// The color description of the `cicp` tag.
+    pub cicp: Option<Cicp>,
+}
+
+impl Profile {
+    pub const RGB: FourByteTag = FourByteTag::from_chars('R', 'G', 'B', ' ');
+    pub const CMYK: FourByteTag = FourByteTag::from_chars('C', 'M', 'Y', 'K');
+    pub const GRAY: FourByteTag = FourByteTag::from_chars('G', 'R', 'A', 'Y');
+    pub const XYZ: FourByteTag = FourByteTag::from_chars('X', 'Y', 'Z', ' ');
+    pub const LAB: FourByteTag = FourByteTag::from_chars('L', 'a', 'b', ' ');
+
+    /// Parses the ICC profile `icc`. Returns `None` if the profile is invalid or uses features
+    /// skcms does not support.
+    pub fn parse(icc: &[u8]) -> Option<Self> {
+        let mut info = IccProfileInfo {
+            dataColorSpace: 0,
+            pcs: 0,
+            tagCount: 0,
+            hasTRC: false,
+            trcTableEntries: [0; 3],
+            trc: [named_transfer_fn::LINEAR.into_native(); 3],
+            hasToXYZD50: false,
+            toXYZD50: sb::skcms_Matrix3x3 {
+                vals: [[0.0; 3]; 3],
+            },
+            hasA2B: false,
+            hasB2A: false,
+        };
+        unsafe { sb::C_skcms_Parse(icc.as_ptr() as _, icc.len(), &mut info) }.if_true_then_some(
+            || {
+                let curve = |i: usize| match info.trcTableEntries[i] {
+                    0 => Curve::Parametric(ColorSpaceTransferFn::from_native_c(info.trc[i])),
+                    entries => Curve::Table(entries.try_into().unwrap()),
+                };
+                Self {
+                    data_color_space: FourByteTag::new(info.dataColorSpace),
+                    pcs: FourByteTag::new(info.pcs),
+                    tag_count: info.tagCount.try_into().unwrap(),
+                    trc: info
+                        .hasTRC
+                        .if_true_then_some(|| [curve(0), curve(1), curve(2)]),
+                    to_xyzd50: info.hasToXYZD50.if_true_some(info.toXYZD50.vals),
+                    has_a2b: info.hasA2B,
+                    has_b2a: info.hasB2A,
+                    cicp: cicp::read_icc_tag(icc),
+                }
+            },
+        )
+    }
+}
+
+impl ColorSpace {
+    /// Creates the color space that is closest to the ICC profile `icc`.
+    ///
+    /// Returns `None` if the profile can not be parsed, or can not be represented by a transfer
+    /// function and a matrix to XYZ D50, for example CMYK profiles.
+    pub fn from_icc(icc: &[u8]) -> Option<Self> {
+        Self::from_ptr(unsafe { sb::C_SkColorSpace_MakeFromICC(icc.as_ptr() as _, icc.len()) })
+    }
+
+    /// Writes the color space as an ICC profile.
+    ///
+    /// Returns `None` if the transfer function can not be written, like the PQ and HLG transfer
+    /// functions.
+    pub fn to_icc(&self) -> Option<Data> {
+        Data::from_ptr(unsafe { sb::C_SkColorSpace_writeICCProfile(self.native()) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Curve, Profile};
+    use crate::{named_transfer_fn, ColorSpace};
+
+    #[test]
+    fn srgb_round_trip() {
+        let srgb = ColorSpace::new_srgb();
+        let icc = srgb.to_icc().unwrap();
+
+        let profile = Profile::parse(&icc).unwrap();
+        assert_eq!(profile.data_color_space, Profile::RGB);
+        assert_eq!(profile.pcs, Profile::XYZ);
+        assert!(profile.tag_count > 0);
+        assert!(profile.to_xyzd50.is_some());
+        let trc = profile.trc.unwrap();
+        match &trc[0] {
+            Curve::Parametric(tf) => assert!((tf.g - named_transfer_fn::SRGB.g).abs() < 0.01),
+            Curve::Table(_) => panic!("expected a parametric curve"),
+        }
+        assert!(!profile.has_a2b);
+
+        assert_eq!(ColorSpace::from_icc(&icc).unwrap(), srgb);
+        let linear = ColorSpace::new_srgb_linear();
+        assert_eq!(
+            ColorSpace::from_icc(&linear.to_icc().unwrap()).unwrap(),
+            linear
+        );
+    }
+
+    #[test]
+    fn invalid_profile() {
+        assert!(Profile::parse(b"not a profile").is_none());
+        assert!(ColorSpace::from_icc(&[]).is_none());
+    }
+}
@@ -4,10 +4,10 @@ use crate::SurfaceProps;
 use crate::{
     prelude::*, AlphaType, Bitmap, ColorSpace, ColorType, Data, EncodedImageFormat, IPoint, IRect,
     ISize, ImageFilter, ImageGenerator, ImageInfo, Matrix, Paint, Picture, Pixmap, SamplingOptions,
-    Shader, TileMode,
+    Shader, TileMode, YUVColorSpace,
 };
-use skia_bindings::{self as sb, SkImage, SkRefCntBase};
-use std::{fmt, mem, ptr};
+use skia_bindings::{self as sb, SkImage, SkImage_AsyncReadResult, SkRefCntBase};
+use std::{ffi, fmt, mem, ptr, slice};
 
 pub use super::CubicResampler;
 
@@ -39,6 +39,94 @@ variant_name!(BitDepth::F16);
 pub use skia_bindings::SkImage_CachingHint as CachingHint;
 variant_name!(CachingHint::Allow);
 
+/// Controls whether rescaling is done in the linear or the source color space.
+pub use skia_bindings::SkImage_RescaleGamma as RescaleGamma;
+variant_name!(RescaleGamma::Linear);
+
+/// The filter used to rescale the pixels of asynchronous reads.
+pub use skia_bindings::SkImage_RescaleMode as RescaleMode;
+variant_name!(RescaleMode::RepeatedLinear);
+
+/// The pixels of an asynchronous read, see [`Image::async_rescale_and_read_pixels()`] and
+/// [`Image::async_rescale_and_read_pixels_yuv420()`].
+pub struct AsyncReadResult {
+    native: RefHandle<SkImage_AsyncReadResult>,
+    rows: Vec<usize>,
+}
+
+unsafe impl Send for AsyncReadResult {}
+
+impl NativeDrop for SkImage_AsyncReadResult {
+    fn drop(&mut self) {
+        unsafe { sb::C_SkImage_AsyncReadResult_delete(self) }
+    }
+}
+
+impl fmt::Debug for AsyncReadResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncReadResult")
+            .field("count", &self.count())
+            .field("rows", &self.rows)
+            .finish()
+    }
+}
+
+impl AsyncReadResult {
+    /// The number of planes, `1` for RGBA reads and `3` for YUV420 reads.
+    pub fn count(&self) -> usize {
+        unsafe { sb::C_SkImage_AsyncReadResult_count(self.native.native()) }
+            .try_into()
+            .unwrap()
+    }
+
+    /// The pixels of plane `i`.
+    pub fn data(&self, i: usize) -> &[u8] {
+        assert!(i < self.count());
+        let data = unsafe { sb::C_SkImage_AsyncReadResult_data(self.native.native(), i as _) };
+        if data.is_null() {
+            return &[];
+        }
+        unsafe { slice::from_raw_parts(data as *const u8, self.rows[i] * self.row_bytes(i)) }
+    }
+
+    /// The number of bytes between the rows of plane `i`.
+    pub fn row_bytes(&self, i: usize) -> usize {
+        assert!(i < self.count());
+        unsafe { sb::C_SkImage_AsyncReadResult_rowBytes(self.native.native(), i as _) }
+    }
+}
+
+/// The context of an asynchronous read that is passed through Skia.
+struct AsyncReadContext {
+    callback: Box<dyn FnOnce(Option<AsyncReadResult>) + Send>,
+    rows: Vec<usize>,
+}
+
+impl AsyncReadContext {
+    fn new(
+        callback: impl FnOnce(Option<AsyncReadResult>) + Send + 'static,
+        rows: Vec<usize>,
+    ) -> *mut ffi::c_void {
+        let context = Box::new(Self {
+            callback: Box::new(callback),
+            rows,
+        });
+        Box::into_raw(context) as _
+    }
+
+    unsafe extern "C" fn callback(
+        context: *mut ffi::c_void,
+        result: *const SkImage_AsyncReadResult,
+    ) {
+        let context = Box::from_raw(context as *mut Self);
+        let result = RefHandle::from_ptr(result as *mut _).map(|native| AsyncReadResult {
+            native,
+            rows: context.rows,
+        });
+        (context.callback)(result)
+    }
+}
+
 /// [`Image`] describes a two dimensional array of pixels to draw. The pixels may be
 /// decoded in a raster bitmap, encoded in a [`Picture`] or compressed data stream,
 /// or located in GPU memory as a GPU texture.
@@ -373,6 +461,37 @@ impl Image {
         })
     }
 
+    /// Creates a mipmapped copy of `backend_texture` and generates its mip levels from the
+    /// current contents of the texture.
+    ///
+    /// Use this for textures that are rendered by another API, and call it again after their
+    /// contents changed. `backend_texture` should not have mip levels itself, because Skia
+    /// expects them to be up to date and would use the texture as is.
+    ///
+    /// The copy is flushed and submitted, so `backend_texture` can be rendered to again after
+    /// this returns.
+    #[cfg(feature = "gpu")]
+    pub fn from_texture_with_mipmaps(
+        context: &mut gpu::DirectContext,
+        backend_texture: &gpu::BackendTexture,
+        origin: gpu::SurfaceOrigin,
+        color_type: ColorType,
+        alpha_type: AlphaType,
+        color_space: impl Into<Option<ColorSpace>>,
+    ) -> Option<Image> {
+        let image = Image::from_texture(
+            context,
+            backend_texture,
+            origin,
+            color_type,
+            alpha_type,
+            color_space,
+        )?
+        .new_texture_image(context, gpu::Mipmapped::Yes)?;
+        image.flush_and_submit(context);
+        Some(image)
+    }
+
     // TODO: MakeFromCompressedTexture
 
     #[deprecated(since = "0.27.0", note = "renamed, use new_cross_context_from_pixmap")]
@@ -403,6 +522,11 @@ impl Image {
     /// as returned in raster format if possible; `None` may be returned.
     /// Recognized GPU formats vary by platform and GPU back-end.
     ///
+    /// This is typically used to decode and upload images on a worker thread that owns its own
+    /// `context`. The upload must be flushed and submitted on the worker's context with
+    /// [`gpu::DirectContext::flush_and_submit()`] before the [`Image`] is sent to and drawn on
+    /// another thread's context.
+    ///
     /// - `context`                 GPU context
     /// - `pixmap`                  [`ImageInfo`], pixel address, and row bytes
     /// - `build_mips`               create [`Image`] as mip map if `true`
@@ -460,17 +584,19 @@ impl Image {
     }
 
     /// Creates an [`Image`] from `YUV[A]` planar textures. This requires that the textures stay valid
-    /// for the lifetime of the image. The `ReleaseContext` can be used to know when it is safe to
-    /// either delete or overwrite the textures. If `ReleaseProc` is provided it is also called before
-    /// return on failure.
+    /// for the lifetime of the image. The YUV to RGB conversion is done on the GPU when the image
+    /// is drawn.
+    ///
+    /// Decoded video frames are typically described by a [`crate::YUVAInfo`] with
+    /// [`crate::yuva_info::PlaneConfig::Y_UV`] and [`crate::yuva_info::Subsampling::S420`] for
+    /// NV12, or [`crate::yuva_info::PlaneConfig::Y_U_V`] and
+    /// [`crate::yuva_info::Subsampling::S420`] for I420.
     ///
     /// - `context`             GPU context
     /// - `yuva_textures`        A set of textures containing YUVA data and a description of the
     ///                           data and transformation to RGBA.
     /// - `image_color_space`     range of colors of the resulting image after conversion to RGB;
     ///                           may be `None`
-    /// - `texture_release_proc`  called when the backend textures can be released
-    /// - `release_context`      state passed to `texture_release_proc`
     /// Returns: created [`Image`], or `None`
     #[cfg(feature = "gpu")]
     pub fn from_yuva_textures(
@@ -540,7 +666,52 @@ impl Image {
         panic!("Removed without replacement")
     }
 
-    // TODO: MakePromiseTexture
+    /// Create a new image that is backed by a texture that is provided by the client only when
+    /// the image is drawn. This is primarily intended for use with deferred display lists, where
+    /// the texture only needs to exist when the display list is replayed on the
+    /// [`gpu::DirectContext`] that generated `gpu_context_proxy`.
+    ///
+    /// `fulfill` is called when the texture is first needed. It should return a
+    /// [`crate::PromiseImageTexture`] that wraps a texture which matches `backend_format`,
+    /// `dimensions` and `mipmapped`, or `None` if the texture can not be provided, in which case
+    /// drawing the image is skipped. `release` is called once Skia does not need the texture
+    /// anymore and the image has been destroyed. It is also called if this function fails and
+    /// returns `None`.
+    ///
+    /// Both closures may be called from the thread that replays the display list.
+    #[cfg(feature = "gpu")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_promise_texture(
+        gpu_context_proxy: &gpu::ContextThreadSafeProxy,
+        backend_format: &gpu::BackendFormat,
+        dimensions: impl Into<ISize>,
+        mipmapped: gpu::Mipmapped,
+        origin: gpu::SurfaceOrigin,
+        color_type: ColorType,
+        alpha_type: AlphaType,
+        color_space: impl Into<Option<ColorSpace>>,
+        fulfill: impl FnMut() -> Option<crate::PromiseImageTexture> + Send + 'static,
+        release: impl FnOnce() + Send + 'static,
+    ) -> Option<Image> {
+        let (fulfill, release, context) =
+            super::promise_image_texture::new_promise_context(fulfill, release);
+        Image::from_ptr(unsafe {
+            sb::C_SkImage_MakePromiseTexture(
+                gpu_context_proxy.clone().into_ptr(),
+                backend_format.native(),
+                dimensions.into().native(),
+                mipmapped,
+                origin,
+                color_type.into_native(),
+                alpha_type,
+                color_space.into().into_ptr_or_null(),
+                fulfill,
+                release,
+                context,
+            )
+        })
+    }
+
     // TODO: MakePromiseYUVATexture
 
     /// Returns a [`ImageInfo`] describing the width, height, color type, alpha type, and color space
@@ -756,10 +927,13 @@ impl Image {
     ) -> gpu::SemaphoresSubmitted {
         let flush_info_default = gpu::FlushInfo::default();
         let flush_info = flush_info.into().unwrap_or(&flush_info_default);
-        unsafe {
-            self.native()
-                .flush(context.native_mut(), flush_info.native())
-        }
+        // Images that are not texture-backed never call the finished proc.
+        let flush_info = if self.is_texture_backed() {
+            flush_info.native_for_flush()
+        } else {
+            flush_info.native_for_flush_without_gpu()
+        };
+        unsafe { self.native().flush(context.native_mut(), &flush_info) }
     }
 
     /// Flushes any pending uses of texture-backed images in the GPU backend. If the image is not
@@ -814,6 +988,48 @@ impl Image {
         .map(|texture| (texture, origin))
     }
 
+    /// Releases the image's `VkImage` to the queue family `queue_family_index`, for example
+    /// [`gpu::vk::QUEUE_FAMILY_FOREIGN_EXT`] or [`gpu::vk::QUEUE_FAMILY_EXTERNAL`], leaving it in
+    /// `layout`. Skia records the ownership transfer barrier; the work needs to be submitted
+    /// with [`gpu::DirectContext::submit()`] before the image is used outside of Skia.
+    ///
+    /// Returns `false` if the image is not backed by a Vulkan texture of `context`.
+    #[cfg(feature = "vulkan")]
+    pub fn release_vk_image(
+        &self,
+        context: &mut gpu::DirectContext,
+        layout: gpu::vk::ImageLayout,
+        queue_family_index: u32,
+    ) -> bool {
+        match self.backend_texture(true) {
+            Some((texture, _)) if texture.backend() == gpu::BackendAPI::Vulkan => context
+                .set_backend_texture_state(
+                    &texture,
+                    &gpu::MutableTextureState::new_vk(layout, queue_family_index),
+                ),
+            _ => false,
+        }
+    }
+
+    /// Tells Skia that the image's `VkImage` was handed back in `layout` and is owned by the
+    /// queue family `queue_family_index`. Skia acquires the image with a queue family ownership
+    /// transfer barrier before it uses it again.
+    ///
+    /// Returns `false` if the image is not backed by a Vulkan texture.
+    #[cfg(feature = "vulkan")]
+    pub fn vk_image_acquired(&self, layout: gpu::vk::ImageLayout, queue_family_index: u32) -> bool {
+        match self.backend_texture(false) {
+            Some((mut texture, _)) if texture.backend() == gpu::BackendAPI::Vulkan => {
+                texture.set_mutable_state(&gpu::MutableTextureState::new_vk(
+                    layout,
+                    queue_family_index,
+                ));
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Copies [`crate::Rect`] of pixels from [`Image`] to `dst_pixels`. Copy starts at offset (`src_x`, `src_y`),
     /// and does not exceed [`Image`] (width(), height()).
     ///
@@ -970,10 +1186,86 @@ impl Image {
             .readPixels1(ptr::null_mut(), dst.native(), src.x, src.y, caching_hint)
     }
 
-    // TODO:
-    // AsyncReadResult, ReadPixelsContext, ReadPixelsCallback, RescaleGamma,
-    // RescaleMode,
-    // asyncRescaleAndReadPixels, asyncRescaleAndReadPixelsYUV420
+    /// Makes image pixel data available to the caller, possibly asynchronously. The pixels of
+    /// `src_rect` are rescaled to the dimensions of `info` and converted to its color type,
+    /// alpha type, and color space.
+    ///
+    /// `callback` is called with the result, or with `None` if the pixels could not be read.
+    /// For raster images, it is called before this function returns. For texture backed
+    /// images, it is called from the thread that calls [`gpu::DirectContext::submit()`] or
+    /// [`gpu::DirectContext::check_async_work_completion()`] after the GPU work has
+    /// finished.
+    ///
+    /// - `info`            info of the requested pixels
+    /// - `src_rect`        subrectangle of image to read
+    /// - `rescale_gamma`   controls whether rescaling is done in the linear or the source
+    ///                     color space
+    /// - `rescale_mode`    controls the quality (and cost) of the rescaling
+    /// - `callback`        function to call with the result of the read
+    pub fn async_rescale_and_read_pixels(
+        &self,
+        info: &ImageInfo,
+        src_rect: impl AsRef<IRect>,
+        rescale_gamma: RescaleGamma,
+        rescale_mode: RescaleMode,
+        callback: impl FnOnce(Option<AsyncReadResult>) + Send + 'static,
+    ) {
+        let rows = vec![info.height().max(0) as usize];
+        unsafe {
+            sb::C_SkImage_asyncRescaleAndReadPixels(
+                self.native(),
+                info.native(),
+                src_rect.as_ref().native(),
+                rescale_gamma,
+                rescale_mode,
+                Some(AsyncReadContext::callback),
+                AsyncReadContext::new(callback, rows),
+            )
+        }
+    }
+
+    /// Like [`Self::async_rescale_and_read_pixels()`], but converts the pixels to YUV with
+    /// `yuv_color_space` and returns them in three planes: a Y plane of `dst_size`, and U and V
+    /// planes of half the width and the height. The image is first converted to
+    /// `dst_color_space`, which defaults to sRGB if `None` is passed.
+    ///
+    /// Both dimensions of `dst_size` must be even, otherwise the read fails and `callback` is
+    /// called with `None`.
+    ///
+    /// Only images with a color type that has no alpha channel or whose alpha type is
+    /// [`AlphaType::Opaque`] or [`AlphaType::Premul`] can be read as YUV.
+    #[allow(clippy::too_many_arguments)]
+    pub fn async_rescale_and_read_pixels_yuv420(
+        &self,
+        yuv_color_space: YUVColorSpace,
+        dst_color_space: impl Into<Option<ColorSpace>>,
+        src_rect: impl AsRef<IRect>,
+        dst_size: impl Into<ISize>,
+        rescale_gamma: RescaleGamma,
+        rescale_mode: RescaleMode,
+        callback: impl FnOnce(Option<AsyncReadResult>) + Send + 'static,
+    ) {
+        let dst_size = dst_size.into();
+        if dst_size.width % 2 != 0 || dst_size.height % 2 != 0 {
+            callback(None);
+            return;
+        }
+        let height = dst_size.height.max(0) as usize;
+        let rows = vec![height, height / 2, height / 2];
+        unsafe {
+            sb::C_SkImage_asyncRescaleAndReadPixelsYUV420(
+                self.native(),
+                yuv_color_space,
+                dst_color_space.into().into_ptr_or_null(),
+                src_rect.as_ref().native(),
+                dst_size.native(),
+                rescale_gamma,
+                rescale_mode,
+                Some(AsyncReadContext::callback),
+                AsyncReadContext::new(callback, rows),
+            )
+        }
+    }
 
     /// Copies [`Image`] to dst, scaling pixels to fit `dst.width()` and `dst.height()`, and
     /// converting pixels to match `dst.color_type()` and `dst.alpha_type()`. Returns `true` if
@@ -1198,7 +1490,6 @@ impl Image {
         Image::from_ptr(unsafe { sb::C_SkImage_makeRasterImage(self.native(), caching_hint) })
     }
 
-    // TODO: rename to with_filter()?
     /// Creates filtered [`Image`]. filter processes original [`Image`], potentially changing
     /// color, position, and size. subset is the bounds of original [`Image`] processed
     /// by filter. `clip_bounds` is the expected bounds of the filtered [`Image`]. `out_subset`
@@ -1272,6 +1563,60 @@ impl Image {
         .map(|image| (image, out_subset, offset))
     }
 
+    /// Applies `filter` to the `subset` of this image offscreen and returns the part of the
+    /// result that is inside `clip_bounds`, together with the position it needs to be drawn at
+    /// relative to this image.
+    ///
+    /// In contrast to [`Self::new_with_filter()`], the returned image contains only the valid
+    /// pixels of the result, so it can be drawn with [`crate::Canvas::draw_image()`] at the
+    /// returned position.
+    ///
+    /// - `context`      the [`gpu::RecordingContext`] the image was created with, if any
+    /// - `filter`       the filter chain to apply
+    /// - `subset`       bounds of [`Image`] processed by filter
+    /// - `clip_bounds`  expected bounds of the filtered [`Image`]
+    #[cfg(feature = "gpu")]
+    pub fn with_filter(
+        &self,
+        mut context: Option<&mut gpu::RecordingContext>,
+        filter: &ImageFilter,
+        subset: impl Into<IRect>,
+        clip_bounds: impl Into<IRect>,
+    ) -> Option<(Image, IPoint)> {
+        let mut direct_context = context
+            .as_deref_mut()
+            .and_then(|context| context.as_direct_context());
+        let (image, out_subset, offset) =
+            self.new_with_filter(context, filter, clip_bounds, subset)?;
+        if out_subset == image.bounds() {
+            return Some((image, offset));
+        }
+        image
+            .new_subset_with_context(out_subset, direct_context.as_mut())
+            .map(|image| (image, offset))
+    }
+
+    /// Applies `filter` to the `subset` of this image offscreen and returns the part of the
+    /// result that is inside `clip_bounds`, together with the position it needs to be drawn at
+    /// relative to this image.
+    ///
+    /// In contrast to [`Self::new_with_filter()`], the returned image contains only the valid
+    /// pixels of the result, so it can be drawn with [`crate::Canvas::draw_image()`] at the
+    /// returned position.
+    #[cfg(not(feature = "gpu"))]
+    pub fn with_filter(
+        &self,
+        filter: &ImageFilter,
+        subset: impl Into<IRect>,
+        clip_bounds: impl Into<IRect>,
+    ) -> Option<(Image, IPoint)> {
+        let (image, out_subset, offset) = self.new_with_filter(filter, clip_bounds, subset)?;
+        if out_subset == image.bounds() {
+            return Some((image, offset));
+        }
+        image.new_subset(out_subset).map(|image| (image, offset))
+    }
+
     // TODO: MakeBackendTextureFromSkImage()
 
     /// Returns `true` if [`Image`] is backed by an image-generator or other service that creates
@@ -1335,3 +1680,32 @@ impl Image {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{RescaleGamma, RescaleMode};
+    use crate::{AlphaType, Color, ColorType, IRect, ImageInfo, Surface};
+    use std::sync::mpsc;
+
+    #[test]
+    fn async_read_of_raster_image_completes_immediately() {
+        let mut surface = Surface::new_raster_n32_premul((8, 8)).unwrap();
+        surface.canvas().clear(Color::RED);
+        let image = surface.image_snapshot();
+
+        let info = ImageInfo::new((4, 4), ColorType::RGBA8888, AlphaType::Premul, None);
+        let (sender, receiver) = mpsc::channel();
+        image.async_rescale_and_read_pixels(
+            &info,
+            IRect::from_wh(8, 8),
+            RescaleGamma::Src,
+            RescaleMode::Nearest,
+            move |result| sender.send(result).unwrap(),
+        );
+
+        let result = receiver.try_recv().unwrap().unwrap();
+        assert_eq!(result.count(), 1);
+        assert!(result.row_bytes(0) >= 16);
+        assert_eq!(&result.data(0)[..4], &[0xff, 0, 0, 0xff]);
+    }
+}
@@ -54,14 +54,20 @@ impl ImageGenerator {
 
     // TODO: m86: get_pixels(&Pixmap)
 
-    // TODO: generateTexture()
-
     #[cfg(feature = "gpu")]
     #[deprecated(since = "0.29.0", note = "removed without replacement")]
     pub fn textures_are_cacheable(&self) -> ! {
         unimplemented!("removed without replacement")
     }
 
+    /// Creates an image generator that forwards to `generator`. Pass it to
+    /// [`crate::Image::from_generator()`] to create a lazily generated image.
+    pub fn new_custom(generator: impl CustomImageGenerator) -> Self {
+        let info = generator.info();
+        let param = rust_image_generator::new_param(Box::new(generator));
+        Self::from_ptr(unsafe { sb::C_RustImageGenerator_new(info.native(), &param) }).unwrap()
+    }
+
     pub fn from_encoded(encoded: impl Into<Data>) -> Option<Self> {
         Self::from_ptr(unsafe {
             sb::C_SkImageGenerator_MakeFromEncoded(encoded.into().into_ptr(), ptr::null())
@@ -124,3 +130,162 @@ impl ImageGenerator {
         })
     }
 }
+
+/// An image generator implemented in Rust, see [`ImageGenerator::new_custom()`].
+///
+/// Images created from a generator produce their pixels only when they are drawn or read, and
+/// Skia caches the pixels (or textures) it generates, so they can be purged under memory
+/// pressure and regenerated later on.
+pub trait CustomImageGenerator: Send + 'static {
+    /// The dimensions and the preferred format of the image. Called once when the generator is
+    /// created.
+    fn info(&self) -> ImageInfo;
+
+    /// Returns the encoded data the image was created from, if there is any.
+    fn encoded_data(&mut self) -> Option<Data> {
+        None
+    }
+
+    /// Writes the pixels of the image to `pixels`, which contains the rows of `info` that are
+    /// `row_bytes` apart.
+    ///
+    /// `info` has the dimensions of [`Self::info()`], but may request a different color type,
+    /// alpha type, or color space. Returns `false` if the pixels can not be generated in the
+    /// requested format.
+    fn get_pixels(&mut self, info: &ImageInfo, pixels: &mut [u8], row_bytes: usize) -> bool;
+
+    /// Generates a texture backed image of the dimensions of `info` on `context`.
+    ///
+    /// The image must have the dimensions and the color type of `info`, otherwise no texture is
+    /// generated. Images that are not texture backed on `context` are uploaded.
+    ///
+    /// Return `None` to let Skia upload the pixels returned from [`Self::get_pixels()`]
+    /// instead, which is what the default implementation does.
+    #[cfg(feature = "gpu")]
+    fn generate_texture(
+        &mut self,
+        _context: &mut gpu::RecordingContext,
+        _info: &ImageInfo,
+        _mipmapped: gpu::Mipmapped,
+    ) -> Option<crate::Image> {
+        None
+    }
+}
+
+mod rust_image_generator {
+    use super::CustomImageGenerator;
+    #[cfg(feature = "gpu")]
+    use crate::gpu;
+    use crate::{prelude::*, ImageInfo};
+    use skia_bindings::{self as sb, RustImageGenerator_Param, SkImageInfo, TraitObject};
+    use std::{
+        ffi, mem,
+        panic::{self, AssertUnwindSafe},
+        slice,
+    };
+
+    pub fn new_param(generator: Box<dyn CustomImageGenerator>) -> RustImageGenerator_Param {
+        RustImageGenerator_Param {
+            trait_: unsafe { mem::transmute(Box::into_raw(generator)) },
+            refEncodedData: Some(ref_encoded_data),
+            getPixels: Some(get_pixels),
+            #[cfg(feature = "gpu")]
+            generateTexture: Some(generate_texture),
+            #[cfg(not(feature = "gpu"))]
+            generateTexture: None,
+            drop: Some(drop),
+        }
+    }
+
+    unsafe extern "C" fn ref_encoded_data(to: TraitObject) -> *mut sb::SkData {
+        panic::catch_unwind(AssertUnwindSafe(|| to_generator(to).encoded_data()))
+            .ok()
+            .flatten()
+            .into_ptr_or_null()
+    }
+
+    unsafe extern "C" fn get_pixels(
+        to: TraitObject,
+        info: *const SkImageInfo,
+        pixels: *mut ffi::c_void,
+        row_bytes: usize,
+    ) -> bool {
+        let info = ImageInfo::from_native_ref(&*info);
+        let pixels =
+            slice::from_raw_parts_mut(pixels as *mut u8, info.compute_byte_size(row_bytes));
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            to_generator(to).get_pixels(info, pixels, row_bytes)
+        }))
+        .unwrap_or(false)
+    }
+
+    #[cfg(feature = "gpu")]
+    unsafe extern "C" fn generate_texture(
+        to: TraitObject,
+        context: *mut sb::GrRecordingContext,
+        info: *const SkImageInfo,
+        mipmapped: gpu::Mipmapped,
+    ) -> *mut sb::SkImage {
+        let mut context = match gpu::RecordingContext::from_unshared_ptr(context) {
+            Some(context) => context,
+            None => return std::ptr::null_mut(),
+        };
+        let info = ImageInfo::from_native_ref(&*info);
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            to_generator(to).generate_texture(&mut context, info, mipmapped)
+        }))
+        .ok()
+        .flatten()
+        .into_ptr_or_null()
+    }
+
+    unsafe extern "C" fn drop(to: TraitObject) {
+        mem::drop(Box::from_raw(to_generator(to)))
+    }
+
+    unsafe fn to_generator<'a>(to: TraitObject) -> &'a mut dyn CustomImageGenerator {
+        &mut *mem::transmute::<TraitObject, *mut dyn CustomImageGenerator>(to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CustomImageGenerator, ImageGenerator};
+    use crate::{image::CachingHint, AlphaType, ColorType, Image, ImageInfo};
+
+    struct Checkerboard;
+
+    impl CustomImageGenerator for Checkerboard {
+        fn info(&self) -> ImageInfo {
+            ImageInfo::new((4, 4), ColorType::RGBA8888, AlphaType::Opaque, None)
+        }
+
+        fn get_pixels(&mut self, info: &ImageInfo, pixels: &mut [u8], row_bytes: usize) -> bool {
+            if info.color_type() != ColorType::RGBA8888 {
+                return false;
+            }
+            for y in 0..info.height() as usize {
+                for x in 0..info.width() as usize {
+                    let value = if (x + y) % 2 == 0 { 0xff } else { 0 };
+                    let pixel = y * row_bytes + x * 4;
+                    pixels[pixel..pixel + 4].copy_from_slice(&[value, value, value, 0xff]);
+                }
+            }
+            true
+        }
+    }
+
+    #[test]
+    fn image_from_custom_generator() {
+        let generator = ImageGenerator::new_custom(Checkerboard);
+        assert_eq!(generator.info().width(), 4);
+        let image = Image::from_generator(generator).unwrap();
+        assert!(image.is_lazy_generated());
+
+        let info = ImageInfo::new((4, 4), ColorType::RGBA8888, AlphaType::Opaque, None);
+        let mut pixels = vec![0u8; 4 * 4 * 4];
+        assert!(image.read_pixels(&info, &mut pixels, 16, (0, 0), CachingHint::Allow));
+        assert_eq!(pixels[0..4], [0xff, 0xff, 0xff, 0xff]);
+        assert_eq!(pixels[4..8], [0, 0, 0, 0xff]);
+    }
+}
@@ -0,0 +1,317 @@
+//! Conversions between the pixel containers of Skia and the ones of the
+//! [image](https://crates.io/crates/image) crate.
+//!
+//! Pixels are shared without copying between [`Pixmap`] and [`ImageBuffer`] if they have the
+//! same layout:
+//!
+//! | `image` pixel  | [`ColorType`]                          | [`AlphaType`]              |
+//! |----------------|----------------------------------------|----------------------------|
+//! | `Luma<u8>`     | [`ColorType::Gray8`]                   | [`AlphaType::Opaque`]      |
+//! | `Rgba<u8>`     | [`ColorType::RGBA8888`]                | `Unpremul` or `Opaque`     |
+//! | `Rgba<u16>`    | [`ColorType::R16G16B16A16UNorm`]       | `Unpremul` or `Opaque`     |
+//! | `Rgba<f32>`    | [`ColorType::RGBAF32`]                 | `Unpremul` or `Opaque`     |
+//!
+//! Conversions from and to [`DynamicImage`] copy the pixels and convert them if needed.
+
+use crate::{
+    image::CachingHint, prelude::*, AlphaType, Bitmap, ColorType, Data, Image, ImageInfo, Pixmap,
+};
+use image::{DynamicImage, ImageBuffer, Luma, Pixel, Rgba};
+use std::{borrow::Cow, error, fmt, mem, ops::Deref, slice};
+
+/// The error that is returned when a conversion between the types of Skia and the types of the
+/// `image` crate fails.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ImageConversionError {
+    /// The pixels can not be shared, because the color type, the alpha type, or the row bytes
+    /// differ.
+    LayoutMismatch,
+    /// The pixels could not be read, for example from a texture backed [`Image`].
+    ReadPixels,
+    /// The image is empty or too large.
+    InvalidDimensions,
+}
+
+impl fmt::Display for ImageConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ImageConversionError::LayoutMismatch => "pixel layouts do not match",
+            ImageConversionError::ReadPixels => "failed to read pixels",
+            ImageConversionError::InvalidDimensions => "invalid image dimensions",
+        })
+    }
+}
+
+impl error::Error for ImageConversionError {}
+
+macro_rules! shared_pixels {
+    ($pixel:ty, $subpixel:ty, $color_type:expr, $alpha_type:expr) => {
+        /// Shares the pixels of the [`Pixmap`] if their layout matches.
+        impl<'a> TryFrom<&'a Pixmap> for ImageBuffer<$pixel, &'a [$subpixel]> {
+            type Error = ImageConversionError;
+
+            fn try_from(pixmap: &'a Pixmap) -> Result<Self, Self::Error> {
+                let info = pixmap.info();
+                if info.color_type() != $color_type
+                    || info.alpha_type() == AlphaType::Premul
+                    || pixmap.row_bytes() != info.min_row_bytes()
+                {
+                    return Err(ImageConversionError::LayoutMismatch);
+                }
+                let bytes = pixmap.bytes().ok_or(ImageConversionError::ReadPixels)?;
+                let (prefix, subpixels, suffix) = unsafe { bytes.align_to::<$subpixel>() };
+                if !prefix.is_empty() || !suffix.is_empty() {
+                    return Err(ImageConversionError::LayoutMismatch);
+                }
+                let (width, height) = (info.width() as u32, info.height() as u32);
+                ImageBuffer::from_raw(width, height, subpixels)
+                    .ok_or(ImageConversionError::InvalidDimensions)
+            }
+        }
+
+        /// Shares the pixels of the [`ImageBuffer`].
+        impl<'a, C> TryFrom<&'a ImageBuffer<$pixel, C>> for Borrows<'a, Pixmap>
+        where
+            C: Deref<Target = [$subpixel]>,
+        {
+            type Error = ImageConversionError;
+
+            fn try_from(buffer: &'a ImageBuffer<$pixel, C>) -> Result<Self, Self::Error> {
+                let info = image_info(buffer.dimensions(), $color_type, $alpha_type)?;
+                let row_bytes = info.min_row_bytes();
+                Ok(Pixmap::new(&info, as_bytes(buffer.as_raw()), row_bytes))
+            }
+        }
+    };
+}
+
+shared_pixels!(Luma<u8>, u8, ColorType::Gray8, AlphaType::Opaque);
+shared_pixels!(Rgba<u8>, u8, ColorType::RGBA8888, AlphaType::Unpremul);
+shared_pixels!(
+    Rgba<u16>,
+    u16,
+    ColorType::R16G16B16A16UNorm,
+    AlphaType::Unpremul
+);
+shared_pixels!(Rgba<f32>, f32, ColorType::RGBAF32, AlphaType::Unpremul);
+
+/// Copies the pixels, converts premultiplied colors and color types the `image` crate does not
+/// support.
+impl TryFrom<&Pixmap> for DynamicImage {
+    type Error = ImageConversionError;
+
+    fn try_from(pixmap: &Pixmap) -> Result<Self, Self::Error> {
+        read_dynamic_image(pixmap.info(), |info, pixels, row_bytes| {
+            pixmap.read_pixels(info, pixels, row_bytes, (0, 0))
+        })
+    }
+}
+
+/// Copies the pixels of the bitmap.
+impl TryFrom<&Bitmap> for DynamicImage {
+    type Error = ImageConversionError;
+
+    fn try_from(bitmap: &Bitmap) -> Result<Self, Self::Error> {
+        DynamicImage::try_from(bitmap.pixmap())
+    }
+}
+
+/// Copies the pixels of raster and lazy generated images. The pixels of texture backed images
+/// can not be read without a context and need to be read with
+/// [`Image::read_pixels_with_context()`] instead.
+impl TryFrom<&Image> for DynamicImage {
+    type Error = ImageConversionError;
+
+    fn try_from(image: &Image) -> Result<Self, Self::Error> {
+        read_dynamic_image(image.image_info(), |info, pixels, row_bytes| {
+            image.read_pixels(info, pixels, row_bytes, (0, 0), CachingHint::Disallow)
+        })
+    }
+}
+
+/// Copies the pixels into a new raster image.
+impl TryFrom<&DynamicImage> for Image {
+    type Error = ImageConversionError;
+
+    fn try_from(image: &DynamicImage) -> Result<Self, Self::Error> {
+        with_pixmap(image, |pixmap| {
+            let bytes = pixmap.bytes().ok_or(ImageConversionError::ReadPixels)?;
+            Image::from_raster_data(pixmap.info(), Data::new_copy(bytes), pixmap.row_bytes())
+                .ok_or(ImageConversionError::InvalidDimensions)
+        })
+    }
+}
+
+/// Copies the pixels into a new bitmap.
+impl TryFrom<&DynamicImage> for Bitmap {
+    type Error = ImageConversionError;
+
+    fn try_from(image: &DynamicImage) -> Result<Self, Self::Error> {
+        with_pixmap(image, |pixmap| {
+            let mut bitmap = Bitmap::new();
+            if !bitmap.try_alloc_pixels_flags(pixmap.info()) {
+                return Err(ImageConversionError::InvalidDimensions);
+            }
+            if !pixmap.read_pixels_to_pixmap(bitmap.pixmap(), (0, 0)) {
+                return Err(ImageConversionError::ReadPixels);
+            }
+            bitmap.notify_pixels_changed();
+            Ok(bitmap)
+        })
+    }
+}
+
+/// Calls `f` with a pixmap that shares the pixels of `image`, after converting them to one of
+/// the pixel formats Skia supports.
+fn with_pixmap<R>(
+    image: &DynamicImage,
+    f: impl FnOnce(&Pixmap) -> Result<R, ImageConversionError>,
+) -> Result<R, ImageConversionError> {
+    let image = match image {
+        DynamicImage::ImageLuma8(_)
+        | DynamicImage::ImageRgba8(_)
+        | DynamicImage::ImageRgba16(_)
+        | DynamicImage::ImageRgba32F(_) => Cow::Borrowed(image),
+        DynamicImage::ImageLuma16(_)
+        | DynamicImage::ImageLumaA16(_)
+        | DynamicImage::ImageRgb16(_) => Cow::Owned(DynamicImage::ImageRgba16(image.to_rgba16())),
+        DynamicImage::ImageRgb32F(_) => Cow::Owned(DynamicImage::ImageRgba32F(image.to_rgba32f())),
+        _ => Cow::Owned(DynamicImage::ImageRgba8(image.to_rgba8())),
+    };
+    let pixmap = match image.as_ref() {
+        DynamicImage::ImageLuma8(buffer) => Borrows::<Pixmap>::try_from(buffer)?,
+        DynamicImage::ImageRgba16(buffer) => Borrows::<Pixmap>::try_from(buffer)?,
+        DynamicImage::ImageRgba32F(buffer) => Borrows::<Pixmap>::try_from(buffer)?,
+        DynamicImage::ImageRgba8(buffer) => Borrows::<Pixmap>::try_from(buffer)?,
+        _ => unreachable!(),
+    };
+    f(&pixmap)
+}
+
+/// Reads pixels of `info`'s dimensions into the [`DynamicImage`] variant that preserves most of
+/// the precision of `info`'s color type.
+fn read_dynamic_image(
+    info: &ImageInfo,
+    read_pixels: impl FnOnce(&ImageInfo, &mut [u8], usize) -> bool,
+) -> Result<DynamicImage, ImageConversionError> {
+    use ColorType::*;
+    let alpha_type = if info.alpha_type() == AlphaType::Opaque {
+        AlphaType::Opaque
+    } else {
+        AlphaType::Unpremul
+    };
+    let image = match info.color_type() {
+        Gray8 => DynamicImage::ImageLuma8(read_buffer(
+            info.with_color_type(Gray8)
+                .with_alpha_type(AlphaType::Opaque),
+            read_pixels,
+        )?),
+        RGBA1010102 | BGRA1010102 | RGB101010x | BGR101010x | R16G16UNorm | A16UNorm
+        | R16G16B16A16UNorm => DynamicImage::ImageRgba16(read_buffer(
+            info.with_color_type(R16G16B16A16UNorm)
+                .with_alpha_type(alpha_type),
+            read_pixels,
+        )?),
+        BGR101010xXR | RGBAF16Norm | RGBAF16 | RGBAF32 | A16Float | R16G16Float => {
+            DynamicImage::ImageRgba32F(read_buffer(
+                info.with_color_type(RGBAF32).with_alpha_type(alpha_type),
+                read_pixels,
+            )?)
+        }
+        _ => DynamicImage::ImageRgba8(read_buffer(
+            info.with_color_type(RGBA8888).with_alpha_type(alpha_type),
+            read_pixels,
+        )?),
+    };
+    Ok(image)
+}
+
+fn read_buffer<P: Pixel>(
+    info: ImageInfo,
+    read_pixels: impl FnOnce(&ImageInfo, &mut [u8], usize) -> bool,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ImageConversionError> {
+    let (width, height) = match (u32::try_from(info.width()), u32::try_from(info.height())) {
+        (Ok(width), Ok(height)) if width > 0 && height > 0 => (width, height),
+        _ => return Err(ImageConversionError::InvalidDimensions),
+    };
+    let mut buffer = ImageBuffer::<P, Vec<P::Subpixel>>::new(width, height);
+    let row_bytes = info.min_row_bytes();
+    if !read_pixels(&info, as_bytes_mut(&mut buffer), row_bytes) {
+        return Err(ImageConversionError::ReadPixels);
+    }
+    Ok(buffer)
+}
+
+fn image_info(
+    (width, height): (u32, u32),
+    color_type: ColorType,
+    alpha_type: AlphaType,
+) -> Result<ImageInfo, ImageConversionError> {
+    match (i32::try_from(width), i32::try_from(height)) {
+        (Ok(width), Ok(height)) => Ok(ImageInfo::new(
+            (width, height),
+            color_type,
+            alpha_type,
+            None,
+        )),
+        _ => Err(ImageConversionError::InvalidDimensions),
+    }
+}
+
+/// The subpixel types of the `image` crate are plain numbers that can be viewed as bytes.
+fn as_bytes<T: Copy>(subpixels: &[T]) -> &[u8] {
+    unsafe { slice::from_raw_parts(subpixels.as_ptr() as *const u8, mem::size_of_val(subpixels)) }
+}
+
+fn as_bytes_mut<T: Copy>(subpixels: &mut [T]) -> &mut [u8] {
+    unsafe {
+        slice::from_raw_parts_mut(
+            subpixels.as_mut_ptr() as *mut u8,
+            mem::size_of_val(subpixels),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AlphaType, Borrows, ColorType, Image, ImageInfo, Pixmap};
+    use image::{DynamicImage, ImageBuffer, Rgb, Rgba};
+
+    #[test]
+    fn pixmap_shares_the_pixels_of_an_image_buffer() {
+        let buffer = ImageBuffer::from_pixel(3, 2, Rgba([1u8, 2, 3, 4]));
+        let pixmap = Borrows::<Pixmap>::try_from(&buffer).unwrap();
+        assert_eq!(pixmap.color_type(), ColorType::RGBA8888);
+        assert_eq!(pixmap.bytes().unwrap().as_ptr(), buffer.as_raw().as_ptr());
+
+        let shared = ImageBuffer::<Rgba<u8>, &[u8]>::try_from(&*pixmap).unwrap();
+        assert_eq!(shared.get_pixel(2, 1), &Rgba([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn premultiplied_pixels_are_not_shared() {
+        let info = ImageInfo::new((1, 1), ColorType::RGBA8888, AlphaType::Premul, None);
+        let pixels = [0x80u8, 0x80, 0x80, 0x80];
+        let pixmap = Pixmap::new(&info, &pixels, 4);
+        assert!(ImageBuffer::<Rgba<u8>, &[u8]>::try_from(&*pixmap).is_err());
+
+        let image = DynamicImage::try_from(&*pixmap).unwrap();
+        assert_eq!(
+            image.as_rgba8().unwrap().get_pixel(0, 0),
+            &Rgba([0xff, 0xff, 0xff, 0x80])
+        );
+    }
+
+    #[test]
+    fn dynamic_image_round_trip() {
+        let rgb = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(2, 2, Rgb([10u8, 20, 30])));
+        let image = Image::try_from(&rgb).unwrap();
+        assert_eq!(image.color_type(), ColorType::RGBA8888);
+
+        let back = DynamicImage::try_from(&image).unwrap();
+        assert_eq!(
+            back.as_rgba8().unwrap().get_pixel(1, 1),
+            &Rgba([10, 20, 30, 255])
+        );
+    }
+}
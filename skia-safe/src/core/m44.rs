@@ -8,6 +8,7 @@ use std::{
 
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct V2 {
     pub x: f32,
     pub y: f32,
@@ -151,6 +152,7 @@ impl DivAssign<scalar> for V2 {
 
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct V3 {
     pub x: f32,
     pub y: f32,
@@ -279,6 +281,7 @@ impl MulAssign<scalar> for V3 {
 
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct V4 {
     pub x: f32,
     pub y: f32,
@@ -260,10 +260,21 @@ impl Paint {
         self
     }
 
+    /// Returns the user-supplied blend function, if any.
+    /// A `None` value indicates that the paint uses [`BlendMode::SrcOver`].
     pub fn blender(&self) -> Option<Blender> {
         Blender::from_unshared_ptr(self.native().fBlender.fPtr)
     }
 
+    /// Sets the current blender, increasing its refcnt, and if a blender is already present,
+    /// decreasing that object's refcnt.
+    ///
+    /// - A `None` blender signifies the default [`BlendMode::SrcOver`] behavior.
+    /// - For convenience, you can call [`Self::set_blend_mode()`] if the blend effect can be
+    ///   expressed as one of those values.
+    ///
+    /// Custom blend equations can be created with [`crate::RuntimeEffect::make_blender()`] or
+    /// [`crate::blenders::arithmetic()`].
     pub fn set_blender(&mut self, blender: impl Into<Option<Blender>>) -> &mut Self {
         unsafe { sb::C_SkPaint_setBlender(self.native_mut(), blender.into().into_ptr_or_null()) }
         self
@@ -357,3 +368,31 @@ fn set_color4f_color_space() {
     let color2 = Color4f::from(Color::DARK_GRAY);
     paint.set_color4f(color2, Some(&color_space));
 }
+
+#[test]
+fn set_runtime_blender() {
+    use crate::{RuntimeEffect, Surface};
+
+    let effect = RuntimeEffect::make_for_blender(
+        "half4 main(half4 src, half4 dst) { return half4(src.r, dst.g, 0, 1); }",
+        None,
+    )
+    .unwrap();
+    let blender = effect.make_blender(crate::Data::new_empty(), None).unwrap();
+
+    let mut paint = Paint::default();
+    paint.set_color(Color::RED).set_blender(blender);
+    assert!(paint.blender().is_some());
+    assert_eq!(paint.as_blend_mode(), None);
+
+    let mut surface = Surface::new_raster_n32_premul((1, 1)).unwrap();
+    surface.canvas().clear(Color::GREEN).draw_paint(&paint);
+    assert_eq!(
+        surface.canvas().peek_pixels().unwrap().get_color((0, 0)),
+        Color::YELLOW
+    );
+
+    paint.set_blender(None);
+    assert!(paint.blender().is_none());
+    assert_eq!(paint.as_blend_mode(), Some(BlendMode::SrcOver));
+}
@@ -169,6 +169,27 @@ impl<'a> Iterator for Iter<'a> {
     }
 }
 
+/// Calls `f` with each verb of `path`, the points it adds, and its conic weight, which is `1.0`
+/// for verbs that are not conics.
+///
+/// Unlike the points returned by [`Iter`], the points passed to `f` do not contain the end point of
+/// the previous verb, and the lines [`Iter`] inserts before a close are skipped.
+pub(crate) fn visit_verbs(path: &Path, mut f: impl FnMut(Verb, &[Point], scalar)) {
+    let mut iter = Iter::new(path, false);
+    while let Some((verb, points)) = iter.next() {
+        // The iterator inserts a line to the start point before a close.
+        if verb == Verb::Line && iter.is_close_line() {
+            continue;
+        }
+        // Except for Move, the first point is the end of the previous verb.
+        let points = match verb {
+            Verb::Move | Verb::Close => &points[..],
+            _ => &points[1..],
+        };
+        f(verb, points, iter.conic_weight().unwrap_or(1.0));
+    }
+}
+
 #[repr(C)]
 #[deprecated(
     since = "0.30.0",
@@ -127,4 +127,159 @@ impl PathEffect {
     pub fn needs_ctm(&self) -> bool {
         unsafe { self.native().needsCTM() }
     }
+
+    /// Creates a path effect that forwards to `path_effect`.
+    ///
+    /// Path effects implemented in Rust can not be serialized: pictures that use them can be
+    /// recorded and played back, but deserializing them fails.
+    ///
+    /// Requires the `unstable` feature, because Skia only supports path effects that are
+    /// implemented outside of Skia through a private API.
+    #[cfg(feature = "unstable")]
+    pub fn new_custom(path_effect: impl CustomPathEffect) -> Self {
+        let needs_ctm = path_effect.needs_ctm();
+        let param = rust_path_effect::new_param(Box::new(path_effect), needs_ctm);
+        PathEffect::from_ptr(unsafe { sb::C_RustPathEffect_new(&param) }).unwrap()
+    }
+}
+
+/// A path effect implemented in Rust, see [`PathEffect::new_custom()`].
+#[cfg(feature = "unstable")]
+///
+/// Skia may call a path effect from multiple threads at the same time.
+pub trait CustomPathEffect: Send + Sync + 'static {
+    /// Given a `src` path (input) and a `stroke_rec` (input and output), apply this effect to the
+    /// `src` path, returning the new path in `dst`, and return `true`. If this effect cannot be
+    /// applied, return `false` and ignore `dst` and `stroke_rec`.
+    ///
+    /// The `stroke_rec` specifies the initial request for stroking (if any). The effect can treat
+    /// this as input only, or it can choose to change the `stroke_rec` to be a fill, for example
+    /// when the effect itself creates the outline.
+    ///
+    /// `cull_rect` is the area that is visible, if known. `ctm` is the current transformation
+    /// matrix, which is only set if [`Self::needs_ctm()`] returns `true`.
+    fn filter_path(
+        &self,
+        dst: &mut Path,
+        src: &Path,
+        stroke_rec: &mut StrokeRec,
+        cull_rect: Option<&Rect>,
+        ctm: &Matrix,
+    ) -> bool;
+
+    /// Returns `true` if [`Self::filter_path()`] depends on the current transformation matrix.
+    /// Called once when the path effect is created.
+    fn needs_ctm(&self) -> bool {
+        false
+    }
+
+    /// Computes conservative bounds of the effect, given the `bounds` of the path. Returns `None`
+    /// if the bounds can not be computed, which is what the default implementation does.
+    fn fast_bounds(&self, _bounds: &Rect) -> Option<Rect> {
+        None
+    }
+}
+
+#[cfg(feature = "unstable")]
+mod rust_path_effect {
+    use super::CustomPathEffect;
+    use crate::{prelude::*, Matrix, Path, Rect, StrokeRec};
+    use skia_bindings::{RustPathEffect_Param, SkMatrix, SkPath, SkRect, SkStrokeRec, TraitObject};
+    use std::{
+        mem,
+        panic::{self, AssertUnwindSafe},
+    };
+
+    pub fn new_param(
+        path_effect: Box<dyn CustomPathEffect>,
+        needs_ctm: bool,
+    ) -> RustPathEffect_Param {
+        RustPathEffect_Param {
+            trait_: unsafe { mem::transmute(Box::into_raw(path_effect)) },
+            filterPath: Some(filter_path),
+            computeFastBounds: Some(compute_fast_bounds),
+            needsCTM: needs_ctm,
+            drop: Some(drop),
+        }
+    }
+
+    unsafe extern "C" fn filter_path(
+        to: TraitObject,
+        dst: *mut SkPath,
+        src: *const SkPath,
+        stroke_rec: *mut SkStrokeRec,
+        cull_rect: *const SkRect,
+        ctm: *const SkMatrix,
+    ) -> bool {
+        let dst = Path::from_native_ref_mut(&mut *dst);
+        let src = Path::from_native_ref(&*src);
+        let stroke_rec = StrokeRec::from_native_ref_mut(&mut *stroke_rec);
+        let cull_rect = cull_rect.as_ref().map(Rect::from_native_ref);
+        let ctm = Matrix::from_native_ref(&*ctm);
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            to_path_effect(to).filter_path(dst, src, stroke_rec, cull_rect, ctm)
+        }))
+        .unwrap_or(false)
+    }
+
+    unsafe extern "C" fn compute_fast_bounds(to: TraitObject, bounds: *mut SkRect) -> bool {
+        let bounds = Rect::from_native_ref_mut(&mut *bounds);
+        match panic::catch_unwind(AssertUnwindSafe(|| to_path_effect(to).fast_bounds(bounds))) {
+            Ok(Some(fast_bounds)) => {
+                *bounds = fast_bounds;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    unsafe extern "C" fn drop(to: TraitObject) {
+        mem::drop(Box::from_raw(mem::transmute::<
+            TraitObject,
+            *mut dyn CustomPathEffect,
+        >(to)))
+    }
+
+    unsafe fn to_path_effect<'a>(to: TraitObject) -> &'a dyn CustomPathEffect {
+        &*mem::transmute::<TraitObject, *const dyn CustomPathEffect>(to)
+    }
+}
+
+#[cfg(all(test, feature = "unstable"))]
+mod tests {
+    use super::{CustomPathEffect, PathEffect};
+    use crate::{Matrix, Path, Rect, StrokeRec};
+
+    /// Replaces every path with its bounding box.
+    struct BoundsEffect;
+
+    impl CustomPathEffect for BoundsEffect {
+        fn filter_path(
+            &self,
+            dst: &mut Path,
+            src: &Path,
+            _stroke_rec: &mut StrokeRec,
+            _cull_rect: Option<&Rect>,
+            _ctm: &Matrix,
+        ) -> bool {
+            dst.add_rect(src.bounds(), None);
+            true
+        }
+    }
+
+    #[test]
+    fn filter_path_with_custom_effect() {
+        let effect = PathEffect::new_custom(BoundsEffect);
+        let mut src = Path::default();
+        src.move_to((1.0, 2.0))
+            .line_to((5.0, 3.0))
+            .line_to((2.0, 8.0));
+        let stroke_rec = StrokeRec::new_fill();
+        let (dst, _) = effect
+            .filter_path(&src, &stroke_rec, Rect::new(0.0, 0.0, 10.0, 10.0))
+            .unwrap();
+        assert_eq!(*dst.bounds(), Rect::new(1.0, 2.0, 5.0, 8.0));
+        assert!(dst.is_rect().is_some());
+        assert!(!effect.needs_ctm());
+    }
 }
@@ -94,6 +94,26 @@ impl PathMeasure {
         .if_true_some((position, tangent))
     }
 
+    /// Returns the positions and tangents at each of the `distances` along the current contour,
+    /// or `None` if there is no contour. Distances are pinned to `0..=length()`.
+    ///
+    /// Equivalent to calling [`Self::pos_tan()`] for each distance, but crosses the FFI boundary
+    /// only once.
+    pub fn sample(&mut self, distances: &[scalar]) -> Option<Vec<(Point, Vector)>> {
+        let mut positions = vec![Point::default(); distances.len()];
+        let mut tangents = vec![Vector::default(); distances.len()];
+        unsafe {
+            sb::C_SkPathMeasure_getPosTans(
+                self.native_mut(),
+                distances.as_ptr(),
+                distances.len(),
+                positions.native_mut().as_mut_ptr(),
+                tangents.native_mut().as_mut_ptr(),
+            )
+        }
+        .if_true_then_some(|| positions.into_iter().zip(tangents).collect())
+    }
+
     // TODO: rename to get_matrix(), because the function has arguments?
     pub fn matrix(
         &mut self,
@@ -138,3 +158,29 @@ impl PathMeasure {
         unsafe { self.native_mut().nextContour() }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PathMeasure;
+    use crate::{Path, Point, Vector};
+
+    #[test]
+    fn sample_positions_and_tangents() {
+        let mut path = Path::default();
+        path.move_to((0.0, 0.0)).line_to((100.0, 0.0));
+        let mut measure = PathMeasure::new(&path, false, None);
+        let samples = measure.sample(&[0.0, 25.0, 150.0]).unwrap();
+        assert_eq!(
+            samples,
+            [
+                (Point::new(0.0, 0.0), Vector::new(1.0, 0.0)),
+                (Point::new(25.0, 0.0), Vector::new(1.0, 0.0)),
+                (Point::new(100.0, 0.0), Vector::new(1.0, 0.0)),
+            ]
+        );
+        assert_eq!(measure.sample(&[]), Some(vec![]));
+
+        let mut measure = PathMeasure::new(&Path::default(), false, None);
+        assert_eq!(measure.sample(&[0.0]), None);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use crate::{Matrix, Paint, Path, Rect};
+use crate::{scalar, Matrix, Paint, Path, Rect};
 use skia_bindings as sb;
 
 /// Returns the filled equivalent of the stroked path.
@@ -33,3 +33,66 @@ pub fn fill_path_with_paint<'a>(
         )
     }
 }
+
+/// Returns the filled equivalent of the stroked path like [`fill_path_with_paint()`], but
+/// specifies the precision with `res_scale` instead of a matrix: if `res_scale` > 1, increase
+/// precision, else if (0 < `res_scale` < 1) reduce precision to favor speed and size.
+pub fn fill_path_with_paint_and_res_scale<'a>(
+    src: &Path,
+    paint: &Paint,
+    dst: &mut Path,
+    cull_rect: impl Into<Option<&'a Rect>>,
+    res_scale: scalar,
+) -> bool {
+    let cull_rect: Option<&'a Rect> = cull_rect.into();
+
+    unsafe {
+        sb::C_PathUtils_FillPathWithPaintResScale(
+            src.native(),
+            paint.native(),
+            dst.native_mut(),
+            cull_rect.native_ptr_or_null(),
+            res_scale,
+        )
+    }
+}
+
+/// Returns the outline that is filled when `src` is drawn with `paint`, including the stroke
+/// caps, joins, and the path effect of `paint`, or `None` if the path can not be filled (e.g. if
+/// it is drawn as a hairline).
+///
+/// The outline can be used to hit-test strokes with [`Path::contains()`], or to export strokes
+/// to formats that only support fills.
+pub fn fill_path<'a>(
+    src: &Path,
+    paint: &Paint,
+    cull_rect: impl Into<Option<&'a Rect>>,
+    matrix: impl Into<Option<Matrix>>,
+) -> Option<Path> {
+    let mut dst = Path::default();
+    fill_path_with_paint(src, paint, &mut dst, cull_rect, matrix).if_true_some(dst)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{paint, Paint, Path};
+
+    #[test]
+    fn fill_stroked_path() {
+        let mut src = Path::default();
+        src.move_to((10.0, 10.0)).line_to((90.0, 10.0));
+        let mut paint = Paint::default();
+        paint
+            .set_style(paint::Style::Stroke)
+            .set_stroke_width(10.0)
+            .set_stroke_cap(paint::Cap::Square);
+
+        let outline = super::fill_path(&src, &paint, None, None).unwrap();
+        assert!(outline.contains((5.0, 12.0)));
+        assert!(outline.contains((50.0, 14.0)));
+        assert!(!outline.contains((50.0, 16.0)));
+
+        paint.set_stroke_width(0.0);
+        assert!(super::fill_path(&src, &paint, None, None).is_none());
+    }
+}
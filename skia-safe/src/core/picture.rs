@@ -1,6 +1,9 @@
-use crate::{prelude::*, Canvas, Data, FilterMode, Matrix, Rect, Shader, TileMode};
+use crate::{
+    prelude::*, Canvas, Data, DeserialProcs, FilterMode, Matrix, Rect, SerialProcs, Shader,
+    TileMode,
+};
 use skia_bindings::{self as sb, SkPicture, SkRefCntBase};
-use std::fmt;
+use std::{fmt, fs, io, path};
 
 pub type Picture = RCHandle<SkPicture>;
 unsafe_send_sync!(Picture);
@@ -23,8 +26,6 @@ impl fmt::Debug for Picture {
 impl Picture {
     // TODO: wrap MakeFromStream
 
-    // TODO: may support SkSerialProcs in MakeFromData?
-
     pub fn from_data(data: &Data) -> Option<Picture> {
         Picture::from_ptr(unsafe { sb::C_SkPicture_MakeFromData(data.native()) })
     }
@@ -35,6 +36,17 @@ impl Picture {
         })
     }
 
+    /// Deserializes a picture that was serialized with [`Self::serialize_with_procs()`].
+    pub fn deserialize_with_procs(bytes: &[u8], procs: &mut DeserialProcs) -> Option<Picture> {
+        Picture::from_ptr(unsafe {
+            sb::C_SkPicture_MakeFromDataWithProcs(
+                bytes.as_ptr() as _,
+                bytes.len(),
+                &procs.native_procs(),
+            )
+        })
+    }
+
     // TODO: AbortCallback and the function that use it.
 
     pub fn playback(&self, canvas: &mut Canvas) {
@@ -49,12 +61,52 @@ impl Picture {
         unsafe { sb::C_SkPicture_uniqueID(self.native()) }
     }
 
-    // TODO: support SkSerialProcs in serialize()?
-
     pub fn serialize(&self) -> Data {
         Data::from_ptr(unsafe { sb::C_SkPicture_serialize(self.native()) }).unwrap()
     }
 
+    /// Serializes the picture and uses `procs` to serialize the pictures, images, and typefaces it
+    /// references.
+    pub fn serialize_with_procs(&self, procs: &mut SerialProcs) -> Data {
+        Data::from_ptr(unsafe {
+            sb::C_SkPicture_serializeWithProcs(self.native(), &procs.native_procs())
+        })
+        .unwrap()
+    }
+
+    /// Writes the picture to the SKP file at `path`, which can be replayed with
+    /// [`Self::read_skp()`] or inspected with Skia's debugger.
+    ///
+    /// Pass `procs` to serialize the images and typefaces the picture references in a custom
+    /// way, for example to leave out large images.
+    pub fn write_skp(
+        &self,
+        path: impl AsRef<path::Path>,
+        procs: Option<&mut SerialProcs>,
+    ) -> io::Result<()> {
+        let data = match procs {
+            Some(procs) => self.serialize_with_procs(procs),
+            None => self.serialize(),
+        };
+        fs::write(path, data.as_bytes())
+    }
+
+    /// Reads a picture from the SKP file at `path`.
+    ///
+    /// `procs` must match the procs the file was written with. Returns an error of the kind
+    /// [`io::ErrorKind::InvalidData`] if the file does not contain a valid picture.
+    pub fn read_skp(
+        path: impl AsRef<path::Path>,
+        procs: Option<&mut DeserialProcs>,
+    ) -> io::Result<Picture> {
+        let bytes = fs::read(path)?;
+        let picture = match procs {
+            Some(procs) => Self::deserialize_with_procs(&bytes, procs),
+            None => Self::from_bytes(&bytes),
+        };
+        picture.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid SKP data"))
+    }
+
     pub fn new_placeholder(cull: impl AsRef<Rect>) -> Picture {
         Picture::from_ptr(unsafe { sb::C_SkPicture_MakePlaceholder(cull.as_ref().native()) })
             .unwrap()
@@ -107,3 +159,46 @@ impl Picture {
         .unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Data, DeserialProcs, Paint, Picture, PictureRecorder, Rect, SerialProcs};
+    use std::{env, fs, io, process, time::SystemTime};
+
+    #[test]
+    fn write_and_read_skp() {
+        let mut recorder = PictureRecorder::new();
+        let canvas = recorder.begin_recording(Rect::new(0.0, 0.0, 100.0, 50.0), None);
+        canvas.draw_rect(Rect::new(10.0, 10.0, 90.0, 40.0), &Paint::default());
+        let picture = recorder.finish_recording_as_picture(None).unwrap();
+
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path = env::temp_dir().join(format!(
+            "rust-skia-write-and-read-skp-{}-{nanos}.skp",
+            process::id()
+        ));
+        picture.write_skp(&path, None).unwrap();
+        let restored = Picture::read_skp(&path, None).unwrap();
+        assert_eq!(restored.cull_rect(), picture.cull_rect());
+        assert_eq!(
+            restored.approximate_op_count(),
+            picture.approximate_op_count()
+        );
+
+        let mut serial_procs = SerialProcs::new();
+        serial_procs.set_image_proc(|_| Some(Data::new_copy(b"image")));
+        picture.write_skp(&path, Some(&mut serial_procs)).unwrap();
+        let mut deserial_procs = DeserialProcs::new();
+        deserial_procs.set_image_proc(|_| None);
+        let restored = Picture::read_skp(&path, Some(&mut deserial_procs)).unwrap();
+        assert_eq!(restored.cull_rect(), picture.cull_rect());
+
+        fs::write(&path, b"not a picture").unwrap();
+        let error = Picture::read_skp(&path, None).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(&path).unwrap();
+    }
+}
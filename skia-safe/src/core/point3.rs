@@ -7,6 +7,7 @@ pub type Color3f = Point3;
 
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point3 {
     pub x: scalar,
     pub y: scalar,
@@ -1 +1,80 @@
-// TODO
+use crate::{gpu::BackendTexture, prelude::*};
+use skia_bindings::{self as sb, SkPromiseImageTexture};
+use std::{fmt, os::raw};
+
+/// A texture that is returned from the fulfill closure of a promise image, see
+/// [`crate::Image::new_promise_texture()`].
+pub type PromiseImageTexture = RCHandle<SkPromiseImageTexture>;
+unsafe_send_sync!(PromiseImageTexture);
+
+impl NativeRefCounted for SkPromiseImageTexture {
+    fn _ref(&self) {
+        unsafe { sb::C_SkPromiseImageTexture_ref(self) }
+    }
+
+    fn _unref(&self) {
+        unsafe { sb::C_SkPromiseImageTexture_unref(self) }
+    }
+
+    fn unique(&self) -> bool {
+        unsafe { sb::C_SkPromiseImageTexture_unique(self) }
+    }
+}
+
+impl fmt::Debug for PromiseImageTexture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PromiseImageTexture")
+            .field("backend_texture", &self.backend_texture())
+            .finish()
+    }
+}
+
+impl PromiseImageTexture {
+    /// Returns `None` if `backend_texture` is not valid.
+    pub fn new(backend_texture: &BackendTexture) -> Option<Self> {
+        Self::from_ptr(unsafe { sb::C_SkPromiseImageTexture_Make(backend_texture.native()) })
+    }
+
+    pub fn backend_texture(&self) -> BackendTexture {
+        BackendTexture::from_ptr(unsafe {
+            sb::C_GrBackendTexture_Clone(sb::C_SkPromiseImageTexture_backendTexture(self.native()))
+        })
+        .unwrap()
+    }
+}
+
+/// The closures of a promise image. Skia owns them until it calls the release proc.
+struct PromiseContext {
+    fulfill: Box<dyn FnMut() -> Option<PromiseImageTexture> + Send>,
+    release: Box<dyn FnOnce() + Send>,
+}
+
+/// Moves `fulfill` and `release` into a native promise image texture context.
+pub(crate) fn new_promise_context(
+    fulfill: impl FnMut() -> Option<PromiseImageTexture> + Send + 'static,
+    release: impl FnOnce() + Send + 'static,
+) -> (
+    sb::RustPromiseImageTexture_Fulfill,
+    sb::RustPromiseImageTexture_Release,
+    *mut raw::c_void,
+) {
+    let context = Box::new(PromiseContext {
+        fulfill: Box::new(fulfill),
+        release: Box::new(release),
+    });
+    (
+        Some(fulfill_proc),
+        Some(release_proc),
+        Box::into_raw(context) as *mut raw::c_void,
+    )
+}
+
+unsafe extern "C" fn fulfill_proc(context: *mut raw::c_void) -> *mut SkPromiseImageTexture {
+    let context = &mut *(context as *mut PromiseContext);
+    (context.fulfill)().into_ptr_or_null()
+}
+
+unsafe extern "C" fn release_proc(context: *mut raw::c_void) {
+    let context = Box::from_raw(context as *mut PromiseContext);
+    (context.release)()
+}
@@ -11,6 +11,7 @@ use std::{
 
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IRect {
     /// The x coordinate of the rectangle's left edge.
     pub left: i32,
@@ -331,6 +332,7 @@ impl Contains<Rect> for IRect {
 
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rect {
     /// The x coordinate of the rectangle's left edge.
     pub left: scalar,
@@ -0,0 +1,147 @@
+//! [serde](https://crates.io/crates/serde) support for types that can not derive it.
+//!
+//! - [`Matrix`] is a sequence of 9 and [`M44`] a sequence of 16 scalars in row-major order.
+//! - [`RRect`] is a struct with its `rect` and the `radii` of the upper left, upper right, lower
+//!   right, and lower left corners.
+//! - [`Path`] is a struct with its `fill_type` and a sequence of `verbs`. Each verb carries the
+//!   points it adds, for example `{"Quad": [[x1, y1], [x2, y2]]}`, or `"Close"`.
+//!
+//! All other geometry and color types derive `Serialize` and `Deserialize`.
+
+use crate::{
+    path::Verb, rrect::Corner, scalar, Matrix, Path, PathFillType, Point, RRect, Rect, Vector, M44,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+impl Serialize for Matrix {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut values = [0.0; 9];
+        self.get_9(&mut values);
+        values.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Matrix {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = <[scalar; 9]>::deserialize(deserializer)?;
+        let mut matrix = Matrix::default();
+        matrix.set_9(&values);
+        Ok(matrix)
+    }
+}
+
+impl Serialize for M44 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut values = [0.0; 16];
+        self.get_row_major(&mut values);
+        values.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for M44 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = <[scalar; 16]>::deserialize(deserializer)?;
+        Ok(M44::row_major(&values))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "RRect")]
+struct RRectRepr {
+    rect: Rect,
+    radii: [Vector; 4],
+}
+
+impl Serialize for RRect {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RRectRepr {
+            rect: *self.rect(),
+            radii: [
+                self.radii(Corner::UpperLeft),
+                self.radii(Corner::UpperRight),
+                self.radii(Corner::LowerRight),
+                self.radii(Corner::LowerLeft),
+            ],
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RRect {
+    /// The radii are validated and scaled down in the same way as by [`RRect::new_rect_radii()`].
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = RRectRepr::deserialize(deserializer)?;
+        Ok(RRect::new_rect_radii(repr.rect, &repr.radii))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "PathFillType")]
+enum PathFillTypeRepr {
+    Winding,
+    EvenOdd,
+    InverseWinding,
+    InverseEvenOdd,
+}
+
+#[derive(Serialize, Deserialize)]
+enum VerbRepr {
+    Move(Point),
+    Line(Point),
+    Quad(Point, Point),
+    Conic(Point, Point, scalar),
+    Cubic(Point, Point, Point),
+    Close,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Path")]
+struct PathRepr {
+    #[serde(with = "PathFillTypeRepr")]
+    fill_type: PathFillType,
+    verbs: Vec<VerbRepr>,
+}
+
+impl Serialize for Path {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut verbs = Vec::new();
+        crate::path::visit_verbs(self, |verb, points, weight| {
+            verbs.push(match verb {
+                Verb::Move => VerbRepr::Move(points[0]),
+                Verb::Line => VerbRepr::Line(points[0]),
+                Verb::Quad => VerbRepr::Quad(points[0], points[1]),
+                Verb::Conic => VerbRepr::Conic(points[0], points[1], weight),
+                Verb::Cubic => VerbRepr::Cubic(points[0], points[1], points[2]),
+                Verb::Close => VerbRepr::Close,
+                Verb::Done => return,
+            })
+        });
+        PathRepr {
+            fill_type: self.fill_type(),
+            verbs,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Path {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = PathRepr::deserialize(deserializer)?;
+        let mut path = Path::new();
+        path.set_fill_type(repr.fill_type);
+        for verb in repr.verbs {
+            match verb {
+                VerbRepr::Move(p) => path.move_to(p),
+                VerbRepr::Line(p) => path.line_to(p),
+                VerbRepr::Quad(p1, p2) => path.quad_to(p1, p2),
+                VerbRepr::Conic(p1, p2, w) => path.conic_to(p1, p2, w),
+                VerbRepr::Cubic(p1, p2, p3) => path.cubic_to(p1, p2, p3),
+                VerbRepr::Close => path.close(),
+            };
+        }
+        if !path.is_finite() {
+            return Err(de::Error::custom("path contains non-finite points"));
+        }
+        Ok(path)
+    }
+}
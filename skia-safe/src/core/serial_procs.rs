@@ -0,0 +1,312 @@
+use crate::{prelude::*, Data, Image, Picture, Typeface};
+use skia_bindings::{RustDeserialProcs, RustSerialProcs, SkData, SkImage, SkPicture, SkTypeface};
+use std::{
+    ffi::c_void,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    ptr,
+};
+
+type SerialProc<'a, T> = Box<dyn FnMut(&T) -> Option<Data> + 'a>;
+type DeserialProc<'a, T> = Box<dyn FnMut(&[u8]) -> Option<T> + 'a>;
+
+/// Callbacks that replace the default serialization of the pictures, images, and typefaces that
+/// are referenced by a [`Picture`] or [`crate::TextBlob`].
+///
+/// For example, an image proc can store a key that refers to an image stored elsewhere instead of
+/// the encoded image, and a typeface proc can store a family name instead of the font data. A proc
+/// that returns `None` leaves the object to the default serialization.
+///
+/// Data that was serialized with custom procs must be deserialized with matching
+/// [`DeserialProcs`].
+#[derive(Default)]
+pub struct SerialProcs<'a> {
+    picture: Option<SerialProc<'a, Picture>>,
+    image: Option<SerialProc<'a, Image>>,
+    typeface: Option<SerialProc<'a, Typeface>>,
+}
+
+impl fmt::Debug for SerialProcs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SerialProcs")
+            .field("picture", &self.picture.is_some())
+            .field("image", &self.image.is_some())
+            .field("typeface", &self.typeface.is_some())
+            .finish()
+    }
+}
+
+impl<'a> SerialProcs<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_picture_proc(
+        &mut self,
+        proc: impl FnMut(&Picture) -> Option<Data> + 'a,
+    ) -> &mut Self {
+        self.picture = Some(Box::new(proc));
+        self
+    }
+
+    pub fn set_image_proc(&mut self, proc: impl FnMut(&Image) -> Option<Data> + 'a) -> &mut Self {
+        self.image = Some(Box::new(proc));
+        self
+    }
+
+    pub fn set_typeface_proc(
+        &mut self,
+        proc: impl FnMut(&Typeface) -> Option<Data> + 'a,
+    ) -> &mut Self {
+        self.typeface = Some(Box::new(proc));
+        self
+    }
+
+    /// Returns the callbacks for the native side. `self` must outlive their use.
+    pub(crate) fn native_procs(&mut self) -> RustSerialProcs {
+        unsafe extern "C" fn picture(context: *mut c_void, picture: *mut SkPicture) -> *mut SkData {
+            let procs = &mut *(context as *mut SerialProcs);
+            serialize(
+                procs.picture.as_mut(),
+                Picture::from_unshared_ptr_ref(&picture),
+            )
+        }
+
+        unsafe extern "C" fn image(context: *mut c_void, image: *mut SkImage) -> *mut SkData {
+            let procs = &mut *(context as *mut SerialProcs);
+            serialize(procs.image.as_mut(), Image::from_unshared_ptr_ref(&image))
+        }
+
+        unsafe extern "C" fn typeface(
+            context: *mut c_void,
+            typeface: *mut SkTypeface,
+        ) -> *mut SkData {
+            let procs = &mut *(context as *mut SerialProcs);
+            serialize(
+                procs.typeface.as_mut(),
+                Typeface::from_unshared_ptr_ref(&typeface),
+            )
+        }
+
+        // A panicking proc leaves the value to the default serialization.
+        fn serialize<T>(proc: Option<&mut SerialProc<T>>, value: &Option<T>) -> *mut SkData {
+            match (proc, value) {
+                (Some(proc), Some(value)) => panic::catch_unwind(AssertUnwindSafe(|| proc(value)))
+                    .ok()
+                    .flatten()
+                    .into_ptr_or_null(),
+                _ => ptr::null_mut(),
+            }
+        }
+
+        RustSerialProcs {
+            context: self as *mut Self as *mut c_void,
+            picture: self.picture.is_some().if_true_some(picture as _),
+            image: self.image.is_some().if_true_some(image as _),
+            typeface: self.typeface.is_some().if_true_some(typeface as _),
+        }
+    }
+}
+
+/// Callbacks that create the pictures, images, and typefaces from the data that was written by
+/// the procs of [`SerialProcs`].
+///
+/// A proc that returns `None` leaves the data to the default deserialization, so objects that
+/// were serialized without a custom proc are still restored.
+#[derive(Default)]
+pub struct DeserialProcs<'a> {
+    picture: Option<DeserialProc<'a, Picture>>,
+    image: Option<DeserialProc<'a, Image>>,
+    typeface: Option<DeserialProc<'a, Typeface>>,
+}
+
+impl fmt::Debug for DeserialProcs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeserialProcs")
+            .field("picture", &self.picture.is_some())
+            .field("image", &self.image.is_some())
+            .field("typeface", &self.typeface.is_some())
+            .finish()
+    }
+}
+
+impl<'a> DeserialProcs<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_picture_proc(
+        &mut self,
+        proc: impl FnMut(&[u8]) -> Option<Picture> + 'a,
+    ) -> &mut Self {
+        self.picture = Some(Box::new(proc));
+        self
+    }
+
+    pub fn set_image_proc(&mut self, proc: impl FnMut(&[u8]) -> Option<Image> + 'a) -> &mut Self {
+        self.image = Some(Box::new(proc));
+        self
+    }
+
+    /// Sets the proc that restores typefaces.
+    ///
+    /// Pictures that were serialized without a typeface proc store their typefaces in a separate
+    /// table that can not be read with a custom proc, so set this only for data that was
+    /// serialized with [`SerialProcs::set_typeface_proc()`].
+    pub fn set_typeface_proc(
+        &mut self,
+        proc: impl FnMut(&[u8]) -> Option<Typeface> + 'a,
+    ) -> &mut Self {
+        self.typeface = Some(Box::new(proc));
+        self
+    }
+
+    /// Returns the callbacks for the native side. `self` must outlive their use.
+    pub(crate) fn native_procs(&mut self) -> RustDeserialProcs {
+        unsafe extern "C" fn picture(
+            context: *mut c_void,
+            data: *const c_void,
+            length: usize,
+        ) -> *mut SkPicture {
+            let procs = &mut *(context as *mut DeserialProcs);
+            deserialize(procs.picture.as_mut(), data, length)
+        }
+
+        unsafe extern "C" fn image(
+            context: *mut c_void,
+            data: *const c_void,
+            length: usize,
+        ) -> *mut SkImage {
+            let procs = &mut *(context as *mut DeserialProcs);
+            deserialize(procs.image.as_mut(), data, length)
+        }
+
+        unsafe extern "C" fn typeface(
+            context: *mut c_void,
+            data: *const c_void,
+            length: usize,
+        ) -> *mut SkTypeface {
+            let procs = &mut *(context as *mut DeserialProcs);
+            deserialize(procs.typeface.as_mut(), data, length)
+        }
+
+        unsafe fn deserialize<N: NativeRefCounted>(
+            proc: Option<&mut DeserialProc<RCHandle<N>>>,
+            data: *const c_void,
+            length: usize,
+        ) -> *mut N {
+            match proc {
+                Some(proc) => {
+                    let data = safer::from_raw_parts(data as *const u8, length);
+                    panic::catch_unwind(AssertUnwindSafe(|| proc(data)))
+                        .ok()
+                        .flatten()
+                        .into_ptr_or_null()
+                }
+                None => ptr::null_mut(),
+            }
+        }
+
+        RustDeserialProcs {
+            context: self as *mut Self as *mut c_void,
+            picture: self.picture.is_some().if_true_some(picture as _),
+            image: self.image.is_some().if_true_some(image as _),
+            typeface: self.typeface.is_some().if_true_some(typeface as _),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeserialProcs, SerialProcs};
+    use crate::{
+        Color, Data, Font, FontMgr, FontStyle, Picture, PictureRecorder, Rect, Surface, TextBlob,
+        TextBlobIter, Typeface,
+    };
+
+    #[test]
+    fn store_images_as_references() {
+        let mut surface = Surface::new_raster_n32_premul((64, 64)).unwrap();
+        surface.canvas().clear(Color::RED);
+        let image = surface.image_snapshot();
+
+        let mut recorder = PictureRecorder::new();
+        let canvas = recorder.begin_recording(Rect::new(0.0, 0.0, 64.0, 64.0), None);
+        canvas.draw_image(&image, (0, 0), None);
+        let picture = recorder.finish_recording_as_picture(None).unwrap();
+
+        let mut images = Vec::new();
+        let serialized = picture.serialize_with_procs(SerialProcs::new().set_image_proc(|image| {
+            images.push(image.clone());
+            Some(Data::new_copy(&(images.len() - 1).to_le_bytes()))
+        }));
+        assert_eq!(images.len(), 1);
+        assert!(serialized.len() < picture.serialize().len());
+
+        let mut restored_images = 0;
+        let restored = Picture::deserialize_with_procs(
+            &serialized,
+            DeserialProcs::new().set_image_proc(|data| {
+                restored_images += 1;
+                images
+                    .get(usize::from_le_bytes(data.try_into().ok()?))
+                    .cloned()
+            }),
+        )
+        .unwrap();
+        assert_eq!(restored_images, 1);
+        assert_eq!(restored.cull_rect(), picture.cull_rect());
+    }
+
+    #[test]
+    fn panicking_procs_fall_back_to_the_default_serialization() {
+        let mut surface = Surface::new_raster_n32_premul((8, 8)).unwrap();
+        surface.canvas().clear(Color::RED);
+        let image = surface.image_snapshot();
+
+        let mut recorder = PictureRecorder::new();
+        let canvas = recorder.begin_recording(Rect::new(0.0, 0.0, 8.0, 8.0), None);
+        canvas.draw_image(&image, (0, 0), None);
+        let picture = recorder.finish_recording_as_picture(None).unwrap();
+
+        let serialized =
+            picture.serialize_with_procs(SerialProcs::new().set_image_proc(|_| panic!("image")));
+        assert_eq!(serialized.as_bytes(), picture.serialize().as_bytes());
+        let restored = Picture::deserialize_with_procs(
+            &serialized,
+            DeserialProcs::new().set_image_proc(|_| panic!("image")),
+        );
+        assert!(restored.is_some());
+    }
+
+    #[test]
+    fn store_typefaces_as_family_names() {
+        let typeface = Typeface::default();
+        let blob = TextBlob::new("Hello", &Font::new(typeface.clone(), 12.0)).unwrap();
+        let serialized = blob
+            .serialize(SerialProcs::new().set_typeface_proc(|typeface| {
+                Some(Data::new_copy(typeface.family_name().as_bytes()))
+            }))
+            .unwrap();
+
+        let font_mgr = FontMgr::default();
+        let mut requested = Vec::new();
+        let restored = TextBlob::deserialize(
+            &serialized,
+            DeserialProcs::new().set_typeface_proc(|data| {
+                let family = std::str::from_utf8(data).ok()?;
+                requested.push(family.to_owned());
+                font_mgr.match_family_style(family, FontStyle::default())
+            }),
+        )
+        .unwrap();
+        assert_eq!(requested, [typeface.family_name()]);
+
+        let glyphs = |blob: &TextBlob| {
+            TextBlobIter::new(blob)
+                .flat_map(|run| run.glyph_indices.to_vec())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(glyphs(&restored), glyphs(&blob));
+    }
+}
@@ -97,6 +97,8 @@ impl Shader {
 }
 
 pub mod shaders {
+    #[cfg(feature = "unstable")]
+    use crate::{codec::GainmapInfo, Image, SamplingOptions};
     use crate::{prelude::*, Blender, Color, Color4f, ColorSpace, Rect, Shader};
     use skia_bindings as sb;
 
@@ -136,4 +138,41 @@ pub mod shaders {
             sb::C_SkShaders_CoordClamp(shader.into().into_ptr(), rect.as_ref().native())
         })
     }
+
+    /// Creates a shader that draws `base_rect` of `base_image` into `dst_rect`, with the
+    /// `gainmap_rect` of `gainmap_image` applied as far as the display's HDR ratio
+    /// `dst_hdr_ratio` allows. Pass `1.0` to render for SDR displays.
+    ///
+    /// `dst_color_space` should be the color space of the destination surface.
+    ///
+    /// Requires the `unstable` feature, because Skia declares gainmaps in private headers.
+    #[cfg(feature = "unstable")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn gainmap(
+        base_image: &Image,
+        base_rect: impl AsRef<Rect>,
+        base_sampling: impl Into<SamplingOptions>,
+        gainmap_image: &Image,
+        gainmap_rect: impl AsRef<Rect>,
+        gainmap_sampling: impl Into<SamplingOptions>,
+        gainmap_info: &GainmapInfo,
+        dst_rect: impl AsRef<Rect>,
+        dst_hdr_ratio: f32,
+        dst_color_space: impl Into<Option<ColorSpace>>,
+    ) -> Option<Shader> {
+        Shader::from_ptr(unsafe {
+            sb::C_SkGainmapShader_Make(
+                base_image.clone().into_ptr(),
+                base_rect.as_ref().native(),
+                base_sampling.into().native(),
+                gainmap_image.clone().into_ptr(),
+                gainmap_rect.as_ref().native(),
+                gainmap_sampling.into().native(),
+                gainmap_info.native(),
+                dst_rect.as_ref().native(),
+                dst_hdr_ratio,
+                dst_color_space.into().into_ptr_or_null(),
+            )
+        })
+    }
 }
@@ -310,10 +310,14 @@ impl Surface {
     /// * `color_space` - range of colors; may be `None`
     /// * `surface_props` - LCD striping orientation and setting for device independent
     ///                        fonts; may be `None`
-    /// * `drawable` - Pointer to drawable to be filled in when this surface is
-    ///                        instantiated; may not be `None`
-    /// Returns: created [`Surface`], or `None`
-    #[allow(clippy::missing_safety_doc)]
+    /// Returns: created [`Surface`] and the [`gpu::mtl::MetalDrawable`] it renders to, or `None`.
+    ///     The drawable is acquired when the surface is flushed for the first time and can be
+    ///     presented after the work was submitted. [`gpu::mtl::draw_frame()`] manages this cycle.
+    ///
+    /// # Safety
+    /// `layer` must be a `CAMetalLayer` whose device is the device of `context`. The returned
+    /// drawable must not be dropped before the surface was flushed or dropped, because Skia
+    /// stores the acquired drawable in it.
     #[allow(clippy::too_many_arguments)]
     #[cfg(feature = "metal")]
     pub unsafe fn from_ca_metal_layer(
@@ -324,8 +328,8 @@ impl Surface {
         color_type: crate::ColorType,
         color_space: impl Into<Option<crate::ColorSpace>>,
         surface_props: Option<&SurfaceProps>,
-        drawable: *mut gpu::mtl::Handle,
-    ) -> Option<Self> {
+    ) -> Option<(Self, gpu::mtl::MetalDrawable)> {
+        let mut drawable = gpu::mtl::MetalDrawable::new();
         Self::from_ptr(sb::C_SkSurface_MakeFromCAMetalLayer(
             context.native_mut(),
             layer,
@@ -334,8 +338,9 @@ impl Surface {
             color_type.into_native(),
             color_space.into().into_ptr_or_null(),
             surface_props.native_ptr_or_null(),
-            drawable,
+            drawable.handle_ptr(),
         ))
+        .map(|surface| (surface, drawable))
     }
 
     #[allow(clippy::missing_safety_doc)]
@@ -942,7 +947,8 @@ impl Surface {
         access: BackendSurfaceAccess,
         info: &gpu::FlushInfo,
     ) -> gpu::SemaphoresSubmitted {
-        unsafe { self.native_mut().flush(access, info.native()) }
+        let info = self.native_flush_info(info);
+        unsafe { self.native_mut().flush(access, &info) }
     }
 
     /// Issues pending [`Surface`] commands to the GPU-backed API objects and resolves any [`Surface`]
@@ -994,13 +1000,93 @@ impl Surface {
         info: &gpu::FlushInfo,
         new_state: impl Into<Option<&'a gpu::MutableTextureState>>,
     ) -> gpu::SemaphoresSubmitted {
+        let info = self.native_flush_info(info);
         unsafe {
             self.native_mut()
-                .flush1(info.native(), new_state.into().native_ptr_or_null())
+                .flush1(&info, new_state.into().native_ptr_or_null())
+        }
+    }
+
+    /// Only surfaces of a [`gpu::DirectContext`] flush to a GPU backend and call the finished proc
+    /// of `info`.
+    #[cfg(feature = "gpu")]
+    fn native_flush_info(&mut self, info: &gpu::FlushInfo) -> sb::GrFlushInfo {
+        let reaches_gpu = self
+            .recording_context()
+            .and_then(|mut context| context.as_direct_context())
+            .is_some();
+        if reaches_gpu {
+            info.native_for_flush()
+        } else {
+            info.native_for_flush_without_gpu()
+        }
+    }
+
+    /// Flushes the surface and releases its `VkImage` to the queue family `queue_family_index`,
+    /// for example [`gpu::vk::QUEUE_FAMILY_FOREIGN_EXT`] or [`gpu::vk::QUEUE_FAMILY_EXTERNAL`],
+    /// leaving it in `layout`. Skia records the ownership transfer barrier; the work needs to be
+    /// submitted with [`gpu::DirectContext::submit()`] before the image is used outside of Skia.
+    ///
+    /// After the image was handed back, call [`Self::vk_image_acquired()`].
+    #[cfg(feature = "vulkan")]
+    pub fn flush_and_release_vk_image(
+        &mut self,
+        layout: gpu::vk::ImageLayout,
+        queue_family_index: u32,
+    ) -> gpu::SemaphoresSubmitted {
+        let state = gpu::MutableTextureState::new_vk(layout, queue_family_index);
+        self.flush_with_mutable_state(&gpu::FlushInfo::default(), &state)
+    }
+
+    /// Tells Skia that the surface's `VkImage` was handed back in `layout` and is owned by the
+    /// queue family `queue_family_index`. Skia acquires the image with a queue family ownership
+    /// transfer barrier before it uses it again.
+    ///
+    /// Returns `false` if the surface is not backed by a Vulkan image.
+    #[cfg(feature = "vulkan")]
+    pub fn vk_image_acquired(
+        &mut self,
+        layout: gpu::vk::ImageLayout,
+        queue_family_index: u32,
+    ) -> bool {
+        match self.get_backend_render_target(BackendHandleAccess::FlushRead) {
+            Some(mut render_target) if render_target.backend() == gpu::BackendAPI::Vulkan => {
+                render_target.set_mutable_state(&gpu::MutableTextureState::new_vk(
+                    layout,
+                    queue_family_index,
+                ));
+                true
+            }
+            _ => false,
         }
     }
 
-    // TODO: wait()
+    /// Inserts a list of GPU semaphores that the current GPU-backed API must wait on before
+    /// executing any more commands on the GPU for this surface. We only guarantee blocking
+    /// transfer and fragment shader work, but may block earlier stages as well depending on the
+    /// backend. If this call returns `false`, then the GPU back-end will not wait on any passed in
+    /// semaphores, and the client will still own the semaphores, regardless of the value of
+    /// `delete_semaphores_after_wait`.
+    ///
+    /// If `delete_semaphores_after_wait` is `false` then Skia will not delete the semaphores. In
+    /// this case it is the client's responsibility to not destroy or attempt to reuse the
+    /// semaphores until it knows that Skia has finished waiting on them. This can be done by
+    /// using finished procs on flush calls.
+    #[cfg(feature = "gpu")]
+    pub fn wait(
+        &mut self,
+        wait_semaphores: &[gpu::BackendSemaphore],
+        delete_semaphores_after_wait: impl Into<Option<bool>>,
+    ) -> bool {
+        unsafe {
+            sb::C_SkSurface_wait(
+                self.native_mut(),
+                wait_semaphores.native().as_ptr(),
+                wait_semaphores.len(),
+                delete_semaphores_after_wait.into().unwrap_or(true),
+            )
+        }
+    }
 
     /// Initializes [`SurfaceCharacterization`] that can be used to perform GPU back-end
     /// processing in a separate thread. Typically this is used to divide drawing
@@ -118,7 +118,11 @@ impl SurfaceCharacterization {
 
 #[cfg(feature = "gpu")]
 impl SurfaceCharacterization {
-    // TODO: contextInfo() / refContextInfo()
+    pub fn context_info(&self) -> Option<gpu::ContextThreadSafeProxy> {
+        gpu::ContextThreadSafeProxy::from_ptr(unsafe {
+            sb::C_SkSurfaceCharacterization_refContextInfo(self.native())
+        })
+    }
 
     pub fn cache_max_resource_bytes(&self) -> usize {
         self.native().fCacheMaxResourceBytes
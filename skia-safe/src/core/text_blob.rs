@@ -1,5 +1,6 @@
 use crate::{
-    prelude::*, scalar, Font, GlyphId, Paint, Point, RSXform, Rect, TextEncoding, Typeface,
+    prelude::*, scalar, Data, DeserialProcs, Font, GlyphId, Paint, Point, RSXform, Rect,
+    SerialProcs, TextEncoding, Typeface,
 };
 use skia_bindings::{
     self as sb, SkTextBlob, SkTextBlobBuilder, SkTextBlob_Iter, SkTextBlob_Iter_Run, SkTypeface,
@@ -65,6 +66,19 @@ impl TextBlob {
         }
     }
 
+    /// Serializes the text blob and uses `procs` to serialize its typefaces. Without a typeface
+    /// proc, the fonts are embedded.
+    pub fn serialize(&self, procs: &mut SerialProcs) -> Option<Data> {
+        Data::from_ptr(unsafe { sb::C_SkTextBlob_serialize(self.native(), &procs.native_procs()) })
+    }
+
+    /// Deserializes a text blob that was serialized with [`Self::serialize()`].
+    pub fn deserialize(data: &[u8], procs: &mut DeserialProcs) -> Option<TextBlob> {
+        TextBlob::from_ptr(unsafe {
+            sb::C_SkTextBlob_Deserialize(data.as_ptr() as _, data.len(), &procs.native_procs())
+        })
+    }
+
     pub fn from_str(str: impl AsRef<str>, font: &Font) -> Option<TextBlob> {
         Self::from_text(str.as_ref().as_bytes(), TextEncoding::UTF8, font)
     }
@@ -243,6 +257,11 @@ impl TextBlobBuilder {
         }
     }
 
+    /// Allocates a run like [`Self::alloc_run()`] and buffers for the UTF-8 text the glyphs
+    /// represent and the cluster of each glyph.
+    ///
+    /// The cluster of a glyph is the byte offset of its text in the UTF-8 buffer. Devices like the
+    /// PDF backend use the text and the clusters to make the glyphs selectable and searchable.
     pub fn alloc_run_text(
         &mut self,
         font: &Font,
@@ -269,6 +288,8 @@ impl TextBlobBuilder {
         }
     }
 
+    /// Allocates a run like [`Self::alloc_run_pos_h()`] and buffers for the text and clusters, see
+    /// [`Self::alloc_run_text()`].
     pub fn alloc_run_text_pos_h(
         &mut self,
         font: &Font,
@@ -294,6 +315,8 @@ impl TextBlobBuilder {
         }
     }
 
+    /// Allocates a run like [`Self::alloc_run_pos()`] and buffers for the text and clusters, see
+    /// [`Self::alloc_run_text()`].
     pub fn alloc_run_text_pos(
         &mut self,
         font: &Font,
@@ -317,6 +340,8 @@ impl TextBlobBuilder {
         }
     }
 
+    /// Allocates a run like [`Self::alloc_run_rsxform()`] and buffers for the text and clusters,
+    /// see [`Self::alloc_run_text()`].
     pub fn alloc_run_text_rsxform(
         &mut self,
         font: &Font,
@@ -325,7 +350,7 @@ impl TextBlobBuilder {
         bounds: Option<&Rect>,
     ) -> (&mut [GlyphId], &mut [RSXform], &mut [u8], &mut [u32]) {
         unsafe {
-            let buffer = &*self.native_mut().allocRunTextPos(
+            let buffer = &*self.native_mut().allocRunTextRSXform(
                 font.native(),
                 count.try_into().unwrap(),
                 text_byte_count.try_into().unwrap(),
@@ -408,3 +433,35 @@ fn test_point_size_equals_size_of_two_scalars_used_in_alloc_run_pos() {
     use std::mem;
     assert_eq!(mem::size_of::<Point>(), mem::size_of::<[scalar; 2]>())
 }
+
+#[test]
+fn test_alloc_run_text_with_clusters() {
+    let font = Font::new(Typeface::default(), 12.0);
+    let text = "fi\u{e9}";
+    let mut glyphs = [0; 3];
+    font.str_to_glyphs(text, &mut glyphs);
+
+    let mut builder = TextBlobBuilder::new();
+    {
+        let (run_glyphs, pos, utf8, clusters) =
+            builder.alloc_run_text_pos(&font, glyphs.len(), text.len(), None);
+        run_glyphs.copy_from_slice(&glyphs);
+        for (i, p) in pos.iter_mut().enumerate() {
+            *p = Point::new(i as scalar * 10.0, 0.0);
+        }
+        utf8.copy_from_slice(text.as_bytes());
+        clusters.copy_from_slice(&[0, 1, 2]);
+    }
+    {
+        let (run_glyphs, xforms, utf8, clusters) =
+            builder.alloc_run_text_rsxform(&font, glyphs.len(), text.len(), None);
+        run_glyphs.copy_from_slice(&glyphs);
+        xforms.fill(RSXform::new(1.0, 0.0, (0.0, 20.0)));
+        utf8.copy_from_slice(text.as_bytes());
+        clusters.copy_from_slice(&[0, 1, 2]);
+    }
+    let blob = builder.make().unwrap();
+    let runs: Vec<_> = TextBlobIter::new(&blob).collect();
+    assert_eq!(runs.len(), 2);
+    assert!(runs.iter().all(|run| run.glyph_indices == glyphs));
+}
@@ -1 +1,145 @@
-// TODO: wrap SkTraceMemoryDump
+use crate::prelude::*;
+use skia_bindings::{self as sb, RustTraceMemoryDump, SkTraceMemoryDump};
+
+pub use sb::SkTraceMemoryDump_LevelOfDetail as LevelOfDetail;
+variant_name!(LevelOfDetail::ObjectsBreakdowns);
+
+/// Interface for memory tracing.
+///
+/// This interface is meant to be passed as argument to the memory dump methods of Skia objects,
+/// like [`crate::graphics::dump_memory_statistics()`] or
+/// [`crate::gpu::DirectContext::dump_memory_statistics()`]. The implementation of this interface
+/// is provided by the embedder.
+pub trait TraceMemoryDump {
+    /// Appends a new memory dump (i.e. a row) to the trace memory infrastructure. If
+    /// `dump_name` does not exist yet, a new one is created. Otherwise, a new column is appended
+    /// to the previously created dump.
+    ///
+    /// Arguments:
+    ///   - `dump_name`: an absolute, slash-separated, name for the item being dumped e.g.,
+    ///     "skia/CacheX/EntryY".
+    ///   - `value_name`: a string indicating the name of the column. e.g., "size",
+    ///     "active_size", "number_of_objects".
+    ///   - `units`: a string indicating the units for the value. e.g., "bytes", "objects".
+    ///   - `value`: the actual value being dumped.
+    fn dump_numeric_value(&mut self, dump_name: &str, value_name: &str, units: &str, value: u64);
+
+    fn dump_string_value(&mut self, _dump_name: &str, _value_name: &str, _value: &str) {}
+
+    /// Sets the memory backing for an existing dump. `backing_type` and `backing_object_id` are
+    /// used by the embedder to associate the memory dumped via [`Self::dump_numeric_value()`]
+    /// with the corresponding dump that backs the memory.
+    fn set_memory_backing(&mut self, dump_name: &str, backing_type: &str, backing_object_id: &str);
+
+    /// Returns the type of details requested in the dump. The granularity of the dump is
+    /// supposed to match the [`LevelOfDetail`] argument. [`LevelOfDetail::Light`] is intended to
+    /// be used for faster periodic dumps and [`LevelOfDetail::ObjectsBreakdowns`] for more
+    /// detailed inspection.
+    fn requested_details(&self) -> LevelOfDetail;
+
+    /// Returns `true` if we should dump wrapped objects. Wrapped objects come from outside Skia,
+    /// and may be independently tracked there.
+    fn should_dump_wrapped_objects(&self) -> bool {
+        true
+    }
+
+    /// If [`Self::should_dump_wrapped_objects()`] returns `true` then this function will be
+    /// called to populate the output with information on whether the item being dumped is a
+    /// wrapped object.
+    fn dump_wrapped_state(&mut self, _dump_name: &str, _is_wrapped_object: bool) {}
+}
+
+/// Calls `f` with a native `SkTraceMemoryDump` that forwards to `dump`.
+pub(crate) fn with_native_trace_memory_dump<R>(
+    dump: &mut dyn TraceMemoryDump,
+    f: impl FnOnce(&mut SkTraceMemoryDump) -> R,
+) -> R {
+    let param = unsafe { rust_trace_memory_dump::new_param(dump) };
+    let mut native: RustTraceMemoryDump =
+        construct(|td| unsafe { sb::C_RustTraceMemoryDump_construct(td, &param) });
+    f(native.base_mut())
+}
+
+impl NativeBase<SkTraceMemoryDump> for RustTraceMemoryDump {}
+
+mod rust_trace_memory_dump {
+    use super::{LevelOfDetail, TraceMemoryDump};
+    use skia_bindings::{RustTraceMemoryDump_Param, TraitObject};
+    use std::{ffi::CStr, mem, os::raw};
+
+    pub unsafe fn new_param(dump: &mut dyn TraceMemoryDump) -> RustTraceMemoryDump_Param {
+        RustTraceMemoryDump_Param {
+            trait_: mem::transmute(dump),
+            dumpNumericValue: Some(dump_numeric_value),
+            dumpStringValue: Some(dump_string_value),
+            setMemoryBacking: Some(set_memory_backing),
+            getRequestedDetails: Some(get_requested_details),
+            shouldDumpWrappedObjects: Some(should_dump_wrapped_objects),
+            dumpWrappedState: Some(dump_wrapped_state),
+        }
+    }
+
+    unsafe extern "C" fn dump_numeric_value(
+        to: TraitObject,
+        dump_name: *const raw::c_char,
+        value_name: *const raw::c_char,
+        units: *const raw::c_char,
+        value: u64,
+    ) {
+        to_dump(to).dump_numeric_value(
+            &to_str(dump_name),
+            &to_str(value_name),
+            &to_str(units),
+            value,
+        )
+    }
+
+    unsafe extern "C" fn dump_string_value(
+        to: TraitObject,
+        dump_name: *const raw::c_char,
+        value_name: *const raw::c_char,
+        value: *const raw::c_char,
+    ) {
+        to_dump(to).dump_string_value(&to_str(dump_name), &to_str(value_name), &to_str(value))
+    }
+
+    unsafe extern "C" fn set_memory_backing(
+        to: TraitObject,
+        dump_name: *const raw::c_char,
+        backing_type: *const raw::c_char,
+        backing_object_id: *const raw::c_char,
+    ) {
+        to_dump(to).set_memory_backing(
+            &to_str(dump_name),
+            &to_str(backing_type),
+            &to_str(backing_object_id),
+        )
+    }
+
+    extern "C" fn get_requested_details(to: TraitObject) -> LevelOfDetail {
+        to_dump(to).requested_details()
+    }
+
+    extern "C" fn should_dump_wrapped_objects(to: TraitObject) -> bool {
+        to_dump(to).should_dump_wrapped_objects()
+    }
+
+    unsafe extern "C" fn dump_wrapped_state(
+        to: TraitObject,
+        dump_name: *const raw::c_char,
+        is_wrapped_object: bool,
+    ) {
+        to_dump(to).dump_wrapped_state(&to_str(dump_name), is_wrapped_object)
+    }
+
+    unsafe fn to_str<'a>(str: *const raw::c_char) -> std::borrow::Cow<'a, str> {
+        if str.is_null() {
+            return "".into();
+        }
+        CStr::from_ptr(str).to_string_lossy()
+    }
+
+    fn to_dump<'a>(to: TraitObject) -> &'a mut dyn TraceMemoryDump {
+        unsafe { mem::transmute(to) }
+    }
+}
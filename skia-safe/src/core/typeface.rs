@@ -1,9 +1,9 @@
 use crate::{
     font_arguments,
-    font_parameters::VariationAxis,
+    font_parameters::{VariationAxis, VariationNamedInstance},
     interop::{self, MemoryStream, NativeStreamBase, StreamAsset},
     prelude::*,
-    Data, FontArguments, FontStyle, FourByteTag, GlyphId, Rect, TextEncoding, Unichar,
+    Color, Data, FontArguments, FontStyle, FourByteTag, GlyphId, Rect, TextEncoding, Unichar,
 };
 use skia_bindings::{self as sb, SkRefCntBase, SkTypeface, SkTypeface_LocalizedStrings};
 use std::{ffi, fmt, mem, ptr};
@@ -107,6 +107,62 @@ impl Typeface {
         }
     }
 
+    /// Returns the named instances of a variable font, like "Bold" or "Condensed Light", read
+    /// from its `fvar` table. Returns `None` if the typeface is not a variable font.
+    pub fn variation_named_instances(&self) -> Option<Vec<VariationNamedInstance>> {
+        let fvar = self.copy_table_data(u32::from_be_bytes(*b"fvar"))?;
+        let name = self.copy_table_data(u32::from_be_bytes(*b"name"));
+        fvar::named_instances(fvar.as_bytes(), name.as_ref().map(|name| name.as_bytes()))
+    }
+
+    /// Returns a clone of this typeface with the variation axes set to the `(axis, value)` pairs
+    /// of `coordinates`. Axes that are not listed keep their current value.
+    pub fn clone_with_variation(&self, coordinates: &[(FourByteTag, f32)]) -> Option<Typeface> {
+        let coordinates: Vec<_> = coordinates
+            .iter()
+            .map(|&(axis, value)| font_arguments::variation_position::Coordinate { axis, value })
+            .collect();
+        let arguments =
+            FontArguments::new().set_variation_design_position(font_arguments::VariationPosition {
+                coordinates: &coordinates,
+            });
+        self.clone_with_arguments(&arguments)
+    }
+
+    /// Returns the color palettes of the `CPAL` table, or `None` if the typeface has none.
+    ///
+    /// Use the index of a palette and overrides for some of its entries with
+    /// [`Self::clone_with_palette()`] to recolor `COLR` glyphs.
+    pub fn palettes(&self) -> Option<Vec<Vec<Color>>> {
+        let cpal = self.copy_table_data(u32::from_be_bytes(*b"CPAL"))?;
+        cpal::palettes(cpal.as_bytes())
+    }
+
+    /// Returns a clone of this typeface that renders color glyphs with the palette `index`, in
+    /// which the entries listed in `overrides` are replaced by the given colors.
+    ///
+    /// Returns `None` if an index is out of the range of `i32`.
+    pub fn clone_with_palette(
+        &self,
+        index: usize,
+        overrides: &[(usize, Color)],
+    ) -> Option<Typeface> {
+        let overrides: Vec<_> = overrides
+            .iter()
+            .map(|&(index, color)| {
+                Some(font_arguments::palette::Override {
+                    index: index.try_into().ok()?,
+                    color,
+                })
+            })
+            .collect::<Option<_>>()?;
+        let arguments = FontArguments::new().set_palette(font_arguments::Palette {
+            index: index.try_into().ok()?,
+            overrides: &overrides,
+        });
+        self.clone_with_arguments(&arguments)
+    }
+
     pub fn unique_id(&self) -> TypefaceId {
         self.native().fUniqueID
     }
@@ -203,15 +259,18 @@ impl Typeface {
         unsafe { self.native().countGlyphs().try_into().unwrap() }
     }
 
+    /// Returns the number of tables in the font.
     pub fn count_tables(&self) -> usize {
         unsafe { self.native().countTables().try_into().unwrap() }
     }
 
+    /// Returns the tags of the tables in the font, for example `u32::from_be_bytes(*b"OS/2")`.
     pub fn table_tags(&self) -> Option<Vec<FontTableTag>> {
         let mut v: Vec<FontTableTag> = vec![0; self.count_tables()];
         (unsafe { self.native().getTableTags(v.as_mut_ptr()) } != 0).if_true_some(v)
     }
 
+    /// Returns the size of the table `tag` in bytes, or `None` if the font has no such table.
     pub fn get_table_size(&self, tag: FontTableTag) -> Option<usize> {
         let size = unsafe { self.native().getTableSize(tag) };
         if size != 0 {
@@ -221,13 +280,23 @@ impl Typeface {
         }
     }
 
+    /// Copies the start of the table `tag` into `data` and returns the number of bytes copied.
+    ///
+    /// The table data is returned as it is stored in the font, so all values are big-endian.
     pub fn get_table_data(&self, tag: FontTableTag, data: &mut [u8]) -> usize {
+        self.get_table_data_at(tag, 0, data)
+    }
+
+    /// Copies the part of the table `tag` that starts at `offset` into `data` and returns the number
+    /// of bytes copied, which is `0` if the font has no such table or `offset` is out of range.
+    pub fn get_table_data_at(&self, tag: FontTableTag, offset: usize, data: &mut [u8]) -> usize {
         unsafe {
             self.native()
-                .getTableData(tag, 0, data.len(), data.as_mut_ptr() as _)
+                .getTableData(tag, offset, data.len(), data.as_mut_ptr() as _)
         }
     }
 
+    /// Returns a copy of the table `tag`, or `None` if the font has no such table.
     pub fn copy_table_data(&self, tag: FontTableTag) -> Option<Data> {
         Data::from_ptr(unsafe { sb::C_SkTypeface_copyTableData(self.native(), tag) })
     }
@@ -333,6 +402,259 @@ impl Iterator for LocalizedStringsIter {
     }
 }
 
+/// Reads the named instances from the `fvar` table and their names from the `name` table.
+mod fvar {
+    use crate::{
+        font_arguments::variation_position::Coordinate, font_parameters::VariationNamedInstance,
+        FourByteTag,
+    };
+
+    pub fn named_instances(
+        fvar: &[u8],
+        name: Option<&[u8]>,
+    ) -> Option<Vec<VariationNamedInstance>> {
+        let axes_offset = usize::from(u16_at(fvar, 4)?);
+        let axis_count = usize::from(u16_at(fvar, 8)?);
+        let axis_size = usize::from(u16_at(fvar, 10)?);
+        let instance_count = usize::from(u16_at(fvar, 12)?);
+        let instance_size = usize::from(u16_at(fvar, 14)?);
+
+        let axes: Vec<FourByteTag> = (0..axis_count)
+            .map(|i| u32_at(fvar, axes_offset + i * axis_size).map(FourByteTag::new))
+            .collect::<Option<_>>()?;
+        let instances_offset = axes_offset + axis_count * axis_size;
+        // The PostScript name ID is optional and follows the coordinates.
+        let has_postscript_name = instance_size >= 4 + axis_count * 4 + 2;
+        let lookup = |name_id: u16| name.and_then(|name| lookup_name(name, name_id));
+
+        (0..instance_count)
+            .map(|i| {
+                let offset = instances_offset + i * instance_size;
+                let coordinates = axes
+                    .iter()
+                    .enumerate()
+                    .map(|(a, &axis)| {
+                        let value = fixed_at(fvar, offset + 4 + a * 4)?;
+                        Some(Coordinate { axis, value })
+                    })
+                    .collect::<Option<_>>()?;
+                let postscript_name = if has_postscript_name {
+                    u16_at(fvar, offset + 4 + axis_count * 4)
+                        .filter(|&id| id != 0xffff)
+                        .and_then(lookup)
+                } else {
+                    None
+                };
+                Some(VariationNamedInstance {
+                    name: lookup(u16_at(fvar, offset)?),
+                    postscript_name,
+                    coordinates,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the string with `name_id`. English Unicode strings are preferred.
+    fn lookup_name(name: &[u8], name_id: u16) -> Option<String> {
+        let count = usize::from(u16_at(name, 2)?);
+        let storage = usize::from(u16_at(name, 4)?);
+        (0..count)
+            .filter_map(|i| {
+                let record = 6 + i * 12;
+                if u16_at(name, record + 6)? != name_id {
+                    return None;
+                }
+                let platform = u16_at(name, record)?;
+                let encoding = u16_at(name, record + 2)?;
+                let language = u16_at(name, record + 4)?;
+                let length = usize::from(u16_at(name, record + 8)?);
+                let start = storage + usize::from(u16_at(name, record + 10)?);
+                let bytes = name.get(start..start + length)?;
+                match (platform, encoding) {
+                    (3, 1) | (3, 10) => {
+                        Some((if language == 0x409 { 0 } else { 2 }, utf16be(bytes)?))
+                    }
+                    (0, _) => Some((1, utf16be(bytes)?)),
+                    (1, 0) if bytes.is_ascii() => {
+                        Some((3, String::from_utf8(bytes.to_vec()).ok()?))
+                    }
+                    _ => None,
+                }
+            })
+            .min_by_key(|(rank, _)| *rank)
+            .map(|(_, string)| string)
+    }
+
+    fn utf16be(bytes: &[u8]) -> Option<String> {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_be_bytes([c[0], c[1]]))
+            .collect();
+        String::from_utf16(&units).ok()
+    }
+
+    fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+        let bytes = data.get(offset..offset + 2)?;
+        Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+        let bytes = data.get(offset..offset + 4)?;
+        Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Reads a 16.16 fixed point number.
+    fn fixed_at(data: &[u8], offset: usize) -> Option<f32> {
+        u32_at(data, offset).map(|v| v as i32 as f32 / 65536.0)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::named_instances;
+        use crate::FourByteTag;
+
+        fn be16(v: u16) -> [u8; 2] {
+            v.to_be_bytes()
+        }
+
+        fn fixed(v: f32) -> [u8; 4] {
+            ((v * 65536.0) as i32).to_be_bytes()
+        }
+
+        #[test]
+        fn parse_named_instances() {
+            let mut fvar = Vec::new();
+            // Header: version, axes offset, reserved, axis count, axis size, instance count,
+            // instance size (with PostScript name ID).
+            for v in [1, 0, 16, 2, 2, 20, 2, 14] {
+                fvar.extend(be16(v));
+            }
+            for (tag, min, def, max) in [
+                (b"wght", 100.0, 400.0, 900.0),
+                (b"wdth", 75.0, 100.0, 100.0),
+            ] {
+                fvar.extend(tag);
+                fvar.extend(fixed(min));
+                fvar.extend(fixed(def));
+                fvar.extend(fixed(max));
+                fvar.extend(be16(0));
+                fvar.extend(be16(256));
+            }
+            for (name_id, weight, width, ps_name_id) in
+                [(257, 700.0, 100.0, 258), (259, 300.0, 87.5, 0xffff)]
+            {
+                fvar.extend(be16(name_id));
+                fvar.extend(be16(0));
+                fvar.extend(fixed(weight));
+                fvar.extend(fixed(width));
+                fvar.extend(be16(ps_name_id));
+            }
+
+            let strings: [(u16, u16, u16, &str); 3] = [
+                (3, 0x409, 257, "Bold"),
+                (1, 0, 258, "Font-Bold"),
+                (3, 0x407, 259, "Schmal Leicht"),
+            ];
+            let mut records = Vec::new();
+            let mut storage = Vec::new();
+            for (platform, language, name_id, string) in strings {
+                let bytes: Vec<u8> = if platform == 3 {
+                    string.encode_utf16().flat_map(u16::to_be_bytes).collect()
+                } else {
+                    string.as_bytes().to_vec()
+                };
+                let encoding = if platform == 3 { 1 } else { 0 };
+                for v in [
+                    platform,
+                    encoding,
+                    language,
+                    name_id,
+                    bytes.len() as u16,
+                    storage.len() as u16,
+                ] {
+                    records.extend(be16(v));
+                }
+                storage.extend(bytes);
+            }
+            let mut name = Vec::new();
+            for v in [0, strings.len() as u16, 6 + records.len() as u16] {
+                name.extend(be16(v));
+            }
+            name.extend(records);
+            name.extend(storage);
+
+            let instances = named_instances(&fvar, Some(&name)).unwrap();
+            assert_eq!(instances.len(), 2);
+            assert_eq!(instances[0].name.as_deref(), Some("Bold"));
+            assert_eq!(instances[0].postscript_name.as_deref(), Some("Font-Bold"));
+            assert_eq!(
+                instances[0].coordinates[0].axis,
+                FourByteTag::from_chars('w', 'g', 'h', 't')
+            );
+            assert_eq!(instances[0].coordinates[0].value, 700.0);
+            assert_eq!(instances[1].name.as_deref(), Some("Schmal Leicht"));
+            assert_eq!(instances[1].postscript_name, None);
+            assert_eq!(instances[1].coordinates[1].value, 87.5);
+
+            assert!(named_instances(&fvar[..30], None).is_none());
+        }
+    }
+}
+
+/// Reads the color palettes from the `CPAL` table.
+mod cpal {
+    use crate::Color;
+
+    pub fn palettes(cpal: &[u8]) -> Option<Vec<Vec<Color>>> {
+        let entries = usize::from(u16_at(cpal, 2)?);
+        let palettes = usize::from(u16_at(cpal, 4)?);
+        let records =
+            usize::try_from(u32::from_be_bytes(cpal.get(8..12)?.try_into().ok()?)).ok()?;
+        (0..palettes)
+            .map(|i| {
+                let first = usize::from(u16_at(cpal, 12 + i * 2)?);
+                (first..first + entries)
+                    .map(|record| {
+                        // Color records are stored as BGRA, not premultiplied.
+                        let bgra = cpal.get(records + record * 4..records + record * 4 + 4)?;
+                        Some(Color::from_argb(bgra[3], bgra[2], bgra[1], bgra[0]))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+        let bytes = data.get(offset..offset + 2)?;
+        Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    #[test]
+    fn parse_palettes() {
+        let mut cpal = Vec::new();
+        // version, entries per palette, palettes, color records, offset of the records
+        for v in [0u16, 2, 2, 3] {
+            cpal.extend(v.to_be_bytes());
+        }
+        cpal.extend(16u32.to_be_bytes());
+        // The palettes share the second record.
+        for v in [0u16, 1] {
+            cpal.extend(v.to_be_bytes());
+        }
+        cpal.extend([0, 0, 255, 255, 0, 255, 0, 128, 255, 0, 0, 255]);
+
+        let palettes = palettes(&cpal).unwrap();
+        assert_eq!(
+            palettes,
+            [
+                vec![Color::RED, Color::from_argb(128, 0, 255, 0)],
+                vec![Color::from_argb(128, 0, 255, 0), Color::BLUE]
+            ]
+        );
+        assert!(palettes(&cpal[..20]).is_none());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{SerializeBehavior, Typeface};
@@ -369,6 +691,38 @@ mod tests {
         assert!(any);
     }
 
+    #[test]
+    fn clone_with_palette_rejects_out_of_range_indices() {
+        let tf = Typeface::default();
+        assert!(tf.clone_with_palette(usize::MAX, &[]).is_none());
+        assert!(tf
+            .clone_with_palette(0, &[(usize::MAX, crate::Color::RED)])
+            .is_none());
+    }
+
+    #[test]
+    fn read_tables_of_default() {
+        let tf = Typeface::default();
+        let tags = tf.table_tags().unwrap();
+        assert_eq!(tags.len(), tf.count_tables());
+
+        let cmap = u32::from_be_bytes(*b"cmap");
+        assert!(tags.contains(&cmap));
+        let size = tf.get_table_size(cmap).unwrap();
+        let data = tf.copy_table_data(cmap).unwrap();
+        assert_eq!(data.len(), size);
+
+        let mut header = [0u8; 4];
+        assert_eq!(tf.get_table_data(cmap, &mut header), 4);
+        assert_eq!(header, data[..4]);
+        let mut tail = [0u8; 8];
+        assert_eq!(tf.get_table_data_at(cmap, size - 2, &mut tail), 2);
+        assert_eq!(tail[..2], data[size - 2..]);
+        assert_eq!(tf.get_table_data_at(cmap, size, &mut tail), 0);
+
+        assert!(tf.get_table_size(u32::from_be_bytes(*b"none")).is_none());
+    }
+
     #[test]
     fn get_font_data_of_default() {
         let tf = Typeface::default();
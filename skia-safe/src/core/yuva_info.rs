@@ -292,3 +292,40 @@ impl YUVAInfo {
         info.fPlaneConfig != PlaneConfig::Unknown
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{PlaneConfig, Subsampling, YUVAInfo};
+    use crate::{ISize, YUVColorSpace};
+
+    #[test]
+    fn nv12_and_i420_plane_dimensions() {
+        let nv12 = YUVAInfo::new(
+            (64, 32),
+            PlaneConfig::Y_UV,
+            Subsampling::S420,
+            YUVColorSpace::Rec709_Limited,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(nv12.num_planes(), 2);
+        assert_eq!(nv12.num_channels_in_plane(1), Some(2));
+        assert_eq!(
+            nv12.plane_dimensions(),
+            vec![ISize::new(64, 32), ISize::new(32, 16)]
+        );
+
+        let i420 = YUVAInfo::new(
+            (64, 32),
+            PlaneConfig::Y_U_V,
+            Subsampling::S420,
+            YUVColorSpace::Rec709_Limited,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(i420.num_planes(), 3);
+        assert_eq!(i420.plane_dimensions()[2], ISize::new(32, 16));
+    }
+}
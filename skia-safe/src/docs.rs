@@ -1,2 +1,6 @@
+#[cfg(feature = "unstable")]
+mod multi_picture_document;
 mod pdf_document;
+#[cfg(feature = "unstable")]
+pub use multi_picture_document::*;
 pub use pdf_document::*;
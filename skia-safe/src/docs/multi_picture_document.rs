@@ -0,0 +1,143 @@
+pub mod multi_picture_document {
+    //! Multi-picture documents (MSKP files), which store one [`Picture`] per page.
+    //!
+    //! Skia's debugger and viewer open MSKP files, so they can capture the frames of an
+    //! animation or the pages of a document for debugging.
+    //!
+    //! Requires the `unstable` feature, because Skia declares multi-picture documents in a
+    //! private header.
+    use crate::{
+        interop::RustWStream, prelude::*, DeserialProcs, Document, Picture, SerialProcs, Size,
+    };
+    use skia_bindings as sb;
+    use std::{io, ptr};
+
+    /// A page read by [`read()`].
+    #[derive(Clone, Debug)]
+    pub struct Page {
+        pub picture: Picture,
+        pub size: Size,
+    }
+
+    /// Creates a multi-picture document that is written to `output` when it is closed with
+    /// [`Document::close_to_writer()`].
+    ///
+    /// `procs` serialize the images and typefaces the pages reference. They are used when the
+    /// document is closed, so they are borrowed as long as the document.
+    ///
+    /// Returns `None` if the document could not be created.
+    pub fn new_document<'a>(
+        output: &'a mut impl io::Write,
+        procs: Option<&'a mut SerialProcs>,
+    ) -> Option<Document<'a>> {
+        let native_procs = procs.map(|procs| procs.native_procs());
+        Document::new(RustWStream::new(output), |stream| unsafe {
+            sb::C_SkMultiPictureDocument_Make(
+                stream,
+                native_procs.as_ref().map_or(ptr::null(), |procs| procs),
+            )
+        })
+    }
+
+    /// Returns the number of pages of the multi-picture document `data`, or `0` if `data` is not
+    /// a multi-picture document.
+    pub fn read_page_count(data: &[u8]) -> usize {
+        let count =
+            unsafe { sb::C_SkMultiPictureDocument_ReadPageCount(data.as_ptr() as _, data.len()) };
+        count.try_into().unwrap_or_default()
+    }
+
+    /// Reads the pages of the multi-picture document `data`.
+    ///
+    /// `procs` must match the procs the document was written with. Returns `None` if `data` is
+    /// not a valid multi-picture document.
+    pub fn read(data: &[u8], procs: Option<&mut DeserialProcs>) -> Option<Vec<Page>> {
+        let count = read_page_count(data);
+        if count == 0 {
+            return None;
+        }
+        let mut pictures = vec![ptr::null_mut(); count];
+        let mut sizes = vec![Size::default(); count];
+        let native_procs = procs.map(|procs| procs.native_procs());
+        let read = unsafe {
+            sb::C_SkMultiPictureDocument_Read(
+                data.as_ptr() as _,
+                data.len(),
+                pictures.as_mut_ptr(),
+                sizes.native_mut().as_mut_ptr(),
+                count.try_into().unwrap(),
+                native_procs.as_ref().map_or(ptr::null(), |procs| procs),
+            )
+        };
+        // Take ownership of all pictures before checking them, so that none leaks.
+        let pictures: Vec<Option<Picture>> = pictures.into_iter().map(Picture::from_ptr).collect();
+        if !read {
+            return None;
+        }
+        pictures
+            .into_iter()
+            .zip(sizes)
+            .map(|(picture, size)| {
+                Some(Page {
+                    picture: picture?,
+                    size,
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{new_document, read, read_page_count};
+        use crate::{Color, Data, DeserialProcs, Paint, Rect, SerialProcs, Size, Surface};
+
+        #[test]
+        fn write_and_read_pages() {
+            let mut surface = Surface::new_raster_n32_premul((8, 8)).unwrap();
+            surface.canvas().clear(Color::BLUE);
+            let image = surface.image_snapshot();
+
+            let mut images = Vec::new();
+            let mut serial_procs = SerialProcs::new();
+            serial_procs.set_image_proc(|image| {
+                images.push(image.clone());
+                Some(Data::new_copy(&(images.len() - 1).to_le_bytes()))
+            });
+
+            let page_sizes = [Size::new(100.0, 50.0), Size::new(20.0, 30.0)];
+            let mut mskp = Vec::new();
+            let mut document = new_document(&mut mskp, Some(&mut serial_procs)).unwrap();
+            for (i, size) in page_sizes.into_iter().enumerate() {
+                let mut page = document.begin_page(size, None);
+                if i == 0 {
+                    page.canvas()
+                        .draw_rect(Rect::new(10.0, 10.0, 90.0, 40.0), &Paint::default());
+                } else {
+                    page.canvas().draw_image(&image, (0, 0), None);
+                }
+                document = page.end_page();
+            }
+            document.close_to_writer().unwrap();
+            drop(serial_procs);
+            assert_eq!(images.len(), 1);
+
+            assert_eq!(read_page_count(&mskp), 2);
+            let mut restored_images = 0;
+            let mut deserial_procs = DeserialProcs::new();
+            deserial_procs.set_image_proc(|data| {
+                restored_images += 1;
+                images
+                    .get(usize::from_le_bytes(data.try_into().ok()?))
+                    .cloned()
+            });
+            let pages = read(&mskp, Some(&mut deserial_procs)).unwrap();
+            drop(deserial_procs);
+            assert_eq!(restored_images, 1);
+            let sizes: Vec<_> = pages.iter().map(|page| page.size).collect();
+            assert_eq!(sizes, page_sizes);
+
+            assert_eq!(read_page_count(b"not a document"), 0);
+            assert!(read(b"not a document", None).is_none());
+        }
+    }
+}
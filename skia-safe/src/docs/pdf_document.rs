@@ -1,13 +1,13 @@
 pub mod pdf {
     use crate::{
-        interop::{AsStr, DynamicMemoryWStream, SetStr},
+        interop::{AsStr, RustWStream, SetStr},
         prelude::*,
-        scalar, DateTime, Document,
+        scalar, Canvas, DateTime, Document,
     };
     use skia_bindings::{
         self as sb, SkPDF_AttributeList, SkPDF_Metadata, SkPDF_StructureElementNode,
     };
-    use std::{ffi::CString, fmt, mem, ptr};
+    use std::{ffi::CString, fmt, io, mem, ptr};
 
     pub type AttributeList = Handle<SkPDF_AttributeList>;
     unsafe_send_sync!(AttributeList);
@@ -82,6 +82,12 @@ pub mod pdf {
         }
     }
 
+    /// A node in a PDF structure tree, giving a semantic representation of the content. Each
+    /// node has a `type_string` (like `"H1"`, `"P"`, or `"Figure"`) and a `node_id` that
+    /// refers to the content drawn after [`set_node_id()`] was called with the same id.
+    ///
+    /// Pass the root of the tree in [`Metadata::structure_element_tree_root`] to create a
+    /// tagged PDF.
     #[repr(transparent)]
     pub struct StructureElementNode(ptr::NonNull<SkPDF_StructureElementNode>);
 
@@ -102,7 +108,7 @@ pub mod pdf {
 
     impl Default for StructureElementNode {
         fn default() -> Self {
-            Self::new("")
+            Self(ptr::NonNull::new(unsafe { sb::C_SkPDF_StructureElementNode_New() }).unwrap())
         }
     }
 
@@ -112,6 +118,7 @@ pub mod pdf {
                 .field("type_string", &self.type_string())
                 .field("child_vector", &self.child_vector())
                 .field("node_id", &self.node_id())
+                .field("additional_node_ids", &self.additional_node_ids())
                 .field("attributes", &self.attributes())
                 .field("alt", &self.alt())
                 .field("lang", &self.lang())
@@ -135,16 +142,21 @@ pub mod pdf {
             self.native().fTypeString.as_str()
         }
 
-        pub fn set_child_vector(
-            &mut self,
-            mut child_vector: Vec<StructureElementNode>,
-        ) -> &mut Self {
-            // strategy is to move them out by setting them to nullptr (drop() will handle a nullptr on the rust side)
+        pub fn set_child_vector(&mut self, child_vector: Vec<StructureElementNode>) -> &mut Self {
+            // The native node takes ownership of the children.
+            let mut children: Vec<*mut SkPDF_StructureElementNode> = child_vector
+                .into_iter()
+                .map(|node| {
+                    let ptr = node.0.as_ptr();
+                    mem::forget(node);
+                    ptr
+                })
+                .collect();
             unsafe {
                 sb::C_SkPDF_StructureElementNode_setChildVector(
                     self.native_mut(),
-                    child_vector.as_mut_ptr() as _,
-                    child_vector.len(),
+                    children.as_mut_ptr(),
+                    children.len(),
                 )
             }
             self
@@ -159,7 +171,7 @@ pub mod pdf {
         }
 
         pub fn child_vector(&self) -> &[StructureElementNode] {
-            let mut ptr = ptr::null_mut();
+            let mut ptr = ptr::null();
             unsafe {
                 let len = sb::C_SkPDF_StructureElementNode_getChildVector(self.native(), &mut ptr);
                 safer::from_raw_parts(ptr as _, len)
@@ -175,6 +187,28 @@ pub mod pdf {
             self.native().fNodeId
         }
 
+        /// Sets the ids of other content that belongs to this node, for example when a
+        /// paragraph continues on the next page.
+        pub fn set_additional_node_ids(&mut self, node_ids: &[i32]) -> &mut Self {
+            unsafe {
+                sb::C_SkPDF_StructureElementNode_setAdditionalNodeIds(
+                    self.native_mut(),
+                    node_ids.as_ptr(),
+                    node_ids.len(),
+                )
+            }
+            self
+        }
+
+        pub fn additional_node_ids(&self) -> &[i32] {
+            let mut ptr = ptr::null();
+            unsafe {
+                let len =
+                    sb::C_SkPDF_StructureElementNode_getAdditionalNodeIds(self.native(), &mut ptr);
+                safer::from_raw_parts(ptr, len)
+            }
+        }
+
         pub fn attributes(&self) -> &AttributeList {
             AttributeList::from_native_ref(&self.native().fAttributes)
         }
@@ -202,26 +236,108 @@ pub mod pdf {
         }
     }
 
+    /// Controls the outline (bookmarks) of the document.
+    pub use sb::SkPDF_Metadata_Outline as Outline;
+    variant_name!(Outline::StructureElementHeaders);
+
+    /// The zlib compression level of the content streams.
+    pub use sb::SkPDF_Metadata_CompressionLevel as CompressionLevel;
+    variant_name!(CompressionLevel::HighButSlow);
+
+    /// Optional metadata to be passed into the PDF factory function.
     #[derive(Default, Debug)]
     pub struct Metadata {
+        /// The document's title.
         pub title: String,
+        /// The name of the person who created the document.
         pub author: String,
+        /// The subject of the document.
         pub subject: String,
+        /// Keywords associated with the document. Commas may be used to delineate keywords
+        /// within the string.
         pub keywords: String,
+        /// If the document was converted to PDF from another format, the name of the
+        /// conforming product that created the original document from which it was converted.
         pub creator: String,
+        /// The product that is converting this document to PDF, defaults to "Skia/PDF".
         pub producer: String,
+        /// The date and time the document was created.
         pub creation: Option<DateTime>,
+        /// The date and time the document was most recently modified.
         pub modified: Option<DateTime>,
+        /// The DPI (pixels-per-inch) at which features without native PDF support will be
+        /// rasterized (e.g. draw image with perspective, draw text with perspective, ...). A
+        /// larger DPI would create a PDF that reflects the original intent with better
+        /// fidelity, but it can make for larger PDF files too, which would use more memory
+        /// while rendering, and it would be slower to be processed or sent online or to
+        /// printer. Defaults to `72`.
         pub raster_dpi: Option<scalar>,
+        /// If `true`, the document is created to conform to PDF/A-2b, which is meant for
+        /// long-term archiving. The document then embeds its metadata as XMP, an sRGB output
+        /// intent, and a document ID that is derived from the metadata, so the metadata should
+        /// identify the document.
+        ///
+        /// For PDF/UA (accessibility) conformance, create a tagged PDF by setting
+        /// [`Self::structure_element_tree_root`] and a [`Self::title`]. Skia does not add the
+        /// PDF/UA identification to the XMP metadata.
         pub pdfa: bool,
+        /// Encoding quality controls the trade-off between size and quality. By default this
+        /// is set to `101` percent, which corresponds to lossless encoding. If this value is
+        /// set to a value <= `100`, and the image is opaque, it will be encoded (using JPEG)
+        /// with that quality setting.
+        ///
+        /// Images that were created from encoded JPEG data, for example with
+        /// [`crate::Image::from_encoded()`], are embedded as they are and not encoded again,
+        /// as long as their encoded data is still available and they use the YUV or the
+        /// grayscale color model. This version of Skia does not support custom JPEG encoder
+        /// and decoder callbacks.
         pub encoding_quality: Option<i32>,
-        // TODO: this is not supported yet
-        structure_element_tree_root: Option<StructureElementNode>,
+        /// The root of the structure tree. If set, the document is created as a tagged PDF.
+        pub structure_element_tree_root: Option<StructureElementNode>,
+        /// With [`Outline::StructureElementHeaders`], the outline of the document is created
+        /// from the `"H1"` to `"H6"` nodes of the structure tree. Each entry links to the
+        /// content that was drawn with the node id of its heading, so
+        /// [`Self::structure_element_tree_root`] needs to be set, too. Defaults to
+        /// [`Outline::None`].
+        pub outline: Outline,
+        /// The compression level of the content streams. Higher levels produce smaller files,
+        /// but take longer to encode. Defaults to [`CompressionLevel::Default`].
+        pub compression_level: Option<CompressionLevel>,
     }
 
-    // TODO: SetNodeId
+    /// Associates the content that is drawn next on `canvas` with the [`StructureElementNode`]
+    /// with the same `node_id`. Pass `0` to mark the content that follows as not being part of
+    /// the structure tree.
+    ///
+    /// Has no effect if `canvas` is not the canvas of a page of a PDF document.
+    pub fn set_node_id(canvas: &mut Canvas, node_id: i32) {
+        unsafe { sb::C_SkPDF_SetNodeId(canvas.native_mut(), node_id) }
+    }
+
+    /// Creates a PDF document that is kept in memory until it is closed with
+    /// [`Document::close()`].
+    pub fn new_document(metadata: Option<&Metadata>) -> Document<'static> {
+        let md = internal_metadata(metadata);
+        Document::new_in_memory(|stream| unsafe { sb::C_SkPDF_MakeDocument(stream, md.native()) })
+            .unwrap()
+    }
 
-    pub fn new_document(metadata: Option<&Metadata>) -> Document {
+    /// Creates a PDF document that is written to `output` while its pages are drawn, so large
+    /// documents do not need to be held in memory. Close it with
+    /// [`Document::close_to_writer()`].
+    ///
+    /// Returns `None` if the document could not be created.
+    pub fn new_document_to_writer<'a>(
+        output: &'a mut impl io::Write,
+        metadata: Option<&Metadata>,
+    ) -> Option<Document<'a>> {
+        let md = internal_metadata(metadata);
+        Document::new(RustWStream::new(output), |stream| unsafe {
+            sb::C_SkPDF_MakeDocument(stream, md.native())
+        })
+    }
+
+    fn internal_metadata(metadata: Option<&Metadata>) -> InternalMetadata {
         let mut md = InternalMetadata::default();
         if let Some(metadata) = metadata {
             let internal = md.native_mut();
@@ -244,9 +360,13 @@ pub mod pdf {
             if let Some(encoding_quality) = metadata.encoding_quality {
                 internal.fEncodingQuality = encoding_quality
             }
-            if let Some(_structure_element_tree_root) = &metadata.structure_element_tree_root {
-                // TODO: How can we be sure that the tree root is not being dropped while the document is processed?
-                unimplemented!("");
+            // The document copies the tree when it's created.
+            if let Some(structure_element_tree_root) = &metadata.structure_element_tree_root {
+                internal.fStructureElementTreeRoot = structure_element_tree_root.0.as_ptr();
+            }
+            internal.fOutline = metadata.outline;
+            if let Some(compression_level) = metadata.compression_level {
+                internal.fCompressionLevel = compression_level;
             }
         }
 
@@ -254,15 +374,7 @@ pub mod pdf {
         #[cfg(all(feature = "textlayout", feature = "embed-icudtl"))]
         crate::icu::init();
 
-        // we can't move the memory stream around anymore as soon it's referred by
-        // the document.
-        let mut memory_stream = Box::pin(DynamicMemoryWStream::new());
-        let document = RCHandle::from_ptr(unsafe {
-            sb::C_SkPDF_MakeDocument(memory_stream.native_mut().base_mut(), md.native())
-        })
-        .unwrap();
-
-        Document::new(memory_stream, document)
+        md
     }
 
     //
@@ -290,3 +402,150 @@ fn create_attribute_list() {
     let mut _al = AttributeList::default();
     _al.append_float_array("Owner", "Name", &[1.0, 2.0, 3.0]);
 }
+
+#[test]
+fn create_tagged_document() {
+    use crate::{Paint, Rect};
+    use pdf::StructureElementNode;
+
+    let mut heading = StructureElementNode::new("H1");
+    heading.set_node_id(2);
+    let mut root = StructureElementNode::new("Document");
+    root.set_node_id(1).append_child(heading);
+    assert_eq!(root.child_vector().len(), 1);
+    assert_eq!(root.child_vector()[0].type_string(), "H1");
+
+    let metadata = pdf::Metadata {
+        structure_element_tree_root: Some(root),
+        ..Default::default()
+    };
+    let mut data = Vec::new();
+    let mut document = pdf::new_document_to_writer(&mut data, Some(&metadata))
+        .unwrap()
+        .begin_page((100, 100), None);
+    pdf::set_node_id(document.canvas(), 2);
+    document
+        .canvas()
+        .draw_rect(Rect::new(10.0, 10.0, 90.0, 20.0), &Paint::default());
+    pdf::set_node_id(document.canvas(), 0);
+    document.end_page().close_to_writer().unwrap();
+    assert!(!data.is_empty());
+}
+
+#[test]
+fn create_pdfa_document() {
+    let metadata = pdf::Metadata {
+        title: "Archive".into(),
+        pdfa: true,
+        ..Default::default()
+    };
+    let mut data = Vec::new();
+    let document = pdf::new_document_to_writer(&mut data, Some(&metadata))
+        .unwrap()
+        .begin_page((100, 100), None);
+    document.end_page().close_to_writer().unwrap();
+    let contains = |pattern: &[u8]| data.windows(pattern.len()).any(|w| w == pattern);
+    assert!(contains(b"/OutputIntents"));
+    assert!(contains(b"pdfaid:part"));
+}
+
+#[test]
+fn create_document_with_outline() {
+    use crate::{Paint, Rect};
+    use pdf::StructureElementNode;
+
+    let mut chapter = StructureElementNode::new("H1");
+    chapter.set_node_id(2).set_alt("Chapter 1");
+    let mut root = StructureElementNode::new("Document");
+    root.set_node_id(1).append_child(chapter);
+
+    let metadata = pdf::Metadata {
+        structure_element_tree_root: Some(root),
+        outline: pdf::Outline::StructureElementHeaders,
+        ..Default::default()
+    };
+    let mut data = Vec::new();
+    let mut document = pdf::new_document_to_writer(&mut data, Some(&metadata))
+        .unwrap()
+        .begin_page((100, 100), None);
+    pdf::set_node_id(document.canvas(), 2);
+    document
+        .canvas()
+        .draw_rect(Rect::new(10.0, 10.0, 90.0, 20.0), &Paint::default());
+    document.end_page().close_to_writer().unwrap();
+    assert!(data.windows(9).any(|w| w == b"/Outlines"));
+}
+
+#[test]
+fn uncompressed_document_is_larger() {
+    use crate::{Paint, Rect};
+
+    let create = |compression_level| {
+        let metadata = pdf::Metadata {
+            compression_level: Some(compression_level),
+            ..Default::default()
+        };
+        let mut data = Vec::new();
+        let mut document = pdf::new_document_to_writer(&mut data, Some(&metadata))
+            .unwrap()
+            .begin_page((200, 200), None);
+        let paint = Paint::default();
+        for i in 0..100 {
+            let x = (i % 10 * 20) as f32;
+            let y = (i / 10 * 20) as f32;
+            document
+                .canvas()
+                .draw_rect(Rect::from_xywh(x, y, 10.0, 10.0), &paint);
+        }
+        document.end_page().close_to_writer().unwrap();
+        data.len()
+    };
+    assert!(create(pdf::CompressionLevel::None) > create(pdf::CompressionLevel::HighButSlow));
+}
+
+#[test]
+fn jpeg_images_are_passed_through() {
+    use crate::{Data, Image};
+
+    let jpeg = include_bytes!("../../tests/images/color_wheel.jpg");
+    let image = Image::from_encoded(Data::new_copy(jpeg)).unwrap();
+
+    let mut data = Vec::new();
+    let mut document = pdf::new_document_to_writer(&mut data, None)
+        .unwrap()
+        .begin_page((200, 200), None);
+    document.canvas().draw_image(&image, (0, 0), None);
+    document.end_page().close_to_writer().unwrap();
+    assert!(data.windows(10).any(|w| w == b"/DCTDecode"));
+}
+
+#[test]
+fn writer_errors_are_returned_from_close() {
+    use std::io;
+
+    struct Full;
+
+    impl io::Write for Full {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::WriteZero.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut output = Full;
+    let document = pdf::new_document_to_writer(&mut output, None)
+        .unwrap()
+        .begin_page((100, 100), None);
+    let error = document.end_page().close_to_writer().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn memory_document_returns_data() {
+    let document = pdf::new_document(None).begin_page((100, 100), None);
+    let data = document.end_page().close();
+    assert!(data.as_bytes().starts_with(b"%PDF"));
+}
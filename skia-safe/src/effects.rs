@@ -7,6 +7,7 @@ pub mod corner_path_effect;
 pub mod dash_path_effect;
 pub mod discrete_path_effect;
 pub mod gradient_shader;
+pub mod hdr;
 pub mod high_contrast_filter;
 pub mod image_filters;
 pub mod luma_color_filter;
@@ -24,4 +25,4 @@ pub use _2d_path_effect::*;
 pub use color_matrix::ColorMatrix;
 pub use high_contrast_filter::{high_contrast_config, HighContrastConfig};
 pub use op_path_effect::*;
-pub use runtime_effect::RuntimeEffect;
+pub use runtime_effect::{RuntimeEffect, RuntimeEffectBuilder, SkslUniforms};
@@ -0,0 +1,142 @@
+//! Rendering of HDR content onto SDR and HDR surfaces.
+//!
+//! The HDR color spaces created here decode SDR reference white to `1.0`, so brighter colors
+//! decode to values above `1.0`. The headroom of content or a display is the ratio of its peak
+//! brightness to SDR reference white: `1.0` for SDR, and for example `4.0` for a display that
+//! shows four times the brightness of SDR white.
+//!
+//! Surfaces with 8 bit color types clip colors above `1.0`. To keep the highlights of content
+//! with a higher headroom than the target, draw it with a [`tone_map()`] color filter.
+use crate::{
+    named_transfer_fn, ColorFilter, ColorSpace, ColorSpaceTransferFn, RuntimeEffect,
+    RuntimeEffectBuilder,
+};
+
+/// The peak brightness of PQ encoded content in nits.
+pub const PQ_PEAK_NITS: f32 = 10000.0;
+
+/// The brightness of SDR reference white in nits as recommended by ITU-R BT.2408.
+pub const SDR_WHITE_NITS: f32 = 203.0;
+
+/// The value HLG decodes the 75% signal to, which is SDR reference white according to
+/// ITU-R BT.2408. HLG decodes the 100% signal to `12.0`.
+const HLG_SDR_WHITE: f32 = 3.179_55;
+
+/// The headroom of HLG content.
+pub const HLG_HEADROOM: f32 = 12.0 / HLG_SDR_WHITE;
+
+/// Returns the headroom of PQ content when SDR reference white is shown at `sdr_white_nits`.
+pub fn pq_headroom(sdr_white_nits: f32) -> f32 {
+    PQ_PEAK_NITS / sdr_white_nits
+}
+
+/// Creates a color space with the PQ transfer function that decodes `sdr_white_nits` to `1.0`.
+///
+/// `gamut` is the matrix to XYZ D50 of the primaries, usually [`crate::named_gamut::REC2020`].
+pub fn pq_color_space(gamut: &[[f32; 3]; 3], sdr_white_nits: f32) -> Option<ColorSpace> {
+    scaled_color_space(&named_transfer_fn::PQ, gamut, pq_headroom(sdr_white_nits))
+}
+
+/// Creates a color space with the HLG transfer function that decodes the 75% signal to `1.0`.
+///
+/// `gamut` is the matrix to XYZ D50 of the primaries, usually [`crate::named_gamut::REC2020`].
+pub fn hlg_color_space(gamut: &[[f32; 3]; 3]) -> Option<ColorSpace> {
+    scaled_color_space(&named_transfer_fn::HLG, gamut, 1.0 / HLG_SDR_WHITE)
+}
+
+fn scaled_color_space(
+    transfer_fn: &ColorSpaceTransferFn,
+    gamut: &[[f32; 3]; 3],
+    scale: f32,
+) -> Option<ColorSpace> {
+    let gamut = gamut.map(|row| row.map(|v| v * scale));
+    ColorSpace::new_rgb(transfer_fn, &gamut)
+}
+
+const TONE_MAP_SKSL: &str = "
+    uniform half sourceHeadroom;
+    uniform half targetHeadroom;
+    uniform half invWhite2;
+
+    half4 main(half4 color) {
+        half m = max(max(color.r, color.g), color.b);
+        if (m <= 0) {
+            return color;
+        }
+        half x = min(m, sourceHeadroom) / targetHeadroom;
+        half mapped = targetHeadroom * x * (1 + x * invWhite2) / (1 + x);
+        return half4(color.rgb * (mapped / m), color.a);
+    }
+";
+
+/// Creates a color filter that compresses colors up to `source_headroom` to colors up to
+/// `target_headroom`.
+///
+/// The filter applies the extended Reinhard operator to the largest linear sRGB component, so
+/// that hues are preserved, dark colors stay nearly unchanged, and `source_headroom` maps to
+/// `target_headroom`. Colors above `source_headroom` are clipped to it.
+///
+/// Returns `None` if `target_headroom` is not positive or at least `source_headroom`, in which
+/// case the content can be drawn without tone mapping.
+pub fn tone_map(source_headroom: f32, target_headroom: f32) -> Option<ColorFilter> {
+    if !(target_headroom > 0.0 && source_headroom > target_headroom) {
+        return None;
+    }
+    let white = source_headroom / target_headroom;
+
+    let effect = RuntimeEffect::make_for_color_filer(TONE_MAP_SKSL, None).unwrap();
+    let mut builder = RuntimeEffectBuilder::new(effect);
+    builder
+        .set_uniform_float("sourceHeadroom", source_headroom)
+        .unwrap();
+    builder
+        .set_uniform_float("targetHeadroom", target_headroom)
+        .unwrap();
+    builder
+        .set_uniform_float("invWhite2", 1.0 / (white * white))
+        .unwrap();
+    Some(
+        builder
+            .make_color_filter()?
+            .with_working_color_space(ColorSpace::new_srgb_linear()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hlg_color_space, pq_color_space, pq_headroom, tone_map, SDR_WHITE_NITS};
+    use crate::{named_gamut, named_transfer_fn, Color4f, ColorSpace};
+
+    #[test]
+    fn hdr_color_spaces() {
+        let pq = pq_color_space(&named_gamut::REC2020, SDR_WHITE_NITS).unwrap();
+        assert_eq!(pq.transfer_fn(), named_transfer_fn::PQ);
+        let to_xyzd50 = pq.to_xyzd50().unwrap();
+        let scale = pq_headroom(SDR_WHITE_NITS);
+        assert!((to_xyzd50[1][1] - named_gamut::REC2020[1][1] * scale).abs() < 0.001);
+
+        let hlg = hlg_color_space(&named_gamut::REC2020).unwrap();
+        assert!(!hlg.is_numerical_transfer_fn());
+    }
+
+    #[test]
+    fn tone_map_to_headroom() {
+        assert!(tone_map(1.0, 2.0).is_none());
+        assert!(tone_map(2.0, 0.0).is_none());
+
+        let filter = tone_map(4.0, 1.0).unwrap();
+        let linear = ColorSpace::new_srgb_linear();
+        let map = |c: f32| {
+            filter
+                .filter_color4f(Color4f::new(c, c / 2.0, 0.0, 1.0), &linear, Some(&linear))
+                .r
+        };
+        assert!((map(4.0) - 1.0).abs() < 0.01);
+        assert!((map(8.0) - 1.0).abs() < 0.01);
+        assert!((map(0.01) - 0.01).abs() < 0.001);
+        assert!(map(2.0) < map(3.0));
+
+        let color = filter.filter_color4f(Color4f::new(4.0, 2.0, 0.0, 1.0), &linear, Some(&linear));
+        assert!((color.g - color.r / 2.0).abs() < 0.01);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
-    prelude::*, scalar, Blender, Color, ColorChannel, ColorFilter, CubicResampler, IPoint, IRect,
-    ISize, Image, ImageFilter, Matrix, Picture, Point3, Rect, Region, SamplingOptions, Shader,
-    TileMode, Vector,
+    interop, prelude::*, scalar, Blender, Color, ColorChannel, ColorFilter, CubicResampler, IPoint,
+    IRect, ISize, Image, ImageFilter, Matrix, Picture, Point3, Rect, Region, RuntimeEffectBuilder,
+    SamplingOptions, Shader, TileMode, Vector,
 };
 use skia_bindings::{self as sb, SkImageFilter, SkImageFilters_CropRect};
 
@@ -366,6 +366,44 @@ pub fn picture<'a>(
     })
 }
 
+/// Create a filter that fills the output with the per-pixel evaluation of the runtime effect of
+/// `builder`. The effect must be a shader. The result of `input`, or the source image if `input`
+/// is `None`, is bound to the child shader `child_shader_name`, which must not be set in
+/// `builder`.
+pub fn runtime_shader(
+    builder: &RuntimeEffectBuilder,
+    child_shader_name: impl AsRef<str>,
+    input: impl Into<Option<ImageFilter>>,
+) -> Option<ImageFilter> {
+    runtime_shader_with_inputs(builder, [(child_shader_name, input.into())])
+}
+
+/// Like [`runtime_shader()`], but binds the results of multiple `inputs` to the child shaders
+/// with the given names.
+pub fn runtime_shader_with_inputs(
+    builder: &RuntimeEffectBuilder,
+    inputs: impl IntoIterator<Item = (impl AsRef<str>, Option<ImageFilter>)>,
+) -> Option<ImageFilter> {
+    let (names, inputs): (Vec<interop::String>, Vec<*mut SkImageFilter>) = inputs
+        .into_iter()
+        .map(|(name, input)| (interop::String::from_str(name), input.into_ptr_or_null()))
+        .unzip();
+    let names = names.native();
+    let uniforms = builder.uniforms();
+    let children = builder.native_children();
+    ImageFilter::from_ptr(unsafe {
+        sb::C_SkImageFilters_RuntimeShader(
+            builder.effect().native(),
+            uniforms.native(),
+            children.as_ptr(),
+            children.len(),
+            names.as_ptr(),
+            inputs.as_ptr(),
+            inputs.len(),
+        )
+    })
+}
+
 pub use skia_bindings::SkImageFilters_Dither as Dither;
 variant_name!(Dither::Yes);
 
@@ -888,7 +926,10 @@ impl Picture {
 #[cfg(test)]
 mod tests {
     use super::CropRect;
-    use crate::{IRect, Rect};
+    use crate::{
+        AlphaType, Color, ColorType, IRect, ImageInfo, Paint, Rect, RuntimeEffect,
+        RuntimeEffectBuilder, Surface,
+    };
 
     fn cr(crop_rect: impl Into<CropRect>) -> CropRect {
         crop_rect.into()
@@ -922,4 +963,24 @@ mod tests {
         let cr_by_ref = cr(&rect);
         assert_eq!(cr_by_ref, CropRect(rect));
     }
+
+    #[test]
+    fn runtime_shader_filters_source() {
+        let effect = RuntimeEffect::make_for_shader(
+            "uniform shader src; half4 main(float2 p) { return src.eval(p).bgra; }",
+            None,
+        )
+        .unwrap();
+        let builder = RuntimeEffectBuilder::new(effect);
+        let filter = super::runtime_shader(&builder, "src", None).unwrap();
+
+        let mut surface = Surface::new_raster_n32_premul((1, 1)).unwrap();
+        let mut paint = Paint::default();
+        paint.set_color(Color::RED).set_image_filter(filter);
+        surface.canvas().draw_rect(Rect::from_wh(1.0, 1.0), &paint);
+        let mut pixel = [0u8; 4];
+        let info = ImageInfo::new((1, 1), ColorType::RGBA8888, AlphaType::Premul, None);
+        assert!(surface.read_pixels(&info, &mut pixel, 4, (0, 0)));
+        assert_eq!(pixel, [0, 0, 255, 255]);
+    }
 }
@@ -1,13 +1,18 @@
 use crate::{
     interop::{self, AsStr},
     prelude::*,
-    Blender, ColorFilter, Data, Matrix, Shader,
+    Blender, Color4f, ColorFilter, ColorSpace, Data, Matrix, Shader, M44,
 };
 use sb::{SkFlattenable, SkRuntimeEffect_Child};
 use skia_bindings::{
     self as sb, SkRefCntBase, SkRuntimeEffect, SkRuntimeEffect_Options, SkRuntimeEffect_Uniform,
 };
-use std::{fmt, marker::PhantomData, ops::DerefMut, ptr};
+use std::{
+    error, fmt,
+    marker::PhantomData,
+    ops::{DerefMut, Range},
+    ptr,
+};
 
 pub type Uniform = Handle<SkRuntimeEffect_Uniform>;
 unsafe_send_sync!(Uniform);
@@ -67,6 +72,28 @@ pub mod uniform {
     pub use sb::SkRuntimeEffect_Uniform_Type as Type;
     variant_name!(Type::Float2x2);
 
+    impl Type {
+        /// The number of `f32` or `i32` values of one element of this type.
+        pub const fn component_count(self) -> usize {
+            match self {
+                Type::Float | Type::Int => 1,
+                Type::Float2 | Type::Int2 => 2,
+                Type::Float3 | Type::Int3 => 3,
+                Type::Float4 | Type::Int4 | Type::Float2x2 => 4,
+                Type::Float3x3 => 9,
+                Type::Float4x4 => 16,
+            }
+        }
+
+        pub fn is_float(self) -> bool {
+            !self.is_int()
+        }
+
+        pub fn is_int(self) -> bool {
+            matches!(self, Type::Int | Type::Int2 | Type::Int3 | Type::Int4)
+        }
+    }
+
     bitflags! {
         #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
         pub struct Flags : u32 {
@@ -79,6 +106,103 @@ pub mod uniform {
     }
 }
 
+/// A Rust type with the memory layout of an SkSL uniform type, used by [`SkslUniforms`].
+///
+/// Vectors are `[f32; N]` or `[i32; N]` and matrices are column-major arrays of columns, like
+/// `[[f32; 3]; 3]` for a `float3x3`.
+///
+/// # Safety
+///
+/// The type must consist of exactly `TYPE.component_count()` `f32` or `i32` values, depending on
+/// `TYPE`.
+pub unsafe trait UniformValue {
+    const TYPE: uniform::Type;
+}
+
+macro_rules! uniform_values {
+    ($($t:ty => $ty:ident),* $(,)?) => {
+        $(unsafe impl UniformValue for $t {
+            const TYPE: uniform::Type = uniform::Type::$ty;
+        })*
+    };
+}
+
+uniform_values!(
+    f32 => Float,
+    [f32; 2] => Float2,
+    [f32; 3] => Float3,
+    [f32; 4] => Float4,
+    [[f32; 2]; 2] => Float2x2,
+    [[f32; 3]; 3] => Float3x3,
+    [[f32; 4]; 4] => Float4x4,
+    i32 => Int,
+    [i32; 2] => Int2,
+    [i32; 3] => Int3,
+    [i32; 4] => Int4,
+    crate::Point => Float2,
+    crate::V2 => Float2,
+    crate::Point3 => Float3,
+    crate::V3 => Float3,
+    crate::V4 => Float4,
+    crate::Color4f => Float4,
+    crate::M44 => Float4x4,
+    crate::IPoint => Int2,
+);
+
+/// The position of a uniform in a type that implements [`SkslUniforms`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct UniformField {
+    pub name: &'static str,
+    pub ty: uniform::Type,
+    /// The number of array elements, `1` for uniforms that are not arrays.
+    pub count: usize,
+    /// The offset in bytes.
+    pub offset: usize,
+}
+
+/// A struct that mirrors the uniforms of a runtime effect, so that they can be set with
+/// [`RuntimeEffectBuilder::set_uniforms()`] instead of packing them manually.
+///
+/// Enable the `derive` feature to implement it with `#[derive(SkslUniforms)]`:
+///
+/// ```ignore
+/// use skia_safe::{runtime_effect::SkslUniforms, Color4f};
+///
+/// #[derive(SkslUniforms)]
+/// #[repr(C)]
+/// struct Uniforms {
+///     offset: [f32; 2],
+///     #[sksl(name = "tint")]
+///     color: Color4f,
+///     #[sksl(array)]
+///     weights: [f32; 3],
+/// }
+/// ```
+///
+/// The derive macro checks at compile time that every field has a uniform type and that the
+/// struct contains no padding. The uniforms are matched by name when they are set, so the order
+/// of the fields does not need to match the SkSL declarations.
+///
+/// # Safety
+///
+/// `FIELDS` must describe every field of `Self`, and `Self` must not contain padding.
+pub unsafe trait SkslUniforms: Sized {
+    const FIELDS: &'static [UniformField];
+
+    /// The memory of `self`.
+    fn as_bytes(&self) -> &[u8] {
+        unsafe {
+            std::slice::from_raw_parts(
+                self as *const Self as *const u8,
+                std::mem::size_of::<Self>(),
+            )
+        }
+    }
+}
+
+#[cfg(feature = "derive")]
+pub use skia_safe_derive::SkslUniforms;
+
 pub use sb::SkRuntimeEffect_ChildType as ChildType;
 variant_name!(ChildType::Shader);
 
@@ -161,7 +285,8 @@ impl RuntimeEffect {
     pub fn make_for_color_filer<'a>(
         sksl: impl AsRef<str>,
         options: impl Into<Option<&'a Options>>,
-    ) -> Result<RuntimeEffect, String> {
+    ) -> Result<RuntimeEffect, CompileError> {
+        let sksl = sksl.as_ref();
         let str = interop::String::from_str(sksl);
         let options = options.into().copied().unwrap_or_default();
         let mut error = interop::String::default();
@@ -172,33 +297,35 @@ impl RuntimeEffect {
                 error.native_mut(),
             )
         })
-        .ok_or_else(|| error.to_string())
+        .ok_or_else(|| CompileError::parse(sksl, error.as_str()))
     }
 
     pub fn make_for_shader<'a>(
         sksl: impl AsRef<str>,
         options: impl Into<Option<&'a Options>>,
-    ) -> Result<RuntimeEffect, String> {
+    ) -> Result<RuntimeEffect, CompileError> {
+        let sksl = sksl.as_ref();
         let str = interop::String::from_str(sksl);
         let options = options.into().copied().unwrap_or_default();
         let mut error = interop::String::default();
         RuntimeEffect::from_ptr(unsafe {
             sb::C_SkRuntimeEffect_MakeForShader(str.native(), options.native(), error.native_mut())
         })
-        .ok_or_else(|| error.to_string())
+        .ok_or_else(|| CompileError::parse(sksl, error.as_str()))
     }
 
     pub fn make_for_blender<'a>(
         sksl: impl AsRef<str>,
         options: impl Into<Option<&'a Options>>,
-    ) -> Result<RuntimeEffect, String> {
+    ) -> Result<RuntimeEffect, CompileError> {
+        let sksl = sksl.as_ref();
         let str = interop::String::from_str(sksl);
         let options = options.into().copied().unwrap_or_default();
         let mut error = interop::String::default();
         RuntimeEffect::from_ptr(unsafe {
             sb::C_SkRuntimeEffect_MakeForBlender(str.native(), options.native(), error.native_mut())
         })
-        .ok_or_else(|| error.to_string())
+        .ok_or_else(|| CompileError::parse(sksl, error.as_str()))
     }
 
     pub fn make_shader<'a>(
@@ -417,23 +544,726 @@ impl ChildPtr {
     // Skia will copy the pointers and increase the reference counters if it uses the actual
     // objects.
     pub(self) fn native(&self) -> Borrows<sb::SkRuntimeEffect_ChildPtr> {
-        let flattenable: *mut SkFlattenable = match self {
+        native_child_ptr(Some(self)).borrows(self)
+    }
+
+    fn flattenable(&self) -> *mut SkFlattenable {
+        match self {
             // casting to &T &mut T is UB, so we don't use the base() indirection and directly cast
             // to a pointer.
             ChildPtr::Shader(shader) => unsafe { shader.native_mut_force() as _ },
             ChildPtr::ColorFilter(color_filter) => unsafe { color_filter.native_mut_force() as _ },
             ChildPtr::Blender(blender) => unsafe { blender.native_mut_force() as _ },
+        }
+    }
+}
+
+/// A borrowed `ChildPtr`, which is `null` for children that are not set.
+fn native_child_ptr(child: Option<&ChildPtr>) -> sb::SkRuntimeEffect_ChildPtr {
+    sb::SkRuntimeEffect_ChildPtr {
+        fChild: sb::sk_sp {
+            fPtr: child.map(ChildPtr::flattenable).unwrap_or(ptr::null_mut()),
+            _phantom_0: PhantomData,
+        },
+    }
+}
+
+/// The error returned when SkSL fails to compile in [`RuntimeEffect::make_for_shader()`],
+/// [`RuntimeEffect::make_for_color_filer()`], or [`RuntimeEffect::make_for_blender()`].
+///
+/// Skia reports compile errors as text only. The text is parsed into [`Diagnostic`]s that refer
+/// to positions in the SkSL source, so that tools can highlight the offending code.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CompileError {
+    message: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// The severity of a [`Diagnostic`]. The SkSL compiler currently only reports errors.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single message reported by the SkSL compiler.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// The position of the offending code, `None` if the error is not tied to a position (for
+    /// example a missing `main` function).
+    pub location: Option<SourceLocation>,
+}
+
+/// The position of a [`Diagnostic`] in the SkSL source.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SourceLocation {
+    /// The 1-based line number.
+    pub line: usize,
+    /// The 1-based column, counted in characters.
+    pub column: usize,
+    /// The byte range of the offending code in the SkSL source. If the compiler did not mark the
+    /// code, this covers the whole line.
+    pub span: Range<usize>,
+    /// The offending code.
+    pub text: String,
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message.trim_end())
+    }
+}
+
+impl error::Error for CompileError {}
+
+impl From<CompileError> for String {
+    fn from(error: CompileError) -> Self {
+        error.message
+    }
+}
+
+impl CompileError {
+    /// The unmodified error text reported by the SkSL compiler.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Parses compiler output of the form
+    ///
+    /// ```text
+    /// error: 2: unknown identifier 'foo'
+    /// half4 main(float2 p) { return foo; }
+    ///                               ^^^
+    /// 1 error
+    /// ```
+    ///
+    /// The source line and the caret line are optional.
+    fn parse(sksl: &str, message: &str) -> Self {
+        let lines: Vec<&str> = message.lines().collect();
+        let mut diagnostics = Vec::new();
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i];
+            i += 1;
+            let (severity, rest) = if let Some(rest) = line.strip_prefix("error: ") {
+                (Severity::Error, rest)
+            } else if let Some(rest) = line.strip_prefix("warning: ") {
+                (Severity::Warning, rest)
+            } else {
+                continue;
+            };
+            let (line_number, message) = match rest.split_once(": ") {
+                Some((n, message)) => match n.parse::<usize>() {
+                    Ok(n) => (Some(n), message),
+                    Err(_) => (None, rest),
+                },
+                None => (None, rest),
+            };
+            let location = line_number.and_then(|line_number| {
+                let is_caret_line = |l: &&str| {
+                    let l = l.trim_start();
+                    !l.is_empty() && l.chars().all(|c| c == '^')
+                };
+                match (
+                    lines.get(i),
+                    lines.get(i + 1).copied().filter(is_caret_line),
+                ) {
+                    (Some(printed), Some(carets)) => {
+                        i += 2;
+                        SourceLocation::from_carets(sksl, line_number, printed, carets)
+                    }
+                    _ => None,
+                }
+                .or_else(|| SourceLocation::from_line(sksl, line_number))
+            });
+            diagnostics.push(Diagnostic {
+                severity,
+                message: message.into(),
+                location,
+            });
+        }
+        CompileError {
+            message: message.into(),
+            diagnostics,
+        }
+    }
+}
+
+impl SourceLocation {
+    fn from_line(sksl: &str, line: usize) -> Option<Self> {
+        let span = line_span(sksl, line)?;
+        Some(Self::new(sksl, line, span))
+    }
+
+    /// Locates the code marked by `carets` below the `printed` source line. Long lines are
+    /// printed shortened and prefixed with `...`.
+    fn from_carets(sksl: &str, line: usize, printed: &str, carets: &str) -> Option<Self> {
+        let line_span = line_span(sksl, line)?;
+        let source_line = &sksl[line_span.clone()];
+        let (prefix, printed) = match printed.strip_prefix("...") {
+            Some(printed) => (3, printed),
+            None => (0, printed),
         };
+        let printed = printed.strip_suffix("...").unwrap_or(printed);
+        let printed_start = line_span.start + source_line.find(printed)?;
+        let start = printed_start + carets.find('^')?.checked_sub(prefix)?;
+        let end = (start + carets.trim().len()).min(line_span.end);
+        if start > end || !sksl.is_char_boundary(start) || !sksl.is_char_boundary(end) {
+            return None;
+        }
+        Some(Self::new(sksl, line, start..end))
+    }
+
+    fn new(sksl: &str, line: usize, span: Range<usize>) -> Self {
+        let line_start = sksl[..span.start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        Self {
+            line,
+            column: sksl[line_start..span.start].chars().count() + 1,
+            text: sksl[span.clone()].into(),
+            span,
+        }
+    }
+}
+
+/// The byte range of the 1-based `line` in `sksl`, excluding the line break.
+fn line_span(sksl: &str, line: usize) -> Option<Range<usize>> {
+    let start = if line == 1 {
+        0
+    } else {
+        sksl.match_indices('\n').nth(line.checked_sub(2)?)?.0 + 1
+    };
+    let end = sksl[start..]
+        .find('\n')
+        .map(|i| start + i)
+        .unwrap_or(sksl.len());
+    Some(start..end)
+}
+
+/// The error that is returned when a child of a [`RuntimeEffectBuilder`] can not be set.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ChildError {
+    /// The effect has no child with this name.
+    NotFound(String),
+    /// The child is declared with a different type, for example a `shader` child was set to a
+    /// [`ColorFilter`].
+    TypeMismatch { name: String, ty: ChildType },
+}
+
+impl fmt::Display for ChildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChildError::NotFound(name) => write!(f, "child '{name}' not found"),
+            ChildError::TypeMismatch { name, ty } => {
+                write!(f, "child '{name}' must be a {ty:?}")
+            }
+        }
+    }
+}
+
+impl error::Error for ChildError {}
+
+/// The error that is returned when a uniform of a [`RuntimeEffectBuilder`] can not be set.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum UniformError {
+    /// The effect has no uniform with this name.
+    NotFound(String),
+    /// The uniform has a type that can not be set with the value.
+    TypeMismatch { name: String, ty: uniform::Type },
+    /// The number of values does not match the size of the uniform. `expected` is the number of
+    /// `f32` or `i32` values the uniform consists of.
+    SizeMismatch {
+        name: String,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl fmt::Display for UniformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UniformError::NotFound(name) => write!(f, "uniform '{name}' not found"),
+            UniformError::TypeMismatch { name, ty } => {
+                write!(
+                    f,
+                    "uniform '{name}' of type {ty:?} can not be set to this value"
+                )
+            }
+            UniformError::SizeMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "uniform '{name}' expects {expected} values, but {actual} were provided"
+            ),
+        }
+    }
+}
+
+impl error::Error for UniformError {}
 
-        sb::SkRuntimeEffect_ChildPtr {
-            fChild: sb::sk_sp {
-                fPtr: flattenable,
-                _phantom_0: PhantomData,
-            },
+/// Collects the uniforms and children of a [`RuntimeEffect`] by name
+/// (`SkRuntimeEffectBuilder`, `SkRuntimeShaderBuilder`, `SkRuntimeColorFilterBuilder`, and
+/// `SkRuntimeBlendBuilder`).
+///
+/// The setters check the type and size of each uniform and write the values at the uniform's
+/// offset, so uniform blocks don't need to be packed manually. Uniforms that are not set are
+/// zero, children that are not set are `null` and evaluate to transparent black.
+///
+/// ```no_run
+/// # use skia_safe::{RuntimeEffect, RuntimeEffectBuilder, Shader, Color};
+/// let effect = RuntimeEffect::make_for_shader(
+///     "uniform shader image; uniform float amount;
+///      half4 main(float2 p) { return image.eval(p + float2(amount, 0)); }",
+///     None,
+/// )
+/// .unwrap();
+/// let mut builder = RuntimeEffectBuilder::new(effect);
+/// builder.set_uniform_float("amount", 4.0).unwrap();
+/// builder.set_child("image", Shader::color(Color::RED)).unwrap();
+/// let shader = builder.make_shader(None).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct RuntimeEffectBuilder {
+    effect: RuntimeEffect,
+    uniforms: Vec<u8>,
+    children: Vec<Option<ChildPtr>>,
+}
+
+impl RuntimeEffectBuilder {
+    pub fn new(effect: RuntimeEffect) -> Self {
+        let uniforms = vec![0; effect.uniform_size()];
+        let children = vec![None; effect.children().len()];
+        Self {
+            effect,
+            uniforms,
+            children,
+        }
+    }
+
+    pub fn effect(&self) -> &RuntimeEffect {
+        &self.effect
+    }
+
+    /// The uniform block, to be passed to [`RuntimeEffect::make_shader()`],
+    /// [`RuntimeEffect::make_color_filter()`], or [`RuntimeEffect::make_blender()`].
+    pub fn uniforms(&self) -> Data {
+        Data::new_copy(&self.uniforms)
+    }
+
+    /// Sets a `float` uniform.
+    pub fn set_uniform_float(
+        &mut self,
+        name: impl AsRef<str>,
+        value: f32,
+    ) -> Result<(), UniformError> {
+        self.set_uniform_float_array(name, &[value])
+    }
+
+    /// Sets a uniform of a float type (`float`, `float2` ... `float4x4`, or `half` variants,
+    /// and arrays of them). Matrices are expected in column-major order.
+    pub fn set_uniform_float_array(
+        &mut self,
+        name: impl AsRef<str>,
+        values: &[f32],
+    ) -> Result<(), UniformError> {
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_ne_bytes()).collect();
+        self.set_uniform_bytes(name.as_ref(), uniform::Type::is_float, values.len(), &bytes)
+    }
+
+    /// Sets an `int` uniform.
+    pub fn set_uniform_int(
+        &mut self,
+        name: impl AsRef<str>,
+        value: i32,
+    ) -> Result<(), UniformError> {
+        self.set_uniform_int_array(name, &[value])
+    }
+
+    /// Sets a uniform of an integer type (`int`, `int2` ... `int4`, and arrays of them).
+    pub fn set_uniform_int_array(
+        &mut self,
+        name: impl AsRef<str>,
+        values: &[i32],
+    ) -> Result<(), UniformError> {
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_ne_bytes()).collect();
+        self.set_uniform_bytes(name.as_ref(), uniform::Type::is_int, values.len(), &bytes)
+    }
+
+    /// Sets a `float3x3` uniform.
+    pub fn set_uniform_matrix(
+        &mut self,
+        name: impl AsRef<str>,
+        matrix: &Matrix,
+    ) -> Result<(), UniformError> {
+        let m = matrix;
+        #[rustfmt::skip]
+        let column_major = [
+            m[0], m[3], m[6],
+            m[1], m[4], m[7],
+            m[2], m[5], m[8],
+        ];
+        self.set_uniform_float_array(name, &column_major)
+    }
+
+    /// Sets a `float4x4` uniform.
+    pub fn set_uniform_m44(&mut self, name: impl AsRef<str>, m: &M44) -> Result<(), UniformError> {
+        let mut column_major = [0.0; 16];
+        m.get_col_major(&mut column_major);
+        self.set_uniform_float_array(name, &column_major)
+    }
+
+    /// Sets a `float3` or `float4` uniform to an unpremultiplied color in `color_space`, `None`
+    /// for sRGB.
+    ///
+    /// The color is converted to sRGB, which is the color space Skia expects for uniforms that
+    /// are declared with `layout(color)`, and which it converts from sRGB to the color space of
+    /// the destination.
+    pub fn set_uniform_color(
+        &mut self,
+        name: impl AsRef<str>,
+        color: impl Into<Color4f>,
+        color_space: Option<&ColorSpace>,
+    ) -> Result<(), UniformError> {
+        let mut color = color.into();
+        if let Some(color_space) = color_space {
+            let srgb = ColorSpace::new_srgb();
+            unsafe {
+                sb::C_SkRuntimeEffect_transformColor(
+                    color_space.native(),
+                    srgb.native(),
+                    color.native_mut(),
+                )
+            }
         }
-        .borrows(self)
+        let name = name.as_ref();
+        match self.effect.find_uniform(name).map(|u| u.ty()) {
+            Some(uniform::Type::Float3) => {
+                self.set_uniform_float_array(name, &[color.r, color.g, color.b])
+            }
+            Some(uniform::Type::Float4) => {
+                self.set_uniform_float_array(name, &[color.r, color.g, color.b, color.a])
+            }
+            Some(ty) => Err(UniformError::TypeMismatch {
+                name: name.into(),
+                ty,
+            }),
+            None => Err(UniformError::NotFound(name.into())),
+        }
+    }
+
+    /// Sets the child `name` to a shader, color filter, or blender, which must match the type it
+    /// is declared with.
+    pub fn set_child(
+        &mut self,
+        name: impl AsRef<str>,
+        child: impl Into<ChildPtr>,
+    ) -> Result<(), ChildError> {
+        let name = name.as_ref();
+        let (index, ty) = self.find_child_index(name)?;
+        let child = child.into();
+        if child.ty() != ty {
+            return Err(ChildError::TypeMismatch {
+                name: name.into(),
+                ty,
+            });
+        }
+        self.children[index] = Some(child);
+        Ok(())
+    }
+
+    /// Resets the child `name` so that it is passed as `null` to the effect.
+    pub fn clear_child(&mut self, name: impl AsRef<str>) -> Result<(), ChildError> {
+        let (index, _) = self.find_child_index(name.as_ref())?;
+        self.children[index] = None;
+        Ok(())
+    }
+
+    fn find_child_index(&self, name: &str) -> Result<(usize, ChildType), ChildError> {
+        let declared = self
+            .effect
+            .find_child(name)
+            .ok_or_else(|| ChildError::NotFound(name.into()))?;
+        Ok((declared.index(), declared.ty()))
+    }
+
+    pub fn child(&self, name: impl AsRef<str>) -> Option<&ChildPtr> {
+        let index = self.effect.find_child(name)?.index();
+        self.children[index].as_ref()
+    }
+
+    /// Sets all uniforms of `uniforms` by name.
+    pub fn set_uniforms<U: SkslUniforms>(&mut self, uniforms: &U) -> Result<(), UniformError> {
+        let bytes = uniforms.as_bytes();
+        for field in U::FIELDS {
+            let count = field.ty.component_count() * field.count;
+            self.set_uniform_bytes(
+                field.name,
+                |ty| ty == field.ty,
+                count,
+                &bytes[field.offset..field.offset + count * 4],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn make_shader<'a>(&self, local_matrix: impl Into<Option<&'a Matrix>>) -> Option<Shader> {
+        let mut children = self.native_children();
+        Shader::from_ptr(unsafe {
+            sb::C_SkRuntimeEffect_makeShader(
+                self.effect.native(),
+                self.uniforms().into_ptr(),
+                children.as_mut_ptr(),
+                children.len(),
+                local_matrix.into().native_ptr_or_null(),
+            )
+        })
+    }
+
+    pub fn make_color_filter(&self) -> Option<ColorFilter> {
+        let mut children = self.native_children();
+        ColorFilter::from_ptr(unsafe {
+            sb::C_SkRuntimeEffect_makeColorFilter(
+                self.effect.native(),
+                self.uniforms().into_ptr(),
+                children.as_mut_ptr(),
+                children.len(),
+            )
+        })
+    }
+
+    pub fn make_blender(&self) -> Option<Blender> {
+        let mut children = self.native_children();
+        Blender::from_ptr(unsafe {
+            sb::C_SkRuntimeEffect_makeBlender(
+                self.effect.native(),
+                self.uniforms().into_ptr(),
+                children.as_mut_ptr(),
+                children.len(),
+            )
+        })
+    }
+
+    // The returned `ChildPtr`s borrow the children of `self`.
+    pub(crate) fn native_children(&self) -> Vec<sb::SkRuntimeEffect_ChildPtr> {
+        self.children
+            .iter()
+            .map(|child| native_child_ptr(child.as_ref()))
+            .collect()
+    }
+
+    fn set_uniform_bytes(
+        &mut self,
+        name: &str,
+        accepts: impl Fn(uniform::Type) -> bool,
+        count: usize,
+        bytes: &[u8],
+    ) -> Result<(), UniformError> {
+        let uniform = self
+            .effect
+            .find_uniform(name)
+            .ok_or_else(|| UniformError::NotFound(name.into()))?;
+        let ty = uniform.ty();
+        if !accepts(ty) {
+            return Err(UniformError::TypeMismatch {
+                name: name.into(),
+                ty,
+            });
+        }
+        if bytes.len() != uniform.size_in_bytes() {
+            return Err(UniformError::SizeMismatch {
+                name: name.into(),
+                expected: ty.component_count() * usize::try_from(uniform.count()).unwrap(),
+                actual: count,
+            });
+        }
+        let offset = uniform.offset();
+        self.uniforms[offset..offset + bytes.len()].copy_from_slice(bytes);
+        Ok(())
     }
 }
 
-// TODO: wrap SkRuntimeEffectBuilder, SkRuntimeShaderBuilder, SkRuntimeColorFilterBuilder,
-// SkRuntimeBlendBuilder
+#[cfg(test)]
+mod tests {
+    use super::{
+        ChildError, ChildType, CompileError, RuntimeEffect, RuntimeEffectBuilder, Severity,
+        UniformError,
+    };
+    use crate::{
+        color_filters, AlphaType, Color, Color4f, ColorSpace, ColorType, ImageInfo, Matrix, Paint,
+        Shader, Surface,
+    };
+
+    const SKSL: &str = r#"
+        uniform float2 offset;
+        uniform int count[2];
+        uniform float3x3 transform;
+        layout(color) uniform half4 tint;
+
+        half4 main(float2 p) {
+            return tint;
+        }
+    "#;
+
+    #[test]
+    fn set_uniforms_by_name() {
+        let effect = RuntimeEffect::make_for_shader(SKSL, None).unwrap();
+        let mut builder = RuntimeEffectBuilder::new(effect);
+        builder
+            .set_uniform_float_array("offset", &[1.0, 2.0])
+            .unwrap();
+        builder.set_uniform_int_array("count", &[3, 4]).unwrap();
+        builder
+            .set_uniform_matrix("transform", &Matrix::translate((5.0, 6.0)))
+            .unwrap();
+        builder
+            .set_uniform_color("tint", Color::BLUE, None)
+            .unwrap();
+
+        let offset = builder.effect().find_uniform("transform").unwrap().offset();
+        let translate_x = &builder.uniforms().as_bytes()[offset + 6 * 4..offset + 7 * 4];
+        assert_eq!(translate_x, 5.0f32.to_ne_bytes());
+
+        let shader = builder
+            .effect()
+            .make_shader(builder.uniforms(), &[], None)
+            .unwrap();
+        let mut surface = Surface::new_raster_n32_premul((1, 1)).unwrap();
+        surface
+            .canvas()
+            .draw_paint(Paint::default().set_shader(shader));
+        let mut pixel = [0u8; 4];
+        let info = ImageInfo::new((1, 1), ColorType::RGBA8888, AlphaType::Premul, None);
+        assert!(surface.read_pixels(&info, &mut pixel, 4, (0, 0)));
+        assert_eq!(pixel, [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn validate_uniforms() {
+        let effect = RuntimeEffect::make_for_shader(SKSL, None).unwrap();
+        let mut builder = RuntimeEffectBuilder::new(effect);
+        assert_eq!(
+            builder.set_uniform_float("missing", 1.0),
+            Err(UniformError::NotFound("missing".into()))
+        );
+        assert!(matches!(
+            builder.set_uniform_float("count", 1.0),
+            Err(UniformError::TypeMismatch { .. })
+        ));
+        assert_eq!(
+            builder.set_uniform_int_array("count", &[1]),
+            Err(UniformError::SizeMismatch {
+                name: "count".into(),
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert!(builder
+            .set_uniform_color(
+                "tint",
+                Color4f::new(0.5, 0.0, 1.0, 0.5),
+                Some(&ColorSpace::new_srgb_linear())
+            )
+            .is_ok());
+
+        // Linear 0.5 is about 0.735 in sRGB, alpha is left alone.
+        let offset = builder.effect().find_uniform("tint").unwrap().offset();
+        let tint: Vec<f32> = builder.uniforms().as_bytes()[offset..offset + 16]
+            .chunks(4)
+            .map(|b| f32::from_ne_bytes(b.try_into().unwrap()))
+            .collect();
+        assert!((tint[0] - 0.735).abs() < 0.01);
+        assert!(tint[1].abs() < 0.001);
+        assert!((tint[2] - 1.0).abs() < 0.001);
+        assert_eq!(tint[3], 0.5);
+    }
+
+    #[test]
+    fn bind_children_by_name() {
+        let effect = RuntimeEffect::make_for_shader(
+            "uniform shader a; uniform shader b; uniform colorFilter filter;
+             half4 main(float2 p) { return filter.eval(a.eval(p) + b.eval(p)); }",
+            None,
+        )
+        .unwrap();
+        let mut builder = RuntimeEffectBuilder::new(effect);
+        builder.set_child("a", Shader::color(Color::RED)).unwrap();
+        builder.set_child("b", Shader::color(Color::BLUE)).unwrap();
+        assert_eq!(
+            builder.set_child("missing", Shader::color(Color::RED)),
+            Err(ChildError::NotFound("missing".into()))
+        );
+        let filter = color_filters::linear_to_srgb();
+        assert_eq!(
+            builder.set_child("b", filter.clone()),
+            Err(ChildError::TypeMismatch {
+                name: "b".into(),
+                ty: ChildType::Shader
+            })
+        );
+        builder.set_child("filter", filter).unwrap();
+        assert!(builder.child("a").is_some());
+        builder.clear_child("a").unwrap();
+        assert!(builder.child("a").is_none());
+        assert_eq!(
+            builder.clear_child("missing"),
+            Err(ChildError::NotFound("missing".into()))
+        );
+        builder.set_child("a", Shader::color(Color::RED)).unwrap();
+
+        let mut surface = Surface::new_raster_n32_premul((1, 1)).unwrap();
+        surface
+            .canvas()
+            .draw_paint(Paint::default().set_shader(builder.make_shader(None).unwrap()));
+        let mut pixel = [0u8; 4];
+        let info = ImageInfo::new((1, 1), ColorType::RGBA8888, AlphaType::Premul, None);
+        assert!(surface.read_pixels(&info, &mut pixel, 4, (0, 0)));
+        assert_eq!(pixel, [255, 0, 255, 255]);
+    }
+
+    #[test]
+    fn parse_compile_errors() {
+        let sksl = "uniform float x;\nhalf4 main(float2 p) { return foo + bar; }\n";
+        let error = CompileError::parse(
+            sksl,
+            "error: 2: unknown identifier 'foo'\n\
+             half4 main(float2 p) { return foo + bar; }\n\
+             \x20                             ^^^\n\
+             error: 2: unknown identifier 'bar'\n\
+             error: function 'main' is missing\n\
+             3 errors\n",
+        );
+        let diagnostics = error.diagnostics();
+        assert_eq!(diagnostics.len(), 3);
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+        assert_eq!(diagnostics[0].message, "unknown identifier 'foo'");
+
+        let location = diagnostics[0].location.as_ref().unwrap();
+        assert_eq!((location.line, location.column), (2, 31));
+        assert_eq!(location.text, "foo");
+        assert_eq!(&sksl[location.span.clone()], "foo");
+
+        let location = diagnostics[1].location.as_ref().unwrap();
+        assert_eq!((location.line, location.column), (2, 1));
+        assert_eq!(location.text, "half4 main(float2 p) { return foo + bar; }");
+
+        assert_eq!(diagnostics[2].message, "function 'main' is missing");
+        assert!(diagnostics[2].location.is_none());
+    }
+
+    #[test]
+    fn report_compile_errors() {
+        let error = RuntimeEffect::make_for_shader("\nhalf4 main(float2 p) { return foo; }", None)
+            .unwrap_err();
+        assert!(error.to_string().contains("foo"));
+        let diagnostic = &error.diagnostics()[0];
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.location.as_ref().unwrap().line, 2);
+    }
+}
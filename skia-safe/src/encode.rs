@@ -0,0 +1,24 @@
+//! Encoding of pixels into image file formats.
+//!
+//! The format specific encoders in `jpeg_encoder`, `png_encoder`, and `webp_encoder` (feature
+//! `webp-encode`) expose all the options Skia supports and can write to any [`std::io::Write`].
+
+use crate::{prelude::*, Bitmap, Data, EncodedImageFormat, Pixmap};
+use skia_bindings as sb;
+
+pub mod jpeg_encoder;
+pub mod png_encoder;
+#[cfg(feature = "webp-encode")]
+pub mod webp_encoder;
+
+pub fn pixmap(src: &Pixmap, format: EncodedImageFormat, quality: usize) -> Option<Data> {
+    Data::from_ptr(unsafe {
+        sb::C_SkEncodePixmap(src.native(), format, quality.try_into().unwrap())
+    })
+}
+
+pub fn bitmap(src: &Bitmap, format: EncodedImageFormat, quality: usize) -> Option<Data> {
+    Data::from_ptr(unsafe {
+        sb::C_SkEncodeBitmap(src.native(), format, quality.try_into().unwrap())
+    })
+}
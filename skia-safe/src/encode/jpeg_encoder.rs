@@ -0,0 +1,111 @@
+use crate::{
+    interop::{DynamicMemoryWStream, RustWStream},
+    prelude::*,
+    Data, Pixmap,
+};
+use skia_bindings::{self as sb, SkJpegEncoder_AlphaOption, SkJpegEncoder_Downsample, SkWStream};
+use std::io;
+
+/// The chroma subsampling of the encoded image.
+#[repr(i32)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub enum Downsample {
+    /// Reduction by a factor of two in both the horizontal and vertical directions (4:2:0).
+    #[default]
+    BothDirections = SkJpegEncoder_Downsample::k420 as _,
+    /// Reduction by a factor of two in the horizontal direction (4:2:2).
+    Horizontal = SkJpegEncoder_Downsample::k422 as _,
+    /// No downsampling (4:4:4).
+    No = SkJpegEncoder_Downsample::k444 as _,
+}
+
+native_transmutable!(SkJpegEncoder_Downsample, Downsample, downsample_layout);
+
+/// JPEGs must be opaque, this defines how transparent pixels are encoded.
+#[repr(i32)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub enum AlphaOption {
+    /// Ignores the alpha channel.
+    #[default]
+    Ignore = SkJpegEncoder_AlphaOption::kIgnore as _,
+    /// Blends the pixels onto a black background.
+    BlendOnBlack = SkJpegEncoder_AlphaOption::kBlendOnBlack as _,
+}
+
+native_transmutable!(SkJpegEncoder_AlphaOption, AlphaOption, alpha_option_layout);
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Options {
+    /// The quality of the encoded image, in the range `0..=100`.
+    pub quality: u32,
+    pub downsample: Downsample,
+    pub alpha_option: AlphaOption,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            quality: 100,
+            downsample: Downsample::default(),
+            alpha_option: AlphaOption::default(),
+        }
+    }
+}
+
+/// Encodes `pixmap` as a JPEG and writes it to `writer`. The color space of `pixmap` is
+/// embedded as an ICC profile.
+///
+/// Returns `false` if the pixmap can not be encoded or writing failed.
+pub fn encode(pixmap: &Pixmap, writer: &mut impl io::Write, options: &Options) -> bool {
+    let mut stream = RustWStream::new(writer);
+    encode_to_stream(pixmap, stream.stream_mut(), options)
+}
+
+/// Encodes `pixmap` as a JPEG.
+pub fn encode_to_data(pixmap: &Pixmap, options: &Options) -> Option<Data> {
+    let mut stream = DynamicMemoryWStream::new();
+    encode_to_stream(pixmap, stream.native_mut().base_mut(), options)
+        .if_true_then_some(|| stream.detach_as_data())
+}
+
+fn encode_to_stream(pixmap: &Pixmap, stream: &mut SkWStream, options: &Options) -> bool {
+    unsafe {
+        sb::C_SkJpegEncoder_Encode(
+            stream,
+            pixmap.native(),
+            options.quality.min(100) as _,
+            options.downsample.into_native(),
+            options.alpha_option.into_native(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode, encode_to_data, Options};
+    use crate::{codec, AlphaType, Codec, ColorType, Data, EncodedImageFormat, ImageInfo, Pixmap};
+
+    #[test]
+    fn encode_and_decode() {
+        let info = ImageInfo::new((16, 16), ColorType::RGBA8888, AlphaType::Opaque, None);
+        let pixels = [200u8, 100, 50, 255].repeat(16 * 16);
+        let pixmap = Pixmap::new(&info, &pixels, info.min_row_bytes());
+
+        let mut jpeg = Vec::new();
+        assert!(encode(&pixmap, &mut jpeg, &Options::default()));
+        let data = encode_to_data(&pixmap, &Options::default()).unwrap();
+        assert_eq!(data.as_bytes(), &jpeg[..]);
+
+        let mut codec = Codec::from_data(Data::new_copy(&jpeg)).unwrap();
+        assert_eq!(codec.encoded_format(), EncodedImageFormat::JPEG);
+        assert_eq!(codec.dimensions(), info.dimensions());
+        let mut decoded = vec![0u8; pixels.len()];
+        let result = codec.get_pixels_with_options(&info, &mut decoded, info.min_row_bytes(), None);
+        assert_eq!(result, codec::Result::Success);
+        // JPEG is lossy, but a single color survives almost unchanged.
+        assert!(decoded
+            .iter()
+            .zip(&pixels)
+            .all(|(decoded, pixel)| decoded.abs_diff(*pixel) <= 4));
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    cicp::{self, Cicp},
+    interop::{DynamicMemoryWStream, RustWStream},
+    prelude::*,
+    Data, Pixmap,
+};
+use skia_bindings::{self as sb, SkWStream};
+use std::{
+    ffi::{self, CString},
+    io, ptr,
+};
+
+bitflags! {
+    /// The row filters libpng may choose from. If a single filter is selected, it is used for
+    /// every row, otherwise libpng picks the filter per row.
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct FilterFlag: u32 {
+        const ZERO = 0x00;
+        const NONE = 0x08;
+        const SUB = 0x10;
+        const UP = 0x20;
+        const AVG = 0x40;
+        const PAETH = 0x80;
+        const ALL = Self::NONE.bits()
+            | Self::SUB.bits()
+            | Self::UP.bits()
+            | Self::AVG.bits()
+            | Self::PAETH.bits();
+    }
+}
+
+impl Default for FilterFlag {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// A comment that is stored in a `tEXt` chunk.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Comment {
+    pub keyword: String,
+    pub text: String,
+}
+
+impl Comment {
+    pub fn new(keyword: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            keyword: keyword.into(),
+            text: text.into(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Options {
+    pub filter_flags: FilterFlag,
+    /// The zlib compression level, in the range `0..=9`. `0` is no compression and `9` is the
+    /// best, but slowest compression.
+    pub z_lib_level: i32,
+    pub comments: Vec<Comment>,
+    /// The color description that is stored in a `cICP` chunk, in addition to the ICC profile
+    /// of the pixmap's color space.
+    pub cicp: Option<Cicp>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            filter_flags: FilterFlag::default(),
+            z_lib_level: 6,
+            comments: Vec::new(),
+            cicp: None,
+        }
+    }
+}
+
+/// Encodes `pixmap` as a PNG and writes it to `writer`. The color space of `pixmap` is
+/// embedded as an ICC profile.
+///
+/// Returns `false` if the pixmap can not be encoded, a comment contains a `NUL` character, or
+/// writing failed.
+pub fn encode(pixmap: &Pixmap, writer: &mut impl io::Write, options: &Options) -> bool {
+    if options.cicp.is_some() {
+        // The `cICP` chunk is inserted after encoding.
+        return match encode_to_data(pixmap, options) {
+            Some(png) => writer.write_all(png.as_bytes()).is_ok(),
+            None => false,
+        };
+    }
+    let mut stream = RustWStream::new(writer);
+    encode_to_stream(pixmap, stream.stream_mut(), options)
+}
+
+/// Encodes `pixmap` as a PNG.
+pub fn encode_to_data(pixmap: &Pixmap, options: &Options) -> Option<Data> {
+    let mut stream = DynamicMemoryWStream::new();
+    let png = encode_to_stream(pixmap, stream.native_mut().base_mut(), options)
+        .if_true_then_some(|| stream.detach_as_data())?;
+    match &options.cicp {
+        Some(cicp) => Some(Data::new_copy(&cicp::with_png_chunk(png.as_bytes(), cicp)?)),
+        None => Some(png),
+    }
+}
+
+fn encode_to_stream(pixmap: &Pixmap, stream: &mut SkWStream, options: &Options) -> bool {
+    // Skia expects the keywords and texts interleaved and `NUL` terminated.
+    let comments: Option<Vec<CString>> = options
+        .comments
+        .iter()
+        .flat_map(|comment| [&comment.keyword, &comment.text])
+        .map(|str| CString::new(str.as_str()).ok())
+        .collect();
+    let comments = match comments {
+        Some(comments) => comments,
+        None => return false,
+    };
+    let comment_ptrs: Vec<*const ffi::c_void> =
+        comments.iter().map(|str| str.as_ptr() as _).collect();
+    let comment_sizes: Vec<usize> = comments
+        .iter()
+        .map(|str| str.as_bytes_with_nul().len())
+        .collect();
+    let comment_count = match comments.len().try_into() {
+        Ok(count) => count,
+        Err(_) => return false,
+    };
+
+    unsafe {
+        sb::C_SkPngEncoder_Encode(
+            stream,
+            pixmap.native(),
+            options.filter_flags.bits() as _,
+            options.z_lib_level.clamp(0, 9),
+            if comments.is_empty() {
+                ptr::null()
+            } else {
+                comment_ptrs.as_ptr()
+            },
+            comment_sizes.as_ptr(),
+            comment_count,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode, Comment, Options};
+    use crate::{ImageInfo, Pixmap};
+
+    #[test]
+    fn encode_with_comments() {
+        let info = ImageInfo::new_n32_premul((2, 2), None);
+        let pixels = [0xffu8; 16];
+        let pixmap = Pixmap::new(&info, &pixels, 8);
+        let options = Options {
+            comments: vec![Comment::new("Software", "rust-skia")],
+            ..Options::default()
+        };
+        let mut png = Vec::new();
+        assert!(encode(&pixmap, &mut png, &options));
+        assert!(png.starts_with(b"\x89PNG"));
+        assert!(png.windows(9).any(|w| w == b"rust-skia"));
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{
+    interop::{DynamicMemoryWStream, RustWStream},
+    prelude::*,
+    Data, Pixmap,
+};
+use skia_bindings::{self as sb, SkWStream, SkWebpEncoder_Compression};
+use std::io;
+
+#[repr(i32)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub enum Compression {
+    #[default]
+    Lossy = SkWebpEncoder_Compression::kLossy as _,
+    Lossless = SkWebpEncoder_Compression::kLossless as _,
+}
+
+native_transmutable!(SkWebpEncoder_Compression, Compression, compression_layout);
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Options {
+    pub compression: Compression,
+    /// With [`Compression::Lossy`], the quality of the encoded image in the range `0.0..=100.0`.
+    ///
+    /// With [`Compression::Lossless`], the effort spent to compress the image, where `0.0` is
+    /// the fastest and `100.0` the smallest result.
+    pub quality: f32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            compression: Compression::default(),
+            quality: 100.0,
+        }
+    }
+}
+
+/// Encodes `pixmap` as a WebP and writes it to `writer`. The color space of `pixmap` is
+/// embedded as an ICC profile.
+///
+/// Returns `false` if the pixmap can not be encoded or writing failed.
+pub fn encode(pixmap: &Pixmap, writer: &mut impl io::Write, options: &Options) -> bool {
+    let mut stream = RustWStream::new(writer);
+    encode_to_stream(pixmap, stream.stream_mut(), options)
+}
+
+/// Encodes `pixmap` as a WebP.
+pub fn encode_to_data(pixmap: &Pixmap, options: &Options) -> Option<Data> {
+    let mut stream = DynamicMemoryWStream::new();
+    encode_to_stream(pixmap, stream.native_mut().base_mut(), options)
+        .if_true_then_some(|| stream.detach_as_data())
+}
+
+fn encode_to_stream(pixmap: &Pixmap, stream: &mut SkWStream, options: &Options) -> bool {
+    unsafe {
+        sb::C_SkWebpEncoder_Encode(
+            stream,
+            pixmap.native(),
+            options.compression.into_native(),
+            options.quality.clamp(0.0, 100.0),
+        )
+    }
+}
+
+#[cfg(all(test, feature = "webp-decode"))]
+mod tests {
+    use super::{encode, encode_to_data, Compression, Options};
+    use crate::{codec, AlphaType, Codec, ColorType, Data, EncodedImageFormat, ImageInfo, Pixmap};
+
+    #[test]
+    fn encode_and_decode_lossless() {
+        let info = ImageInfo::new((16, 16), ColorType::RGBA8888, AlphaType::Opaque, None);
+        // Lossless WebP may change the color of fully transparent pixels, so all are opaque.
+        let pixels: Vec<u8> = (0..16 * 16 * 4)
+            .map(|i| if i % 4 == 3 { 255 } else { (i * 7 % 256) as u8 })
+            .collect();
+        let pixmap = Pixmap::new(&info, &pixels, info.min_row_bytes());
+        let options = Options {
+            compression: Compression::Lossless,
+            ..Options::default()
+        };
+
+        let mut webp = Vec::new();
+        assert!(encode(&pixmap, &mut webp, &options));
+        let data = encode_to_data(&pixmap, &options).unwrap();
+        assert_eq!(data.as_bytes(), &webp[..]);
+
+        let mut codec = Codec::from_data(Data::new_copy(&webp)).unwrap();
+        assert_eq!(codec.encoded_format(), EncodedImageFormat::WEBP);
+        assert_eq!(codec.dimensions(), info.dimensions());
+        let mut decoded = vec![0u8; pixels.len()];
+        let result = codec.get_pixels_with_options(&info, &mut decoded, info.min_row_bytes(), None);
+        assert_eq!(result, codec::Result::Success);
+        assert_eq!(decoded, pixels);
+    }
+}
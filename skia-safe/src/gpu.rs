@@ -1,9 +1,13 @@
 mod backend_drawable_info;
+mod backend_semaphore;
 mod backend_surface;
 mod backend_surface_mutable_state;
 pub mod context_options;
+mod context_thread_safe_proxy;
 #[cfg(feature = "d3d")]
 pub mod d3d;
+#[cfg(feature = "dawn")]
+pub mod dawn;
 mod direct_context;
 mod driver_bug_workarounds;
 #[cfg(feature = "gl")]
@@ -12,19 +16,25 @@ pub mod gl;
 pub mod mtl;
 mod mutable_texture_state;
 mod recording_context;
+#[cfg(feature = "unstable")]
+mod triangulator;
 mod types;
 #[cfg(feature = "vulkan")]
 pub mod vk;
 mod yuva_backend_textures;
 
 pub use backend_drawable_info::*;
+pub use backend_semaphore::*;
 pub use backend_surface::*;
 pub use backend_surface_mutable_state::*;
-pub use context_options::ContextOptions;
+pub use context_options::{ContextOptions, PersistentCache, ShaderErrorHandler};
+pub use context_thread_safe_proxy::*;
 pub use direct_context::*;
 pub use driver_bug_workarounds::DriverBugWorkarounds;
 pub use mutable_texture_state::*;
 pub use recording_context::*;
+#[cfg(feature = "unstable")]
+pub use triangulator::*;
 pub use types::*;
 pub use yuva_backend_textures::*;
 
@@ -0,0 +1,139 @@
+#[cfg(feature = "d3d")]
+use crate::gpu::d3d;
+#[cfg(feature = "gl")]
+use crate::gpu::gl;
+#[cfg(feature = "metal")]
+use crate::gpu::mtl;
+#[cfg(feature = "vulkan")]
+use crate::gpu::vk;
+use crate::{gpu::BackendAPI, prelude::*};
+use skia_bindings::{self as sb, GrBackendSemaphore};
+use std::fmt;
+
+/// Wrapper class for passing into and receiving data from Skia about a backend semaphore object.
+pub type BackendSemaphore = Handle<GrBackendSemaphore>;
+unsafe_send_sync!(BackendSemaphore);
+
+impl NativeDrop for GrBackendSemaphore {
+    fn drop(&mut self) {
+        unsafe { sb::C_GrBackendSemaphore_destruct(self) }
+    }
+}
+
+impl NativeClone for GrBackendSemaphore {
+    fn clone(&self) -> Self {
+        construct(|s| unsafe { sb::C_GrBackendSemaphore_CopyConstruct(s, self) })
+    }
+}
+
+impl Default for BackendSemaphore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for BackendSemaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BackendSemaphore")
+            .field("is_initialized", &self.is_initialized())
+            .field("backend", &self.backend())
+            .finish()
+    }
+}
+
+impl BackendSemaphore {
+    /// Creates an uninitialized semaphore. Uninitialized semaphores can be passed to
+    /// [`crate::gpu::DirectContext::flush_and_signal_semaphores()`], which initializes them with
+    /// semaphores created by the backend.
+    pub fn new() -> Self {
+        Self::construct(|s| unsafe { sb::C_GrBackendSemaphore_Construct(s) })
+    }
+
+    /// Wraps a `GLsync` fence object. This is the only kind of GL synchronization object Skia's
+    /// GL backend can wait on or signal; semaphores imported through `GL_EXT_semaphore_fd` are
+    /// not supported and must be waited on by the client with `glWaitSemaphoreEXT()` before
+    /// Skia starts to render.
+    #[cfg(feature = "gl")]
+    pub fn new_gl(sync: gl::GLsync) -> Self {
+        let mut semaphore = Self::new();
+        unsafe { sb::C_GrBackendSemaphore_initGL(semaphore.native_mut(), sync) }
+        semaphore
+    }
+
+    /// Wraps a `VkSemaphore`.
+    ///
+    /// To consume work of another API (for example GL through `GL_EXT_semaphore_fd`), create the
+    /// semaphore with `VkExportSemaphoreCreateInfo` or import the other side's opaque fd into it
+    /// with `vkImportSemaphoreFdKHR()` (`VK_KHR_external_semaphore_fd`), and pass the wrapped
+    /// semaphore to [`crate::gpu::DirectContext::wait()`]. In the other direction, a semaphore
+    /// that was created with export support and wrapped here can be passed to
+    /// [`crate::gpu::DirectContext::flush_and_signal_semaphores()`]; its fd can then be exported
+    /// with `vkGetSemaphoreFdKHR()` once the flush was submitted. Semaphores that Skia creates
+    /// itself are not exportable.
+    #[cfg(feature = "vulkan")]
+    pub fn new_vulkan(semaphore: vk::Semaphore) -> Self {
+        let mut s = Self::new();
+        unsafe { sb::C_GrBackendSemaphore_initVulkan(s.native_mut(), semaphore) }
+        s
+    }
+
+    /// It is the creator's responsibility to ref the `MTLEvent` passed in here, via
+    /// `__bridge_retained`. The other end will wrap this `BackendSemaphore` and take the ref,
+    /// via `__bridge_transfer`.
+    #[cfg(feature = "metal")]
+    pub fn new_metal(event: mtl::Handle, value: u64) -> Self {
+        let mut semaphore = Self::new();
+        unsafe { sb::C_GrBackendSemaphore_initMetal(semaphore.native_mut(), event, value) }
+        semaphore
+    }
+
+    /// Signals or waits on `info.fence` with `info.value`.
+    #[cfg(feature = "d3d")]
+    pub fn new_d3d(info: &d3d::FenceInfo) -> Self {
+        let mut semaphore = Self::new();
+        unsafe { sb::C_GrBackendSemaphore_initDirect3D(semaphore.native_mut(), info.native()) }
+        semaphore
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        unsafe { sb::C_GrBackendSemaphore_isInitialized(self.native()) }
+    }
+
+    pub fn backend(&self) -> BackendAPI {
+        unsafe { sb::C_GrBackendSemaphore_backend(self.native()) }
+    }
+
+    #[cfg(feature = "gl")]
+    pub fn gl_sync(&self) -> Option<gl::GLsync> {
+        (self.is_initialized() && self.backend() == BackendAPI::OpenGL)
+            .then(|| unsafe { sb::C_GrBackendSemaphore_glSync(self.native()) })
+    }
+
+    #[cfg(feature = "vulkan")]
+    pub fn vk_semaphore(&self) -> Option<vk::Semaphore> {
+        (self.is_initialized() && self.backend() == BackendAPI::Vulkan)
+            .then(|| unsafe { sb::C_GrBackendSemaphore_vkSemaphore(self.native()) })
+    }
+
+    #[cfg(feature = "d3d")]
+    pub fn d3d_fence_info(&self) -> Option<d3d::FenceInfo> {
+        unsafe {
+            // A zeroed `GrD3DFenceInfo` holds a null fence.
+            let mut info: sb::GrD3DFenceInfo = std::mem::zeroed();
+            sb::C_GrBackendSemaphore_getD3DFenceInfo(self.native(), &mut info)
+                .if_true_then_some(|| d3d::FenceInfo::from_native_c(info))
+        }
+    }
+
+    #[cfg(feature = "metal")]
+    pub fn mtl_semaphore(&self) -> Option<mtl::Handle> {
+        (self.is_initialized() && self.backend() == BackendAPI::Metal)
+            .then(|| unsafe { sb::C_GrBackendSemaphore_mtlSemaphore(self.native()) })
+    }
+
+    #[cfg(feature = "metal")]
+    pub fn mtl_value(&self) -> Option<u64> {
+        (self.is_initialized() && self.backend() == BackendAPI::Metal)
+            .then(|| unsafe { sb::C_GrBackendSemaphore_mtlValue(self.native()) })
+    }
+}
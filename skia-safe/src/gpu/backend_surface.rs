@@ -1,5 +1,7 @@
 #[cfg(feature = "d3d")]
 use super::d3d;
+#[cfg(feature = "dawn")]
+use super::dawn;
 #[cfg(feature = "gl")]
 use super::gl;
 #[cfg(feature = "metal")]
@@ -28,6 +30,12 @@ impl NativeClone for GrBackendFormat {
     }
 }
 
+impl NativePartialEq for GrBackendFormat {
+    fn eq(&self, rhs: &Self) -> bool {
+        unsafe { sb::C_GrBackendFormat_Equals(self, rhs) }
+    }
+}
+
 impl fmt::Debug for BackendFormat {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut d = f.debug_struct("BackendFormat");
@@ -131,6 +139,18 @@ impl BackendFormat {
         unsafe { self.native().asVkFormat(&mut r) }.if_true_some(r)
     }
 
+    /// Returns the sampler Y'CbCr conversion of a Vulkan format that was created with
+    /// [`Self::new_vulkan_ycbcr()`], or `None` if the format does not use one.
+    #[cfg(feature = "vulkan")]
+    pub fn vk_ycbcr_conversion_info(&self) -> Option<&vk::YcbcrConversionInfo> {
+        unsafe {
+            vk::YcbcrConversionInfo::from_native_ptr(
+                sb::C_GrBackendFormat_getVkYcbcrConversionInfo(self.native()),
+            )
+            .as_ref()
+        }
+    }
+
     #[cfg(feature = "metal")]
     pub fn as_mtl_format(&self) -> Option<mtl::PixelFormat> {
         let pixel_format = unsafe { self.native().asMtlFormat() };
@@ -206,6 +226,8 @@ impl fmt::Debug for BackendTexture {
             "d3d_texture_resource_info",
             &self.d3d_texture_resource_info(),
         );
+        #[cfg(feature = "dawn")]
+        d.field("dawn_texture_info", &self.dawn_texture_info());
         d.field("backend_format", &self.backend_format());
         d.field("is_protected", &self.is_protected());
         d.finish()
@@ -325,6 +347,32 @@ impl BackendTexture {
         .unwrap()
     }
 
+    #[cfg(feature = "dawn")]
+    #[allow(clippy::missing_safety_doc)]
+    pub unsafe fn new_dawn((width, height): (i32, i32), dawn_info: &dawn::TextureInfo) -> Self {
+        Self::new_dawn_with_label((width, height), dawn_info, "")
+    }
+
+    #[cfg(feature = "dawn")]
+    #[allow(clippy::missing_safety_doc)]
+    pub unsafe fn new_dawn_with_label(
+        (width, height): (i32, i32),
+        dawn_info: &dawn::TextureInfo,
+        label: impl AsRef<str>,
+    ) -> Self {
+        let label = label.as_ref().as_bytes();
+        Self::from_native_if_valid(sb::C_GrBackendTexture_NewDawn(
+            width,
+            height,
+            dawn_info.texture,
+            dawn_info.format,
+            dawn_info.level_count,
+            label.as_ptr() as _,
+            label.len(),
+        ))
+        .unwrap()
+    }
+
     pub(crate) unsafe fn from_native_if_valid(
         backend_texture: *mut GrBackendTexture,
     ) -> Option<BackendTexture> {
@@ -365,6 +413,20 @@ impl BackendTexture {
         self.native().fBackend
     }
 
+    #[cfg(feature = "dawn")]
+    pub fn dawn_texture_info(&self) -> Option<dawn::TextureInfo> {
+        let mut info = dawn::TextureInfo::new(std::ptr::null_mut(), 0);
+        unsafe {
+            sb::C_GrBackendTexture_getDawnTextureInfo(
+                self.native(),
+                &mut info.texture,
+                &mut info.format,
+                &mut info.level_count,
+            )
+        }
+        .if_true_some(info)
+    }
+
     #[cfg(feature = "gl")]
     pub fn gl_texture_info(&self) -> Option<gl::TextureInfo> {
         unsafe {
@@ -559,6 +621,27 @@ impl BackendRenderTarget {
         })
     }
 
+    #[cfg(feature = "dawn")]
+    pub fn new_dawn(
+        (width, height): (i32, i32),
+        sample_count: usize,
+        stencil_bits: usize,
+        dawn_info: &dawn::RenderTargetInfo,
+    ) -> Self {
+        Self::construct(|brt| unsafe {
+            sb::C_GrBackendRenderTarget_ConstructDawn(
+                brt,
+                width,
+                height,
+                sample_count.try_into().unwrap(),
+                stencil_bits.try_into().unwrap(),
+                dawn_info.texture_view,
+                dawn_info.format,
+                dawn_info.level_count,
+            )
+        })
+    }
+
     pub(crate) fn from_native_c_if_valid(
         native: GrBackendRenderTarget,
     ) -> Option<BackendRenderTarget> {
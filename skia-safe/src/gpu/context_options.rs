@@ -1,6 +1,6 @@
-use crate::{gpu::DriverBugWorkarounds, prelude::*};
+use crate::{gpu::DriverBugWorkarounds, prelude::*, Data};
 use skia_bindings::{self as sb, GrContextOptions};
-use std::os::raw;
+use std::{collections::HashMap, mem, os::raw, sync::Mutex};
 
 pub use skia_bindings::GrContextOptions_Enable as Enable;
 variant_name!(Enable::Yes);
@@ -54,8 +54,161 @@ impl ContextOptions {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Sets the cache that is used to store and retrieve compiled shaders and programs across
+    /// runs of the application.
+    ///
+    /// The cache must live as long as all contexts that are created with these options, which is
+    /// why a `'static` reference is required.
+    pub fn set_persistent_cache(&mut self, cache: &'static dyn PersistentCache) -> &mut Self {
+        let key: AdapterKey = unsafe { mem::transmute(cache) };
+        let adapter = *PERSISTENT_CACHES
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_insert_with(|| {
+                let param = sb::RustPersistentCache_Param {
+                    trait_: unsafe { mem::transmute(cache) },
+                    load: Some(persistent_cache::load),
+                    store: Some(persistent_cache::store),
+                };
+                unsafe { sb::C_RustPersistentCache_new(&param) as usize }
+            });
+        self.persistent_cache = adapter as _;
+        self
+    }
+
+    /// Sets the handler that gets called when a shader fails to compile. By default, Skia
+    /// prints the shader and the errors to the debug output.
+    ///
+    /// The handler must live as long as all contexts that are created with these options.
+    pub fn set_shader_error_handler(
+        &mut self,
+        handler: &'static dyn ShaderErrorHandler,
+    ) -> &mut Self {
+        let key: AdapterKey = unsafe { mem::transmute(handler) };
+        let adapter = *SHADER_ERROR_HANDLERS
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_insert_with(|| {
+                let param = sb::RustShaderErrorHandler_Param {
+                    trait_: unsafe { mem::transmute(handler) },
+                    compileError: Some(shader_error_handler::compile_error),
+                };
+                unsafe { sb::C_RustShaderErrorHandler_new(&param) as usize }
+            });
+        self.shader_error_handler = adapter as _;
+        self
+    }
 }
 
 native_transmutable!(GrContextOptions, ContextOptions, context_options_layout);
 
-// TODO: PersistentCache, ShaderErrorHandler
+/// The address and vtable of a trait object.
+type AdapterKey = [usize; 2];
+
+lazy_static! {
+    // The native adapters of the trait objects passed to the setters of `ContextOptions`, keyed
+    // by the trait object. Skia does not notify us when the contexts that reference an adapter
+    // are gone, so adapters are never freed. Instead, there is exactly one adapter per trait
+    // object, which is reused whenever the same trait object is set again.
+    static ref PERSISTENT_CACHES: Mutex<HashMap<AdapterKey, usize>> = Default::default();
+    static ref SHADER_ERROR_HANDLERS: Mutex<HashMap<AdapterKey, usize>> = Default::default();
+}
+
+/// Abstract class to cache compiled shaders and programs (`GrContextOptions::PersistentCache`).
+///
+/// The functions may be called from the thread the context is used on.
+pub trait PersistentCache: Send + Sync {
+    /// Returns the data that was previously stored for `key`, or `None` if there is none.
+    fn load(&self, key: &Data) -> Option<Data>;
+
+    /// Stores `data` for `key`. `description` is a human readable description of the stored
+    /// data, intended for debugging.
+    fn store(&self, key: &Data, data: &Data, description: &str);
+}
+
+/// Abstract class to report errors when compiling shaders
+/// (`GrContextOptions::ShaderErrorHandler`).
+pub trait ShaderErrorHandler: Send + Sync {
+    fn compile_error(&self, shader: &str, errors: &str);
+}
+
+mod persistent_cache {
+    use super::PersistentCache;
+    use crate::{interop::AsStr, prelude::*, Data};
+    use skia_bindings::{SkData, SkString, TraitObject};
+    use std::{mem, ptr};
+
+    pub unsafe extern "C" fn load(to: TraitObject, key: *const SkData) -> *mut SkData {
+        let key = Data::from_unshared_ptr(key as _).unwrap();
+        match to_cache(to).load(&key) {
+            Some(data) => data.into_ptr(),
+            None => ptr::null_mut(),
+        }
+    }
+
+    pub unsafe extern "C" fn store(
+        to: TraitObject,
+        key: *const SkData,
+        data: *const SkData,
+        description: *const SkString,
+    ) {
+        let key = Data::from_unshared_ptr(key as _).unwrap();
+        let data = Data::from_unshared_ptr(data as _).unwrap();
+        to_cache(to).store(&key, &data, (*description).as_str())
+    }
+
+    fn to_cache(to: TraitObject) -> &'static dyn PersistentCache {
+        unsafe { mem::transmute(to) }
+    }
+}
+
+mod shader_error_handler {
+    use super::ShaderErrorHandler;
+    use skia_bindings::TraitObject;
+    use std::{ffi::CStr, mem, os::raw};
+
+    pub unsafe extern "C" fn compile_error(
+        to: TraitObject,
+        shader: *const raw::c_char,
+        errors: *const raw::c_char,
+    ) {
+        let handler: &dyn ShaderErrorHandler = mem::transmute(to);
+        handler.compile_error(
+            &CStr::from_ptr(shader).to_string_lossy(),
+            &CStr::from_ptr(errors).to_string_lossy(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContextOptions, PersistentCache};
+    use crate::Data;
+
+    struct NoCache;
+
+    impl PersistentCache for NoCache {
+        fn load(&self, _key: &Data) -> Option<Data> {
+            None
+        }
+
+        fn store(&self, _key: &Data, _data: &Data, _description: &str) {}
+    }
+
+    static NO_CACHE: NoCache = NoCache;
+
+    #[test]
+    fn setting_the_same_persistent_cache_reuses_its_adapter() {
+        let mut first = ContextOptions::new();
+        first.set_persistent_cache(&NO_CACHE);
+        let mut second = ContextOptions::new();
+        second
+            .set_persistent_cache(&NO_CACHE)
+            .set_persistent_cache(&NO_CACHE);
+        assert!(!first.persistent_cache.is_null());
+        assert_eq!(first.persistent_cache, second.persistent_cache);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    gpu::{BackendFormat, Protected, Renderable, SurfaceOrigin},
+    prelude::*,
+    ColorType, ImageInfo, SurfaceCharacterization, SurfaceProps,
+};
+use skia_bindings::{self as sb, GrContextThreadSafeProxy};
+use std::fmt;
+
+/// Can be used to perform actions related to the generating [`super::DirectContext`] in a
+/// thread safe manner. The proxy does not access the 3D API (e.g. OpenGL) that backs the
+/// generating [`super::DirectContext`].
+pub type ContextThreadSafeProxy = RCHandle<GrContextThreadSafeProxy>;
+unsafe_send_sync!(ContextThreadSafeProxy);
+
+impl NativeRefCounted for GrContextThreadSafeProxy {
+    fn _ref(&self) {
+        unsafe { sb::C_GrContextThreadSafeProxy_ref(self) }
+    }
+
+    fn _unref(&self) {
+        unsafe { sb::C_GrContextThreadSafeProxy_unref(self) }
+    }
+
+    fn unique(&self) -> bool {
+        unsafe { sb::C_GrContextThreadSafeProxy_unique(self) }
+    }
+}
+
+impl fmt::Debug for ContextThreadSafeProxy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextThreadSafeProxy")
+            .field("is_valid", &self.is_valid())
+            .finish()
+    }
+}
+
+impl ContextThreadSafeProxy {
+    /// Create a surface characterization for a DDL that will be replayed into the
+    /// [`super::DirectContext`] that created this proxy. The characterization can be created on
+    /// any thread and passed to a [`crate::DeferredDisplayListRecorder`].
+    ///
+    /// Returns `None` if the requested characterization is not supported by the context.
+    ///
+    /// - `cache_max_resource_bytes` The max resource bytes limit that will be in effect when the
+    ///   DDL created with this characterization is replayed. Note: the contract here is that the
+    ///   DDL will be created as if it had a full `cache_max_resource_bytes` to use. If replayed
+    ///   into a context that has already used some of that budget, it may trigger unexpected
+    ///   purging.
+    /// - `image_info` The image info specifying properties of the surface that the DDL created
+    ///   with this characterization will be replayed into.
+    /// - `backend_format` Information about the format of the surface.
+    /// - `sample_count` The sample count of the surface.
+    /// - `origin` The origin of the surface.
+    /// - `surface_props` The surface properties of the surface.
+    /// - `is_mipmapped` Will the surface the DDL will be replayed into have space allocated for
+    ///   mipmaps?
+    /// - `will_use_glfbo0` Will the surface the DDL will be replayed into be backed by GL FBO 0.
+    ///   This flag is only valid if using a GL backend.
+    /// - `is_textureable` Will the surface be able to act as a texture?
+    /// - `is_protected` Will the (Vulkan) surface be DRM protected?
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_characterization(
+        &self,
+        cache_max_resource_bytes: usize,
+        image_info: &ImageInfo,
+        backend_format: &BackendFormat,
+        sample_count: usize,
+        origin: SurfaceOrigin,
+        surface_props: &SurfaceProps,
+        is_mipmapped: bool,
+        will_use_glfbo0: impl Into<Option<bool>>,
+        is_textureable: impl Into<Option<bool>>,
+        is_protected: impl Into<Option<Protected>>,
+    ) -> Option<SurfaceCharacterization> {
+        let characterization = SurfaceCharacterization::construct(|sc| unsafe {
+            sb::C_GrContextThreadSafeProxy_createCharacterization(
+                self.native_mut_force(),
+                cache_max_resource_bytes,
+                image_info.native(),
+                backend_format.native(),
+                sample_count.try_into().unwrap(),
+                origin,
+                surface_props.native(),
+                is_mipmapped,
+                will_use_glfbo0.into().unwrap_or(false),
+                is_textureable.into().unwrap_or(true),
+                is_protected.into().unwrap_or(Protected::No),
+                sc,
+            )
+        });
+        characterization.is_valid().if_true_some(characterization)
+    }
+
+    /// Retrieve the default [`BackendFormat`] for a given [`ColorType`] and renderability.
+    /// It is guaranteed that this backend format will be the one used by the following
+    /// [`ColorType`] and `SurfaceCharacterization`-based `create_backend_texture` methods.
+    ///
+    /// The caller should check that the returned format is valid.
+    pub fn default_backend_format(&self, ct: ColorType, renderable: Renderable) -> BackendFormat {
+        let mut format = BackendFormat::new_invalid();
+        unsafe {
+            sb::C_GrContextThreadSafeProxy_defaultBackendFormat(
+                self.native(),
+                ct.into_native(),
+                renderable,
+                format.native_mut(),
+            )
+        };
+        format
+    }
+
+    pub fn is_valid(&self) -> bool {
+        unsafe { sb::C_GrContextThreadSafeProxy_isValid(self.native()) }
+    }
+}
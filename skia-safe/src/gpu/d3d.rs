@@ -7,6 +7,11 @@ pub use backend_context::*;
 mod types;
 pub use types::*;
 
+#[cfg(feature = "windows")]
+pub mod swapchain;
+#[cfg(feature = "windows")]
+mod windows_interop;
+
 // re-export D3D types we use
 
 pub use sb::GrD3DResourceStateEnum as ResourceStateEnum;
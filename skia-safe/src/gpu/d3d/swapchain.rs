@@ -0,0 +1,285 @@
+//! A helper that presents [`Surface`]s wrapping the back buffers of a flip-model DXGI swapchain.
+//!
+//! ```ignore
+//! let mut swapchain =
+//!     Swapchain::new_for_hwnd(&backend_context, &mut context, hwnd, window_size, 2)?;
+//! loop {
+//!     draw(swapchain.surface()?.canvas());
+//!     swapchain.present(&mut context, 1)?;
+//! }
+//! ```
+
+use super::{BackendContext, TextureResourceInfo};
+use crate::{
+    gpu::{BackendRenderTarget, DirectContext, FlushInfo, SurfaceOrigin},
+    surface::BackendSurfaceAccess,
+    ColorType, ISize, Surface,
+};
+use ::windows::{
+    core::{ComInterface, Result},
+    Win32::{
+        Foundation::{E_FAIL, E_INVALIDARG, HANDLE, HWND},
+        Graphics::{
+            Direct3D12::{ID3D12CommandQueue, ID3D12Fence, ID3D12Resource, D3D12_FENCE_FLAG_NONE},
+            DirectComposition::IDCompositionVisual,
+            Dxgi::{
+                Common::{
+                    DXGI_ALPHA_MODE, DXGI_ALPHA_MODE_IGNORE, DXGI_ALPHA_MODE_PREMULTIPLIED,
+                    DXGI_FORMAT_R8G8B8A8_UNORM, DXGI_FORMAT_UNKNOWN, DXGI_SAMPLE_DESC,
+                },
+                IDXGIFactory2, IDXGISwapChain1, IDXGISwapChain3, DXGI_SCALING_STRETCH,
+                DXGI_SWAP_CHAIN_DESC1, DXGI_SWAP_EFFECT_FLIP_DISCARD,
+                DXGI_USAGE_RENDER_TARGET_OUTPUT,
+            },
+        },
+    },
+};
+use std::fmt;
+use winapi::{shared::dxgiformat, um::d3d12};
+
+/// Owns an `IDXGISwapChain3` and a [`Surface`] for each of its back buffers.
+///
+/// The back buffers are `DXGI_FORMAT_R8G8B8A8_UNORM` and are drawn to with
+/// [`ColorType::RGBA8888`]. Before a back buffer is handed out again, [`Swapchain::surface()`]
+/// waits until the GPU has finished the work of the frame that last presented it.
+pub struct Swapchain {
+    queue: ID3D12CommandQueue,
+    swapchain: IDXGISwapChain3,
+    fence: ID3D12Fence,
+    fence_values: Vec<u64>,
+    next_fence_value: u64,
+    size: ISize,
+    surfaces: Vec<Surface>,
+}
+
+impl fmt::Debug for Swapchain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Swapchain")
+            .field("swapchain", &self.swapchain)
+            .field("size", &self.size)
+            .field("buffer_count", &self.surfaces.len())
+            .finish()
+    }
+}
+
+impl Drop for Swapchain {
+    fn drop(&mut self) {
+        self.wait_idle().ok();
+    }
+}
+
+impl Swapchain {
+    /// Creates a swapchain with `buffer_count` back buffers of the given `size` that presents to
+    /// the window `hwnd`.
+    ///
+    /// Returns `E_INVALIDARG` if `buffer_count` is not in the range `2..=16`.
+    pub fn new_for_hwnd(
+        backend_context: &BackendContext,
+        context: &mut DirectContext,
+        hwnd: HWND,
+        size: impl Into<ISize>,
+        buffer_count: usize,
+    ) -> Result<Self> {
+        let size = size.into();
+        let queue = backend_context.windows_queue();
+        let desc = swapchain_desc(size, buffer_count, DXGI_ALPHA_MODE_IGNORE)?;
+        let swapchain = unsafe {
+            factory(backend_context)?.CreateSwapChainForHwnd(&queue, hwnd, &desc, None, None)?
+        };
+        Self::new(backend_context, context, queue, swapchain, size)
+    }
+
+    /// Creates a swapchain with `buffer_count` back buffers of the given `size` and sets it as
+    /// the content of the DirectComposition `visual`.
+    ///
+    /// The back buffers use premultiplied alpha. The change to `visual` becomes visible after
+    /// the DirectComposition device has been committed.
+    ///
+    /// Returns `E_INVALIDARG` if `buffer_count` is not in the range `2..=16`.
+    pub fn new_for_composition(
+        backend_context: &BackendContext,
+        context: &mut DirectContext,
+        visual: &IDCompositionVisual,
+        size: impl Into<ISize>,
+        buffer_count: usize,
+    ) -> Result<Self> {
+        let size = size.into();
+        let queue = backend_context.windows_queue();
+        let desc = swapchain_desc(size, buffer_count, DXGI_ALPHA_MODE_PREMULTIPLIED)?;
+        let swapchain = unsafe {
+            let swapchain =
+                factory(backend_context)?.CreateSwapChainForComposition(&queue, &desc, None)?;
+            visual.SetContent(&swapchain)?;
+            swapchain
+        };
+        Self::new(backend_context, context, queue, swapchain, size)
+    }
+
+    fn new(
+        backend_context: &BackendContext,
+        context: &mut DirectContext,
+        queue: ID3D12CommandQueue,
+        swapchain: IDXGISwapChain1,
+        size: ISize,
+    ) -> Result<Self> {
+        let fence = unsafe {
+            backend_context
+                .windows_device()
+                .CreateFence(0, D3D12_FENCE_FLAG_NONE)?
+        };
+        let mut swapchain = Swapchain {
+            queue,
+            swapchain: swapchain.cast()?,
+            fence,
+            fence_values: Vec::new(),
+            next_fence_value: 1,
+            size,
+            surfaces: Vec::new(),
+        };
+        swapchain.create_surfaces(context)?;
+        Ok(swapchain)
+    }
+
+    /// Resizes the back buffers, for example after the window was resized.
+    ///
+    /// All [`Surface`]s of the previous back buffers are released.
+    pub fn resize(&mut self, context: &mut DirectContext, size: impl Into<ISize>) -> Result<()> {
+        let size = size.into();
+        self.wait_idle()?;
+        self.surfaces.clear();
+        self.fence_values.clear();
+        // Skia may still hold references to the back buffers in its resource cache.
+        context.free_gpu_resources();
+        unsafe {
+            self.swapchain.ResizeBuffers(
+                0,
+                size.width.max(1) as _,
+                size.height.max(1) as _,
+                DXGI_FORMAT_UNKNOWN,
+                0,
+            )?
+        };
+        self.size = size;
+        self.create_surfaces(context)
+    }
+
+    /// Returns the [`Surface`] of the current back buffer.
+    ///
+    /// Blocks until the GPU has finished the frame that previously presented the back buffer.
+    pub fn surface(&mut self) -> Result<&mut Surface> {
+        let index = self.current_index();
+        let fence_value = self.fence_values[index];
+        unsafe {
+            if self.fence.GetCompletedValue() < fence_value {
+                // A null event makes the call block until the fence reaches the value.
+                self.fence
+                    .SetEventOnCompletion(fence_value, HANDLE::default())?;
+            }
+        }
+        Ok(&mut self.surfaces[index])
+    }
+
+    /// Flushes the drawing of the current back buffer, transitions it to
+    /// `D3D12_RESOURCE_STATE_PRESENT`, submits the work and presents it.
+    ///
+    /// `sync_interval` is passed to `IDXGISwapChain::Present()`: `0` presents immediately, `1`
+    /// synchronizes the presentation with the next vertical blank.
+    pub fn present(&mut self, context: &mut DirectContext, sync_interval: u32) -> Result<()> {
+        let index = self.current_index();
+        self.surfaces[index]
+            .flush_with_access_info(BackendSurfaceAccess::Present, &FlushInfo::default());
+        context.submit(None);
+        unsafe {
+            self.swapchain.Present(sync_interval, 0).ok()?;
+            self.queue.Signal(&self.fence, self.next_fence_value)?;
+        }
+        self.fence_values[index] = self.next_fence_value;
+        self.next_fence_value += 1;
+        Ok(())
+    }
+
+    pub fn size(&self) -> ISize {
+        self.size
+    }
+
+    pub fn buffer_count(&self) -> usize {
+        self.surfaces.len()
+    }
+
+    pub fn handle(&self) -> &IDXGISwapChain3 {
+        &self.swapchain
+    }
+
+    fn current_index(&self) -> usize {
+        unsafe { self.swapchain.GetCurrentBackBufferIndex() as usize }
+    }
+
+    fn create_surfaces(&mut self, context: &mut DirectContext) -> Result<()> {
+        let desc = unsafe { self.swapchain.GetDesc1()? };
+        for i in 0..desc.BufferCount {
+            let buffer: ID3D12Resource = unsafe { self.swapchain.GetBuffer(i)? };
+            let info = TextureResourceInfo {
+                format: dxgiformat::DXGI_FORMAT_R8G8B8A8_UNORM,
+                level_count: 1,
+                ..TextureResourceInfo::from_windows_resource(&buffer)
+                    .with_state(d3d12::D3D12_RESOURCE_STATE_PRESENT)
+            };
+            let render_target =
+                BackendRenderTarget::new_d3d((desc.Width as _, desc.Height as _), &info);
+            let surface = Surface::from_backend_render_target(
+                context,
+                &render_target,
+                SurfaceOrigin::TopLeft,
+                ColorType::RGBA8888,
+                None,
+                None,
+            )
+            .ok_or_else(|| ::windows::core::Error::from(E_FAIL))?;
+            self.surfaces.push(surface);
+            self.fence_values.push(0);
+        }
+        Ok(())
+    }
+
+    /// Blocks until the GPU has finished all work that was submitted to the queue.
+    fn wait_idle(&mut self) -> Result<()> {
+        let fence_value = self.next_fence_value;
+        self.next_fence_value += 1;
+        unsafe {
+            self.queue.Signal(&self.fence, fence_value)?;
+            self.fence
+                .SetEventOnCompletion(fence_value, HANDLE::default())
+        }
+    }
+}
+
+fn factory(backend_context: &BackendContext) -> Result<IDXGIFactory2> {
+    unsafe { backend_context.windows_adapter().GetParent() }
+}
+
+fn swapchain_desc(
+    size: ISize,
+    buffer_count: usize,
+    alpha_mode: DXGI_ALPHA_MODE,
+) -> Result<DXGI_SWAP_CHAIN_DESC1> {
+    // Flip-model swapchains need at least 2 and support at most
+    // `DXGI_MAX_SWAP_CHAIN_BUFFERS` buffers.
+    if !(2..=16).contains(&buffer_count) {
+        return Err(E_INVALIDARG.into());
+    }
+    Ok(DXGI_SWAP_CHAIN_DESC1 {
+        Width: size.width.max(1) as _,
+        Height: size.height.max(1) as _,
+        Format: DXGI_FORMAT_R8G8B8A8_UNORM,
+        SampleDesc: DXGI_SAMPLE_DESC {
+            Count: 1,
+            Quality: 0,
+        },
+        BufferUsage: DXGI_USAGE_RENDER_TARGET_OUTPUT,
+        BufferCount: buffer_count as _,
+        Scaling: DXGI_SCALING_STRETCH,
+        SwapEffect: DXGI_SWAP_EFFECT_FLIP_DISCARD,
+        AlphaMode: alpha_mode,
+        ..Default::default()
+    })
+}
@@ -1,7 +1,8 @@
 use super::{ID3D12Resource, D3D12_RESOURCE_STATES, DXGI_FORMAT};
 use crate::{gpu, prelude::*};
 use skia_bindings::{
-    GrD3DAlloc, GrD3DMemoryAllocator, GrD3DSurfaceInfo, GrD3DTextureResourceInfo, SkRefCntBase,
+    GrD3DAlloc, GrD3DFenceInfo, GrD3DMemoryAllocator, GrD3DSurfaceInfo, GrD3DTextureResourceInfo,
+    SkRefCntBase,
 };
 use std::{fmt, os::raw::c_uint};
 use winapi::{
@@ -119,6 +120,14 @@ pub struct FenceInfo {
 
 unsafe_send_sync!(FenceInfo);
 
+native_transmutable!(GrD3DFenceInfo, FenceInfo, fence_info_layout);
+
+impl FenceInfo {
+    pub fn new(fence: cp<d3d12::ID3D12Fence>, value: u64) -> Self {
+        Self { fence, value }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 #[repr(C)]
 pub struct SurfaceInfo {
@@ -0,0 +1,69 @@
+//! Conversions from and to the COM interfaces of the [`windows`](::windows) crate.
+
+use super::{cp, BackendContext, FenceInfo, TextureResourceInfo};
+use crate::gpu;
+use ::windows::{
+    core::ComInterface,
+    Win32::Graphics::{
+        Direct3D12::{ID3D12CommandQueue, ID3D12Device, ID3D12Fence, ID3D12Resource},
+        Dxgi::IDXGIAdapter1,
+    },
+};
+
+/// Returns a new reference to `object` as a `ComPtr`.
+fn to_cp<T: winapi::Interface>(object: &impl ComInterface) -> cp<T> {
+    unsafe { cp::from_raw(object.clone().into_raw() as *mut T) }
+}
+
+/// Returns a new reference to the object `ptr` points to.
+fn from_cp<W: ComInterface, T: winapi::Interface>(ptr: &cp<T>) -> W {
+    unsafe { W::from_raw(ptr.clone().into_raw() as _) }
+}
+
+impl BackendContext {
+    pub fn from_windows(
+        adapter: &IDXGIAdapter1,
+        device: &ID3D12Device,
+        queue: &ID3D12CommandQueue,
+    ) -> Self {
+        Self {
+            adapter: to_cp(adapter),
+            device: to_cp(device),
+            queue: to_cp(queue),
+            memory_allocator: None,
+            protected_context: gpu::Protected::No,
+        }
+    }
+
+    pub fn windows_adapter(&self) -> IDXGIAdapter1 {
+        from_cp(&self.adapter)
+    }
+
+    pub fn windows_device(&self) -> ID3D12Device {
+        from_cp(&self.device)
+    }
+
+    pub fn windows_queue(&self) -> ID3D12CommandQueue {
+        from_cp(&self.queue)
+    }
+}
+
+impl TextureResourceInfo {
+    pub fn from_windows_resource(resource: &ID3D12Resource) -> Self {
+        Self::from_resource(to_cp(resource))
+    }
+
+    pub fn windows_resource(&self) -> ID3D12Resource {
+        from_cp(&self.resource)
+    }
+}
+
+impl FenceInfo {
+    pub fn from_windows_fence(fence: &ID3D12Fence, value: u64) -> Self {
+        Self::new(to_cp(fence), value)
+    }
+
+    pub fn windows_fence(&self) -> ID3D12Fence {
+        from_cp(&self.fence)
+    }
+}
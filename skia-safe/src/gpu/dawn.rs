@@ -0,0 +1,47 @@
+//! The Dawn (WebGPU) backend.
+//!
+//! Dawn objects are passed as their handles of the WebGPU C API (`webgpu.h`). Skia adds a
+//! reference to every object it keeps, so the caller keeps ownership of the handles it passes.
+
+use skia_bindings as sb;
+
+pub use sb::WGPUDevice as Device;
+pub use sb::WGPUTexture as Texture;
+pub use sb::WGPUTextureView as TextureView;
+
+/// A `WGPUTextureFormat` value.
+pub type TextureFormat = u32;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TextureInfo {
+    pub texture: Texture,
+    pub format: TextureFormat,
+    pub level_count: u32,
+}
+
+impl TextureInfo {
+    pub fn new(texture: Texture, format: TextureFormat) -> Self {
+        Self {
+            texture,
+            format,
+            level_count: 1,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct RenderTargetInfo {
+    pub texture_view: TextureView,
+    pub format: TextureFormat,
+    pub level_count: u32,
+}
+
+impl RenderTargetInfo {
+    pub fn new(texture_view: TextureView, format: TextureFormat) -> Self {
+        Self {
+            texture_view,
+            format,
+            level_count: 1,
+        }
+    }
+}
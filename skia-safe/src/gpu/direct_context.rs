@@ -1,14 +1,22 @@
 #[cfg(feature = "d3d")]
 use super::d3d;
+#[cfg(feature = "dawn")]
+use super::dawn;
 #[cfg(feature = "gl")]
 use super::gl;
 #[cfg(feature = "vulkan")]
 use super::vk;
 use super::{
-    BackendFormat, BackendRenderTarget, BackendTexture, ContextOptions, FlushInfo,
-    MutableTextureState, RecordingContext, SemaphoresSubmitted,
+    BackendFormat, BackendRenderTarget, BackendSemaphore, BackendTexture, ContextOptions,
+    FlushInfo, Mipmapped, MutableTextureState, Protected, RecordingContext, Renderable,
+    SemaphoresSubmitted, SurfaceOrigin,
+};
+use crate::{
+    image::{self, CachingHint},
+    prelude::*,
+    trace_memory_dump, AlphaType, Color4f, ColorType, Data, IPoint, ISize, Image, Pixmap,
+    TraceMemoryDump,
 };
-use crate::{image, prelude::*, Data};
 use skia_bindings::{self as sb, GrDirectContext, GrDirectContext_DirectContextID, SkRefCntBase};
 use std::{
     fmt,
@@ -29,6 +37,24 @@ native_transmutable!(
     direct_context_id_layout
 );
 
+/// A context that executes GPU work directly on a backend API.
+///
+/// # Device loss and context resets
+///
+/// If the backend device is lost (for example, a Vulkan call returned `VK_ERROR_DEVICE_LOST`),
+/// or the context was abandoned explicitly, all further work submitted to the context is
+/// dropped silently. Long-running applications should check [`RecordingContext::abandoned()`]
+/// after each frame was submitted. If it returns `true`:
+///
+/// 1. Drop all [`crate::Surface`]s and GPU backed [`crate::Image`]s that were created from the
+///    context. They must not be drawn or read from anymore.
+/// 2. Call [`DirectContext::release_resources_and_abandon()`] to release the backend objects
+///    that are still alive and drop the context.
+/// 3. Recreate the backend device (or GL context), a new [`DirectContext`], and then
+///    recreate all surfaces and re-upload the images from their CPU side copies.
+///
+/// For GL, [`DirectContext::reset()`] must be called instead when the GL state was changed
+/// outside of Skia, this is not sufficient to recover from a GL context loss.
 pub type DirectContext = RCHandle<GrDirectContext>;
 require_type_equality!(sb::GrDirectContext_INHERITED, sb::GrRecordingContext);
 
@@ -133,6 +159,21 @@ impl DirectContext {
         ))
     }
 
+    /// Creates a context that uses the Dawn `device` and its default queue.
+    ///
+    /// # Safety
+    /// `device` must be a valid `WGPUDevice`.
+    #[cfg(feature = "dawn")]
+    pub unsafe fn new_dawn<'a>(
+        device: dawn::Device,
+        options: impl Into<Option<&'a ContextOptions>>,
+    ) -> Option<DirectContext> {
+        DirectContext::from_ptr(sb::C_GrDirectContext_MakeDawn(
+            device,
+            options.into().native_ptr_or_null(),
+        ))
+    }
+
     pub fn reset(&mut self, backend_state: Option<u32>) -> &mut Self {
         unsafe {
             self.native_mut()
@@ -154,8 +195,6 @@ impl DirectContext {
         self
     }
 
-    // TODO: threadSafeProxy()
-
     pub fn oomed(&mut self) -> bool {
         unsafe { self.native_mut().oomed() }
     }
@@ -252,7 +291,30 @@ impl DirectContext {
         self
     }
 
-    // TODO: wait()
+    /// Inserts a list of GPU semaphores that the current GPU-backed API must wait on before
+    /// executing any more commands on the GPU. We only guarantee blocking transfer and fragment
+    /// shader work, but may block earlier stages as well depending on the backend. If this call
+    /// returns `false`, then the GPU back-end will not wait on any passed in semaphores, and the
+    /// client will still own the semaphores, regardless of the value of `delete_semaphores_after_wait`.
+    ///
+    /// If `delete_semaphores_after_wait` is `false` then Skia will not delete the semaphores. In
+    /// this case it is the client's responsibility to not destroy or attempt to reuse the
+    /// semaphores until it knows that Skia has finished waiting on them. This can be done by
+    /// using finished procs on flush calls.
+    pub fn wait(
+        &mut self,
+        wait_semaphores: &[BackendSemaphore],
+        delete_semaphores_after_wait: impl Into<Option<bool>>,
+    ) -> bool {
+        unsafe {
+            sb::C_GrDirectContext_wait(
+                self.native_mut(),
+                wait_semaphores.native().as_ptr(),
+                wait_semaphores.len(),
+                delete_semaphores_after_wait.into().unwrap_or(true),
+            )
+        }
+    }
 
     pub fn flush_and_submit(&mut self) -> &mut Self {
         unsafe { sb::C_GrDirectContext_flushAndSubmit(self.native_mut()) }
@@ -265,6 +327,19 @@ impl DirectContext {
         self
     }
 
+    /// Flushes and submits all pending work and calls `finished` once the GPU has completed it.
+    ///
+    /// On Metal, this is the place to hook into the completion of the command buffer, for example
+    /// for frame pacing or for recycling resources. See [`FlushInfo::set_finished_proc()`] for
+    /// when and on which thread `finished` is called.
+    pub fn flush_and_submit_with_finished_proc(
+        &mut self,
+        finished: impl FnOnce() + Send + 'static,
+    ) -> bool {
+        self.flush(&FlushInfo::new().with_finished_proc(finished));
+        self.submit(None)
+    }
+
     #[deprecated(since = "0.37.0", note = "Use flush()")]
     pub fn flush_with_info(&mut self, info: &FlushInfo) -> SemaphoresSubmitted {
         self.flush(info)
@@ -273,13 +348,37 @@ impl DirectContext {
     pub fn flush<'a>(&mut self, info: impl Into<Option<&'a FlushInfo>>) -> SemaphoresSubmitted {
         let n = self.native_mut();
         if let Some(info) = info.into() {
-            unsafe { n.flush(info.native()) }
+            unsafe { n.flush(&info.native_for_flush()) }
         } else {
             let info = FlushInfo::default();
-            unsafe { n.flush(info.native()) }
+            unsafe { n.flush(&info.native_for_flush()) }
         }
     }
 
+    /// Flushes like [`Self::flush()`] and additionally signals `signal_semaphores` once the
+    /// flushed work has completed on the GPU.
+    ///
+    /// Semaphores that are not yet initialized are created by the backend and initialized on
+    /// return, so that they can be passed to another API or context. If
+    /// [`SemaphoresSubmitted::No`] is returned, the semaphores were not submitted to the GPU and
+    /// the client must not wait on them.
+    pub fn flush_and_signal_semaphores<'a>(
+        &mut self,
+        info: impl Into<Option<&'a FlushInfo>>,
+        signal_semaphores: &mut [BackendSemaphore],
+    ) -> SemaphoresSubmitted {
+        let default_info;
+        let info = match info.into() {
+            Some(info) => info,
+            None => {
+                default_info = FlushInfo::default();
+                &default_info
+            }
+        };
+        let info = info.native_for_flush_with_signal_semaphores(signal_semaphores);
+        unsafe { self.native_mut().flush(&info) }
+    }
+
     pub fn submit(&mut self, sync_cpu: impl Into<Option<bool>>) -> bool {
         unsafe { self.native_mut().submit(sync_cpu.into().unwrap_or(false)) }
     }
@@ -288,7 +387,12 @@ impl DirectContext {
         unsafe { self.native_mut().checkAsyncWorkCompletion() }
     }
 
-    // TODO: dumpMemoryStatistics()
+    /// Enumerates all cached GPU resources and dumps their memory to `trace_memory_dump`.
+    pub fn dump_memory_statistics(&self, trace_memory_dump: &mut dyn TraceMemoryDump) {
+        trace_memory_dump::with_native_trace_memory_dump(trace_memory_dump, |dump| unsafe {
+            sb::C_GrDirectContext_dumpMemoryStatistics(self.native(), dump)
+        })
+    }
 
     pub fn supports_distance_field_text(&self) -> bool {
         unsafe { self.native().supportsDistanceFieldText() }
@@ -302,13 +406,141 @@ impl DirectContext {
         self
     }
 
-    // TODO: wrap createBackendTexture (several variants)
-    //       introduced in m76, m77, and m79
-    //       extended in m84 with finishedProc and finishedContext
-    //       extended in m107 with label
+    /// Creates an uninitialized texture of `backend_format`.
+    ///
+    /// The client is responsible for deleting the returned texture with
+    /// [`Self::delete_backend_texture()`] once all the images and surfaces that use it are gone.
+    pub fn create_backend_texture(
+        &mut self,
+        dimensions: impl Into<ISize>,
+        backend_format: &BackendFormat,
+        mipmapped: Mipmapped,
+        renderable: Renderable,
+        is_protected: impl Into<Option<Protected>>,
+    ) -> Option<BackendTexture> {
+        let dimensions = dimensions.into();
+        unsafe {
+            BackendTexture::from_native_if_valid(sb::C_GrDirectContext_createBackendTexture(
+                self.native_mut(),
+                dimensions.width,
+                dimensions.height,
+                backend_format.native(),
+                mipmapped,
+                renderable,
+                is_protected.into().unwrap_or(Protected::No),
+            ))
+        }
+    }
+
+    /// Creates an uninitialized texture that is compatible with `color_type`, see
+    /// [`Self::create_backend_texture()`].
+    pub fn create_backend_texture_with_color_type(
+        &mut self,
+        dimensions: impl Into<ISize>,
+        color_type: ColorType,
+        mipmapped: Mipmapped,
+        renderable: Renderable,
+        is_protected: impl Into<Option<Protected>>,
+    ) -> Option<BackendTexture> {
+        let dimensions = dimensions.into();
+        unsafe {
+            BackendTexture::from_native_if_valid(sb::C_GrDirectContext_createBackendTexture2(
+                self.native_mut(),
+                dimensions.width,
+                dimensions.height,
+                color_type.into_native(),
+                mipmapped,
+                renderable,
+                is_protected.into().unwrap_or(Protected::No),
+            ))
+        }
+    }
+
+    /// Creates a texture and initializes it with `src_data`. If `src_data` contains more than
+    /// one pixmap, the texture is mipmapped and `src_data` must contain all the mip levels,
+    /// starting with the base level.
+    ///
+    /// See [`Self::create_backend_texture()`].
+    pub fn create_backend_texture_from_pixmaps(
+        &mut self,
+        src_data: &[Pixmap],
+        origin: SurfaceOrigin,
+        renderable: Renderable,
+        is_protected: impl Into<Option<Protected>>,
+    ) -> Option<BackendTexture> {
+        let num_levels = src_data.len().try_into().ok()?;
+        unsafe {
+            BackendTexture::from_native_if_valid(sb::C_GrDirectContext_createBackendTexture3(
+                self.native_mut(),
+                src_data.native().as_ptr(),
+                num_levels,
+                origin,
+                renderable,
+                is_protected.into().unwrap_or(Protected::No),
+            ))
+        }
+    }
 
-    // TODO: wrap updateBackendTexture (several variants)
-    //       introduced in m84
+    /// Fills all the mip levels of `backend_texture` with `color`.
+    pub fn update_backend_texture_with_color(
+        &mut self,
+        backend_texture: &BackendTexture,
+        color: impl Into<Color4f>,
+    ) -> bool {
+        unsafe {
+            sb::C_GrDirectContext_updateBackendTexture(
+                self.native_mut(),
+                backend_texture.native(),
+                color.into().native(),
+            )
+        }
+    }
+
+    /// Uploads `src_data` to `backend_texture` in place. The number of pixmaps must match the
+    /// number of mip levels of the texture and their dimensions must match the dimensions of the
+    /// levels.
+    pub fn update_backend_texture(
+        &mut self,
+        backend_texture: &BackendTexture,
+        src_data: &[Pixmap],
+        origin: impl Into<Option<SurfaceOrigin>>,
+    ) -> bool {
+        let num_levels = match src_data.len().try_into() {
+            Ok(num_levels) => num_levels,
+            Err(_) => return false,
+        };
+        unsafe {
+            sb::C_GrDirectContext_updateBackendTexture2(
+                self.native_mut(),
+                backend_texture.native(),
+                src_data.native().as_ptr(),
+                num_levels,
+                origin.into().unwrap_or(SurfaceOrigin::TopLeft),
+            )
+        }
+    }
+
+    /// Reads the pixels of `backend_texture` starting at `src` into `dst`. The texture is
+    /// interpreted with `color_type` and `alpha_type` and converted to the format of `dst`.
+    ///
+    /// This flushes and waits for the GPU, so it should not be used on a per frame basis.
+    #[allow(clippy::too_many_arguments)]
+    pub fn read_backend_texture_pixels(
+        &mut self,
+        backend_texture: &BackendTexture,
+        origin: SurfaceOrigin,
+        color_type: ColorType,
+        alpha_type: AlphaType,
+        dst: &Pixmap,
+        src: impl Into<IPoint>,
+    ) -> bool {
+        match Image::from_texture(self, backend_texture, origin, color_type, alpha_type, None) {
+            Some(image) => {
+                image.read_pixels_to_pixmap_with_context(self, dst, src, CachingHint::Disallow)
+            }
+            None => false,
+        }
+    }
 
     pub fn compressed_backend_format(&self, compression: image::CompressionType) -> BackendFormat {
         let mut backend_format = BackendFormat::new_invalid();
@@ -322,12 +554,83 @@ impl DirectContext {
         backend_format
     }
 
-    // TODO: wrap createCompressedBackendTexture (several variants)
-    //       introduced in m81
-    //       extended in m84 with finishedProc and finishedContext
+    /// Creates a texture with the contents of `data`, which must be laid out in the block format
+    /// of `compression`. If `mipmapped` is [`Mipmapped::Yes`], `data` must contain all the mip
+    /// levels, starting with the base level.
+    ///
+    /// The client is responsible for deleting the returned texture with
+    /// [`Self::delete_backend_texture()`] once all the images and surfaces that use it are gone.
+    ///
+    /// Returns `None` if the compression type is not supported, or the texture could not be
+    /// created.
+    pub fn create_compressed_backend_texture(
+        &mut self,
+        dimensions: impl Into<ISize>,
+        compression: image::CompressionType,
+        data: &[u8],
+        mipmapped: Mipmapped,
+        is_protected: impl Into<Option<Protected>>,
+    ) -> Option<BackendTexture> {
+        let dimensions = dimensions.into();
+        unsafe {
+            BackendTexture::from_native_if_valid(
+                sb::C_GrDirectContext_createCompressedBackendTexture(
+                    self.native_mut(),
+                    dimensions.width,
+                    dimensions.height,
+                    compression,
+                    data.as_ptr() as _,
+                    data.len(),
+                    mipmapped,
+                    is_protected.into().unwrap_or(Protected::No),
+                ),
+            )
+        }
+    }
 
-    // TODO: wrap updateCompressedBackendTexture (two variants)
-    //       introduced in m86
+    /// Same as [`Self::create_compressed_backend_texture()`], but with an explicit backend
+    /// format that must be a compressed format, see [`Self::compressed_backend_format()`].
+    pub fn create_compressed_backend_texture_with_format(
+        &mut self,
+        dimensions: impl Into<ISize>,
+        backend_format: &BackendFormat,
+        data: &[u8],
+        mipmapped: Mipmapped,
+        is_protected: impl Into<Option<Protected>>,
+    ) -> Option<BackendTexture> {
+        let dimensions = dimensions.into();
+        unsafe {
+            BackendTexture::from_native_if_valid(
+                sb::C_GrDirectContext_createCompressedBackendTexture2(
+                    self.native_mut(),
+                    dimensions.width,
+                    dimensions.height,
+                    backend_format.native(),
+                    data.as_ptr() as _,
+                    data.len(),
+                    mipmapped,
+                    is_protected.into().unwrap_or(Protected::No),
+                ),
+            )
+        }
+    }
+
+    /// Replaces the contents of a compressed `backend_texture` with `data`. `data` must have the
+    /// same layout as the data the texture was created with.
+    pub fn update_compressed_backend_texture(
+        &mut self,
+        backend_texture: &BackendTexture,
+        data: &[u8],
+    ) -> bool {
+        unsafe {
+            sb::C_GrDirectContext_updateCompressedBackendTexture(
+                self.native_mut(),
+                backend_texture.native(),
+                data.as_ptr() as _,
+                data.len(),
+            )
+        }
+    }
 
     // TODO: add variant with GpuFinishedProc / GpuFinishedContext
     pub fn set_backend_texture_state(
@@ -385,8 +688,19 @@ impl DirectContext {
         .if_true_some(previous)
     }
 
-    // TODO: wrap deleteBackendTexture(),
+    /// Deletes a texture that was created with one of the `create_*_backend_texture` functions.
+    pub fn delete_backend_texture(&mut self, backend_texture: &BackendTexture) {
+        unsafe {
+            sb::C_GrDirectContext_deleteBackendTexture(self.native_mut(), backend_texture.native())
+        }
+    }
 
+    /// Compiles a shader from a `key` and `data` pair that was previously stored in a
+    /// [`super::PersistentCache`].
+    ///
+    /// This only works if the data was captured with
+    /// [`super::context_options::ShaderCacheStrategy::SkSL`]. Returns `true` if the shader was
+    /// compiled.
     pub fn precompile_shader(&mut self, key: &Data, data: &Data) -> bool {
         unsafe {
             self.native_mut()
@@ -394,9 +708,94 @@ impl DirectContext {
         }
     }
 
+    /// Warms up the shader cache by compiling all the `(key, data)` pairs, for example at
+    /// startup. Returns the number of shaders that were compiled.
+    ///
+    /// See [`Self::precompile_shader()`].
+    pub fn precompile_shaders<'a>(
+        &mut self,
+        shaders: impl IntoIterator<Item = (&'a Data, &'a Data)>,
+    ) -> usize {
+        shaders
+            .into_iter()
+            .filter(|(key, data)| self.precompile_shader(key, data))
+            .count()
+    }
+
     pub fn id(&self) -> DirectContextId {
         let mut id = DirectContextId { id: 0 };
         unsafe { sb::C_GrDirectContext_directContextId(self.native(), id.native_mut()) }
         id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::DirectContext;
+    use crate::{
+        gpu::{ContextOptions, Mipmapped, Renderable, SurfaceOrigin},
+        image,
+        prelude::*,
+        AlphaType, ColorType, ISize, ImageInfo, Pixmap,
+    };
+    use skia_bindings as sb;
+    use std::slice;
+
+    /// A context of Skia's mock backend, which tracks GPU resources but does not render.
+    fn new_mock_context() -> DirectContext {
+        DirectContext::from_ptr(unsafe {
+            sb::C_GrDirectContext_MakeMock(ContextOptions::default().native())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn create_update_and_delete_compressed_backend_texture() {
+        let mut context = new_mock_context();
+        let compression = image::CompressionType::ETC2_RGB8_UNORM;
+        // A single 4x4 ETC2 block.
+        let data = [0u8; 8];
+        let texture = context
+            .create_compressed_backend_texture((4, 4), compression, &data, Mipmapped::No, None)
+            .unwrap();
+        assert_eq!(texture.dimensions(), ISize::new(4, 4));
+        assert_eq!(
+            texture.backend_format(),
+            context.compressed_backend_format(compression)
+        );
+        assert!(!texture.has_mipmaps());
+        assert!(context.update_compressed_backend_texture(&texture, &data));
+        context.delete_backend_texture(&texture);
+    }
+
+    #[test]
+    fn create_update_and_read_back_backend_texture() {
+        let mut context = new_mock_context();
+        let info = ImageInfo::new((4, 4), ColorType::RGBA8888, AlphaType::Premul, None);
+        let pixels = [0x7fu8; 4 * 4 * 4];
+        let src = Pixmap::new(&info, &pixels, info.min_row_bytes());
+        let src_data = slice::from_ref(&*src);
+        let texture = context
+            .create_backend_texture_from_pixmaps(
+                src_data,
+                SurfaceOrigin::TopLeft,
+                Renderable::No,
+                None,
+            )
+            .unwrap();
+        assert_eq!(texture.dimensions(), ISize::new(4, 4));
+        assert!(context.update_backend_texture(&texture, src_data, None));
+
+        let read = [0u8; 4 * 4 * 4];
+        let dst = Pixmap::new(&info, &read, info.min_row_bytes());
+        assert!(context.read_backend_texture_pixels(
+            &texture,
+            SurfaceOrigin::TopLeft,
+            ColorType::RGBA8888,
+            AlphaType::Premul,
+            &dst,
+            (0, 0),
+        ));
+        context.delete_backend_texture(&texture);
+    }
+}
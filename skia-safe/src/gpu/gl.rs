@@ -1,3 +1,6 @@
+mod bound_framebuffer;
+pub use bound_framebuffer::*;
+
 mod extensions;
 pub use extensions::*;
 
@@ -0,0 +1,119 @@
+use super::{Format, FramebufferInfo, Interface, UInt};
+use crate::{
+    gpu::{BackendRenderTarget, DirectContext, SurfaceOrigin},
+    prelude::*,
+    ColorSpace, ColorType, ISize, Surface, SurfaceProps,
+};
+use skia_bindings as sb;
+
+/// The parameters of the framebuffer that was bound to the GL context when
+/// [`Interface::bound_framebuffer()`] was called.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct BoundFramebuffer {
+    pub info: FramebufferInfo,
+    /// The size of the current viewport, which matches the framebuffer's size unless the
+    /// viewport was changed after the context was made current.
+    pub size: ISize,
+    pub sample_count: usize,
+    pub stencil_bits: usize,
+}
+
+impl Interface {
+    /// Queries the framebuffer that is currently bound to the GL context, which is usually
+    /// the default framebuffer (FBO 0) of the window.
+    ///
+    /// Returns `None` if the GL context reports an error.
+    ///
+    /// The GL context must be current on the calling thread.
+    pub fn bound_framebuffer(&self) -> Option<BoundFramebuffer> {
+        let mut fboid = 0;
+        let mut viewport = [0; 4];
+        let mut samples = 0;
+        let mut stencil_bits = 0;
+        let mut color_bits = [0; 4];
+        unsafe {
+            sb::C_GrGLInterface_getBoundFramebuffer(
+                self.native(),
+                &mut fboid,
+                viewport.as_mut_ptr(),
+                &mut samples,
+                &mut stencil_bits,
+                color_bits.as_mut_ptr(),
+            )
+        }
+        .if_true_then_some(|| BoundFramebuffer {
+            info: FramebufferInfo {
+                fboid: fboid as UInt,
+                format: format_from_color_bits(color_bits).into(),
+            },
+            size: ISize::new(viewport[2], viewport[3]),
+            sample_count: samples.max(1) as usize,
+            stencil_bits: stencil_bits.max(0) as usize,
+        })
+    }
+}
+
+impl BoundFramebuffer {
+    /// Overrides the size, for example with the window's size in pixels when the viewport
+    /// does not cover the whole framebuffer.
+    #[must_use]
+    pub fn with_size(self, size: impl Into<ISize>) -> Self {
+        Self {
+            size: size.into(),
+            ..self
+        }
+    }
+
+    pub fn format(&self) -> Format {
+        Format::from(self.info.format)
+    }
+
+    /// The color type that matches the framebuffer's format.
+    pub fn color_type(&self) -> ColorType {
+        match self.format() {
+            Format::RGB8 => ColorType::RGB888x,
+            Format::RGB565 => ColorType::RGB565,
+            Format::RGBA4 => ColorType::ARGB4444,
+            Format::RGB10_A2 => ColorType::RGBA1010102,
+            Format::RGBA16F => ColorType::RGBAF16,
+            _ => ColorType::RGBA8888,
+        }
+    }
+
+    pub fn backend_render_target(&self) -> BackendRenderTarget {
+        BackendRenderTarget::new_gl(
+            (self.size.width, self.size.height),
+            self.sample_count,
+            self.stencil_bits,
+            self.info,
+        )
+    }
+
+    /// Wraps the framebuffer in a [`Surface`] with the origin at the bottom left.
+    pub fn to_surface(
+        &self,
+        context: &mut DirectContext,
+        color_space: impl Into<Option<ColorSpace>>,
+        surface_props: Option<&SurfaceProps>,
+    ) -> Option<Surface> {
+        Surface::from_backend_render_target(
+            context,
+            &self.backend_render_target(),
+            SurfaceOrigin::BottomLeft,
+            self.color_type(),
+            color_space,
+            surface_props,
+        )
+    }
+}
+
+fn format_from_color_bits(bits: [i32; 4]) -> Format {
+    match bits {
+        [8, 8, 8, 0] => Format::RGB8,
+        [5, 6, 5, 0] => Format::RGB565,
+        [4, 4, 4, 4] => Format::RGBA4,
+        [10, 10, 10, 2] => Format::RGB10_A2,
+        [16, 16, 16, 16] => Format::RGBA16F,
+        _ => Format::RGBA8,
+    }
+}
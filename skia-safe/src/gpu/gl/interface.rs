@@ -46,6 +46,29 @@ impl Interface {
         })
     }
 
+    /// Loads the GL functions with `load_fn` and returns the interface if it provides all the
+    /// functions Skia requires for the current context's GL version and extensions.
+    ///
+    /// `load_fn` has the same signature as the loader `glow` expects, so the function loader of
+    /// the windowing library can be shared:
+    ///
+    /// ```ignore
+    /// let interface = Interface::new_with_loader(|name| {
+    ///     display.get_proc_address(&CString::new(name).unwrap())
+    /// })?;
+    /// let context = DirectContext::new_gl(interface, None)?;
+    /// ```
+    ///
+    /// The GL context must be current on the calling thread.
+    pub fn new_with_loader<F>(load_fn: F) -> Option<Self>
+    where
+        F: FnMut(&str) -> *const c_void,
+    {
+        Self::new_load_with(load_fn).filter(|interface| interface.validate())
+    }
+
+    /// Validates that the interface provides all the functions Skia requires for its GL version
+    /// and extensions.
     pub fn validate(&self) -> bool {
         unsafe { self.native().validate() }
     }
@@ -7,8 +7,16 @@ variant_name!(Format::ALPHA8);
 pub use skia_bindings::GrGLStandard as Standard;
 variant_name!(Standard::GLES);
 pub use skia_bindings::GrGLenum as Enum;
+pub use skia_bindings::GrGLsync as GLsync;
 pub use skia_bindings::GrGLuint as UInt;
 
+/// `GL_TEXTURE_2D`, the target of regular textures.
+pub const TEXTURE_2D: Enum = 0x0DE1;
+/// `GL_TEXTURE_RECTANGLE`, requires `GL_ARB_texture_rectangle` or `GL_ANGLE_texture_rectangle`.
+pub const TEXTURE_RECTANGLE: Enum = 0x84F5;
+/// `GL_TEXTURE_EXTERNAL_OES`, requires `GL_OES_EGL_image_external`.
+pub const TEXTURE_EXTERNAL_OES: Enum = 0x8D65;
+
 #[derive(Copy, Clone, Eq, Default, Debug)]
 #[repr(C)]
 pub struct TextureInfo {
@@ -33,6 +41,25 @@ impl TextureInfo {
             format: 0,
         }
     }
+
+    /// Describes the external texture `id`, for example a texture an `EGLImage` was bound to
+    /// with `glEGLImageTargetTexture2DOES(GL_TEXTURE_EXTERNAL_OES, image)` or the texture of an
+    /// Android `SurfaceTexture`.
+    ///
+    /// `format` is the format Skia assumes for sampling, usually [`Format::RGBA8`].
+    /// A [`gpu::BackendTexture`] that wraps an external texture can only be read from, for
+    /// example with [`crate::Image::from_texture()`], and not be rendered to or have mipmaps.
+    pub fn new_external(id: UInt, format: Format) -> Self {
+        Self {
+            target: TEXTURE_EXTERNAL_OES,
+            id,
+            format: format.into(),
+        }
+    }
+
+    pub fn is_external(&self) -> bool {
+        self.target == TEXTURE_EXTERNAL_OES
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
@@ -100,7 +127,15 @@ bitflags! {
 
 #[cfg(test)]
 mod tests {
-    use super::{Enum, Format};
+    use super::{Enum, Format, TextureInfo};
+
+    #[test]
+    fn test_external_texture_info() {
+        let info = TextureInfo::new_external(1, Format::RGBA8);
+        assert!(info.is_external());
+        assert_eq!(Format::from(info.format), Format::RGBA8);
+        assert!(!TextureInfo::from_target_and_id(super::TEXTURE_2D, 1).is_external());
+    }
 
     #[test]
     fn test_support_from_format_to_enum_and_back() {
@@ -1,5 +1,10 @@
 mod backend_context;
 pub use backend_context::*;
+mod metal_drawable;
+pub use metal_drawable::*;
+
+#[cfg(feature = "objc2-metal")]
+mod objc2_interop;
 
 mod types;
 pub use types::*;
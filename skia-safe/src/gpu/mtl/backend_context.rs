@@ -25,6 +25,12 @@ impl BackendContext {
     ///
     /// This function retains all the non-`null` handles passed to it and releases them as soon the
     /// [BackendContext] is dropped.
+    ///
+    /// If `binary_archive` is a `MTLBinaryArchive` (macOS 11, iOS 14 and later), Skia looks up
+    /// the render pipelines it needs in the archive and adds the ones it compiles to it. To reuse
+    /// the compiled pipelines across launches, create the archive from a previously serialized
+    /// file and serialize it with `-[MTLBinaryArchive serializeToURL:error:]`, for example when
+    /// the application moves to the background.
     pub unsafe fn new(device: Handle, queue: Handle, binary_archive: Handle) -> Self {
         BackendContext::construct(|bc| {
             sb::C_GrMtlBackendContext_Construct(bc, device, queue, binary_archive)
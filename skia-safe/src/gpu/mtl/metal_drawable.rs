@@ -0,0 +1,104 @@
+use super::{BackendContext, Handle};
+use crate::{
+    gpu::{DirectContext, SurfaceOrigin},
+    prelude::*,
+    Canvas, ColorSpace, ColorType, Surface, SurfaceProps,
+};
+use skia_bindings as sb;
+use std::{fmt, ptr};
+
+/// The `CAMetalDrawable` a [`Surface`] created with [`Surface::from_ca_metal_layer()`] renders
+/// to.
+///
+/// The drawable is acquired from the layer when the surface's GPU work is flushed for the first
+/// time. It is released when the `MetalDrawable` is dropped or presented.
+pub struct MetalDrawable {
+    // Boxed, because Skia writes the drawable to this location when the surface gets
+    // instantiated.
+    handle: Box<Handle>,
+}
+
+impl Drop for MetalDrawable {
+    fn drop(&mut self) {
+        if !self.handle.is_null() {
+            unsafe { sb::C_GrMTLHandle_release(*self.handle) }
+        }
+    }
+}
+
+impl fmt::Debug for MetalDrawable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetalDrawable")
+            .field("handle", &self.handle)
+            .finish()
+    }
+}
+
+impl MetalDrawable {
+    pub(crate) fn new() -> Self {
+        Self {
+            handle: Box::new(ptr::null()),
+        }
+    }
+
+    pub(crate) fn handle_ptr(&mut self) -> *mut Handle {
+        &mut *self.handle
+    }
+
+    /// Returns the `CAMetalDrawable`, or `None` if it was not acquired from the layer yet.
+    pub fn handle(&self) -> Option<Handle> {
+        (!self.handle.is_null()).if_true_some(*self.handle)
+    }
+
+    /// Presents the drawable with a new command buffer of the queue of `backend` and releases it.
+    ///
+    /// The surface's work must have been flushed and submitted before, which also acquires the
+    /// drawable. Returns `false` if the drawable was not acquired or could not be presented.
+    pub fn present(self, backend: &BackendContext) -> bool {
+        match self.handle() {
+            Some(drawable) => unsafe {
+                sb::C_CAMetalDrawable_present(backend.native().fQueue.fObject, drawable)
+            },
+            None => false,
+        }
+    }
+}
+
+/// Renders and presents one frame to a `CAMetalLayer`: acquires the next drawable of `layer`,
+/// calls `draw` with a canvas that renders to it, flushes and submits the work of `context`, and
+/// presents the drawable with the queue of `backend`.
+///
+/// Returns `false` if no surface could be created for the layer or the drawable could not be
+/// presented.
+///
+/// # Safety
+/// `layer` must be a `CAMetalLayer` whose device is the device of `backend` and `context`.
+#[allow(clippy::too_many_arguments)]
+pub unsafe fn draw_frame(
+    context: &mut DirectContext,
+    backend: &BackendContext,
+    layer: Handle,
+    origin: SurfaceOrigin,
+    sample_count: impl Into<Option<usize>>,
+    color_type: ColorType,
+    color_space: impl Into<Option<ColorSpace>>,
+    surface_props: Option<&SurfaceProps>,
+    draw: impl FnOnce(&mut Canvas),
+) -> bool {
+    let (mut surface, drawable) = match Surface::from_ca_metal_layer(
+        context,
+        layer,
+        origin,
+        sample_count,
+        color_type,
+        color_space,
+        surface_props,
+    ) {
+        Some(surface_and_drawable) => surface_and_drawable,
+        None => return false,
+    };
+    draw(surface.canvas());
+    surface.flush_and_submit();
+    drop(surface);
+    drawable.present(backend)
+}
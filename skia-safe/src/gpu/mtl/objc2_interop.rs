@@ -0,0 +1,58 @@
+//! Typed `objc2-metal` variants of the APIs that take or return raw [`Handle`]s.
+
+use super::{BackendContext, Handle, TextureInfo};
+use crate::gpu::BackendSemaphore;
+use objc2::{rc::Retained, runtime::ProtocolObject};
+use objc2_metal::{MTLBinaryArchive, MTLCommandQueue, MTLDevice, MTLEvent, MTLTexture};
+
+fn handle<T: ?Sized>(object: &T) -> Handle {
+    (object as *const T).cast()
+}
+
+impl BackendContext {
+    /// Creates a backend context for `device` and `queue`. Both are retained until the
+    /// `BackendContext` is dropped.
+    pub fn new_with_objc2(
+        device: &ProtocolObject<dyn MTLDevice>,
+        queue: &ProtocolObject<dyn MTLCommandQueue>,
+    ) -> Self {
+        unsafe { Self::new(handle(device), handle(queue), std::ptr::null()) }
+    }
+
+    /// Creates a backend context that caches the compiled render pipelines in `binary_archive`.
+    /// See [`Self::new()`] for how to persist the archive.
+    pub fn new_with_objc2_binary_archive(
+        device: &ProtocolObject<dyn MTLDevice>,
+        queue: &ProtocolObject<dyn MTLCommandQueue>,
+        binary_archive: &ProtocolObject<dyn MTLBinaryArchive>,
+    ) -> Self {
+        unsafe { Self::new(handle(device), handle(queue), handle(binary_archive)) }
+    }
+}
+
+impl TextureInfo {
+    /// Describes `texture`, which is retained until the `TextureInfo` is dropped.
+    pub fn from_objc2_texture(texture: &ProtocolObject<dyn MTLTexture>) -> Self {
+        unsafe { Self::new(handle(texture)) }
+    }
+
+    /// Returns a retained reference to the texture, or `None` if no texture is set.
+    pub fn objc2_texture(&self) -> Option<Retained<ProtocolObject<dyn MTLTexture>>> {
+        unsafe { Retained::retain(self.texture() as *mut ProtocolObject<dyn MTLTexture>) }
+    }
+}
+
+impl BackendSemaphore {
+    /// Creates a semaphore that waits on or signals `event` with `value`. The semaphore takes
+    /// over the reference of `event`.
+    pub fn new_metal_event(event: Retained<ProtocolObject<dyn MTLEvent>>, value: u64) -> Self {
+        Self::new_metal(Retained::into_raw(event).cast_const().cast(), value)
+    }
+
+    /// Returns a retained reference to the Metal event of the semaphore, or `None` if this is not
+    /// an initialized Metal semaphore.
+    pub fn objc2_event(&self) -> Option<Retained<ProtocolObject<dyn MTLEvent>>> {
+        let event = self.mtl_semaphore()?;
+        unsafe { Retained::retain(event as *mut ProtocolObject<dyn MTLEvent>) }
+    }
+}
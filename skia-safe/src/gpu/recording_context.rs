@@ -1,10 +1,13 @@
-use std::fmt;
+use std::{fmt, mem};
 
 use crate::{
-    gpu::{BackendAPI, BackendFormat, DirectContext, Renderable},
+    gpu::{
+        BackendAPI, BackendFormat, ContextThreadSafeProxy, DirectContext, Mipmapped, Protected,
+        Renderable, SurfaceOrigin,
+    },
     image,
     prelude::*,
-    ColorType,
+    AlphaType, ColorType, ImageInfo, SurfaceProps,
 };
 use skia_bindings::{self as sb, GrRecordingContext, SkRefCntBase};
 
@@ -73,8 +76,18 @@ impl RecordingContext {
         format
     }
 
-    // TODO: GrContext_Base::threadSafeProxy
+    // From GrContext_Base
+    pub fn thread_safe_proxy(&mut self) -> ContextThreadSafeProxy {
+        ContextThreadSafeProxy::from_ptr(unsafe {
+            sb::C_GrRecordingContext_threadSafeProxy(self.native_mut())
+        })
+        .unwrap()
+    }
 
+    /// Returns `true` if the context was abandoned, either explicitly or because the backend
+    /// device was lost. In the latter case, the context gets abandoned by this call.
+    ///
+    /// See [`DirectContext`] on how to recover from a lost device.
     pub fn abandoned(&mut self) -> bool {
         unsafe { sb::C_GrRecordingContext_abandoned(self.native_mut()) }
     }
@@ -114,5 +127,142 @@ impl RecordingContext {
         .unwrap()
     }
 
+    //
+    // Format queries
+    //
+    // Skia only offers capability queries per color type, so a format is checked through the
+    // color types whose default backend format it is.
+    //
+
+    /// Returns `true` if textures of `format` can be created and sampled from.
+    ///
+    /// Uncompressed formats that are not the default backend format of a [`ColorType`] are
+    /// reported as not texturable.
+    pub fn is_format_texturable(&self, format: &BackendFormat) -> bool {
+        self.is_format_compressed(format)
+            || self
+                .color_types_of_format(format, Renderable::No)
+                .any(|ct| self.color_type_supported_as_image(ct))
+    }
+
+    /// Returns `true` if `format` can be rendered to with the given sample count.
+    ///
+    /// Formats that are not the default renderable backend format of a [`ColorType`] are
+    /// reported as not renderable.
+    pub fn is_format_renderable(&self, format: &BackendFormat, sample_count: usize) -> bool {
+        let max_sample_count = self.max_render_target_sample_count(format);
+        max_sample_count > 0 && sample_count <= max_sample_count
+    }
+
+    pub fn is_format_compressed(&self, format: &BackendFormat) -> bool {
+        [
+            image::CompressionType::ETC2_RGB8_UNORM,
+            image::CompressionType::BC1_RGB8_UNORM,
+            image::CompressionType::BC1_RGBA8_UNORM,
+        ]
+        .into_iter()
+        .any(|compression_type| self.compressed_backend_format(compression_type) == *format)
+    }
+
+    /// Returns the sample count that is used when a render target of `format` is created with
+    /// `requested_count` samples, or `None` if `format` is not renderable.
+    ///
+    /// The returned count is at least `requested_count` and it is `1` when `requested_count` is
+    /// `1` or less.
+    pub fn render_target_sample_count(
+        &self,
+        requested_count: usize,
+        format: &BackendFormat,
+    ) -> Option<usize> {
+        let color_type = self.color_types_of_format(format, Renderable::Yes).next()?;
+        self.clone()
+            .thread_safe_proxy()
+            .create_characterization(
+                0,
+                &ImageInfo::new((1, 1), color_type, AlphaType::Premul, None),
+                format,
+                requested_count.clamp(1, i32::MAX as usize),
+                SurfaceOrigin::TopLeft,
+                &SurfaceProps::default(),
+                false,
+                false,
+                false,
+                None,
+            )
+            .map(|characterization| characterization.sample_count())
+    }
+
+    /// Returns the maximum supported sample count for render targets of `format`, or `0` if
+    /// `format` is not renderable.
+    pub fn max_render_target_sample_count(&self, format: &BackendFormat) -> usize {
+        self.color_types_of_format(format, Renderable::Yes)
+            .map(|ct| self.max_surface_sample_count_for_color_type(ct))
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn mipmap_support(&self) -> bool {
+        // Skia drops the mipmaps of surface characterizations if the context does not support
+        // them.
+        let format = self.default_backend_format(ColorType::RGBA8888, Renderable::Yes);
+        self.clone()
+            .thread_safe_proxy()
+            .create_characterization(
+                0,
+                &ImageInfo::new((1, 1), ColorType::RGBA8888, AlphaType::Premul, None),
+                &format,
+                1,
+                SurfaceOrigin::TopLeft,
+                &SurfaceProps::default(),
+                true,
+                false,
+                true,
+                None,
+            )
+            .map_or(false, |characterization| characterization.is_mip_mapped())
+    }
+
+    /// Returns `true` if the context can create and render to protected (DRM) textures and
+    /// surfaces.
+    ///
+    /// This is only the case for Vulkan contexts that were created from a
+    /// [`crate::gpu::vk::BackendContext`] with a protected context, see
+    /// [`crate::gpu::vk::BackendContext::set_protected_context()`]. The OpenGL backend does not
+    /// support protected content.
+    ///
+    /// The support is determined by creating a protected texture, so this returns `false` for
+    /// contexts that are not a [`DirectContext`].
+    pub fn supports_protected_content(&self) -> bool {
+        let mut context = match self.clone().as_direct_context() {
+            Some(context) => context,
+            None => return false,
+        };
+        let format = context.default_backend_format(ColorType::RGBA8888, Renderable::No);
+        match context.create_backend_texture(
+            (1, 1),
+            &format,
+            Mipmapped::No,
+            Renderable::No,
+            Protected::Yes,
+        ) {
+            Some(texture) => {
+                context.delete_backend_texture(&texture);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the color types whose default backend format for `renderable` is `format`.
+    fn color_types_of_format<'a>(
+        &'a self,
+        format: &'a BackendFormat,
+        renderable: Renderable,
+    ) -> impl Iterator<Item = ColorType> + 'a {
+        (1..ColorType::COUNT)
+            .map(|i| unsafe { mem::transmute::<i32, ColorType>(i as _) })
+            .filter(move |ct| self.default_backend_format(*ct, renderable) == *format)
+    }
+
     // TODO: Wrap Arenas (if used).
 }
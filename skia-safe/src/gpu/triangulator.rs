@@ -0,0 +1,135 @@
+use crate::{interop::VecSink, prelude::*, scalar, Path, Point, Rect};
+use skia_bindings::{self as sb, SkPoint};
+use std::collections::HashMap;
+
+/// The tolerance Skia uses by default when it approximates curves with line segments, in device
+/// pixels.
+pub const DEFAULT_TRIANGULATION_TOLERANCE: scalar = 0.25;
+
+/// An indexed triangle list that covers the filled area of a [`Path`].
+///
+/// Every three consecutive entries in `indices` form a triangle. Shared corners are stored once
+/// in `vertices`.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct Triangles {
+    pub vertices: Vec<Point>,
+    pub indices: Vec<u32>,
+    /// `true` if the path consists only of lines, so that the triangles do not depend on the
+    /// tolerance and can be transformed freely without being regenerated.
+    pub is_linear: bool,
+}
+
+impl Triangles {
+    /// Returns `true` if there are no triangles.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// The number of triangles.
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Returns an iterator over the corners of each triangle.
+    pub fn triangles(&self) -> impl Iterator<Item = [Point; 3]> + '_ {
+        self.indices.chunks_exact(3).map(|t| {
+            [
+                self.vertices[t[0] as usize],
+                self.vertices[t[1] as usize],
+                self.vertices[t[2] as usize],
+            ]
+        })
+    }
+
+    fn from_triangle_list(points: &[Point], is_linear: bool) -> Self {
+        let mut vertices = Vec::new();
+        let mut lookup = HashMap::new();
+        let indices = points
+            .iter()
+            .map(|p| {
+                *lookup
+                    .entry((p.x.to_bits(), p.y.to_bits()))
+                    .or_insert_with(|| {
+                        vertices.push(*p);
+                        (vertices.len() - 1) as u32
+                    })
+            })
+            .collect();
+        Self {
+            vertices,
+            indices,
+            is_linear,
+        }
+    }
+}
+
+/// Triangulates the filled area of `path` the same way the GPU backend does when it renders
+/// complex paths without MSAA.
+///
+/// Curves are approximated with line segments that deviate at most `tolerance` from the curve,
+/// see [`DEFAULT_TRIANGULATION_TOLERANCE`]. `clip_bounds` limits the area that is generated for
+/// inverse fill types and is otherwise ignored.
+///
+/// The returned triangles are empty if `path` is empty, not finite, or could not be triangulated.
+pub fn triangulate_path(
+    path: &Path,
+    tolerance: scalar,
+    clip_bounds: impl AsRef<Rect>,
+) -> Triangles {
+    let mut points: Vec<Point> = Vec::new();
+    let mut set_points = |triangles: &[SkPoint]| {
+        points = triangles
+            .iter()
+            .map(Point::from_native_ref)
+            .copied()
+            .collect();
+    };
+    let mut is_linear = false;
+    unsafe {
+        sb::C_GrTriangulator_PathToTriangles(
+            path.native(),
+            tolerance,
+            clip_bounds.as_ref().native(),
+            VecSink::new(&mut set_points).native_mut(),
+            &mut is_linear,
+        )
+    }
+    Triangles::from_triangle_list(&points, is_linear)
+}
+
+impl Path {
+    /// Triangulates the filled area of the path, see [`triangulate_path()`].
+    pub fn triangulate(&self, tolerance: scalar, clip_bounds: impl AsRef<Rect>) -> Triangles {
+        triangulate_path(self, tolerance, clip_bounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn triangulate_rect() {
+        let path = Path::rect(Rect::new(10.0, 10.0, 20.0, 30.0), None);
+        let triangles = path.triangulate(DEFAULT_TRIANGULATION_TOLERANCE, path.bounds());
+        assert!(triangles.is_linear);
+        assert_eq!(triangles.triangle_count(), 2);
+        assert_eq!(triangles.vertices.len(), 4);
+        let area: scalar = triangles
+            .triangles()
+            .map(|[a, b, c]| ((b - a).cross(c - a) / 2.0).abs())
+            .sum();
+        assert_eq!(area, 200.0);
+    }
+
+    #[test]
+    fn triangulate_circle() {
+        let path = Path::circle((0.0, 0.0), 10.0, None);
+        let clip = Rect::new(-10.0, -10.0, 10.0, 10.0);
+        let coarse = path.triangulate(1.0, clip);
+        let fine = path.triangulate(0.01, clip);
+        assert!(!coarse.is_linear);
+        assert!(coarse.triangle_count() < fine.triangle_count());
+        assert!(Path::new().triangulate(1.0, clip).is_empty());
+    }
+}
@@ -1,5 +1,9 @@
+use crate::{gpu::BackendSemaphore, prelude::*};
 use skia_bindings as sb;
-use std::ptr;
+use std::{
+    ptr,
+    sync::{Arc, Mutex},
+};
 
 pub use skia_bindings::GrBackendApi as BackendAPI;
 variant_name!(BackendAPI::Dawn);
@@ -52,7 +56,7 @@ variant_name!(SurfaceOrigin::BottomLeft);
 #[allow(dead_code)]
 #[derive(Debug)]
 pub struct FlushInfo {
-    // TODO: wrap access to the following fields in a safe way:
+    // TODO: wrap access to the submitted proc in a safe way:
     num_semaphores: usize,
     signal_semaphores: *mut sb::GrBackendSemaphore,
     finished_proc: sb::GrGpuFinishedProc,
@@ -76,7 +80,151 @@ impl Default for FlushInfo {
 
 native_transmutable!(sb::GrFlushInfo, FlushInfo, flush_info_layout);
 
+impl Drop for FlushInfo {
+    fn drop(&mut self) {
+        if !self.finished_context.is_null() {
+            unsafe { Arc::decrement_strong_count(self.finished_context as *const FinishedCallback) }
+        }
+    }
+}
+
+/// The closure set with [`FlushInfo::set_finished_proc()`]. It is shared between the
+/// [`FlushInfo`] and every flush it was passed to.
+type FinishedCallback = Mutex<Option<Box<dyn FnOnce() + Send>>>;
+
+impl FlushInfo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// See [`Self::set_finished_proc()`].
+    pub fn with_finished_proc(mut self, finished: impl FnOnce() + Send + 'static) -> Self {
+        self.set_finished_proc(finished);
+        self
+    }
+
+    /// Sets a closure that is called when the GPU has finished all the work of the flush this
+    /// `FlushInfo` is passed to. The closure is called even if the flush fails, but it may be
+    /// called from an arbitrary thread that calls into the [`crate::gpu::DirectContext`].
+    ///
+    /// If the `FlushInfo` is passed to more than one flush, the closure is called once, after the
+    /// work of the first completed flush has finished. Flushes that do not reach a GPU backend
+    /// (for example flushing a raster [`crate::Surface`]) never call it.
+    ///
+    /// The backends detect the completion of the GPU work in different ways: on Metal, the
+    /// completion handler of the command buffer marks the work as finished, and the closure is
+    /// then called by the next call into the context that checks for finished work, for example
+    /// [`crate::gpu::DirectContext::check_async_work_completion()`], a flush, or a submit.
+    pub fn set_finished_proc(&mut self, finished: impl FnOnce() + Send + 'static) -> &mut Self {
+        let callback: Arc<FinishedCallback> = Arc::new(Mutex::new(Some(Box::new(finished))));
+        if !self.finished_context.is_null() {
+            unsafe { Arc::decrement_strong_count(self.finished_context as *const FinishedCallback) }
+        }
+        self.finished_proc = Some(finished_proc);
+        self.finished_context = Arc::into_raw(callback) as sb::GrGpuFinishedContext;
+        self
+    }
+
+    /// Returns the native `GrFlushInfo` to be passed to exactly one flush call that reaches a GPU
+    /// backend.
+    ///
+    /// Every such flush calls the finished proc once, which releases the reference to the closure
+    /// that is acquired here.
+    pub(crate) fn native_for_flush(&self) -> sb::GrFlushInfo {
+        if !self.finished_context.is_null() {
+            unsafe { Arc::increment_strong_count(self.finished_context as *const FinishedCallback) }
+        }
+        sb::GrFlushInfo { ..*self.native() }
+    }
+
+    /// Returns the native `GrFlushInfo` to be passed to a flush that does not reach a GPU backend.
+    ///
+    /// Skia never calls the finished proc of these flushes, so it is not passed on.
+    pub(crate) fn native_for_flush_without_gpu(&self) -> sb::GrFlushInfo {
+        sb::GrFlushInfo {
+            fFinishedProc: None,
+            fFinishedContext: ptr::null_mut(),
+            ..*self.native()
+        }
+    }
+
+    /// Same as [`Self::native_for_flush()`], but also signals `semaphores` after the flushed work.
+    ///
+    /// The returned `GrFlushInfo` points into `semaphores` and must not outlive it.
+    pub(crate) fn native_for_flush_with_signal_semaphores(
+        &self,
+        semaphores: &mut [BackendSemaphore],
+    ) -> sb::GrFlushInfo {
+        sb::GrFlushInfo {
+            fNumSemaphores: semaphores.len(),
+            fSignalSemaphores: if semaphores.is_empty() {
+                ptr::null_mut()
+            } else {
+                semaphores[0].native_mut()
+            },
+            ..self.native_for_flush()
+        }
+    }
+}
+
+unsafe extern "C" fn finished_proc(context: sb::GrGpuFinishedContext) {
+    let callback = Arc::from_raw(context as *const FinishedCallback);
+    let finished = callback.lock().unwrap().take();
+    drop(callback);
+    if let Some(finished) = finished {
+        finished()
+    }
+}
+
 pub use sb::GrSemaphoresSubmitted as SemaphoresSubmitted;
 variant_name!(SemaphoresSubmitted::Yes);
 
 // TODO: wrap GrPrepareForExternalIORequests
+
+#[cfg(test)]
+mod tests {
+    use super::FlushInfo;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn finished_proc_is_called_once_and_released() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let info = {
+            let calls = calls.clone();
+            FlushInfo::new().with_finished_proc(move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+
+        for _ in 0..2 {
+            let native = info.native_for_flush();
+            unsafe { (native.fFinishedProc.unwrap())(native.fFinishedContext) }
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        drop(info);
+        assert_eq!(Arc::strong_count(&calls), 1);
+    }
+
+    #[test]
+    fn flushes_without_gpu_do_not_retain_the_finished_proc() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let info = {
+            let calls = calls.clone();
+            FlushInfo::new().with_finished_proc(move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+
+        let native = info.native_for_flush_without_gpu();
+        assert!(native.fFinishedProc.is_none());
+        assert!(native.fFinishedContext.is_null());
+
+        drop(info);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(Arc::strong_count(&calls), 1);
+    }
+}
@@ -2,11 +2,23 @@ use skia_bindings as sb;
 use std::ops::Deref;
 use std::ptr;
 
+#[cfg(feature = "ash")]
+mod ash_interop;
+#[cfg(feature = "ash")]
+pub use ash_interop::*;
 mod backend_context;
 pub use backend_context::*;
 
+mod memory_allocator;
+pub use memory_allocator::*;
+mod secondary_cb_draw_context;
+pub use secondary_cb_draw_context::*;
+#[cfg(feature = "ash")]
+pub mod swapchain;
 mod types;
 pub use types::*;
+#[cfg(feature = "wgpu")]
+mod wgpu_interop;
 
 //
 // Additional Vulkan re-exports and definitions.
@@ -38,9 +50,14 @@ pub use sb::VkRect2D as Rect2D;
 pub use sb::VkRenderPass as RenderPass;
 pub use sb::VkSamplerYcbcrModelConversion as SamplerYcbcrModelConversion;
 pub use sb::VkSamplerYcbcrRange as SamplerYcbcrRange;
+pub use sb::VkSemaphore as Semaphore;
 pub use sb::VkSharingMode as SharingMode;
 
 pub const QUEUE_FAMILY_IGNORED: u32 = !0;
+/// `VK_QUEUE_FAMILY_EXTERNAL`
+pub const QUEUE_FAMILY_EXTERNAL: u32 = !1;
+/// `VK_QUEUE_FAMILY_FOREIGN_EXT`
+pub const QUEUE_FAMILY_FOREIGN_EXT: u32 = !2;
 
 //
 // VK_NULL_HANDLE and conversions.
@@ -108,6 +125,13 @@ impl From<NullHandle> for RenderPass {
     }
 }
 
+#[cfg(target_pointer_width = "64")]
+impl From<NullHandle> for Semaphore {
+    fn from(_: NullHandle) -> Self {
+        ptr::null_mut()
+    }
+}
+
 #[cfg(not(target_pointer_width = "64"))]
 impl From<NullHandle> for u64 {
     fn from(_: NullHandle) -> Self {
@@ -0,0 +1,145 @@
+//! Conversions between the Vulkan types used by Skia and the types of the [`ash`] crate.
+
+use super::{Device, Format, Image, ImageLayout, Instance, PhysicalDevice, Queue, Semaphore};
+use ::ash::vk::{self as ash_vk, Handle};
+use std::{mem, ops::RangeInclusive};
+
+/// Converts an `ash` Vulkan type into the corresponding type Skia uses.
+pub trait FromAsh<A> {
+    fn from_ash(ash: A) -> Self;
+}
+
+/// Converts an `ash` Vulkan enum into the corresponding enum Skia uses.
+///
+/// Returns `None` for values that are not declared in the Vulkan headers Skia is built with, for
+/// example formats of extensions or newer Vulkan versions.
+pub trait TryFromAsh<A>: Sized {
+    fn try_from_ash(ash: A) -> Option<Self>;
+}
+
+/// Converts a Vulkan type Skia uses into the corresponding `ash` type.
+pub trait IntoAsh<A> {
+    fn into_ash(self) -> A;
+}
+
+macro_rules! ash_handle {
+    ($skia:ty, $ash:ty) => {
+        impl FromAsh<$ash> for $skia {
+            fn from_ash(ash: $ash) -> Self {
+                ash.as_raw() as _
+            }
+        }
+
+        impl IntoAsh<$ash> for $skia {
+            fn into_ash(self) -> $ash {
+                <$ash>::from_raw(self as u64)
+            }
+        }
+    };
+}
+
+ash_handle!(Instance, ash_vk::Instance);
+ash_handle!(PhysicalDevice, ash_vk::PhysicalDevice);
+ash_handle!(Device, ash_vk::Device);
+ash_handle!(Queue, ash_vk::Queue);
+// On 32 bit platforms, non-dispatchable handles are all represented by `u64`.
+ash_handle!(Image, ash_vk::Image);
+ash_handle!(Semaphore, ash_vk::Semaphore);
+
+macro_rules! ash_enum {
+    ($skia:ty, $ash:ty, [$($range:expr),* $(,)?]) => {
+        impl TryFromAsh<$ash> for $skia {
+            fn try_from_ash(ash: $ash) -> Option<Self> {
+                let raw = ash.as_raw();
+                let ranges: &[RangeInclusive<i32>] = &[$($range),*];
+                ranges
+                    .iter()
+                    .any(|range| range.contains(&raw))
+                    .then(|| unsafe { mem::transmute::<i32, Self>(raw) })
+            }
+        }
+
+        impl IntoAsh<$ash> for $skia {
+            fn into_ash(self) -> $ash {
+                <$ash>::from_raw(self as i32)
+            }
+        }
+    };
+}
+
+// The ranges list the values of Vulkan 1.3 and its promoted extensions. All of them are declared
+// in the Vulkan headers Skia is built with, so every value in a range is a variant of Skia's enum.
+// Values of other extensions convert to `None`.
+
+ash_enum!(
+    ImageLayout,
+    ash_vk::ImageLayout,
+    [
+        // Vulkan 1.0
+        0..=8,
+        // VK_KHR_swapchain
+        1000001002..=1000001002,
+        // VK_KHR_shared_presentable_image
+        1000111000..=1000111000,
+        // Vulkan 1.1
+        1000117000..=1000117001,
+        // Vulkan 1.2
+        1000241000..=1000241003,
+        // Vulkan 1.3
+        1000314000..=1000314001,
+    ]
+);
+
+ash_enum!(
+    Format,
+    ash_vk::Format,
+    [
+        // Vulkan 1.0
+        0..=184,
+        // VK_IMG_format_pvrtc
+        1000054000..=1000054007,
+        // Vulkan 1.3, ASTC HDR formats
+        1000066000..=1000066013,
+        // Vulkan 1.1, YCbCr formats
+        1000156000..=1000156033,
+        // Vulkan 1.3, 4:4:4 two-plane formats
+        1000330000..=1000330003,
+        // Vulkan 1.3, 4444 formats
+        1000340000..=1000340001,
+    ]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{IntoAsh, TryFromAsh};
+    use crate::gpu::vk;
+    use ::ash::vk as ash_vk;
+
+    #[test]
+    fn enums_round_trip() {
+        let layout: ash_vk::ImageLayout = vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL.into_ash();
+        assert_eq!(layout, ash_vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+        let format = vk::Format::try_from_ash(ash_vk::Format::R8G8B8A8_UNORM);
+        assert_eq!(format, Some(vk::Format::R8G8B8A8_UNORM));
+        let astc = ash_vk::Format::ASTC_12X12_SRGB_BLOCK;
+        assert_eq!(
+            vk::Format::try_from_ash(astc).map(IntoAsh::into_ash),
+            Some(astc)
+        );
+        let ycbcr = ash_vk::Format::G16_B16R16_2PLANE_444_UNORM;
+        assert_eq!(
+            vk::Format::try_from_ash(ycbcr).map(IntoAsh::into_ash),
+            Some(ycbcr)
+        );
+        let layout = ash_vk::ImageLayout::DEPTH_READ_ONLY_OPTIMAL;
+        assert_eq!(
+            vk::ImageLayout::try_from_ash(layout).map(IntoAsh::into_ash),
+            Some(layout)
+        );
+        assert_eq!(vk::Format::try_from_ash(ash_vk::Format::from_raw(-1)), None);
+        assert_eq!(
+            vk::ImageLayout::try_from_ash(ash_vk::ImageLayout::from_raw(-1)),
+            None
+        );
+    }
+}
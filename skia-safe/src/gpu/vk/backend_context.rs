@@ -1,4 +1,7 @@
-use super::{Device, GetProc, GetProcOf, Instance, PhysicalDevice, Queue, Version};
+use super::{
+    memory_allocator, Device, GetProc, GetProcOf, Instance, MemoryAllocator, PhysicalDevice,
+    PhysicalDeviceFeatures2, Queue, Version,
+};
 use crate::{gpu, prelude::*};
 use ffi::CString;
 use raw::c_char;
@@ -89,6 +92,56 @@ impl BackendContext<'_> {
         get_proc: &'a impl GetProc,
         instance_extensions: &[&str],
         device_extensions: &[&str],
+    ) -> BackendContext<'a> {
+        Self::new_with_dyn_get_proc(
+            instance,
+            physical_device,
+            device,
+            (queue, queue_index),
+            get_proc,
+            instance_extensions,
+            device_extensions,
+        )
+    }
+
+    /// Returns a [`BackendContextBuilder`] that configures the extensions, the device features,
+    /// and further options of the `BackendContext` before it is created.
+    ///
+    /// `get_proc` may be any closure that resolves Vulkan function pointers, for example one that
+    /// forwards to `vkGetInstanceProcAddr` and `vkGetDeviceProcAddr`.
+    ///
+    /// # Safety
+    /// `instance`, `physical_device`, `device`, and `queue` must outlive the `BackendContext`
+    /// built.
+    pub unsafe fn builder<'a>(
+        instance: Instance,
+        physical_device: PhysicalDevice,
+        device: Device,
+        (queue, queue_index): (Queue, usize),
+        get_proc: &'a impl GetProc,
+    ) -> BackendContextBuilder<'a> {
+        BackendContextBuilder {
+            instance,
+            physical_device,
+            device,
+            queue: (queue, queue_index),
+            get_proc,
+            instance_extensions: Vec::new(),
+            device_extensions: Vec::new(),
+            device_features: None,
+            protected_context: None,
+            max_api_version: None,
+        }
+    }
+
+    unsafe fn new_with_dyn_get_proc<'a>(
+        instance: Instance,
+        physical_device: PhysicalDevice,
+        device: Device,
+        (queue, queue_index): (Queue, usize),
+        get_proc: &'a dyn GetProc,
+        instance_extensions: &[&str],
+        device_extensions: &[&str],
     ) -> BackendContext<'a> {
         // pin the extensions string in memory and provide pointers to the NewWithExtension function,
         // but there is no need to retain them, because because the implementations copies these strings, too.
@@ -125,6 +178,13 @@ impl BackendContext<'_> {
         }
     }
 
+    /// Creates a context that renders protected content.
+    ///
+    /// With [`gpu::Protected::Yes`], the device must have been created with the
+    /// `protectedMemory` feature enabled and the graphics queue must have been created with
+    /// `VK_DEVICE_QUEUE_CREATE_PROTECTED_BIT`. Protected textures and surfaces, for example
+    /// wrapping a [`super::ImageInfo`] with `protected` set, can then be drawn, but their
+    /// contents can not be read back.
     pub fn set_protected_context(&mut self, protected_context: gpu::Protected) -> &mut Self {
         unsafe {
             sb::C_GrVkBackendContext_setProtectedContext(
@@ -145,6 +205,18 @@ impl BackendContext<'_> {
         self
     }
 
+    /// Routes all of Skia's device memory allocations through `allocator`. If no allocator is
+    /// set, Skia creates one that is based on the Vulkan Memory Allocator library.
+    pub fn set_memory_allocator(&mut self, allocator: impl MemoryAllocator + 'static) -> &mut Self {
+        unsafe {
+            sb::C_GrVkBackendContext_setMemoryAllocator(
+                self.native.as_ptr() as _,
+                memory_allocator::new_native(allocator),
+            )
+        }
+        self
+    }
+
     pub(crate) unsafe fn begin_resolving(&self) -> impl Drop {
         Self::begin_resolving_proc(self.get_proc)
     }
@@ -164,6 +236,96 @@ impl BackendContext<'_> {
     }
 }
 
+/// Builds a [`BackendContext`], see [`BackendContext::builder()`].
+pub struct BackendContextBuilder<'a> {
+    instance: Instance,
+    physical_device: PhysicalDevice,
+    device: Device,
+    queue: (Queue, usize),
+    get_proc: &'a dyn GetProc,
+    instance_extensions: Vec<&'a str>,
+    device_extensions: Vec<&'a str>,
+    device_features: Option<&'a PhysicalDeviceFeatures2>,
+    protected_context: Option<gpu::Protected>,
+    max_api_version: Option<Version>,
+}
+
+impl fmt::Debug for BackendContextBuilder<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BackendContextBuilder")
+            .field("instance", &self.instance)
+            .field("physical_device", &self.physical_device)
+            .field("device", &self.device)
+            .field("queue", &self.queue)
+            .field("instance_extensions", &self.instance_extensions)
+            .field("device_extensions", &self.device_extensions)
+            .field("protected_context", &self.protected_context)
+            .field("max_api_version", &self.max_api_version)
+            .finish()
+    }
+}
+
+impl<'a> BackendContextBuilder<'a> {
+    /// The names of the instance extensions that were enabled when the `VkInstance` was created.
+    pub fn instance_extensions(&mut self, extensions: &[&'a str]) -> &mut Self {
+        self.instance_extensions = extensions.to_vec();
+        self
+    }
+
+    /// The names of the device extensions that were enabled when the `VkDevice` was created.
+    pub fn device_extensions(&mut self, extensions: &[&'a str]) -> &mut Self {
+        self.device_extensions = extensions.to_vec();
+        self
+    }
+
+    /// The features that were enabled when the `VkDevice` was created, including the structures
+    /// chained through `pNext`. Skia only reads them while the
+    /// [`gpu::DirectContext`] is created, but because the chain is referred to by
+    /// pointers, it is borrowed for the lifetime of the `BackendContext`.
+    pub fn device_features(&mut self, features: &'a PhysicalDeviceFeatures2) -> &mut Self {
+        self.device_features = Some(features);
+        self
+    }
+
+    /// See [`BackendContext::set_protected_context()`].
+    pub fn protected_context(&mut self, protected_context: gpu::Protected) -> &mut Self {
+        self.protected_context = Some(protected_context);
+        self
+    }
+
+    /// See [`BackendContext::set_max_api_version()`].
+    pub fn max_api_version(&mut self, version: impl Into<Version>) -> &mut Self {
+        self.max_api_version = Some(version.into());
+        self
+    }
+
+    pub fn build(&self) -> BackendContext<'a> {
+        let mut context = unsafe {
+            BackendContext::new_with_dyn_get_proc(
+                self.instance,
+                self.physical_device,
+                self.device,
+                self.queue,
+                self.get_proc,
+                &self.instance_extensions,
+                &self.device_extensions,
+            )
+        };
+        if let Some(features) = self.device_features {
+            unsafe {
+                sb::C_GrVkBackendContext_setDeviceFeatures2(context.native.as_ptr() as _, features)
+            }
+        }
+        if let Some(protected_context) = self.protected_context {
+            context.set_protected_context(protected_context);
+        }
+        if let Some(version) = self.max_api_version {
+            context.set_max_api_version(version);
+        }
+        context
+    }
+}
+
 struct EndResolving {}
 
 impl Drop for EndResolving {
@@ -0,0 +1,205 @@
+use super::{Alloc, GraphicsBackendMemory};
+use crate::gpu::vk;
+use skia_bindings as sb;
+use std::ffi::c_void;
+
+pub use sb::skgpu_VulkanMemoryAllocator_BufferUsage as BufferUsage;
+variant_name!(BufferUsage::GpuOnly);
+
+bitflags! {
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct AllocationPropertyFlags : u32 {
+        /// Allocation will be placed in its own `VkDeviceMemory` and not suballocated from some
+        /// larger block.
+        const DEDICATED_ALLOCATION = sb::skgpu_VulkanMemoryAllocator_AllocationPropertyFlags_kDedicatedAllocation_AllocationPropertyFlag as _;
+        /// Says that the backing memory can only be accessed by the device. Additionally the
+        /// device may lazily allocate the memory.
+        const LAZY_ALLOCATION = sb::skgpu_VulkanMemoryAllocator_AllocationPropertyFlags_kLazyAllocation_AllocationPropertyFlag as _;
+        /// The allocation will be mapped immediately and stay mapped until it is destroyed.
+        const PERSISTENTLY_MAPPED = sb::skgpu_VulkanMemoryAllocator_AllocationPropertyFlags_kPersistentlyMapped_AllocationPropertyFlag as _;
+        /// Allocation can only be accessed by the device using a protected context.
+        const PROTECTED = sb::skgpu_VulkanMemoryAllocator_AllocationPropertyFlags_kProtected_AllocationPropertyFlag as _;
+    }
+}
+
+/// Allocates the device memory that Skia's Vulkan backend uses for images and buffers.
+///
+/// Set it with [`super::BackendContext::set_memory_allocator()`] so that Skia's allocations go
+/// through the allocator of the application, for example one based on `gpu-alloc` or the
+/// Vulkan Memory Allocator, and show up in its budgeting.
+///
+/// Allocations are identified by an opaque [`GraphicsBackendMemory`] handle that is chosen by
+/// the implementation. The functions that allocate memory must also bind it to the image or
+/// buffer.
+pub trait MemoryAllocator: Send + Sync {
+    fn allocate_image_memory(
+        &self,
+        image: vk::Image,
+        flags: AllocationPropertyFlags,
+    ) -> Option<GraphicsBackendMemory>;
+
+    fn allocate_buffer_memory(
+        &self,
+        buffer: vk::Buffer,
+        usage: BufferUsage,
+        flags: AllocationPropertyFlags,
+    ) -> Option<GraphicsBackendMemory>;
+
+    /// Returns the information about the allocation. The returned [`Alloc`] must have its
+    /// `backend_memory` set to `memory`.
+    fn alloc_info(&self, memory: GraphicsBackendMemory) -> Alloc;
+
+    /// Maps the allocation and returns a pointer to its start, or `None` if the memory could not
+    /// be mapped.
+    fn map_memory(&self, memory: GraphicsBackendMemory) -> Option<*mut c_void>;
+
+    fn unmap_memory(&self, memory: GraphicsBackendMemory);
+
+    /// Flushes a range of a mapped non-coherent allocation. Returns `false` on failure.
+    fn flush_memory(
+        &self,
+        memory: GraphicsBackendMemory,
+        offset: vk::DeviceSize,
+        size: vk::DeviceSize,
+    ) -> bool;
+
+    /// Invalidates a range of a mapped non-coherent allocation. Returns `false` on failure.
+    fn invalidate_memory(
+        &self,
+        memory: GraphicsBackendMemory,
+        offset: vk::DeviceSize,
+        size: vk::DeviceSize,
+    ) -> bool;
+
+    fn free_memory(&self, memory: GraphicsBackendMemory);
+
+    /// Returns the total amount of memory that is allocated from the device and the amount of
+    /// it that is currently used by allocations, both in bytes.
+    fn total_allocated_and_used_memory(&self) -> (u64, u64);
+}
+
+/// Create a native `skgpu::VulkanMemoryAllocator` that forwards all calls to `allocator`. The
+/// returned pointer is owned by the caller.
+pub(crate) fn new_native(
+    allocator: impl MemoryAllocator + 'static,
+) -> *mut sb::skgpu_VulkanMemoryAllocator {
+    let allocator: Box<dyn MemoryAllocator> = Box::new(allocator);
+    let param = rust_memory_allocator::new_param(allocator);
+    unsafe { sb::C_RustMemoryAllocator_new(&param) }
+}
+
+mod rust_memory_allocator {
+    use super::{AllocationPropertyFlags, BufferUsage, MemoryAllocator};
+    use crate::{
+        gpu::vk::{self, Alloc, GraphicsBackendMemory},
+        prelude::*,
+    };
+    use skia_bindings::{self as sb, RustMemoryAllocator_Param, TraitObject};
+    use std::{ffi::c_void, mem, ptr};
+
+    pub fn new_param(allocator: Box<dyn MemoryAllocator>) -> RustMemoryAllocator_Param {
+        RustMemoryAllocator_Param {
+            trait_: unsafe { mem::transmute(Box::into_raw(allocator)) },
+            allocateImageMemory: Some(allocate_image_memory),
+            allocateBufferMemory: Some(allocate_buffer_memory),
+            getAllocInfo: Some(get_alloc_info),
+            mapMemory: Some(map_memory),
+            unmapMemory: Some(unmap_memory),
+            flushMemory: Some(flush_memory),
+            invalidateMemory: Some(invalidate_memory),
+            freeMemory: Some(free_memory),
+            totalAllocatedAndUsedMemory: Some(total_allocated_and_used_memory),
+            drop: Some(drop),
+        }
+    }
+
+    unsafe extern "C" fn allocate_image_memory(
+        to: TraitObject,
+        image: vk::Image,
+        flags: u32,
+        memory: *mut GraphicsBackendMemory,
+    ) -> bool {
+        let flags = AllocationPropertyFlags::from_bits_truncate(flags);
+        match to_allocator(&to).allocate_image_memory(image, flags) {
+            Some(allocated) => {
+                *memory = allocated;
+                true
+            }
+            None => false,
+        }
+    }
+
+    unsafe extern "C" fn allocate_buffer_memory(
+        to: TraitObject,
+        buffer: vk::Buffer,
+        usage: BufferUsage,
+        flags: u32,
+        memory: *mut GraphicsBackendMemory,
+    ) -> bool {
+        let flags = AllocationPropertyFlags::from_bits_truncate(flags);
+        match to_allocator(&to).allocate_buffer_memory(buffer, usage, flags) {
+            Some(allocated) => {
+                *memory = allocated;
+                true
+            }
+            None => false,
+        }
+    }
+
+    unsafe extern "C" fn get_alloc_info(
+        to: TraitObject,
+        memory: GraphicsBackendMemory,
+        alloc: *mut sb::GrVkAlloc,
+    ) {
+        *Alloc::from_native_ref_mut(&mut *alloc) = to_allocator(&to).alloc_info(memory);
+    }
+
+    extern "C" fn map_memory(to: TraitObject, memory: GraphicsBackendMemory) -> *mut c_void {
+        to_allocator(&to)
+            .map_memory(memory)
+            .unwrap_or(ptr::null_mut())
+    }
+
+    extern "C" fn unmap_memory(to: TraitObject, memory: GraphicsBackendMemory) {
+        to_allocator(&to).unmap_memory(memory)
+    }
+
+    extern "C" fn flush_memory(
+        to: TraitObject,
+        memory: GraphicsBackendMemory,
+        offset: vk::DeviceSize,
+        size: vk::DeviceSize,
+    ) -> bool {
+        to_allocator(&to).flush_memory(memory, offset, size)
+    }
+
+    extern "C" fn invalidate_memory(
+        to: TraitObject,
+        memory: GraphicsBackendMemory,
+        offset: vk::DeviceSize,
+        size: vk::DeviceSize,
+    ) -> bool {
+        to_allocator(&to).invalidate_memory(memory, offset, size)
+    }
+
+    extern "C" fn free_memory(to: TraitObject, memory: GraphicsBackendMemory) {
+        to_allocator(&to).free_memory(memory)
+    }
+
+    unsafe extern "C" fn total_allocated_and_used_memory(
+        to: TraitObject,
+        allocated: *mut u64,
+        used: *mut u64,
+    ) {
+        (*allocated, *used) = to_allocator(&to).total_allocated_and_used_memory();
+    }
+
+    unsafe extern "C" fn drop(to: TraitObject) {
+        let allocator: *mut dyn MemoryAllocator = mem::transmute(to);
+        mem::drop(Box::from_raw(allocator));
+    }
+
+    fn to_allocator(to: &TraitObject) -> &dyn MemoryAllocator {
+        unsafe { &*mem::transmute::<TraitObject, *const dyn MemoryAllocator>(*to) }
+    }
+}
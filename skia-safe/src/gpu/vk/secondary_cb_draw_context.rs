@@ -0,0 +1,132 @@
+use super::DrawableInfo;
+use crate::{
+    gpu::{BackendSemaphore, RecordingContext},
+    prelude::*,
+    Canvas, DeferredDisplayList, ImageInfo, SurfaceCharacterization, SurfaceProps,
+};
+use skia_bindings::{self as sb, GrVkSecondaryCBDrawContext};
+use std::fmt;
+
+/// Draws into a Vulkan secondary command buffer that is owned by the client.
+///
+/// The secondary command buffer, its compatible render pass and the color attachment index are
+/// described by a [`DrawableInfo`], which is usually received from
+/// [`crate::gpu::BackendDrawableInfo`] inside of a [`crate::Drawable`]'s GPU draw handler, or
+/// set up by the client that owns the render pass. Skia only records commands into the
+/// secondary command buffer; it never begins or ends the render pass and does not submit
+/// anything. [`Self::flush()`] must be called before the client ends recording of the primary
+/// command buffer, and [`Self::release_resources()`] should be called after the GPU has finished
+/// executing the commands. Otherwise, the resources are released when the last reference to the
+/// `SecondaryCBDrawContext` is dropped, which must not happen before the GPU has finished.
+pub type SecondaryCBDrawContext = RCHandle<GrVkSecondaryCBDrawContext>;
+
+impl NativeRefCounted for GrVkSecondaryCBDrawContext {
+    fn _ref(&self) {
+        unsafe { sb::C_GrVkSecondaryCBDrawContext_ref(self) }
+    }
+
+    fn _unref(&self) {
+        unsafe { sb::C_GrVkSecondaryCBDrawContext_unref(self) }
+    }
+
+    fn unique(&self) -> bool {
+        unsafe { sb::C_GrVkSecondaryCBDrawContext_unique(self) }
+    }
+}
+
+impl fmt::Debug for SecondaryCBDrawContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecondaryCBDrawContext")
+            .field("props", self.props())
+            .finish()
+    }
+}
+
+impl SecondaryCBDrawContext {
+    /// # Safety
+    /// The secondary command buffer and the render pass referred to by `vk_info` must outlive
+    /// the returned `SecondaryCBDrawContext`.
+    pub unsafe fn new<'a>(
+        context: &mut RecordingContext,
+        image_info: &ImageInfo,
+        vk_info: &DrawableInfo,
+        props: impl Into<Option<&'a SurfaceProps>>,
+    ) -> Option<Self> {
+        Self::from_ptr(sb::C_GrVkSecondaryCBDrawContext_Make(
+            context.native_mut(),
+            image_info.native(),
+            vk_info.native(),
+            props.into().native_ptr_or_null(),
+        ))
+    }
+
+    pub fn canvas(&mut self) -> &mut Canvas {
+        let canvas_ref =
+            unsafe { &mut *sb::C_GrVkSecondaryCBDrawContext_getCanvas(self.native_mut()) };
+        Canvas::borrow_from_native_mut(canvas_ref)
+    }
+
+    /// Records all pending draws into the secondary command buffer.
+    pub fn flush(&mut self) {
+        unsafe { sb::C_GrVkSecondaryCBDrawContext_flush(self.native_mut()) }
+    }
+
+    /// Inserts a list of GPU semaphores that Skia will have the driver wait on before executing
+    /// the commands for this context. If this call returns `false`, Skia has not added any
+    /// semaphores to wait on, and the client is responsible for their deletion.
+    pub fn wait(
+        &mut self,
+        semaphores: &[BackendSemaphore],
+        delete_semaphores_after_wait: bool,
+    ) -> bool {
+        unsafe {
+            sb::C_GrVkSecondaryCBDrawContext_wait(
+                self.native_mut(),
+                semaphores.native().as_ptr(),
+                semaphores.len(),
+                delete_semaphores_after_wait,
+            )
+        }
+    }
+
+    /// Releases all resources that Skia holds for the secondary command buffer. Must be called
+    /// after the GPU has finished executing the commands recorded, and is called when the last
+    /// reference to the context is dropped.
+    pub fn release_resources(&mut self) {
+        unsafe { sb::C_GrVkSecondaryCBDrawContext_releaseResources(self.native_mut()) }
+    }
+
+    pub fn props(&self) -> &SurfaceProps {
+        SurfaceProps::from_native_ref(unsafe {
+            &*sb::C_GrVkSecondaryCBDrawContext_props(self.native())
+        })
+    }
+
+    /// Returns a [`SurfaceCharacterization`] that can be used to record
+    /// [`DeferredDisplayList`]s that are compatible with this context.
+    pub fn characterize(&self) -> Option<SurfaceCharacterization> {
+        let mut sc = SurfaceCharacterization::default();
+        unsafe { sb::C_GrVkSecondaryCBDrawContext_characterize(self.native(), sc.native_mut()) }
+            .if_true_some(sc)
+    }
+
+    /// Draws a deferred display list into the secondary command buffer. Returns `false` and
+    /// skips the draw if the display list is not compatible.
+    pub fn draw_display_list(
+        &mut self,
+        deferred_display_list: impl Into<DeferredDisplayList>,
+    ) -> bool {
+        unsafe {
+            sb::C_GrVkSecondaryCBDrawContext_draw(
+                self.native_mut(),
+                deferred_display_list.into().into_ptr() as *const _,
+            )
+        }
+    }
+
+    pub fn is_compatible(&self, characterization: &SurfaceCharacterization) -> bool {
+        unsafe {
+            sb::C_GrVkSecondaryCBDrawContext_isCompatible(self.native(), characterization.native())
+        }
+    }
+}
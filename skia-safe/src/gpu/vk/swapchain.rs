@@ -0,0 +1,385 @@
+//! A helper that presents [`Surface`]s wrapping the images of a `VkSwapchainKHR`.
+//!
+//! ```ignore
+//! let mut swapchain = unsafe {
+//!     Swapchain::new(&instance, &surface_loader, physical_device, &device, queue, surface,
+//!                    vk::PresentModeKHR::FIFO, &mut context, window_size)?
+//! };
+//! loop {
+//!     let (index, surface) = match swapchain.acquire() {
+//!         Ok(acquired) => acquired,
+//!         Err(vk::Result::ERROR_OUT_OF_DATE_KHR) => {
+//!             swapchain.recreate(&mut context, window_size)?;
+//!             continue;
+//!         }
+//!         Err(e) => return Err(e),
+//!     };
+//!     draw(surface.canvas());
+//!     swapchain.present(&mut context, index)?;
+//! }
+//! ```
+
+use super::{Alloc, FromAsh, ImageInfo, TryFromAsh};
+use crate::{
+    gpu::{
+        self, BackendRenderTarget, BackendSemaphore, DirectContext, FlushInfo, MutableTextureState,
+        SemaphoresSubmitted, SurfaceOrigin,
+    },
+    ColorType, ISize, Surface,
+};
+use ::ash::{extensions::khr, vk, Device, Instance};
+use std::fmt;
+
+type VkResult<T> = Result<T, vk::Result>;
+
+/// Owns a `VkSwapchainKHR` and a [`Surface`] for each of its images.
+///
+/// The queue that is passed to [`Swapchain::new()`] must be the graphics queue of the
+/// [`DirectContext`] and must support presenting to the `VkSurfaceKHR`. The device must have been
+/// created with the `VK_KHR_swapchain` extension enabled.
+pub struct Swapchain {
+    device: Device,
+    surface_loader: khr::Surface,
+    swapchain_loader: khr::Swapchain,
+    physical_device: vk::PhysicalDevice,
+    queue: vk::Queue,
+    queue_index: u32,
+    surface: vk::SurfaceKHR,
+    present_mode: vk::PresentModeKHR,
+    swapchain: vk::SwapchainKHR,
+    format: vk::SurfaceFormatKHR,
+    color_type: ColorType,
+    extent: vk::Extent2D,
+    surfaces: Vec<Surface>,
+    render_semaphores: Vec<vk::Semaphore>,
+}
+
+impl fmt::Debug for Swapchain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Swapchain")
+            .field("swapchain", &self.swapchain)
+            .field("format", &self.format)
+            .field("color_type", &self.color_type)
+            .field("extent", &self.extent)
+            .field("present_mode", &self.present_mode)
+            .field("image_count", &self.surfaces.len())
+            .finish()
+    }
+}
+
+impl Drop for Swapchain {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.device_wait_idle().ok();
+            self.destroy_resources();
+            self.swapchain_loader
+                .destroy_swapchain(self.swapchain, None);
+        }
+    }
+}
+
+impl Swapchain {
+    /// Creates a swapchain for `surface` with images of the given `size`, unless the surface
+    /// dictates its extent. If `present_mode` is not supported, `FIFO` is used.
+    ///
+    /// # Safety
+    /// `instance`, `physical_device`, `device`, `queue` and `surface` must be valid and must
+    /// outlive the returned `Swapchain`, and `context` must have been created for them.
+    #[allow(clippy::too_many_arguments)]
+    pub unsafe fn new(
+        instance: &Instance,
+        surface_loader: &khr::Surface,
+        physical_device: vk::PhysicalDevice,
+        device: &Device,
+        (queue, queue_index): (vk::Queue, usize),
+        surface: vk::SurfaceKHR,
+        present_mode: vk::PresentModeKHR,
+        context: &mut DirectContext,
+        size: impl Into<ISize>,
+    ) -> VkResult<Self> {
+        let mut swapchain = Swapchain {
+            device: device.clone(),
+            surface_loader: surface_loader.clone(),
+            swapchain_loader: khr::Swapchain::new(instance, device),
+            physical_device,
+            queue,
+            queue_index: queue_index.try_into().unwrap(),
+            surface,
+            present_mode,
+            swapchain: vk::SwapchainKHR::null(),
+            format: vk::SurfaceFormatKHR::default(),
+            color_type: ColorType::Unknown,
+            extent: vk::Extent2D::default(),
+            surfaces: Vec::new(),
+            render_semaphores: Vec::new(),
+        };
+        swapchain.create(context, size.into())?;
+        Ok(swapchain)
+    }
+
+    /// Recreates the swapchain, for example after the window was resized or after
+    /// [`Self::acquire()`] or [`Self::present()`] returned `ERROR_OUT_OF_DATE_KHR`.
+    ///
+    /// All [`Surface`]s of the previous swapchain are released.
+    pub fn recreate(
+        &mut self,
+        context: &mut DirectContext,
+        size: impl Into<ISize>,
+    ) -> VkResult<()> {
+        unsafe {
+            self.device.device_wait_idle()?;
+            self.destroy_resources();
+            self.create(context, size.into())
+        }
+    }
+
+    /// Acquires the next image of the swapchain and returns its index and its [`Surface`].
+    ///
+    /// The GPU work that draws into the surface waits until the image is available. After
+    /// drawing, the image needs to be handed back with [`Self::present()`].
+    pub fn acquire(&mut self) -> VkResult<(usize, &mut Surface)> {
+        unsafe {
+            let semaphore = self
+                .device
+                .create_semaphore(&vk::SemaphoreCreateInfo::default(), None)?;
+            let index = match self.swapchain_loader.acquire_next_image(
+                self.swapchain,
+                u64::MAX,
+                semaphore,
+                vk::Fence::null(),
+            ) {
+                Ok((index, _suboptimal)) => index as usize,
+                Err(e) => {
+                    // A failed acquire does not signal the semaphore, but the queue may still
+                    // use it from a previous frame.
+                    self.device.queue_wait_idle(self.queue)?;
+                    self.device.destroy_semaphore(semaphore, None);
+                    return Err(e);
+                }
+            };
+
+            let surface = &mut self.surfaces[index];
+            // Skia takes ownership of the semaphore and deletes it after the wait.
+            let wait = [BackendSemaphore::new_vulkan(FromAsh::from_ash(semaphore))];
+            if !surface.wait(&wait, true) {
+                // The semaphore will be signaled by the presentation engine, so consume the
+                // signal with an empty submission before destroying it.
+                let wait_semaphores = [semaphore];
+                let wait_stages = [vk::PipelineStageFlags::ALL_COMMANDS];
+                let submit_info = vk::SubmitInfo::builder()
+                    .wait_semaphores(&wait_semaphores)
+                    .wait_dst_stage_mask(&wait_stages)
+                    .build();
+                self.device
+                    .queue_submit(self.queue, &[submit_info], vk::Fence::null())?;
+                self.device.queue_wait_idle(self.queue)?;
+                self.device.destroy_semaphore(semaphore, None);
+            }
+            Ok((index, surface))
+        }
+    }
+
+    /// Flushes the drawing of the surface at `index`, transitions its image to
+    /// `PRESENT_SRC_KHR`, submits the work and queues the image for presentation.
+    ///
+    /// Returns `true` if the swapchain does not match the surface properties exactly anymore
+    /// and should be recreated.
+    pub fn present(&mut self, context: &mut DirectContext, index: usize) -> VkResult<bool> {
+        let present_state =
+            MutableTextureState::new_vk(gpu::vk::ImageLayout::PRESENT_SRC_KHR, self.queue_index);
+        self.surfaces[index].flush_with_mutable_state(&FlushInfo::default(), &present_state);
+
+        let render_semaphore = self.render_semaphores[index];
+        let mut signal = [BackendSemaphore::new_vulkan(FromAsh::from_ash(
+            render_semaphore,
+        ))];
+        let submitted = context.flush_and_signal_semaphores(None, &mut signal);
+        context.submit(None);
+
+        let render_semaphores = [render_semaphore];
+        let wait_semaphores = match submitted {
+            SemaphoresSubmitted::Yes => &render_semaphores[..],
+            SemaphoresSubmitted::No => &[],
+        };
+        let swapchains = [self.swapchain];
+        let image_indices = [index as u32];
+        let present_info = vk::PresentInfoKHR::builder()
+            .wait_semaphores(wait_semaphores)
+            .swapchains(&swapchains)
+            .image_indices(&image_indices);
+        unsafe {
+            self.swapchain_loader
+                .queue_present(self.queue, &present_info)
+        }
+    }
+
+    pub fn surface(&mut self, index: usize) -> &mut Surface {
+        &mut self.surfaces[index]
+    }
+
+    pub fn image_count(&self) -> usize {
+        self.surfaces.len()
+    }
+
+    pub fn extent(&self) -> ISize {
+        ISize::new(self.extent.width as _, self.extent.height as _)
+    }
+
+    pub fn format(&self) -> vk::SurfaceFormatKHR {
+        self.format
+    }
+
+    pub fn color_type(&self) -> ColorType {
+        self.color_type
+    }
+
+    pub fn handle(&self) -> vk::SwapchainKHR {
+        self.swapchain
+    }
+
+    unsafe fn create(&mut self, context: &mut DirectContext, size: ISize) -> VkResult<()> {
+        let capabilities = self
+            .surface_loader
+            .get_physical_device_surface_capabilities(self.physical_device, self.surface)?;
+        let formats = self
+            .surface_loader
+            .get_physical_device_surface_formats(self.physical_device, self.surface)?;
+        let present_modes = self
+            .surface_loader
+            .get_physical_device_surface_present_modes(self.physical_device, self.surface)?;
+
+        let (format, color_type) = formats
+            .iter()
+            .find_map(|format| match format.format {
+                vk::Format::B8G8R8A8_UNORM => Some((*format, ColorType::BGRA8888)),
+                vk::Format::R8G8B8A8_UNORM => Some((*format, ColorType::RGBA8888)),
+                _ => None,
+            })
+            .ok_or(vk::Result::ERROR_FORMAT_NOT_SUPPORTED)?;
+
+        let extent = if capabilities.current_extent.width != u32::MAX {
+            capabilities.current_extent
+        } else {
+            vk::Extent2D {
+                width: (size.width.max(1) as u32).clamp(
+                    capabilities.min_image_extent.width,
+                    capabilities.max_image_extent.width,
+                ),
+                height: (size.height.max(1) as u32).clamp(
+                    capabilities.min_image_extent.height,
+                    capabilities.max_image_extent.height,
+                ),
+            }
+        };
+
+        let mut image_count = capabilities.min_image_count + 1;
+        if capabilities.max_image_count > 0 {
+            image_count = image_count.min(capabilities.max_image_count);
+        }
+
+        let usage_flags = (vk::ImageUsageFlags::COLOR_ATTACHMENT
+            | vk::ImageUsageFlags::TRANSFER_SRC
+            | vk::ImageUsageFlags::TRANSFER_DST)
+            & capabilities.supported_usage_flags;
+        if !usage_flags.contains(vk::ImageUsageFlags::COLOR_ATTACHMENT) {
+            return Err(vk::Result::ERROR_FEATURE_NOT_PRESENT);
+        }
+
+        let pre_transform = if capabilities
+            .supported_transforms
+            .contains(vk::SurfaceTransformFlagsKHR::IDENTITY)
+        {
+            vk::SurfaceTransformFlagsKHR::IDENTITY
+        } else {
+            capabilities.current_transform
+        };
+
+        let composite_alpha = [
+            vk::CompositeAlphaFlagsKHR::OPAQUE,
+            vk::CompositeAlphaFlagsKHR::INHERIT,
+            vk::CompositeAlphaFlagsKHR::PRE_MULTIPLIED,
+            vk::CompositeAlphaFlagsKHR::POST_MULTIPLIED,
+        ]
+        .into_iter()
+        .find(|alpha| capabilities.supported_composite_alpha.contains(*alpha))
+        .unwrap_or(vk::CompositeAlphaFlagsKHR::OPAQUE);
+
+        let present_mode = if present_modes.contains(&self.present_mode) {
+            self.present_mode
+        } else {
+            vk::PresentModeKHR::FIFO
+        };
+
+        let old_swapchain = self.swapchain;
+        let create_info = vk::SwapchainCreateInfoKHR::builder()
+            .surface(self.surface)
+            .min_image_count(image_count)
+            .image_format(format.format)
+            .image_color_space(format.color_space)
+            .image_extent(extent)
+            .image_array_layers(1)
+            .image_usage(usage_flags)
+            .image_sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .pre_transform(pre_transform)
+            .composite_alpha(composite_alpha)
+            .present_mode(present_mode)
+            .clipped(true)
+            .old_swapchain(old_swapchain);
+        self.swapchain = self.swapchain_loader.create_swapchain(&create_info, None)?;
+        if old_swapchain != vk::SwapchainKHR::null() {
+            self.swapchain_loader.destroy_swapchain(old_swapchain, None);
+        }
+
+        self.format = format;
+        self.color_type = color_type;
+        self.extent = extent;
+
+        let image_format = TryFromAsh::try_from_ash(format.format)
+            .ok_or(vk::Result::ERROR_FORMAT_NOT_SUPPORTED)?;
+        for image in self.swapchain_loader.get_swapchain_images(self.swapchain)? {
+            let image_info = ImageInfo {
+                image_usage_flags: usage_flags.as_raw(),
+                ..ImageInfo::new(
+                    FromAsh::from_ash(image),
+                    Alloc::default(),
+                    gpu::vk::ImageTiling::OPTIMAL,
+                    gpu::vk::ImageLayout::UNDEFINED,
+                    image_format,
+                    1,
+                    self.queue_index,
+                    None,
+                    None,
+                    None,
+                )
+            };
+            let render_target = BackendRenderTarget::new_vulkan(
+                (extent.width as _, extent.height as _),
+                None,
+                &image_info,
+            );
+            let surface = Surface::from_backend_render_target(
+                context,
+                &render_target,
+                SurfaceOrigin::TopLeft,
+                color_type,
+                None,
+                None,
+            )
+            .ok_or(vk::Result::ERROR_INITIALIZATION_FAILED)?;
+            self.surfaces.push(surface);
+            self.render_semaphores.push(
+                self.device
+                    .create_semaphore(&vk::SemaphoreCreateInfo::default(), None)?,
+            );
+        }
+
+        Ok(())
+    }
+
+    unsafe fn destroy_resources(&mut self) {
+        self.surfaces.clear();
+        for semaphore in self.render_semaphores.drain(..) {
+            self.device.destroy_semaphore(semaphore, None);
+        }
+    }
+}
@@ -142,6 +142,15 @@ impl YcbcrConversionInfo {
         }
     }
 
+    /// Describes the conversion of an image with an external format, for example a camera or
+    /// hardware decoder buffer on Android. `external_format` and `external_format_features` are
+    /// the `externalFormat` and `formatFeatures` that `vkGetAndroidHardwareBufferPropertiesANDROID`
+    /// returns in `VkAndroidHardwareBufferFormatPropertiesANDROID`.
+    ///
+    /// The conversion must be set in both the [`ImageInfo`] of the texture and in the
+    /// [`crate::gpu::BackendFormat`] created with
+    /// [`crate::gpu::BackendFormat::new_vulkan_ycbcr()`], so that Skia creates matching
+    /// `VkSamplerYcbcrConversion` objects.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         ycbcr_model: vk::SamplerYcbcrModelConversion,
@@ -247,6 +256,50 @@ impl ImageInfo {
         }
     }
 
+    /// Describes an `image` that is backed by memory that was imported from outside of this
+    /// Vulkan device, for example a Linux dma-buf that was imported with
+    /// `VK_KHR_external_memory_fd` and `VK_EXT_external_memory_dma_buf`.
+    ///
+    /// If the image was created with DRM format modifiers, `tiling` must be
+    /// [`vk::ImageTiling::DRM_FORMAT_MODIFIER_EXT`] and the [`crate::gpu::BackendFormat`] of
+    /// the texture must be created with `will_use_drm_format_modifiers` set.
+    ///
+    /// The image is initially owned by the foreign queue family
+    /// ([`vk::QUEUE_FAMILY_FOREIGN_EXT`]), which requires `VK_EXT_queue_family_foreign`. Skia
+    /// transfers the ownership to its queue before using the image. To hand it back after
+    /// rendering, flush with a [`crate::gpu::MutableTextureState`] that names the foreign queue
+    /// family.
+    ///
+    /// # Safety
+    /// The Vulkan `image` and `alloc` must outlive the lifetime of the ImageInfo returned.
+    #[allow(clippy::too_many_arguments)]
+    pub unsafe fn new_external(
+        image: vk::Image,
+        alloc: Alloc,
+        tiling: vk::ImageTiling,
+        layout: vk::ImageLayout,
+        format: vk::Format,
+        image_usage_flags: vk::ImageUsageFlags,
+        level_count: u32,
+        ycbcr_conversion_info: impl Into<Option<YcbcrConversionInfo>>,
+    ) -> Self {
+        Self {
+            image_usage_flags,
+            ..Self::new(
+                image,
+                alloc,
+                tiling,
+                layout,
+                format,
+                level_count,
+                vk::QUEUE_FAMILY_FOREIGN_EXT,
+                ycbcr_conversion_info,
+                None,
+                None,
+            )
+        }
+    }
+
     /// # Safety
     /// The Vulkan `info.image` and `info.alloc` must outlive the lifetime of the ImageInfo returned.
     pub unsafe fn from_info(info: &ImageInfo, layout: vk::ImageLayout) -> Self {
@@ -0,0 +1,283 @@
+//! Sharing the Vulkan device and textures of [`wgpu`](::wgpu) with Skia.
+//!
+//! Only the Vulkan backend of `wgpu` is supported. Metal, DX12, GL, and WebGPU devices and
+//! textures are not, and the functions return `None` for them. On platforms where `wgpu`
+//! prefers another backend, create the `wgpu::Instance` with `wgpu::Backends::VULKAN`.
+
+use super::{
+    Alloc, BackendContext, FromAsh, GetProcOf, GetProcResult, ImageInfo, ImageLayout, IntoAsh,
+    TryFromAsh,
+};
+use crate::{
+    gpu::{self, BackendTexture, ContextOptions, DirectContext, RecordingContext, SurfaceOrigin},
+    AlphaType, ColorSpace, ColorType, Image, Surface, SurfaceProps,
+};
+use ::ash::vk;
+use ::wgpu::{hal as wgpu_hal, hal::api::Vulkan, TextureFormat, TextureUsages};
+use std::ptr;
+
+impl DirectContext {
+    /// Creates a Vulkan context that shares the instance, device, and queue of `device`.
+    ///
+    /// Returns `None` if `device` does not use the Vulkan backend.
+    ///
+    /// # Safety
+    /// `device` must outlive the returned context. Work submitted through `wgpu` and Skia is
+    /// not synchronized, so each frame's Skia work needs to be submitted before `wgpu` work that
+    /// depends on it, and the other way around.
+    pub unsafe fn new_vulkan_from_wgpu<'a>(
+        device: &::wgpu::Device,
+        options: impl Into<Option<&'a ContextOptions>>,
+    ) -> Option<DirectContext> {
+        let options = options.into();
+        device.as_hal::<Vulkan, _, _>(|device| {
+            let device = device?;
+            let shared = device.shared_instance();
+            let entry = shared.entry();
+            let instance = shared.raw_instance();
+
+            let get_proc = |of: GetProcOf| -> GetProcResult {
+                let function = match of {
+                    GetProcOf::Instance(instance, name) => {
+                        entry.get_instance_proc_addr(instance.into_ash(), name)
+                    }
+                    GetProcOf::Device(device, name) => {
+                        (instance.fp_v1_0().get_device_proc_addr)(device.into_ash(), name)
+                    }
+                };
+                function.map_or(ptr::null(), |f| f as _)
+            };
+
+            let instance_extensions: Vec<&str> = shared
+                .extensions()
+                .iter()
+                .filter_map(|e| e.to_str().ok())
+                .collect();
+            let device_extensions: Vec<&str> = device
+                .enabled_device_extensions()
+                .iter()
+                .filter_map(|e| e.to_str().ok())
+                .collect();
+
+            let backend_context = BackendContext::builder(
+                FromAsh::from_ash(instance.handle()),
+                FromAsh::from_ash(device.raw_physical_device()),
+                FromAsh::from_ash(device.raw_device().handle()),
+                (
+                    FromAsh::from_ash(device.raw_queue()),
+                    device.queue_family_index() as usize,
+                ),
+                &get_proc,
+            )
+            .instance_extensions(&instance_extensions)
+            .device_extensions(&device_extensions)
+            .build();
+
+            DirectContext::new_vulkan(&backend_context, options)
+        })
+    }
+}
+
+impl BackendTexture {
+    /// Wraps the Vulkan image of `texture`.
+    ///
+    /// `layout` is the layout the image is in when Skia accesses it, which is defined by the
+    /// last `wgpu` command that used the texture, for example `COLOR_ATTACHMENT_OPTIMAL` after a
+    /// render pass or `SHADER_READ_ONLY_OPTIMAL` after sampling.
+    ///
+    /// Returns `None` if `texture` does not use the Vulkan backend or its format is not
+    /// supported by Skia.
+    ///
+    /// # Safety
+    /// `texture` must outlive the returned `BackendTexture` and everything created from it.
+    pub unsafe fn from_wgpu_texture(
+        texture: &::wgpu::Texture,
+        layout: ImageLayout,
+    ) -> Option<BackendTexture> {
+        let format = vk_format(texture.format())?;
+        let mut image = None;
+        texture.as_hal::<Vulkan, _>(|texture| image = texture.map(|texture| texture.raw_handle()));
+
+        let image_info = ImageInfo {
+            image_usage_flags: vk_image_usage(texture.usage()).as_raw(),
+            ..ImageInfo::new(
+                FromAsh::from_ash(image?),
+                Alloc::default(),
+                gpu::vk::ImageTiling::OPTIMAL,
+                layout,
+                TryFromAsh::try_from_ash(format)?,
+                texture.mip_level_count(),
+                None,
+                None,
+                None,
+                None,
+            )
+        };
+        Some(BackendTexture::new_vulkan(
+            (texture.width() as _, texture.height() as _),
+            &image_info,
+        ))
+    }
+
+    /// Creates a `wgpu` texture that refers to the Vulkan image of this texture. `desc` must
+    /// match the image the texture was created with.
+    ///
+    /// The returned texture keeps a reference to this `BackendTexture`, but not to the image.
+    ///
+    /// Returns `None` if this is not a Vulkan texture or `device` does not use the Vulkan
+    /// backend.
+    ///
+    /// # Safety
+    /// The image must outlive the returned texture and must be in a layout `wgpu` expects when
+    /// it uses the texture the first time.
+    pub unsafe fn to_wgpu_texture(
+        &self,
+        device: &::wgpu::Device,
+        desc: &::wgpu::TextureDescriptor,
+    ) -> Option<::wgpu::Texture> {
+        let image_info = self.vulkan_image_info()?;
+        let is_vulkan = device.as_hal::<Vulkan, _, _>(|device| device.is_some());
+        if !is_vulkan {
+            return None;
+        }
+
+        let hal_desc = wgpu_hal::TextureDescriptor {
+            label: desc.label,
+            size: desc.size,
+            mip_level_count: desc.mip_level_count,
+            sample_count: desc.sample_count,
+            dimension: desc.dimension,
+            format: desc.format,
+            usage: hal_texture_uses(desc.usage),
+            memory_flags: wgpu_hal::MemoryFlags::empty(),
+            view_formats: desc.view_formats.to_vec(),
+        };
+        let hal_texture = wgpu_hal::vulkan::Device::texture_from_raw(
+            image_info.image.into_ash(),
+            &hal_desc,
+            Some(Box::new(self.clone())),
+        );
+        Some(device.create_texture_from_hal::<Vulkan>(hal_texture, desc))
+    }
+}
+
+impl Surface {
+    /// Creates a surface that renders into `texture`, which must have been created with
+    /// [`TextureUsages::RENDER_ATTACHMENT`]. See [`BackendTexture::from_wgpu_texture()`] for
+    /// `layout`.
+    ///
+    /// Returns `None` if `texture` does not use the Vulkan backend.
+    ///
+    /// # Safety
+    /// `texture` must outlive the returned surface.
+    pub unsafe fn from_wgpu_texture(
+        context: &mut RecordingContext,
+        texture: &::wgpu::Texture,
+        layout: ImageLayout,
+        color_space: impl Into<Option<ColorSpace>>,
+        surface_props: Option<&SurfaceProps>,
+    ) -> Option<Surface> {
+        let backend_texture = BackendTexture::from_wgpu_texture(texture, layout)?;
+        Surface::from_backend_texture(
+            context,
+            &backend_texture,
+            SurfaceOrigin::TopLeft,
+            texture.sample_count() as usize,
+            color_type(texture.format())?,
+            color_space,
+            surface_props,
+        )
+    }
+}
+
+impl Image {
+    /// Creates an image that samples `texture`, which must have been created with
+    /// [`TextureUsages::TEXTURE_BINDING`]. See [`BackendTexture::from_wgpu_texture()`] for
+    /// `layout`.
+    ///
+    /// Returns `None` if `texture` does not use the Vulkan backend.
+    ///
+    /// # Safety
+    /// `texture` must outlive the returned image.
+    pub unsafe fn from_wgpu_texture(
+        context: &mut RecordingContext,
+        texture: &::wgpu::Texture,
+        layout: ImageLayout,
+        alpha_type: AlphaType,
+        color_space: impl Into<Option<ColorSpace>>,
+    ) -> Option<Image> {
+        let backend_texture = BackendTexture::from_wgpu_texture(texture, layout)?;
+        Image::from_texture(
+            context,
+            &backend_texture,
+            SurfaceOrigin::TopLeft,
+            color_type(texture.format())?,
+            alpha_type,
+            color_space,
+        )
+    }
+}
+
+fn vk_format(format: TextureFormat) -> Option<vk::Format> {
+    Some(match format {
+        TextureFormat::R8Unorm => vk::Format::R8_UNORM,
+        TextureFormat::Rg8Unorm => vk::Format::R8G8_UNORM,
+        TextureFormat::Rgba8Unorm => vk::Format::R8G8B8A8_UNORM,
+        TextureFormat::Rgba8UnormSrgb => vk::Format::R8G8B8A8_SRGB,
+        TextureFormat::Bgra8Unorm => vk::Format::B8G8R8A8_UNORM,
+        TextureFormat::Rgb10a2Unorm => vk::Format::A2B10G10R10_UNORM_PACK32,
+        TextureFormat::Rgba16Float => vk::Format::R16G16B16A16_SFLOAT,
+        _ => return None,
+    })
+}
+
+fn color_type(format: TextureFormat) -> Option<ColorType> {
+    Some(match format {
+        TextureFormat::R8Unorm => ColorType::R8UNorm,
+        TextureFormat::Rg8Unorm => ColorType::R8G8UNorm,
+        TextureFormat::Rgba8Unorm => ColorType::RGBA8888,
+        TextureFormat::Rgba8UnormSrgb => ColorType::SRGBA8888,
+        TextureFormat::Bgra8Unorm => ColorType::BGRA8888,
+        TextureFormat::Rgb10a2Unorm => ColorType::RGBA1010102,
+        TextureFormat::Rgba16Float => ColorType::RGBAF16,
+        _ => return None,
+    })
+}
+
+fn vk_image_usage(usage: TextureUsages) -> vk::ImageUsageFlags {
+    [
+        (TextureUsages::COPY_SRC, vk::ImageUsageFlags::TRANSFER_SRC),
+        (TextureUsages::COPY_DST, vk::ImageUsageFlags::TRANSFER_DST),
+        (TextureUsages::TEXTURE_BINDING, vk::ImageUsageFlags::SAMPLED),
+        (TextureUsages::STORAGE_BINDING, vk::ImageUsageFlags::STORAGE),
+        (
+            TextureUsages::RENDER_ATTACHMENT,
+            vk::ImageUsageFlags::COLOR_ATTACHMENT,
+        ),
+    ]
+    .into_iter()
+    .filter(|(wgpu, _)| usage.contains(*wgpu))
+    .fold(vk::ImageUsageFlags::empty(), |flags, (_, vk)| flags | vk)
+}
+
+fn hal_texture_uses(usage: TextureUsages) -> wgpu_hal::TextureUses {
+    [
+        (TextureUsages::COPY_SRC, wgpu_hal::TextureUses::COPY_SRC),
+        (TextureUsages::COPY_DST, wgpu_hal::TextureUses::COPY_DST),
+        (
+            TextureUsages::TEXTURE_BINDING,
+            wgpu_hal::TextureUses::RESOURCE,
+        ),
+        (
+            TextureUsages::STORAGE_BINDING,
+            wgpu_hal::TextureUses::STORAGE_READ_WRITE,
+        ),
+        (
+            TextureUsages::RENDER_ATTACHMENT,
+            wgpu_hal::TextureUses::COLOR_TARGET,
+        ),
+    ]
+    .into_iter()
+    .filter(|(wgpu, _)| usage.contains(*wgpu))
+    .fold(wgpu_hal::TextureUses::empty(), |uses, (_, hal)| uses | hal)
+}
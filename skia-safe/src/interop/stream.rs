@@ -188,57 +188,6 @@ impl NativeDrop for sb::RustStream {
 #[allow(unused)]
 impl<'a> RustStream<'a> {
     pub fn new<T: io::Read>(val: &'a mut T) -> Self {
-        unsafe extern "C" fn read_trampoline<T>(
-            val: *mut ffi::c_void,
-            buf: *mut ffi::c_void,
-            count: usize,
-        ) -> usize
-        where
-            T: io::Read,
-        {
-            let val: &mut T = &mut *(val as *mut _);
-
-            if buf.is_null() {
-                const BUF_SIZE: usize = 128;
-
-                let mut buf = [0; BUF_SIZE];
-
-                let mut out_bytes = 0;
-                let mut count = count;
-
-                // This is OK because we just abort if it panics anyway, we don't try
-                // to continue at all.
-                let mut val = std::panic::AssertUnwindSafe(val);
-
-                let out_bytes = match std::panic::catch_unwind(move || {
-                    while count > 0 {
-                        let bytes = match val.read(&mut buf[..count.min(BUF_SIZE)]) {
-                            Ok(0) => break,
-                            Ok(bytes) => bytes,
-                            Err(_) => 0,
-                        };
-
-                        count -= bytes;
-                        out_bytes += bytes;
-                    }
-
-                    out_bytes
-                }) {
-                    Ok(res) => res,
-                    Err(_) => {
-                        println!("Panic in FFI callback for `SkStream::read`");
-                        std::process::abort();
-                    }
-                };
-
-                out_bytes
-            } else {
-                let buf: &mut [u8] = std::slice::from_raw_parts_mut(buf as _, count as _);
-
-                val.read(buf).unwrap_or(0)
-            }
-        }
-
         let (length, seek_start, seek_current): (
             usize,
             Option<unsafe extern "C" fn(_, _) -> _>,
@@ -344,11 +293,179 @@ impl<'a> RustStream<'a> {
             _phantom: PhantomData,
         }
     }
+
+    /// Moves `reader` to the heap and returns a new `SkStream` that reads from it. Deleting the
+    /// stream drops the reader.
+    pub(crate) fn new_owned<T: io::Read>(reader: T) -> *mut SkStream {
+        unsafe extern "C" fn drop_trampoline<T>(val: *mut ffi::c_void) {
+            drop(Box::from_raw(val as *mut T));
+        }
+
+        let reader = Box::into_raw(Box::new(reader));
+        unsafe {
+            sb::C_RustStream_newOwned(
+                reader as *mut ffi::c_void,
+                usize::MAX,
+                Some(read_trampoline::<T>),
+                None,
+                None,
+                Some(drop_trampoline::<T>),
+            )
+        }
+    }
+}
+
+unsafe extern "C" fn read_trampoline<T>(
+    val: *mut ffi::c_void,
+    buf: *mut ffi::c_void,
+    count: usize,
+) -> usize
+where
+    T: io::Read,
+{
+    let val: &mut T = &mut *(val as *mut _);
+
+    if buf.is_null() {
+        const BUF_SIZE: usize = 128;
+
+        let mut buf = [0; BUF_SIZE];
+
+        let mut out_bytes = 0;
+        let mut count = count;
+
+        // This is OK because we just abort if it panics anyway, we don't try
+        // to continue at all.
+        let mut val = std::panic::AssertUnwindSafe(val);
+
+        let out_bytes = match std::panic::catch_unwind(move || {
+            while count > 0 {
+                // Stop at the end of the stream and on errors, which are likely to repeat.
+                let bytes = match val.read(&mut buf[..count.min(BUF_SIZE)]) {
+                    Ok(0) | Err(_) => break,
+                    Ok(bytes) => bytes,
+                };
+
+                count -= bytes;
+                out_bytes += bytes;
+            }
+
+            out_bytes
+        }) {
+            Ok(res) => res,
+            Err(_) => {
+                println!("Panic in FFI callback for `SkStream::read`");
+                std::process::abort();
+            }
+        };
+
+        out_bytes
+    } else {
+        let buf: &mut [u8] = std::slice::from_raw_parts_mut(buf as _, count as _);
+
+        val.read(buf).unwrap_or(0)
+    }
+}
+
+pub struct RustWStream<'a> {
+    // note: dropped before the writer it refers to.
+    inner: Handle<sb::RustWStream>,
+    writer: WriterBox<'a>,
+}
+
+impl RustWStream<'_> {
+    pub fn stream_mut(&mut self) -> &mut SkWStream {
+        self.inner.native_mut().base_mut()
+    }
+
+    /// Returns and clears the first error the writer returned since the last call.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        unsafe { (*self.writer.0).take_error() }
+    }
+}
+
+impl NativeBase<SkWStream> for sb::RustWStream {}
+
+impl NativeDrop for sb::RustWStream {
+    fn drop(&mut self) {
+        unsafe { sb::C_RustWStream_destruct(self) }
+    }
+}
+
+trait WriterState {
+    fn take_error(&mut self) -> Option<io::Error>;
+}
+
+/// The writer and the first error it returned.
+struct Writer<'a, T> {
+    writer: &'a mut T,
+    error: Option<io::Error>,
+}
+
+impl<T> WriterState for Writer<'_, T> {
+    fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+}
+
+// Owned by a raw pointer, because the native stream refers to it, too.
+struct WriterBox<'a>(*mut (dyn WriterState + 'a));
+
+impl Drop for WriterBox<'_> {
+    fn drop(&mut self) {
+        drop(unsafe { Box::from_raw(self.0) })
+    }
+}
+
+impl<'a> RustWStream<'a> {
+    pub fn new<T: io::Write>(writer: &'a mut T) -> Self {
+        unsafe extern "C" fn write_trampoline<T: io::Write>(
+            val: *mut ffi::c_void,
+            buf: *const ffi::c_void,
+            count: usize,
+        ) -> bool {
+            if count == 0 {
+                return true;
+            }
+            let val: &mut Writer<T> = &mut *(val as *mut _);
+            let buf: &[u8] = std::slice::from_raw_parts(buf as _, count);
+            match val.writer.write_all(buf) {
+                Ok(()) => true,
+                Err(e) => {
+                    val.error.get_or_insert(e);
+                    false
+                }
+            }
+        }
+
+        unsafe extern "C" fn flush_trampoline<T: io::Write>(val: *mut ffi::c_void) {
+            let val: &mut Writer<T> = &mut *(val as *mut _);
+            if let Err(e) = val.writer.flush() {
+                val.error.get_or_insert(e);
+            }
+        }
+
+        let writer = Box::into_raw(Box::new(Writer {
+            writer,
+            error: None,
+        }));
+
+        RustWStream {
+            inner: Handle::construct(|ptr| unsafe {
+                sb::C_RustWStream_construct(
+                    ptr,
+                    writer as *mut ffi::c_void,
+                    Some(write_trampoline::<T>),
+                    Some(flush_trampoline::<T>),
+                );
+            }),
+            writer: WriterBox(writer),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{MemoryStream, RustStream};
+    use super::{read_trampoline, MemoryStream, RustStream, RustWStream};
     use crate::interop::DynamicMemoryWStream;
 
     #[test]
@@ -375,4 +492,55 @@ mod tests {
         }
         assert_eq!(first_byte, 12i8)
     }
+
+    #[test]
+    fn skipping_in_a_failing_rust_stream_returns() {
+        struct Failing;
+
+        impl std::io::Read for Failing {
+            fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::Other.into())
+            }
+        }
+
+        let mut reader = Failing;
+        // A null buffer skips.
+        let skipped = unsafe {
+            read_trampoline::<Failing>(&mut reader as *mut _ as _, std::ptr::null_mut(), 16)
+        };
+        assert_eq!(skipped, 0);
+
+        let mut stream = RustStream::new(&mut reader);
+        let mut byte = 0i8;
+        assert!(!unsafe { stream.stream_mut().readS8(&mut byte) });
+    }
+
+    #[test]
+    fn write_to_rust_w_stream() {
+        let mut data = Vec::new();
+        {
+            let mut stream = RustWStream::new(&mut data);
+            let bytes = [12u8, 13u8];
+            assert!(unsafe {
+                skia_bindings::C_SkWStream_write(stream.stream_mut(), bytes.as_ptr() as _, 2)
+            });
+        }
+        assert_eq!(data, [12u8, 13u8]);
+    }
+
+    #[test]
+    fn rust_w_stream_keeps_the_first_error() {
+        let mut buf = [0u8; 1];
+        let mut writer = &mut buf[..];
+        let mut stream = RustWStream::new(&mut writer);
+        let bytes = [12u8, 13u8];
+        assert!(!unsafe {
+            skia_bindings::C_SkWStream_write(stream.stream_mut(), bytes.as_ptr() as _, 2)
+        });
+        assert_eq!(
+            stream.take_error().map(|e| e.kind()),
+            Some(std::io::ErrorKind::WriteZero)
+        );
+        assert!(stream.take_error().is_none());
+    }
 }
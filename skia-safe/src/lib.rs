@@ -15,6 +15,7 @@ pub use codec::{Codec, EncodedOrigin};
 mod core;
 mod docs;
 mod effects;
+pub mod encode;
 #[cfg(feature = "gpu")]
 pub mod gpu;
 mod interop;
@@ -1,7 +1,11 @@
 #[cfg(feature = "textlayout")]
 pub(crate) mod paragraph;
+#[cfg(any(feature = "svg", feature = "skottie"))]
+pub mod resources;
 #[cfg(feature = "textlayout")]
 pub mod shaper;
+#[cfg(feature = "skottie")]
+pub mod skottie;
 #[cfg(feature = "svg")]
 pub mod svg;
 #[cfg(feature = "textlayout")]
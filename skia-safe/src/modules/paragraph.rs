@@ -2,6 +2,7 @@ use crate::interop::AsStr;
 use std::ops::Index;
 
 mod dart_types;
+mod dynamic_fonts;
 mod font_arguments;
 mod font_collection;
 mod metrics;
@@ -13,8 +14,10 @@ mod paragraph_style;
 mod text_shadow;
 mod text_style;
 mod typeface_font_provider;
+mod unicode;
 
 pub use dart_types::*;
+pub use dynamic_fonts::*;
 pub use font_arguments::*;
 pub use font_collection::*;
 pub use metrics::*;
@@ -25,6 +28,7 @@ pub use paragraph_style::*;
 pub use text_shadow::*;
 pub use text_style::*;
 pub use typeface_font_provider::*;
+pub use unicode::*;
 
 /// Efficient reference type to a C++ vector of font family SkStrings.
 ///
@@ -0,0 +1,161 @@
+use super::{FontCollection, Paragraph, TypefaceFontProvider};
+use crate::{FontMgr, Typeface};
+
+/// Fonts that are registered and unregistered at runtime, for example web fonts that finish
+/// loading after the text was first laid out.
+///
+/// The fonts are provided to the [`FontCollection`] as its dynamic font manager, so they are
+/// found before the fonts of the default font manager. Each change clears the caches of the font
+/// collection and advances [`Self::generation()`], which is used to find paragraphs that were laid
+/// out with outdated fonts, see [`Self::invalidate()`].
+#[derive(Debug)]
+pub struct DynamicFonts {
+    font_collection: FontCollection,
+    fonts: Vec<(String, Typeface)>,
+    generation: u64,
+}
+
+impl DynamicFonts {
+    /// Takes over the dynamic font manager of `font_collection`.
+    pub fn new(font_collection: FontCollection) -> Self {
+        let mut fonts = Self {
+            font_collection,
+            fonts: Vec::new(),
+            generation: 0,
+        };
+        fonts.update_font_collection();
+        fonts
+    }
+
+    /// The font collection to build paragraphs with.
+    pub fn font_collection(&self) -> &FontCollection {
+        &self.font_collection
+    }
+
+    /// Returns a number that changes whenever fonts are registered or unregistered.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Registers `typeface` under the family name `alias`. Several typefaces, for example
+    /// different weights, can be registered under the same alias.
+    pub fn register(&mut self, typeface: Typeface, alias: impl Into<String>) {
+        self.fonts.push((alias.into(), typeface));
+        self.update_font_collection();
+    }
+
+    /// Loads a font from `bytes` and registers it under the family name `alias`.
+    ///
+    /// Returns the typeface, or `None` if `bytes` do not contain a supported font.
+    pub fn register_data(&mut self, bytes: &[u8], alias: impl Into<String>) -> Option<Typeface> {
+        let typeface = FontMgr::new().new_from_data(bytes, None)?;
+        self.register(typeface.clone(), alias);
+        Some(typeface)
+    }
+
+    /// Unregisters all typefaces of the family name `alias`. Returns `false` if there were none.
+    pub fn unregister(&mut self, alias: impl AsRef<str>) -> bool {
+        let alias = alias.as_ref();
+        let count = self.fonts.len();
+        self.fonts.retain(|(a, _)| a != alias);
+        if self.fonts.len() == count {
+            return false;
+        }
+        self.update_font_collection();
+        true
+    }
+
+    /// Returns the names of the registered families.
+    pub fn aliases(&self) -> impl Iterator<Item = &str> {
+        let mut aliases: Vec<&str> = Vec::new();
+        for (alias, _) in &self.fonts {
+            if !aliases.contains(&alias.as_str()) {
+                aliases.push(alias);
+            }
+        }
+        aliases.into_iter()
+    }
+
+    /// Marks `paragraph` dirty if fonts were registered or unregistered since it was laid out at
+    /// `generation`, and updates `generation` to the current one.
+    ///
+    /// Returns `true` if the paragraph needs to be laid out again.
+    pub fn invalidate(&self, paragraph: &mut Paragraph, generation: &mut u64) -> bool {
+        if *generation == self.generation {
+            return false;
+        }
+        paragraph.mark_dirty();
+        *generation = self.generation;
+        true
+    }
+
+    /// A [`TypefaceFontProvider`] can not unregister fonts, so it is replaced on every change.
+    fn update_font_collection(&mut self) {
+        let mut provider = TypefaceFontProvider::new();
+        for (alias, typeface) in &self.fonts {
+            provider.register_typeface(typeface.clone(), Some(alias));
+        }
+        self.font_collection
+            .set_dynamic_font_manager(FontMgr::from(provider));
+        self.font_collection.clear_caches();
+        self.generation += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DynamicFonts;
+    use crate::{
+        icu,
+        textlayout::{FontCollection, ParagraphBuilder, ParagraphStyle, TextStyle},
+        CustomTypefaceBuilder, FontMgr, FontStyle, Path, Rect,
+    };
+
+    #[test]
+    #[serial_test::serial]
+    fn register_unregister_and_invalidate() {
+        icu::init();
+
+        let mut font_collection = FontCollection::new();
+        font_collection.set_default_font_manager(FontMgr::new(), None);
+        let mut fonts = DynamicFonts::new(font_collection);
+
+        let alias = "Dynamic Test Family";
+        let mut font_collection = fonts.font_collection().clone();
+        let find = |font_collection: &mut FontCollection| {
+            font_collection
+                .find_typefaces(&[alias], FontStyle::default())
+                .first()
+                .map(|typeface| typeface.unique_id())
+        };
+        let fallback = find(&mut font_collection);
+
+        let mut text_style = TextStyle::new();
+        text_style.set_font_families(&[alias]);
+        let mut builder = ParagraphBuilder::new(&ParagraphStyle::new(), font_collection.clone());
+        builder.push_style(&text_style);
+        builder.add_text("Hello");
+        let mut paragraph = builder.build();
+        paragraph.layout(100.0);
+        let mut generation = fonts.generation();
+        assert!(!fonts.invalidate(&mut paragraph, &mut generation));
+
+        let mut typeface_builder = CustomTypefaceBuilder::new();
+        typeface_builder.set_glyph(1u16, 1.0, &Path::rect(Rect::new(0.0, -1.0, 1.0, 0.0), None));
+        let typeface = typeface_builder.detach().unwrap();
+        fonts.register(typeface.clone(), alias);
+        fonts.register(typeface.clone(), "Other Family");
+        fonts.register(typeface.clone(), alias);
+        assert_eq!(fonts.aliases().collect::<Vec<_>>(), [alias, "Other Family"]);
+        assert_eq!(find(&mut font_collection), Some(typeface.unique_id()));
+
+        assert!(fonts.invalidate(&mut paragraph, &mut generation));
+        assert!(!fonts.invalidate(&mut paragraph, &mut generation));
+        paragraph.layout(100.0);
+
+        assert!(fonts.unregister(alias));
+        assert!(!fonts.unregister(alias));
+        assert_eq!(find(&mut font_collection), fallback);
+        assert!(fonts.invalidate(&mut paragraph, &mut generation));
+    }
+}
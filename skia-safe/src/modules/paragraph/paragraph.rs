@@ -1,7 +1,10 @@
 use super::{PositionWithAffinity, RectHeightStyle, RectWidthStyle, TextBox};
-use crate::{interop::VecSink, prelude::*, scalar, textlayout::LineMetrics, Canvas, Point};
+use crate::{
+    interop::VecSink, prelude::*, scalar, textlayout::LineMetrics, Canvas, Font, GlyphId, Point,
+    Rect,
+};
 use skia_bindings as sb;
-use std::{fmt, ops::Range};
+use std::{ffi::c_void, fmt, ops::Range};
 
 pub type Paragraph = RefHandle<sb::skia_textlayout_Paragraph>;
 unsafe_send_sync!(Paragraph);
@@ -70,6 +73,39 @@ impl Paragraph {
         unsafe { sb::C_Paragraph_paint(self.native_mut_force(), canvas.native_mut(), p.x, p.y) }
     }
 
+    /// Paints only the lines in `lines`, which are indices into
+    /// [`Self::get_line_metrics()`]. Lines outside of the paragraph are ignored.
+    ///
+    /// The paragraph is painted clipped to the vertical extent of the lines, so glyphs and
+    /// decorations that reach into neighbouring lines are cut off.
+    ///
+    /// Useful for scrolling views or partial invalidation of very long paragraphs.
+    pub fn paint_lines(&self, canvas: &mut Canvas, p: impl Into<Point>, lines: Range<usize>) {
+        let p = p.into();
+        let metrics = self.get_line_metrics();
+        let end = lines.end.min(metrics.len());
+        let lines = &metrics[lines.start.min(end)..end];
+        let (first, last) = match (lines.first(), lines.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return,
+        };
+        let clip_bounds = match canvas.local_clip_bounds() {
+            Some(clip_bounds) => clip_bounds,
+            None => return,
+        };
+        let top = p.y + (first.baseline - first.ascent) as scalar;
+        let bottom = p.y + (last.baseline + last.descent) as scalar;
+
+        canvas.save();
+        canvas.clip_rect(
+            Rect::new(clip_bounds.left, top, clip_bounds.right, bottom),
+            None,
+            None,
+        );
+        self.paint(canvas, p);
+        canvas.restore();
+    }
+
     /// Returns a vector of bounding boxes that enclose all text between
     /// start and end glyph indexes, including start and excluding end
     pub fn get_rects_for_range(
@@ -166,7 +202,82 @@ impl Paragraph {
             .ok()
     }
 
-    // TODO: wrap visit()
+    /// Visits the glyph runs of all lines in visual order. Requires a call to [`Self::layout()`]
+    /// first.
+    ///
+    /// `visitor` is called with the line number and each run of the line, followed by `None` to
+    /// signal the end of the line.
+    pub fn visit<F>(&mut self, mut visitor: F)
+    where
+        F: FnMut(usize, Option<&VisitorInfo>),
+    {
+        unsafe extern "C" fn visit_line(
+            line_number: i32,
+            info: *const sb::skia_textlayout_Paragraph_VisitorInfo,
+            context: *mut c_void,
+        ) {
+            let visitor = &mut *(context as *mut &mut dyn FnMut(usize, Option<&VisitorInfo>));
+            let info = info.as_ref().map(|info| VisitorInfo::from_native(info));
+            visitor(line_number.try_into().unwrap(), info.as_ref());
+        }
+
+        let mut visitor: &mut dyn FnMut(usize, Option<&VisitorInfo>) = &mut visitor;
+        unsafe {
+            sb::C_Paragraph_visit(
+                self.native_mut(),
+                Some(visit_line),
+                &mut visitor as *mut _ as *mut c_void,
+            )
+        }
+    }
+}
+
+bitflags! {
+    #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct VisitorFlags: u32 {
+        const WHITE_SPACE = sb::skia_textlayout_Paragraph_VisitorFlags_kWhiteSpace_VisitorFlag as _;
+    }
+}
+
+/// A run of glyphs that share a font, passed to the visitor of [`Paragraph::visit()`].
+///
+/// The glyphs of a cluster start at the same UTF-8 index, so `utf8_starts` can be used to map
+/// glyphs back to the text and to find the cluster boundaries. HarfBuzz's unsafe-to-break
+/// information is not exposed by Skia, text that is re-shaped partially should therefore be
+/// split at cluster boundaries between words.
+#[derive(Debug)]
+pub struct VisitorInfo<'a> {
+    pub font: &'a Font,
+    /// The origin of the run relative to the paragraph.
+    pub origin: Point,
+    pub advance_x: scalar,
+    pub glyphs: &'a [GlyphId],
+    /// The positions of the glyphs relative to `origin`.
+    pub positions: &'a [Point],
+    /// The UTF-8 index of the cluster of each glyph, followed by the index of the end of the run.
+    pub utf8_starts: &'a [u32],
+    /// Always empty for the paragraphs created by [`crate::textlayout::ParagraphBuilder`].
+    pub flags: VisitorFlags,
+}
+
+impl<'a> VisitorInfo<'a> {
+    unsafe fn from_native(info: &'a sb::skia_textlayout_Paragraph_VisitorInfo) -> Self {
+        let count: usize = info.count.try_into().unwrap();
+        Self {
+            font: Font::from_native_ref(&*info.font),
+            origin: Point::from_native_c(info.origin),
+            advance_x: info.advanceX,
+            glyphs: safer::from_raw_parts(info.glyphs, count),
+            positions: safer::from_raw_parts(info.positions as *const Point, count),
+            utf8_starts: safer::from_raw_parts(info.utf8Starts, count + 1),
+            flags: VisitorFlags::from_bits_truncate(info.flags),
+        }
+    }
+
+    /// The number of glyphs in the run.
+    pub fn count(&self) -> usize {
+        self.glyphs.len()
+    }
 }
 
 #[deprecated(since = "0.41.0", note = "Use Vec<TextBox>")]
@@ -180,7 +291,7 @@ mod tests {
     use crate::{
         icu,
         textlayout::{FontCollection, ParagraphBuilder, ParagraphStyle, TextStyle},
-        FontMgr,
+        Color, FontMgr, Surface,
     };
 
     #[test]
@@ -206,6 +317,66 @@ mod tests {
         static LOREM_IPSUM: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. Curabitur at leo at nulla tincidunt placerat. Proin eget purus augue. Quisque et est ullamcorper, pellentesque felis nec, pulvinar massa. Aliquam imperdiet, nulla ut dictum euismod, purus dui pulvinar risus, eu suscipit elit neque ac est. Nullam eleifend justo quis placerat ultricies. Vestibulum ut elementum velit. Praesent et dolor sit amet purus bibendum mattis. Aliquam erat volutpat.";
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn visit_runs_and_clusters() {
+        icu::init();
+
+        let mut font_collection = FontCollection::new();
+        font_collection.set_default_font_manager(FontMgr::new(), None);
+        let mut paragraph_builder = ParagraphBuilder::new(&ParagraphStyle::new(), font_collection);
+        paragraph_builder.push_style(&TextStyle::new());
+        let text = "Hello World\nSecond line";
+        paragraph_builder.add_text(text);
+        let mut paragraph = paragraph_builder.build();
+        paragraph.layout(1000.0);
+
+        let mut line_ends = Vec::new();
+        paragraph.visit(|line, info| match info {
+            Some(info) => {
+                assert_eq!(line, line_ends.len());
+                assert_eq!(info.positions.len(), info.count());
+                assert_eq!(info.utf8_starts.len(), info.count() + 1);
+                assert!(info.utf8_starts.iter().all(|&i| i as usize <= text.len()));
+            }
+            None => line_ends.push(line),
+        });
+        assert_eq!(line_ends, [0, 1]);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn paint_lines_paints_only_the_given_lines() {
+        icu::init();
+
+        let mut font_collection = FontCollection::new();
+        font_collection.set_default_font_manager(FontMgr::new(), None);
+        let mut paragraph_builder = ParagraphBuilder::new(&ParagraphStyle::new(), font_collection);
+        let mut text_style = TextStyle::new();
+        text_style.set_color(Color::BLACK).set_font_size(20.0);
+        paragraph_builder.push_style(&text_style);
+        paragraph_builder.add_text("MMMM\nMMMM\nMMMM");
+        let mut paragraph = paragraph_builder.build();
+        paragraph.layout(200.0);
+        let line_metrics = paragraph.get_line_metrics();
+        assert_eq!(line_metrics.len(), 3);
+
+        let mut surface = Surface::new_raster_n32_premul((200, 100)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+        paragraph.paint_lines(surface.canvas(), (0, 0), 1..2);
+        paragraph.paint_lines(surface.canvas(), (0, 0), 5..7);
+
+        let pixmap = surface.peek_pixels().unwrap();
+        let painted_rows: Vec<i32> = (0..pixmap.height())
+            .filter(|&y| (0..pixmap.width()).any(|x| pixmap.get_color((x, y)) != Color::WHITE))
+            .collect();
+        assert!(!painted_rows.is_empty());
+        let line = &line_metrics[1];
+        let top = (line.baseline - line.ascent).floor() as i32;
+        let bottom = (line.baseline + line.descent).ceil() as i32;
+        assert!(painted_rows.iter().all(|&y| y >= top && y < bottom));
+    }
+
     /// Regression test for <https://github.com/rust-skia/rust-skia/issues/585>
     #[test]
     #[serial_test::serial]
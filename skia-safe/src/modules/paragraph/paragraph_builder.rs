@@ -1,4 +1,8 @@
-use super::{FontCollection, Paragraph, ParagraphStyle, PlaceholderStyle, TextStyle};
+#[cfg(feature = "unicode-client")]
+use super::LineBreakBefore;
+use super::{
+    unicode, FontCollection, Paragraph, ParagraphStyle, PlaceholderStyle, TextStyle, Unicode,
+};
 use crate::prelude::*;
 use skia_bindings as sb;
 use std::{fmt, os::raw};
@@ -8,6 +12,8 @@ unsafe_send_sync!(ParagraphBuilder);
 
 impl NativeDrop for sb::skia_textlayout_ParagraphBuilder {
     fn drop(&mut self) {
+        #[cfg(feature = "unicode-client")]
+        client_data::forget(self);
         unsafe { sb::C_ParagraphBuilder_delete(self) }
     }
 }
@@ -54,21 +60,160 @@ impl ParagraphBuilder {
         self
     }
 
+    /// Sets the UTF-8 positions of the word boundaries of the text added so far.
+    ///
+    /// With the feature `unicode-client`, ICU is not available and the word boundaries, grapheme
+    /// breaks, and line breaks must be provided before [`Self::build()`] is called.
+    #[cfg(feature = "unicode-client")]
+    pub fn set_words_utf8(&mut self, words: &[usize]) -> &mut Self {
+        unsafe {
+            sb::C_ParagraphBuilder_setWordsUtf8(self.native_mut(), words.as_ptr(), words.len())
+        }
+        client_data::provide(self.native(), client_data::WORDS);
+        self
+    }
+
+    /// Sets the UTF-8 positions of the grapheme cluster boundaries of the text added so far.
+    #[cfg(feature = "unicode-client")]
+    pub fn set_grapheme_breaks_utf8(&mut self, grapheme_breaks: &[usize]) -> &mut Self {
+        unsafe {
+            sb::C_ParagraphBuilder_setGraphemeBreaksUtf8(
+                self.native_mut(),
+                grapheme_breaks.as_ptr(),
+                grapheme_breaks.len(),
+            )
+        }
+        client_data::provide(self.native(), client_data::GRAPHEME_BREAKS);
+        self
+    }
+
+    /// Sets the line break opportunities of the text added so far.
+    #[cfg(feature = "unicode-client")]
+    pub fn set_line_breaks_utf8(&mut self, line_breaks: &[LineBreakBefore]) -> &mut Self {
+        unsafe {
+            sb::C_ParagraphBuilder_setLineBreaksUtf8(
+                self.native_mut(),
+                line_breaks.native().as_ptr(),
+                line_breaks.len(),
+            )
+        }
+        client_data::provide(self.native(), client_data::LINE_BREAKS);
+        self
+    }
+
+    /// Builds the paragraph.
+    ///
+    /// # Panics
+    ///
+    /// With the feature `unicode-client`, if the builder was created with [`Self::new()`] and the
+    /// word boundaries, grapheme breaks, or line breaks were not set since the builder was
+    /// created, reset, or last built.
     pub fn build(&mut self) -> Paragraph {
+        #[cfg(feature = "unicode-client")]
+        client_data::take(self.native());
         Paragraph::from_ptr(unsafe { sb::C_ParagraphBuilder_Build(self.native_mut()) }).unwrap()
     }
 
     pub fn reset(&mut self) {
         unsafe { sb::C_ParagraphBuilder_Reset(self.native_mut()) }
+        #[cfg(feature = "unicode-client")]
+        client_data::reset(self.native());
     }
 
+    /// Creates a new paragraph builder that uses ICU for text segmentation and bidi resolution.
+    ///
+    /// With the feature `unicode-client`, ICU is not available. Instead, the word boundaries,
+    /// grapheme breaks, and line breaks must be set with [`Self::set_words_utf8()`],
+    /// [`Self::set_grapheme_breaks_utf8()`], and [`Self::set_line_breaks_utf8()`] before each
+    /// call to [`Self::build()`]. Alternatively, use [`Self::new_with_unicode()`].
     pub fn new(style: &ParagraphStyle, font_collection: impl Into<FontCollection>) -> Self {
         #[cfg(feature = "embed-icudtl")]
         crate::icu::init();
 
-        Self::from_ptr(unsafe {
+        let builder = Self::from_ptr(unsafe {
             sb::C_ParagraphBuilder_make(style.native(), font_collection.into().into_ptr())
         })
+        .expect("Unicode initialization error");
+        #[cfg(feature = "unicode-client")]
+        client_data::register(builder.native());
+        builder
+    }
+
+    /// Creates a new paragraph builder that uses `unicode` for text segmentation and bidi
+    /// resolution instead of ICU.
+    pub fn new_with_unicode(
+        style: &ParagraphStyle,
+        font_collection: impl Into<FontCollection>,
+        unicode: impl Unicode + 'static,
+    ) -> Self {
+        let unicode = unicode::new_native(unicode);
+        Self::from_ptr(unsafe {
+            sb::C_ParagraphBuilder_makeWithUnicode(
+                style.native(),
+                font_collection.into().into_ptr(),
+                unicode,
+            )
+        })
         .expect("Unicode initialization error")
     }
 }
+
+/// Tracks which client break data was set for the paragraph builders created with
+/// [`ParagraphBuilder::new()`], because Skia can not lay out their text without it.
+#[cfg(feature = "unicode-client")]
+mod client_data {
+    use skia_bindings as sb;
+    use std::{collections::HashMap, mem, sync::Mutex};
+
+    pub const WORDS: u8 = 1;
+    pub const GRAPHEME_BREAKS: u8 = 2;
+    pub const LINE_BREAKS: u8 = 4;
+    const ALL: u8 = WORDS | GRAPHEME_BREAKS | LINE_BREAKS;
+
+    type Builder = sb::skia_textlayout_ParagraphBuilder;
+
+    lazy_static! {
+        static ref PROVIDED: Mutex<HashMap<usize, u8>> = Mutex::new(HashMap::new());
+    }
+
+    fn key(builder: &Builder) -> usize {
+        builder as *const Builder as usize
+    }
+
+    pub fn register(builder: &Builder) {
+        PROVIDED.lock().unwrap().insert(key(builder), 0);
+    }
+
+    pub fn reset(builder: &Builder) {
+        if let Some(provided) = PROVIDED.lock().unwrap().get_mut(&key(builder)) {
+            *provided = 0;
+        }
+    }
+
+    pub fn provide(builder: &Builder, data: u8) {
+        if let Some(provided) = PROVIDED.lock().unwrap().get_mut(&key(builder)) {
+            *provided |= data;
+        }
+    }
+
+    /// Panics if the builder needs client break data that was not set. Skia moves the data into
+    /// the paragraph, so it must be set again before the next build.
+    pub fn take(builder: &Builder) {
+        let provided = PROVIDED
+            .lock()
+            .unwrap()
+            .get_mut(&key(builder))
+            .map(|provided| mem::replace(provided, 0));
+        if let Some(provided) = provided {
+            assert_eq!(
+                provided, ALL,
+                "With the feature `unicode-client`, the word boundaries, grapheme breaks, and \
+                 line breaks must be set before the paragraph is built"
+            );
+        }
+    }
+
+    pub fn forget(builder: &Builder) {
+        PROVIDED.lock().unwrap().remove(&key(builder));
+    }
+}
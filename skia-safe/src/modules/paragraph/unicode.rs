@@ -0,0 +1,432 @@
+use super::TextDirection;
+use crate::Unichar;
+use skia_bindings as sb;
+use std::sync::Arc;
+
+pub use sb::SkUnicode_BreakType as BreakType;
+variant_name!(BreakType::Graphemes);
+
+pub use sb::SkUnicode_LineBreakType as LineBreakType;
+variant_name!(LineBreakType::HardLineBreak);
+
+/// A line break opportunity before the UTF-8 offset `pos`.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct LineBreakBefore {
+    pub pos: usize,
+    pub break_type: LineBreakType,
+}
+
+native_transmutable!(
+    sb::SkUnicode_LineBreakBefore,
+    LineBreakBefore,
+    line_break_before_layout
+);
+
+impl LineBreakBefore {
+    pub fn new(pos: usize, break_type: LineBreakType) -> Self {
+        Self { pos, break_type }
+    }
+}
+
+/// The embedding level of a bidirectional text run. Even levels are left-to-right, odd levels
+/// right-to-left.
+pub type BidiLevel = u8;
+
+/// The status value of a hard line break as returned from [`Unicode::breaks()`].
+pub const HARD_LINE_BREAK_STATUS: i32 = LineBreakType::HardLineBreak as _;
+
+/// A text break position returned from [`Unicode::breaks()`].
+///
+/// `position` is an UTF-8 offset into the text. For line breaks, `status` must be set to
+/// [`HARD_LINE_BREAK_STATUS`] for mandatory breaks, and `0` for soft breaks.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TextBreak {
+    pub position: usize,
+    pub status: i32,
+}
+
+impl TextBreak {
+    pub fn new(position: usize, status: i32) -> Self {
+        Self { position, status }
+    }
+}
+
+impl From<usize> for TextBreak {
+    fn from(position: usize) -> Self {
+        Self::new(position, 0)
+    }
+}
+
+/// A pluggable Unicode backend for the text layout (`SkUnicode`).
+///
+/// Implementations are used instead of ICU when passed to
+/// [`super::ParagraphBuilder::new_with_unicode()`]. All the character classification functions
+/// and [`Self::to_upper()`] have default implementations based on the Rust standard library.
+pub trait Unicode: Send + Sync {
+    fn is_control(&self, c: Unichar) -> bool {
+        char::from_u32(c as _).map_or(false, char::is_control)
+    }
+
+    fn is_whitespace(&self, c: Unichar) -> bool {
+        char::from_u32(c as _).map_or(false, |c| c.is_whitespace() && c != '\u{00A0}')
+    }
+
+    fn is_space(&self, c: Unichar) -> bool {
+        char::from_u32(c as _).map_or(false, char::is_whitespace)
+    }
+
+    fn is_tabulation(&self, c: Unichar) -> bool {
+        c == '\t' as Unichar
+    }
+
+    fn is_hard_break(&self, c: Unichar) -> bool {
+        matches!(
+            char::from_u32(c as _),
+            Some('\n' | '\u{000B}' | '\u{000C}' | '\r' | '\u{0085}' | '\u{2028}' | '\u{2029}')
+        )
+    }
+
+    fn is_ideographic(&self, c: Unichar) -> bool {
+        matches!(c,
+            0x2E80..=0x2FFF | 0x3000..=0x303F | 0x3040..=0x30FF | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF | 0xF900..=0xFAFF | 0x20000..=0x3FFFF)
+    }
+
+    fn to_upper(&self, str: &str) -> String {
+        str.to_uppercase()
+    }
+
+    /// Returns the bidi embedding level for each UTF-16 code unit of `utf16`, or `None` if the
+    /// levels can not be resolved.
+    fn bidi_levels(&self, utf16: &[u16], direction: TextDirection) -> Option<Vec<BidiLevel>>;
+
+    /// Returns the break positions of the given `break_type` in ascending order. The positions
+    /// are UTF-8 offsets into `utf8` and should include the start and the end of the text.
+    fn breaks(
+        &self,
+        utf8: &str,
+        break_type: BreakType,
+        locale: Option<&str>,
+    ) -> Option<Vec<TextBreak>>;
+
+    /// Returns the logical run index for each visual position of the runs with the given
+    /// levels.
+    ///
+    /// The default implementation follows rule L2 of the Unicode Bidirectional Algorithm.
+    fn reorder_visual(&self, run_levels: &[BidiLevel]) -> Vec<i32> {
+        reorder_visual(run_levels)
+    }
+}
+
+/// Reorders runs from their logical to their visual order following rule L2 of the Unicode
+/// Bidirectional Algorithm and returns the logical index of each visual run.
+pub fn reorder_visual(run_levels: &[BidiLevel]) -> Vec<i32> {
+    let mut logical_from_visual: Vec<i32> = (0..run_levels.len() as i32).collect();
+    let highest = run_levels.iter().max().copied().unwrap_or_default();
+    let lowest_odd = match run_levels.iter().filter(|l| *l % 2 == 1).min() {
+        Some(lowest_odd) => *lowest_odd,
+        None => return logical_from_visual,
+    };
+
+    for level in (lowest_odd..=highest).rev() {
+        let mut i = 0;
+        while i < run_levels.len() {
+            if run_levels[logical_from_visual[i] as usize] < level {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < run_levels.len() && run_levels[logical_from_visual[i] as usize] >= level {
+                i += 1;
+            }
+            logical_from_visual[start..i].reverse();
+        }
+    }
+    logical_from_visual
+}
+
+/// Create a native `SkUnicode` instance that forwards all calls to `unicode`. The returned
+/// pointer is owned by the caller.
+pub(crate) fn new_native(unicode: impl Unicode + 'static) -> *mut sb::SkUnicode {
+    let unicode: Arc<dyn Unicode> = Arc::new(unicode);
+    let param = rust_unicode::new_param(unicode);
+    unsafe { sb::C_RustUnicode_new(&param) }
+}
+
+mod rust_unicode {
+    use super::{BidiLevel, BreakType, TextBreak, Unicode};
+    use crate::{textlayout::TextDirection, Unichar};
+    use skia_bindings::{RustUnicode_Param, SkBidiIterator_Direction, TraitObject, VecWriter};
+    use std::{
+        ffi::CStr,
+        mem,
+        os::raw,
+        panic::{self, AssertUnwindSafe},
+        slice, str,
+        sync::Arc,
+    };
+
+    pub fn new_param(unicode: Arc<dyn Unicode>) -> RustUnicode_Param {
+        RustUnicode_Param {
+            trait_: into_trait_object(unicode),
+            isControl: Some(is_control),
+            isWhitespace: Some(is_whitespace),
+            isSpace: Some(is_space),
+            isTabulation: Some(is_tabulation),
+            isHardBreak: Some(is_hard_break),
+            isIdeographic: Some(is_ideographic),
+            toUpper: Some(to_upper),
+            bidiLevels: Some(bidi_levels),
+            breaks: Some(breaks),
+            reorderVisual: Some(reorder_visual),
+            clone: Some(clone),
+            drop: Some(drop),
+        }
+    }
+
+    extern "C" fn is_control(to: TraitObject, c: Unichar) -> bool {
+        panic::catch_unwind(AssertUnwindSafe(|| to_unicode(&to).is_control(c))).unwrap_or(false)
+    }
+
+    extern "C" fn is_whitespace(to: TraitObject, c: Unichar) -> bool {
+        panic::catch_unwind(AssertUnwindSafe(|| to_unicode(&to).is_whitespace(c))).unwrap_or(false)
+    }
+
+    extern "C" fn is_space(to: TraitObject, c: Unichar) -> bool {
+        panic::catch_unwind(AssertUnwindSafe(|| to_unicode(&to).is_space(c))).unwrap_or(false)
+    }
+
+    extern "C" fn is_tabulation(to: TraitObject, c: Unichar) -> bool {
+        panic::catch_unwind(AssertUnwindSafe(|| to_unicode(&to).is_tabulation(c))).unwrap_or(false)
+    }
+
+    extern "C" fn is_hard_break(to: TraitObject, c: Unichar) -> bool {
+        panic::catch_unwind(AssertUnwindSafe(|| to_unicode(&to).is_hard_break(c))).unwrap_or(false)
+    }
+
+    extern "C" fn is_ideographic(to: TraitObject, c: Unichar) -> bool {
+        panic::catch_unwind(AssertUnwindSafe(|| to_unicode(&to).is_ideographic(c))).unwrap_or(false)
+    }
+
+    unsafe extern "C" fn to_upper(
+        to: TraitObject,
+        str: *const raw::c_char,
+        len: usize,
+        upper: *mut VecWriter<raw::c_char>,
+    ) {
+        let str = as_str(str, len).unwrap_or_default();
+        let result = match panic::catch_unwind(AssertUnwindSafe(|| to_unicode(&to).to_upper(str))) {
+            Ok(result) => result,
+            Err(_) => return,
+        };
+        write(
+            &mut *upper,
+            slice::from_raw_parts(result.as_ptr() as *const raw::c_char, result.len()),
+        );
+    }
+
+    unsafe extern "C" fn bidi_levels(
+        to: TraitObject,
+        utf16: *const u16,
+        len: usize,
+        direction: SkBidiIterator_Direction,
+        levels: *mut VecWriter<BidiLevel>,
+    ) -> bool {
+        let utf16 = if len == 0 {
+            &[]
+        } else {
+            slice::from_raw_parts(utf16, len)
+        };
+        let direction = match direction {
+            SkBidiIterator_Direction::kLTR => TextDirection::LTR,
+            SkBidiIterator_Direction::kRTL => TextDirection::RTL,
+        };
+        match panic::catch_unwind(AssertUnwindSafe(|| {
+            to_unicode(&to).bidi_levels(utf16, direction)
+        }))
+        .ok()
+        .flatten()
+        {
+            Some(result) => {
+                write(&mut *levels, &result);
+                true
+            }
+            None => false,
+        }
+    }
+
+    unsafe extern "C" fn breaks(
+        to: TraitObject,
+        utf8: *const raw::c_char,
+        len: usize,
+        break_type: BreakType,
+        locale: *const raw::c_char,
+        positions: *mut VecWriter<i32>,
+        statuses: *mut VecWriter<i32>,
+    ) -> bool {
+        let utf8 = match as_str(utf8, len) {
+            Some(utf8) => utf8,
+            None => return false,
+        };
+        let locale = (!locale.is_null())
+            .then(|| CStr::from_ptr(locale).to_str().ok())
+            .flatten();
+        match panic::catch_unwind(AssertUnwindSafe(|| {
+            to_unicode(&to).breaks(utf8, break_type, locale)
+        }))
+        .ok()
+        .flatten()
+        {
+            Some(result) => {
+                let p: Option<Vec<i32>> = result
+                    .iter()
+                    .map(|TextBreak { position, .. }| (*position).try_into().ok())
+                    .collect();
+                let p = match p {
+                    Some(p) => p,
+                    None => return false,
+                };
+                let s: Vec<i32> = result
+                    .iter()
+                    .map(|TextBreak { status, .. }| *status)
+                    .collect();
+                write(&mut *positions, &p);
+                write(&mut *statuses, &s);
+                true
+            }
+            None => false,
+        }
+    }
+
+    unsafe extern "C" fn reorder_visual(
+        to: TraitObject,
+        run_levels: *const BidiLevel,
+        levels_count: usize,
+        logical_from_visual: *mut i32,
+    ) {
+        if levels_count == 0 {
+            return;
+        }
+        let run_levels = slice::from_raw_parts(run_levels, levels_count);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            to_unicode(&to).reorder_visual(run_levels)
+        }))
+        .unwrap_or_default();
+        let logical_from_visual = slice::from_raw_parts_mut(logical_from_visual, levels_count);
+        for (i, l) in logical_from_visual.iter_mut().enumerate() {
+            *l = result.get(i).copied().unwrap_or(i as i32);
+        }
+    }
+
+    unsafe extern "C" fn clone(to: TraitObject) -> TraitObject {
+        let unicode: *const dyn Unicode = mem::transmute(to);
+        Arc::increment_strong_count(unicode);
+        to
+    }
+
+    unsafe extern "C" fn drop(to: TraitObject) {
+        let unicode: *const dyn Unicode = mem::transmute(to);
+        let unicode = AssertUnwindSafe(Arc::from_raw(unicode));
+        let _ = panic::catch_unwind(move || mem::drop(unicode));
+    }
+
+    fn into_trait_object(unicode: Arc<dyn Unicode>) -> TraitObject {
+        unsafe { mem::transmute(Arc::into_raw(unicode)) }
+    }
+
+    fn to_unicode(to: &TraitObject) -> &dyn Unicode {
+        unsafe { &*mem::transmute::<TraitObject, *const dyn Unicode>(*to) }
+    }
+
+    unsafe fn as_str<'a>(ptr: *const raw::c_char, len: usize) -> Option<&'a str> {
+        if len == 0 {
+            return Some("");
+        }
+        str::from_utf8(slice::from_raw_parts(ptr as *const u8, len)).ok()
+    }
+
+    fn write<T>(writer: &mut VecWriter<T>, values: &[T]) {
+        if let Some(write_fn) = writer.write_fn {
+            unsafe { write_fn(writer.target, values.as_ptr(), values.len()) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reorder_visual, BidiLevel, BreakType, TextBreak, Unicode};
+    use crate::{
+        textlayout::{FontCollection, ParagraphBuilder, ParagraphStyle, TextDirection, TextStyle},
+        FontMgr,
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    /// Breaks lines and words after spaces, and graphemes at every character.
+    struct SpaceBreaks {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl Unicode for SpaceBreaks {
+        fn bidi_levels(&self, utf16: &[u16], _direction: TextDirection) -> Option<Vec<BidiLevel>> {
+            Some(vec![0; utf16.len()])
+        }
+
+        fn breaks(
+            &self,
+            utf8: &str,
+            break_type: BreakType,
+            _locale: Option<&str>,
+        ) -> Option<Vec<TextBreak>> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            let mut positions: Vec<usize> = match break_type {
+                BreakType::Graphemes => utf8.char_indices().map(|(i, _)| i).collect(),
+                _ => utf8
+                    .char_indices()
+                    .filter(|(_, c)| *c == ' ')
+                    .map(|(i, _)| i + 1)
+                    .collect(),
+            };
+            positions.insert(0, 0);
+            positions.push(utf8.len());
+            positions.dedup();
+            Some(positions.into_iter().map(TextBreak::from).collect())
+        }
+    }
+
+    #[test]
+    fn paragraph_with_custom_unicode() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut font_collection = FontCollection::new();
+        font_collection.set_default_font_manager(FontMgr::new(), None);
+        let mut builder = ParagraphBuilder::new_with_unicode(
+            &ParagraphStyle::new(),
+            font_collection,
+            SpaceBreaks {
+                calls: calls.clone(),
+            },
+        );
+        builder.push_style(&TextStyle::new());
+        builder.add_text("one two three");
+        let mut paragraph = builder.build();
+
+        paragraph.layout(10000.0);
+        assert_eq!(paragraph.line_number(), 1);
+        assert!(calls.load(Ordering::Relaxed) > 0);
+
+        paragraph.layout(1.0);
+        assert_eq!(paragraph.line_number(), 3);
+    }
+
+    #[test]
+    fn reorder_visual_follows_rule_l2() {
+        assert_eq!(reorder_visual(&[]), Vec::<i32>::new());
+        assert_eq!(reorder_visual(&[0, 0, 0]), vec![0, 1, 2]);
+        assert_eq!(reorder_visual(&[1, 1, 1]), vec![2, 1, 0]);
+        assert_eq!(reorder_visual(&[0, 1, 1, 0]), vec![0, 2, 1, 3]);
+        assert_eq!(reorder_visual(&[0, 1, 2, 2, 1, 0]), vec![0, 4, 2, 3, 1, 5]);
+    }
+}
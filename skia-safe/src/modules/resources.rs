@@ -0,0 +1,203 @@
+//! Loading of external assets (`skresources`).
+
+use crate::{Data, Image, Typeface};
+use skia_bindings as sb;
+use std::sync::Arc;
+
+/// Loads the images, fonts, and other external resources an SVG document or a Lottie animation
+/// refers to (`skresources::ResourceProvider`).
+///
+/// Implement this trait to load assets from an asset bundle, an archive, or memory instead of
+/// the filesystem or the network. Images that are embedded as `data:` URIs are decoded without
+/// calling the provider.
+///
+/// ```
+/// # use std::collections::HashMap;
+/// # use skia_safe::{resources::ResourceProvider, Data};
+/// struct Bundle(HashMap<String, Data>);
+///
+/// impl ResourceProvider for Bundle {
+///     fn load(&self, _path: &str, name: &str) -> Option<Data> {
+///         self.0.get(name).cloned()
+///     }
+/// }
+/// ```
+pub trait ResourceProvider: Send + Sync + 'static {
+    /// Loads the resource `name` located at `path`.
+    fn load(&self, path: &str, name: &str) -> Option<Data>;
+
+    /// Loads the image `name` located at `path`. `id` is the image's id in the document, which
+    /// may be empty.
+    ///
+    /// The default implementation decodes the data returned by [`Self::load()`].
+    fn load_image(&self, path: &str, name: &str, id: &str) -> Option<Image> {
+        let _ = id;
+        Image::from_encoded(self.load(path, name)?)
+    }
+
+    /// Loads the typeface of the font family `name`, which may be available at `url`.
+    ///
+    /// The default implementation returns `None`, which leaves the lookup to the font manager.
+    fn load_typeface(&self, name: &str, url: &str) -> Option<Typeface> {
+        let _ = (name, url);
+        None
+    }
+
+    /// Loads the audio track `name` located at `path`, which is played back by the application
+    /// in sync with an animation. `id` is the id of the audio layer.
+    ///
+    /// The default implementation returns `None`, which ignores the audio layer.
+    fn load_audio(&self, path: &str, name: &str, id: &str) -> Option<Box<dyn ExternalTrack>> {
+        let _ = (path, name, id);
+        None
+    }
+}
+
+/// An audio track of an animation (`skresources::ExternalTrackAsset`).
+pub trait ExternalTrack: Send + 'static {
+    /// Called whenever the animation seeks to a new frame. `t` is the time in seconds relative to
+    /// the start of the track, negative if the track is not playing at that time.
+    fn seek(&mut self, t: f32);
+}
+
+impl<T: ResourceProvider + ?Sized> ResourceProvider for Arc<T> {
+    fn load(&self, path: &str, name: &str) -> Option<Data> {
+        (**self).load(path, name)
+    }
+
+    fn load_image(&self, path: &str, name: &str, id: &str) -> Option<Image> {
+        (**self).load_image(path, name, id)
+    }
+
+    fn load_typeface(&self, name: &str, url: &str) -> Option<Typeface> {
+        (**self).load_typeface(name, url)
+    }
+
+    fn load_audio(&self, path: &str, name: &str, id: &str) -> Option<Box<dyn ExternalTrack>> {
+        (**self).load_audio(path, name, id)
+    }
+}
+
+/// Creates a reference counted `skresources::ResourceProvider` that owns `provider`.
+pub(crate) fn new_native(provider: impl ResourceProvider) -> *mut sb::skresources_ResourceProvider {
+    let provider: Box<dyn ResourceProvider> = Box::new(provider);
+    let param = rust_resource_provider::new_param(provider);
+    unsafe { sb::C_RustResourceProvider_new(&param) }
+}
+
+mod rust_resource_provider {
+    use super::{ExternalTrack, ResourceProvider};
+    use crate::prelude::*;
+    use skia_bindings::{
+        self as sb, RustExternalTrack_Param, RustResourceProvider_Param, TraitObject,
+    };
+    use std::{
+        ffi::CStr,
+        mem,
+        os::raw,
+        panic::{self, AssertUnwindSafe},
+    };
+
+    pub fn new_param(provider: Box<dyn ResourceProvider>) -> RustResourceProvider_Param {
+        RustResourceProvider_Param {
+            trait_: unsafe { mem::transmute(Box::into_raw(provider)) },
+            load: Some(load),
+            loadImage: Some(load_image),
+            loadTypeface: Some(load_typeface),
+            loadAudio: Some(load_audio),
+            drop: Some(drop),
+        }
+    }
+
+    unsafe extern "C" fn load(
+        to: TraitObject,
+        path: *const raw::c_char,
+        name: *const raw::c_char,
+    ) -> *mut sb::SkData {
+        let (path, name) = (to_str(path), to_str(name));
+        panic::catch_unwind(AssertUnwindSafe(|| to_provider(to).load(&path, &name)))
+            .ok()
+            .flatten()
+            .into_ptr_or_null()
+    }
+
+    unsafe extern "C" fn load_image(
+        to: TraitObject,
+        path: *const raw::c_char,
+        name: *const raw::c_char,
+        id: *const raw::c_char,
+    ) -> *mut sb::SkImage {
+        let (path, name, id) = (to_str(path), to_str(name), to_str(id));
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            to_provider(to).load_image(&path, &name, &id)
+        }))
+        .ok()
+        .flatten()
+        .into_ptr_or_null()
+    }
+
+    unsafe extern "C" fn load_typeface(
+        to: TraitObject,
+        name: *const raw::c_char,
+        url: *const raw::c_char,
+    ) -> *mut sb::SkTypeface {
+        let (name, url) = (to_str(name), to_str(url));
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            to_provider(to).load_typeface(&name, &url)
+        }))
+        .ok()
+        .flatten()
+        .into_ptr_or_null()
+    }
+
+    unsafe extern "C" fn load_audio(
+        to: TraitObject,
+        path: *const raw::c_char,
+        name: *const raw::c_char,
+        id: *const raw::c_char,
+        track: *mut RustExternalTrack_Param,
+    ) -> bool {
+        let (path, name, id) = (to_str(path), to_str(name), to_str(id));
+        let loaded = panic::catch_unwind(AssertUnwindSafe(|| {
+            to_provider(to).load_audio(&path, &name, &id)
+        }));
+        match loaded {
+            Ok(Some(loaded)) => {
+                *track = RustExternalTrack_Param {
+                    trait_: mem::transmute(Box::into_raw(loaded)),
+                    seek: Some(seek_track),
+                    drop: Some(drop_track),
+                };
+                true
+            }
+            _ => false,
+        }
+    }
+
+    unsafe extern "C" fn seek_track(to: TraitObject, t: f32) {
+        let track = &mut *mem::transmute::<TraitObject, *mut dyn ExternalTrack>(to);
+        panic::catch_unwind(AssertUnwindSafe(|| track.seek(t))).ok();
+    }
+
+    unsafe extern "C" fn drop_track(to: TraitObject) {
+        mem::drop(Box::from_raw(mem::transmute::<
+            TraitObject,
+            *mut dyn ExternalTrack,
+        >(to)))
+    }
+
+    unsafe extern "C" fn drop(to: TraitObject) {
+        mem::drop(Box::from_raw(mem::transmute::<
+            TraitObject,
+            *mut dyn ResourceProvider,
+        >(to)))
+    }
+
+    unsafe fn to_str(ptr: *const raw::c_char) -> String {
+        CStr::from_ptr(ptr).to_string_lossy().into_owned()
+    }
+
+    unsafe fn to_provider<'a>(to: TraitObject) -> &'a dyn ResourceProvider {
+        &*mem::transmute::<TraitObject, *const dyn ResourceProvider>(to)
+    }
+}
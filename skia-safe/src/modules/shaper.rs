@@ -1,12 +1,13 @@
-use crate::{prelude::*, scalar, Font, FontMgr, FourByteTag, Point, TextBlob};
+use crate::{prelude::*, scalar, Font, FontMgr, FourByteTag, GlyphId, Point, TextBlob, Vector};
 use skia_bindings::{
     self as sb, RustRunHandler, SkShaper, SkShaper_BiDiRunIterator, SkShaper_FontRunIterator,
     SkShaper_LanguageRunIterator, SkShaper_RunHandler, SkShaper_RunIterator,
     SkShaper_ScriptRunIterator, SkTextBlobBuilderRunHandler,
 };
-use std::{ffi::CStr, fmt, marker::PhantomData, os::raw};
+use std::{ffi::CStr, fmt, marker::PhantomData, mem, ops::Range, os::raw};
 
 pub use run_handler::RunHandler;
+use run_handler::{Buffer, RunInfo};
 
 pub type Shaper = RefHandle<SkShaper>;
 unsafe_send_sync!(Shaper);
@@ -34,6 +35,8 @@ impl Shaper {
         Self::from_ptr(unsafe { sb::C_SkShaper_MakePrimitive() }).unwrap()
     }
 
+    /// Not available with the feature `unicode-client`, because it needs ICU.
+    #[cfg(not(feature = "unicode-client"))]
     pub fn new_shaper_driven_wrapper(font_mgr: impl Into<Option<FontMgr>>) -> Option<Self> {
         #[cfg(feature = "embed-icudtl")]
         crate::icu::init();
@@ -43,6 +46,8 @@ impl Shaper {
         })
     }
 
+    /// Not available with the feature `unicode-client`, because it needs ICU.
+    #[cfg(not(feature = "unicode-client"))]
     pub fn new_shape_then_wrap(font_mgr: impl Into<Option<FontMgr>>) -> Option<Self> {
         #[cfg(feature = "embed-icudtl")]
         crate::icu::init();
@@ -73,6 +78,10 @@ impl Shaper {
         Self::from_ptr(unsafe { sb::C_SkShaper_MakeCoreText() })
     }
 
+    /// Creates the best shaper that is available.
+    ///
+    /// With the feature `unicode-client`, ICU is not available and this returns the primitive
+    /// shaper, which does not use HarfBuzz and does not reorder bidirectional text.
     pub fn new(font_mgr: impl Into<Option<FontMgr>>) -> Self {
         #[cfg(feature = "embed-icudtl")]
         crate::icu::init();
@@ -200,6 +209,8 @@ impl Shaper {
         .map(|i| i.borrows(utf8))
     }
 
+    /// Not available with the feature `unicode-client`, because it needs ICU.
+    #[cfg(not(feature = "unicode-client"))]
     pub fn new_icu_bidi_run_iterator(utf8: &str, level: u8) -> Option<Borrows<BiDiRunIterator>> {
         let bytes = utf8.as_bytes();
         BiDiRunIterator::from_ptr(unsafe {
@@ -258,6 +269,8 @@ impl Shaper {
 
     // TODO: wrap MakeSkUnicodeHbScriptRunIterator (m88: uses type SkUnicode defined in src/).
 
+    /// Not available with the feature `unicode-client`, because it needs ICU.
+    #[cfg(not(feature = "unicode-client"))]
     pub fn new_hb_icu_script_run_iterator(utf8: &str) -> Borrows<ScriptRunIterator> {
         let bytes = utf8.as_bytes();
         ScriptRunIterator::from_ptr(unsafe {
@@ -726,7 +739,126 @@ impl Shaper {
     }
 }
 
+/// A run of glyphs that share a font and a bidi level, as collected by [`RunCollector`].
+#[derive(Clone, Debug)]
+pub struct ShapedRun {
+    pub font: Font,
+    pub bidi_level: u8,
+    /// The range of the shaped UTF-8 text this run covers.
+    pub utf8_range: Range<usize>,
+    pub glyphs: Vec<GlyphId>,
+    /// The positions of the glyphs relative to the start of the line.
+    pub positions: Vec<Point>,
+    /// The UTF-8 offset of the cluster in the shaped text each glyph belongs to.
+    pub clusters: Vec<u32>,
+    pub advance: Vector,
+}
+
+/// A [`RunHandler`] that collects the glyphs, positions, and clusters of each line.
+///
+/// Runs are stored in visual order.
+#[derive(Clone, Default, Debug)]
+pub struct RunCollector {
+    lines: Vec<Vec<ShapedRun>>,
+    line: Vec<ShapedRun>,
+    x: scalar,
+}
+
+impl RunCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn lines(&self) -> &[Vec<ShapedRun>] {
+        &self.lines
+    }
+
+    pub fn into_lines(self) -> Vec<Vec<ShapedRun>> {
+        self.lines
+    }
+}
+
+impl RunHandler for RunCollector {
+    fn begin_line(&mut self) {
+        self.line.clear();
+        self.x = 0.0;
+    }
+
+    fn run_info(&mut self, _info: &RunInfo) {}
+
+    fn commit_run_info(&mut self) {}
+
+    fn run_buffer(&mut self, info: &RunInfo) -> Buffer {
+        let glyph_count = info.glyph_count;
+        self.line.push(ShapedRun {
+            font: info.font.clone(),
+            bidi_level: info.bidi_level,
+            utf8_range: info.utf8_range.clone(),
+            glyphs: vec![0; glyph_count],
+            positions: vec![Point::default(); glyph_count],
+            clusters: vec![0; glyph_count],
+            advance: info.advance,
+        });
+        let point = Point::new(self.x, 0.0);
+        let run = self.line.last_mut().unwrap();
+        Buffer {
+            glyphs: &mut run.glyphs,
+            positions: &mut run.positions,
+            offsets: None,
+            clusters: Some(&mut run.clusters),
+            point,
+        }
+    }
+
+    fn commit_run_buffer(&mut self, info: &RunInfo) {
+        self.x += info.advance.x;
+    }
+
+    fn commit_line(&mut self) {
+        self.lines.push(mem::take(&mut self.line));
+    }
+}
+
+impl Shaper {
+    /// Shapes `utf8` without wrapping and returns its runs in visual order.
+    ///
+    /// If the text contains line breaks, the runs of all lines are returned and the positions of
+    /// each line start at zero. Use [`RunCollector`] to get the runs of each line.
+    pub fn shape_line(&self, utf8: &str, font: &Font, left_to_right: bool) -> Vec<ShapedRun> {
+        let mut collector = RunCollector::new();
+        self.shape(utf8, font, left_to_right, scalar::INFINITY, &mut collector);
+        collector.into_lines().into_iter().flatten().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{icu, Shaper};
+    use crate::Font;
+
+    #[test]
+    #[serial_test::serial]
+    fn shape_line_with_clusters() {
+        icu::init();
+        let text = "ffi Ä";
+        let runs = Shaper::new(None).shape_line(text, &Font::default(), true);
+        assert!(!runs.is_empty());
+        let mut x = 0.0;
+        for run in &runs {
+            assert_eq!(run.glyphs.len(), run.positions.len());
+            assert_eq!(run.glyphs.len(), run.clusters.len());
+            assert!(run
+                .clusters
+                .iter()
+                .all(|&c| run.utf8_range.contains(&(c as usize))));
+            assert_eq!(run.positions.first().map(|p| p.x), Some(x));
+            x += run.advance.x;
+        }
+    }
+}
+
 pub mod icu {
+    use std::{fs, io, path::Path};
 
     /// On Windows, and if the default feature "embed-icudtl" is _not_ set, this function writes the
     /// file `icudtl.dat` into the current executable's directory making sure that it's available
@@ -735,7 +867,8 @@ pub mod icu {
     /// If your executable directory can not be written to, make sure that `icudtl.dat` is
     /// available.
     ///
-    /// Note that it is currently not possible to load `icudtl.dat` from another location.
+    /// To load `icudtl.dat` from another location, use [`init_from_file()`] or
+    /// [`init_with_data()`].
     ///
     /// If the default feature "embed-icudtl" is set, the `icudtl.dat` file is directly used from
     /// memory, so no `icudtl.dat` file is needed.
@@ -743,6 +876,34 @@ pub mod icu {
         skia_bindings::icu::init();
     }
 
+    /// Initializes ICU with the contents of an `icudtl.dat` file, for example from an embedded
+    /// application resource.
+    ///
+    /// ICU references the data until the process ends, so `data` is copied and never freed. Later
+    /// calls to [`init()`] keep using this data.
+    ///
+    /// Returns an error if ICU rejects `data`, or if ICU was already initialized with different
+    /// data. Initializing ICU again with the same data succeeds and does not copy it again.
+    ///
+    /// On platforms other than Windows, the ICU data is part of the Skia library and this
+    /// function has no effect.
+    pub fn init_with_data(data: &[u8]) -> io::Result<()> {
+        skia_bindings::icu::set_data(data)
+    }
+
+    /// Initializes ICU by loading the `icudtl.dat` file from `path`.
+    ///
+    /// On platforms other than Windows, the ICU data is part of the Skia library, so this
+    /// function does not read `path` and always returns `Ok(())`.
+    ///
+    /// See [`init_with_data()`].
+    pub fn init_from_file(path: impl AsRef<Path>) -> io::Result<()> {
+        if cfg!(windows) {
+            init_with_data(&fs::read(path)?)?;
+        }
+        Ok(())
+    }
+
     #[test]
     #[serial_test::serial]
     fn test_text_blob_builder_run_handler() {
@@ -0,0 +1,664 @@
+//! Rendering of Lottie animations (`modules/skottie`).
+//!
+//! The Skia version this crate is built with does not support slots (the `sid` properties of
+//! themable Lottie files) yet. Until it does, colors, opacities, transforms, and texts can be
+//! themed with the handles a [`PropertyObserver`] receives, which are identified by the names of
+//! their layers instead of slot ids.
+
+use std::{fmt, sync::Arc};
+
+use skia_bindings::{self as sb, skottie_Animation};
+
+use crate::{
+    interop,
+    prelude::*,
+    resources::{self, ResourceProvider},
+    scalar, Canvas, Color, Point, RCHandle, Rect, RefHandle, Size, Typeface, Vector,
+};
+
+pub type Animation = RCHandle<skottie_Animation>;
+
+impl NativeRefCounted for skottie_Animation {
+    fn _ref(&self) {
+        unsafe { sb::C_skottie_Animation_ref(self) }
+    }
+
+    fn _unref(&self) {
+        unsafe { sb::C_skottie_Animation_unref(self) }
+    }
+
+    fn unique(&self) -> bool {
+        unsafe { sb::C_skottie_Animation_unique(self) }
+    }
+}
+
+impl fmt::Debug for Animation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Animation")
+            .field("version", &self.version())
+            .field("duration", &self.duration())
+            .field("fps", &self.fps())
+            .field("in_point", &self.in_point())
+            .field("out_point", &self.out_point())
+            .field("size", &self.size())
+            .finish()
+    }
+}
+
+impl Animation {
+    /// Loads an animation from its Lottie JSON with the default [`Builder`].
+    pub fn from_bytes(json: impl AsRef<[u8]>) -> Option<Self> {
+        Builder::default().make(json)
+    }
+
+    pub fn version(&self) -> String {
+        let mut version = interop::String::default();
+        unsafe { sb::C_skottie_Animation_version(self.native(), version.native_mut()) };
+        version.as_str().into()
+    }
+
+    /// The duration in seconds.
+    pub fn duration(&self) -> f64 {
+        unsafe { sb::C_skottie_Animation_duration(self.native()) }
+    }
+
+    pub fn fps(&self) -> f64 {
+        unsafe { sb::C_skottie_Animation_fps(self.native()) }
+    }
+
+    /// The first frame.
+    pub fn in_point(&self) -> f64 {
+        unsafe { sb::C_skottie_Animation_inPoint(self.native()) }
+    }
+
+    /// The frame after the last frame.
+    pub fn out_point(&self) -> f64 {
+        unsafe { sb::C_skottie_Animation_outPoint(self.native()) }
+    }
+
+    pub fn size(&self) -> Size {
+        let mut size = Size::default();
+        unsafe { sb::C_skottie_Animation_size(self.native(), size.native_mut()) };
+        size
+    }
+
+    /// Updates the animation state for the frame `t`, relative to [`Self::in_point()`]. `t` may
+    /// be fractional.
+    pub fn seek_frame(&mut self, t: f64) {
+        unsafe { sb::C_skottie_Animation_seekFrame(self.native_mut(), t) }
+    }
+
+    /// Updates the animation state for the time `t` in seconds, relative to
+    /// [`Self::in_point()`].
+    pub fn seek_frame_time(&mut self, t: f64) {
+        unsafe { sb::C_skottie_Animation_seekFrameTime(self.native_mut(), t) }
+    }
+
+    /// Draws the current animation frame, scaled to fit into `dst` if provided.
+    pub fn render<'a>(&self, canvas: &mut Canvas, dst: impl Into<Option<&'a Rect>>) {
+        unsafe {
+            sb::C_skottie_Animation_render(
+                self.native(),
+                canvas.native_mut(),
+                dst.into().native_ptr_or_null(),
+            )
+        }
+    }
+}
+
+/// Configures how animations are loaded (`skottie::Animation::Builder`).
+#[derive(Default)]
+pub struct Builder<'a> {
+    resource_provider: Option<Arc<dyn ResourceProvider>>,
+    property_observer: Option<&'a mut dyn PropertyObserver>,
+    marker_observer: Option<&'a mut dyn MarkerObserver>,
+}
+
+impl fmt::Debug for Builder<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builder")
+            .field("resource_provider", &self.resource_provider.is_some())
+            .field("property_observer", &self.property_observer.is_some())
+            .field("marker_observer", &self.marker_observer.is_some())
+            .finish()
+    }
+}
+
+impl<'a> Builder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the images, fonts, and audio tracks the animation refers to with `provider`.
+    pub fn set_resource_provider(&mut self, provider: impl ResourceProvider) -> &mut Self {
+        self.resource_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Reports the properties of the animation to `observer` while it is loaded.
+    pub fn set_property_observer(&mut self, observer: &'a mut dyn PropertyObserver) -> &mut Self {
+        self.property_observer = Some(observer);
+        self
+    }
+
+    /// Reports the markers of the animation to `observer` while it is loaded. A `Vec<Marker>`
+    /// collects all of them.
+    pub fn set_marker_observer(&mut self, observer: &'a mut dyn MarkerObserver) -> &mut Self {
+        self.marker_observer = Some(observer);
+        self
+    }
+
+    /// Loads an animation from its Lottie JSON.
+    pub fn make(&mut self, json: impl AsRef<[u8]>) -> Option<Animation> {
+        let json = json.as_ref();
+        let resource_provider = self
+            .resource_provider
+            .clone()
+            .map(resources::new_native)
+            .unwrap_or(std::ptr::null_mut());
+        let property_observer = self
+            .property_observer
+            .as_deref_mut()
+            .map(property_observer::new_param);
+        let marker_observer = self
+            .marker_observer
+            .as_deref_mut()
+            .map(marker_observer::new_param);
+        Animation::from_ptr(unsafe {
+            sb::C_skottie_Animation_Make(
+                json.as_ptr() as _,
+                json.len(),
+                resource_provider,
+                property_observer
+                    .as_ref()
+                    .map(|p| p as *const _)
+                    .unwrap_or(std::ptr::null()),
+                marker_observer
+                    .as_ref()
+                    .map(|p| p as *const _)
+                    .unwrap_or(std::ptr::null()),
+            )
+        })
+    }
+}
+
+/// A named segment of an animation's timeline.
+///
+/// `t0` and `t1` are the start and the end of the segment, relative to
+/// [`Animation::in_point()`] and [`Animation::out_point()`], from `0` to `1`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Marker {
+    pub name: String,
+    pub t0: f32,
+    pub t1: f32,
+}
+
+impl Marker {
+    /// The start of the segment in seconds, to be passed to [`Animation::seek_frame_time()`].
+    pub fn start_time(&self, animation: &Animation) -> f64 {
+        f64::from(self.t0) * animation.duration()
+    }
+
+    /// The duration of the segment in seconds.
+    pub fn duration(&self, animation: &Animation) -> f64 {
+        f64::from(self.t1 - self.t0) * animation.duration()
+    }
+}
+
+/// Receives the markers of an animation while it is loaded (`skottie::MarkerObserver`).
+pub trait MarkerObserver {
+    /// See [`Marker`] for the meaning of `t0` and `t1`.
+    fn on_marker(&mut self, name: &str, t0: f32, t1: f32);
+}
+
+impl MarkerObserver for Vec<Marker> {
+    fn on_marker(&mut self, name: &str, t0: f32, t1: f32) {
+        self.push(Marker {
+            name: name.into(),
+            t0,
+            t1,
+        })
+    }
+}
+
+pub use sb::skottie_PropertyObserver_NodeType as NodeType;
+variant_name!(NodeType::Composition);
+
+/// Receives the properties of an animation while it is loaded (`skottie::PropertyObserver`).
+///
+/// `node_name` is the name of the layer, shape, or effect the property belongs to, for example
+/// `"Background"`, and may be empty. The handles stay valid as long as they are alive and can be
+/// used to change the properties later, for example to apply a theme. Changes are visible after
+/// the next [`Animation::seek_frame()`], but are overwritten by keyframes of animated properties.
+#[allow(unused_variables)]
+pub trait PropertyObserver {
+    fn on_color_property(&mut self, node_name: &str, handle: ColorPropertyHandle) {}
+
+    fn on_opacity_property(&mut self, node_name: &str, handle: OpacityPropertyHandle) {}
+
+    fn on_text_property(&mut self, node_name: &str, handle: TextPropertyHandle) {}
+
+    fn on_transform_property(&mut self, node_name: &str, handle: TransformPropertyHandle) {}
+
+    fn on_enter_node(&mut self, node_name: &str, node_type: NodeType) {}
+
+    fn on_leaving_node(&mut self, node_name: &str, node_type: NodeType) {}
+}
+
+pub type ColorPropertyHandle = RefHandle<sb::skottie_ColorPropertyHandle>;
+
+impl NativeDrop for sb::skottie_ColorPropertyHandle {
+    fn drop(&mut self) {
+        unsafe { sb::C_skottie_ColorPropertyHandle_delete(self) }
+    }
+}
+
+impl fmt::Debug for ColorPropertyHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ColorPropertyHandle")
+            .field(&self.get())
+            .finish()
+    }
+}
+
+impl ColorPropertyHandle {
+    pub fn get(&self) -> Color {
+        Color::from_native_c(unsafe { sb::C_skottie_ColorPropertyHandle_get(self.native()) })
+    }
+
+    pub fn set(&mut self, color: impl Into<Color>) {
+        unsafe {
+            sb::C_skottie_ColorPropertyHandle_set(self.native_mut(), color.into().into_native())
+        }
+    }
+}
+
+pub type OpacityPropertyHandle = RefHandle<sb::skottie_OpacityPropertyHandle>;
+
+impl NativeDrop for sb::skottie_OpacityPropertyHandle {
+    fn drop(&mut self) {
+        unsafe { sb::C_skottie_OpacityPropertyHandle_delete(self) }
+    }
+}
+
+impl fmt::Debug for OpacityPropertyHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OpacityPropertyHandle")
+            .field(&self.get())
+            .finish()
+    }
+}
+
+impl OpacityPropertyHandle {
+    /// The opacity in percent, from `0` to `100`.
+    pub fn get(&self) -> scalar {
+        unsafe { sb::C_skottie_OpacityPropertyHandle_get(self.native()) }
+    }
+
+    pub fn set(&mut self, opacity: scalar) {
+        unsafe { sb::C_skottie_OpacityPropertyHandle_set(self.native_mut(), opacity) }
+    }
+}
+
+/// The transform of a layer or shape group, rotation and skew are in degrees.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+pub struct TransformPropertyValue {
+    pub anchor_point: Point,
+    pub position: Point,
+    pub scale: Vector,
+    pub rotation: scalar,
+    pub skew: scalar,
+    pub skew_axis: scalar,
+}
+
+native_transmutable!(
+    sb::skottie_TransformPropertyValue,
+    TransformPropertyValue,
+    transform_property_value_layout
+);
+
+pub type TransformPropertyHandle = RefHandle<sb::skottie_TransformPropertyHandle>;
+
+impl NativeDrop for sb::skottie_TransformPropertyHandle {
+    fn drop(&mut self) {
+        unsafe { sb::C_skottie_TransformPropertyHandle_delete(self) }
+    }
+}
+
+impl fmt::Debug for TransformPropertyHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TransformPropertyHandle")
+            .field(&self.get())
+            .finish()
+    }
+}
+
+impl TransformPropertyHandle {
+    pub fn get(&self) -> TransformPropertyValue {
+        let mut value = TransformPropertyValue::default();
+        unsafe { sb::C_skottie_TransformPropertyHandle_get(self.native(), value.native_mut()) };
+        value
+    }
+
+    pub fn set(&mut self, value: &TransformPropertyValue) {
+        unsafe { sb::C_skottie_TransformPropertyHandle_set(self.native_mut(), value.native()) }
+    }
+}
+
+pub type TextPropertyHandle = RefHandle<sb::skottie_TextPropertyHandle>;
+
+impl NativeDrop for sb::skottie_TextPropertyHandle {
+    fn drop(&mut self) {
+        unsafe { sb::C_skottie_TextPropertyHandle_delete(self) }
+    }
+}
+
+impl fmt::Debug for TextPropertyHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextPropertyHandle")
+            .field("text", &self.text())
+            .field("text_size", &self.text_size())
+            .field("fill_color", &self.fill_color())
+            .field("stroke_color", &self.stroke_color())
+            .finish()
+    }
+}
+
+impl TextPropertyHandle {
+    pub fn text(&self) -> String {
+        let mut text = interop::String::default();
+        unsafe { sb::C_skottie_TextPropertyHandle_getText(self.native(), text.native_mut()) };
+        text.as_str().into()
+    }
+
+    pub fn set_text(&mut self, text: impl AsRef<str>) {
+        let text = text.as_ref();
+        unsafe {
+            sb::C_skottie_TextPropertyHandle_setText(
+                self.native_mut(),
+                text.as_ptr() as _,
+                text.len(),
+            )
+        }
+    }
+
+    pub fn text_size(&self) -> scalar {
+        unsafe { sb::C_skottie_TextPropertyHandle_getTextSize(self.native()) }
+    }
+
+    pub fn set_text_size(&mut self, size: scalar) {
+        unsafe { sb::C_skottie_TextPropertyHandle_setTextSize(self.native_mut(), size) }
+    }
+
+    pub fn fill_color(&self) -> Color {
+        Color::from_native_c(unsafe {
+            sb::C_skottie_TextPropertyHandle_getFillColor(self.native())
+        })
+    }
+
+    pub fn set_fill_color(&mut self, color: impl Into<Color>) {
+        unsafe {
+            sb::C_skottie_TextPropertyHandle_setFillColor(
+                self.native_mut(),
+                color.into().into_native(),
+            )
+        }
+    }
+
+    pub fn stroke_color(&self) -> Color {
+        Color::from_native_c(unsafe {
+            sb::C_skottie_TextPropertyHandle_getStrokeColor(self.native())
+        })
+    }
+
+    pub fn set_stroke_color(&mut self, color: impl Into<Color>) {
+        unsafe {
+            sb::C_skottie_TextPropertyHandle_setStrokeColor(
+                self.native_mut(),
+                color.into().into_native(),
+            )
+        }
+    }
+
+    pub fn typeface(&self) -> Option<Typeface> {
+        Typeface::from_ptr(unsafe { sb::C_skottie_TextPropertyHandle_getTypeface(self.native()) })
+    }
+
+    pub fn set_typeface(&mut self, typeface: impl Into<Option<Typeface>>) {
+        unsafe {
+            sb::C_skottie_TextPropertyHandle_setTypeface(
+                self.native_mut(),
+                typeface.into().into_ptr_or_null(),
+            )
+        }
+    }
+}
+
+mod property_observer {
+    use super::{
+        ColorPropertyHandle, NodeType, OpacityPropertyHandle, PropertyObserver, TextPropertyHandle,
+        TransformPropertyHandle,
+    };
+    use skia_bindings::{self as sb, RustPropertyObserver_Param, TraitObject};
+    use std::{
+        ffi::CStr,
+        mem,
+        os::raw,
+        panic::{catch_unwind, AssertUnwindSafe},
+    };
+
+    pub fn new_param(observer: &mut dyn PropertyObserver) -> RustPropertyObserver_Param {
+        RustPropertyObserver_Param {
+            trait_: unsafe { mem::transmute(observer) },
+            onColorProperty: Some(on_color_property),
+            onOpacityProperty: Some(on_opacity_property),
+            onTextProperty: Some(on_text_property),
+            onTransformProperty: Some(on_transform_property),
+            onEnterNode: Some(on_enter_node),
+            onLeavingNode: Some(on_leaving_node),
+        }
+    }
+
+    unsafe extern "C" fn on_color_property(
+        to: TraitObject,
+        node_name: *const raw::c_char,
+        handle: *mut sb::skottie_ColorPropertyHandle,
+    ) {
+        if let Some(handle) = ColorPropertyHandle::from_ptr(handle) {
+            let node_name = to_str(node_name);
+            catch_unwind(AssertUnwindSafe(|| {
+                to_observer(to).on_color_property(&node_name, handle)
+            }))
+            .ok();
+        }
+    }
+
+    unsafe extern "C" fn on_opacity_property(
+        to: TraitObject,
+        node_name: *const raw::c_char,
+        handle: *mut sb::skottie_OpacityPropertyHandle,
+    ) {
+        if let Some(handle) = OpacityPropertyHandle::from_ptr(handle) {
+            let node_name = to_str(node_name);
+            catch_unwind(AssertUnwindSafe(|| {
+                to_observer(to).on_opacity_property(&node_name, handle)
+            }))
+            .ok();
+        }
+    }
+
+    unsafe extern "C" fn on_text_property(
+        to: TraitObject,
+        node_name: *const raw::c_char,
+        handle: *mut sb::skottie_TextPropertyHandle,
+    ) {
+        if let Some(handle) = TextPropertyHandle::from_ptr(handle) {
+            let node_name = to_str(node_name);
+            catch_unwind(AssertUnwindSafe(|| {
+                to_observer(to).on_text_property(&node_name, handle)
+            }))
+            .ok();
+        }
+    }
+
+    unsafe extern "C" fn on_transform_property(
+        to: TraitObject,
+        node_name: *const raw::c_char,
+        handle: *mut sb::skottie_TransformPropertyHandle,
+    ) {
+        if let Some(handle) = TransformPropertyHandle::from_ptr(handle) {
+            let node_name = to_str(node_name);
+            catch_unwind(AssertUnwindSafe(|| {
+                to_observer(to).on_transform_property(&node_name, handle)
+            }))
+            .ok();
+        }
+    }
+
+    unsafe extern "C" fn on_enter_node(
+        to: TraitObject,
+        node_name: *const raw::c_char,
+        node_type: NodeType,
+    ) {
+        let node_name = to_str(node_name);
+        catch_unwind(AssertUnwindSafe(|| {
+            to_observer(to).on_enter_node(&node_name, node_type)
+        }))
+        .ok();
+    }
+
+    unsafe extern "C" fn on_leaving_node(
+        to: TraitObject,
+        node_name: *const raw::c_char,
+        node_type: NodeType,
+    ) {
+        let node_name = to_str(node_name);
+        catch_unwind(AssertUnwindSafe(|| {
+            to_observer(to).on_leaving_node(&node_name, node_type)
+        }))
+        .ok();
+    }
+
+    unsafe fn to_str(ptr: *const raw::c_char) -> String {
+        CStr::from_ptr(ptr).to_string_lossy().into_owned()
+    }
+
+    unsafe fn to_observer<'a>(to: TraitObject) -> &'a mut dyn PropertyObserver {
+        &mut *mem::transmute::<TraitObject, *mut dyn PropertyObserver>(to)
+    }
+}
+
+mod marker_observer {
+    use super::MarkerObserver;
+    use skia_bindings::{RustMarkerObserver_Param, TraitObject};
+    use std::{
+        ffi::CStr,
+        mem,
+        os::raw,
+        panic::{catch_unwind, AssertUnwindSafe},
+    };
+
+    pub fn new_param(observer: &mut dyn MarkerObserver) -> RustMarkerObserver_Param {
+        RustMarkerObserver_Param {
+            trait_: unsafe { mem::transmute(observer) },
+            onMarker: Some(on_marker),
+        }
+    }
+
+    unsafe extern "C" fn on_marker(to: TraitObject, name: *const raw::c_char, t0: f32, t1: f32) {
+        let name = CStr::from_ptr(name).to_string_lossy();
+        let observer = &mut *mem::transmute::<TraitObject, *mut dyn MarkerObserver>(to);
+        catch_unwind(AssertUnwindSafe(|| observer.on_marker(&name, t0, t1))).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Animation, Builder, ColorPropertyHandle, Marker, NodeType, PropertyObserver};
+    use crate::{AlphaType, Color, ColorType, ImageInfo, Surface};
+
+    // A 10x10 animation of 10 frames at 10 fps with one solid layer named "Background".
+    const SOLID: &str = r##"{
+        "v": "5.7.4", "fr": 10, "ip": 0, "op": 10, "w": 10, "h": 10,
+        "layers": [{
+            "ty": 1, "nm": "Background", "ip": 0, "op": 10, "st": 0,
+            "sw": 10, "sh": 10, "sc": "#ff0000",
+            "ks": { "o": { "a": 0, "k": 100 } }
+        }]
+    }"##;
+
+    #[test]
+    fn load_and_render() {
+        let animation = Animation::from_bytes(SOLID).unwrap();
+        assert_eq!(animation.version(), "5.7.4");
+        assert_eq!(animation.fps(), 10.0);
+        assert_eq!(animation.duration(), 1.0);
+        assert_eq!(animation.size(), (10, 10).into());
+        assert_eq!(render_center(&animation), Color::RED);
+        assert!(Animation::from_bytes("{}").is_none());
+    }
+
+    #[test]
+    fn override_colors_with_property_observer() {
+        #[derive(Default)]
+        struct Colors {
+            handles: Vec<(String, ColorPropertyHandle)>,
+            layers: Vec<String>,
+        }
+
+        impl PropertyObserver for Colors {
+            fn on_color_property(&mut self, node_name: &str, handle: ColorPropertyHandle) {
+                self.handles.push((node_name.into(), handle));
+            }
+
+            fn on_enter_node(&mut self, node_name: &str, node_type: NodeType) {
+                if node_type == NodeType::Layer {
+                    self.layers.push(node_name.into());
+                }
+            }
+        }
+
+        let mut colors = Colors::default();
+        let mut animation = Builder::new()
+            .set_property_observer(&mut colors)
+            .make(SOLID)
+            .unwrap();
+        assert_eq!(colors.layers, ["Background"]);
+
+        let (name, handle) = &mut colors.handles[0];
+        assert_eq!(name, "Background");
+        assert_eq!(handle.get(), Color::RED);
+        handle.set(Color::BLUE);
+        animation.seek_frame(0.0);
+        assert_eq!(render_center(&animation), Color::BLUE);
+    }
+
+    #[test]
+    fn collect_markers() {
+        let json = SOLID.replacen(
+            r#""layers""#,
+            r#""markers": [{ "cm": "intro", "tm": 0, "dr": 5 }, { "cm": "outro", "tm": 5, "dr": 5 }],
+            "layers""#,
+            1,
+        );
+        let mut markers = Vec::<Marker>::new();
+        let animation = Builder::new()
+            .set_marker_observer(&mut markers)
+            .make(json)
+            .unwrap();
+        assert_eq!(markers.len(), 2);
+        assert_eq!(markers[1].name, "outro");
+        assert_eq!(markers[1].start_time(&animation), 0.5);
+        assert_eq!(markers[1].duration(&animation), 0.5);
+    }
+
+    fn render_center(animation: &Animation) -> Color {
+        let mut surface = Surface::new_raster_n32_premul((10, 10)).unwrap();
+        animation.render(surface.canvas(), None);
+        let mut pixel = [0u8; 4];
+        let info = ImageInfo::new((1, 1), ColorType::RGBA8888, AlphaType::Premul, None);
+        assert!(surface.read_pixels(&info, &mut pixel, 4, (5, 5)));
+        Color::from_argb(pixel[3], pixel[0], pixel[1], pixel[2])
+    }
+}
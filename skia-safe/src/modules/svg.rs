@@ -1,5 +1,6 @@
 use std::{
     error::Error,
+    ffi::CString,
     fmt,
     io::{self, Read},
     str::FromStr,
@@ -9,9 +10,10 @@ use skia_bindings as sb;
 use skia_bindings::{SkData, SkTypeface};
 
 use crate::{
-    interop::{MemoryStream, NativeStreamBase, RustStream},
+    interop::{self, MemoryStream, NativeStreamBase, RustStream},
     prelude::*,
-    Canvas, Data, RCHandle, Size, Typeface,
+    resources::{self, ResourceProvider},
+    Canvas, Color, Data, RCHandle, Size, Typeface,
 };
 
 pub type Dom = RCHandle<sb::SkSVGDOM>;
@@ -180,6 +182,37 @@ impl Dom {
         Self::from_ptr(out).ok_or(LoadError)
     }
 
+    /// Reads an SVG document and loads the images and fonts it refers to with `provider`.
+    pub fn read_with_resource_provider<R: io::Read>(
+        mut reader: R,
+        provider: impl ResourceProvider,
+    ) -> Result<Self, LoadError> {
+        let mut reader = RustStream::new(&mut reader);
+        let stream = reader.stream_mut();
+        let out = unsafe {
+            sb::C_SkSVGDOM_MakeFromStreamWithResourceProvider(
+                stream,
+                resources::new_native(provider),
+            )
+        };
+        Self::from_ptr(out).ok_or(LoadError)
+    }
+
+    /// Parses an SVG document and loads the images and fonts it refers to with `provider`.
+    pub fn from_bytes_with_resource_provider(
+        svg: &[u8],
+        provider: impl ResourceProvider,
+    ) -> Result<Self, LoadError> {
+        let mut ms = MemoryStream::from_bytes(svg);
+        let out = unsafe {
+            sb::C_SkSVGDOM_MakeFromStreamWithResourceProvider(
+                ms.native_mut().as_stream_mut(),
+                resources::new_native(provider),
+            )
+        };
+        Self::from_ptr(out).ok_or(LoadError)
+    }
+
     pub fn render(&self, canvas: &mut Canvas) {
         // TODO: may be we should init ICU whenever we expose a Canvas?
         #[cfg(all(feature = "embed-icudtl", feature = "textlayout"))]
@@ -192,6 +225,141 @@ impl Dom {
         let size = size.into();
         unsafe { sb::C_SkSVGDOM_setContainerSize(self.native_mut(), size.native()) }
     }
+
+    /// The root `<svg>` element.
+    pub fn root(&self) -> Option<Node> {
+        Node::from_ptr(unsafe { sb::C_SkSVGDOM_getRoot(self.native()) })
+    }
+
+    /// Finds the element with the `id` attribute `id`.
+    ///
+    /// Changes to the returned node are visible in all clones of the [`Dom`] and are rendered
+    /// with the next call to [`Self::render()`].
+    pub fn find_node_by_id(&mut self, id: impl AsRef<str>) -> Option<Node> {
+        let id = CString::new(id.as_ref()).ok()?;
+        Node::from_ptr(unsafe { sb::C_SkSVGDOM_findNodeById(self.native_mut(), id.as_ptr()) })
+    }
+}
+
+pub use sb::SkSVGTag as NodeTag;
+variant_name!(NodeTag::Circle);
+
+/// An element or a text literal in the tree of a [`Dom`].
+///
+/// Skia does not expose the children of a node, so elements are looked up by their `id` with
+/// [`Dom::find_node_by_id()`]. To replace text, add a placeholder element to the document and
+/// append a `<text>` element created with [`Node::new_text()`] to it.
+pub type Node = RCHandle<sb::SkSVGNode>;
+
+impl NativeRefCounted for sb::SkSVGNode {
+    fn _ref(&self) {
+        unsafe { sb::C_SkSVGNode_ref(self) }
+    }
+
+    fn _unref(&self) {
+        unsafe { sb::C_SkSVGNode_unref(self) }
+    }
+
+    fn unique(&self) -> bool {
+        unsafe { sb::C_SkSVGNode_unique(self) }
+    }
+}
+
+impl fmt::Debug for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Node").field("tag", &self.tag()).finish()
+    }
+}
+
+impl Node {
+    /// Creates an empty `<text>` element.
+    pub fn new_text() -> Self {
+        Node::from_ptr(unsafe { sb::C_SkSVGText_Make() }).unwrap()
+    }
+
+    /// Creates a [`NodeTag::TextLiteral`], the content of `<text>`, `<tspan>`, or `<textPath>`
+    /// elements.
+    ///
+    /// Returns `None` if `text` contains a null character.
+    pub fn new_text_literal(text: impl AsRef<str>) -> Option<Self> {
+        let text = CString::new(text.as_ref()).ok()?;
+        Node::from_ptr(unsafe { sb::C_SkSVGTextLiteral_Make(text.as_ptr()) })
+    }
+
+    pub fn tag(&self) -> NodeTag {
+        unsafe { sb::C_SkSVGNode_tag(self.native()) }
+    }
+
+    /// Parses and sets the presentation or element attribute `name`, for example `"fill"`,
+    /// `"stroke-width"`, or `"transform"`.
+    ///
+    /// Returns `false` if the attribute is not supported by the element or `value` could not be
+    /// parsed.
+    pub fn set_attribute(&mut self, name: impl AsRef<str>, value: impl AsRef<str>) -> bool {
+        let (name, value) = match (CString::new(name.as_ref()), CString::new(value.as_ref())) {
+            (Ok(name), Ok(value)) => (name, value),
+            _ => return false,
+        };
+        unsafe { sb::C_SkSVGNode_setAttribute(self.native_mut(), name.as_ptr(), value.as_ptr()) }
+    }
+
+    /// Sets the `fill` and `fill-opacity` attributes.
+    pub fn set_fill(&mut self, color: impl Into<Color>) -> bool {
+        let color = color.into();
+        self.set_attribute("fill", color_value(color))
+            && self.set_attribute("fill-opacity", opacity_value(color))
+    }
+
+    /// Sets the `stroke` and `stroke-opacity` attributes.
+    pub fn set_stroke(&mut self, color: impl Into<Color>) -> bool {
+        let color = color.into();
+        self.set_attribute("stroke", color_value(color))
+            && self.set_attribute("stroke-opacity", opacity_value(color))
+    }
+
+    /// Sets the `visibility` attribute to `visible` or `hidden`.
+    pub fn set_visible(&mut self, visible: bool) -> bool {
+        self.set_attribute("visibility", if visible { "visible" } else { "hidden" })
+    }
+
+    /// Appends `child` to this element. Elements that can not have children, or not children
+    /// with the tag of `child`, ignore it.
+    pub fn append_child(&mut self, child: Node) {
+        unsafe { sb::C_SkSVGNode_appendChild(self.native_mut(), child.into_ptr()) }
+    }
+
+    /// The text of a [`NodeTag::TextLiteral`].
+    pub fn text(&self) -> Option<String> {
+        if self.tag() != NodeTag::TextLiteral {
+            return None;
+        }
+        let mut text = interop::String::default();
+        unsafe { sb::C_SkSVGTextLiteral_getText(self.native(), text.native_mut()) };
+        Some(text.as_str().into())
+    }
+
+    /// Replaces the text of a [`NodeTag::TextLiteral`].
+    ///
+    /// Returns `false` if this is not a text literal or `text` contains a null character.
+    pub fn set_text(&mut self, text: impl AsRef<str>) -> bool {
+        if self.tag() != NodeTag::TextLiteral {
+            return false;
+        }
+        let text = match CString::new(text.as_ref()) {
+            Ok(text) => text,
+            Err(_) => return false,
+        };
+        unsafe { sb::C_SkSVGTextLiteral_setText(self.native_mut(), text.as_ptr()) };
+        true
+    }
+}
+
+fn color_value(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+fn opacity_value(color: Color) -> String {
+    (f32::from(color.a()) / 255.0).to_string()
 }
 
 type StaticCharVec = &'static [char];
@@ -254,9 +422,12 @@ mod base64 {
 
 #[cfg(test)]
 mod tests {
-    use crate::Canvas;
+    use crate::{
+        resources::ResourceProvider, AlphaType, Canvas, Color, ColorType, Data, EncodedImageFormat,
+        ImageInfo, Surface,
+    };
 
-    use super::Dom;
+    use super::{Dom, Node, NodeTag};
 
     #[test]
     fn render_simple_svg() {
@@ -270,4 +441,71 @@ mod tests {
         let dom = str::parse::<Dom>(svg).unwrap();
         dom.render(&mut canvas)
     }
+
+    #[test]
+    fn load_images_with_resource_provider() {
+        struct Bundle(Data);
+
+        impl ResourceProvider for Bundle {
+            fn load(&self, _path: &str, name: &str) -> Option<Data> {
+                (name == "blue.png").then(|| self.0.clone())
+            }
+        }
+
+        let mut source = Surface::new_raster_n32_premul((1, 1)).unwrap();
+        source.canvas().clear(Color::BLUE);
+        let png = source
+            .image_snapshot()
+            .encode_to_data(EncodedImageFormat::PNG)
+            .unwrap();
+
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="10" height="10">
+            <image width="10" height="10" preserveAspectRatio="none" xlink:href="blue.png"/>
+            </svg>"##;
+        let dom = Dom::from_bytes_with_resource_provider(svg.as_bytes(), Bundle(png)).unwrap();
+
+        let mut surface = Surface::new_raster_n32_premul((10, 10)).unwrap();
+        dom.render(surface.canvas());
+        let mut pixel = [0u8; 4];
+        let info = ImageInfo::new((1, 1), ColorType::RGBA8888, AlphaType::Premul, None);
+        assert!(surface.read_pixels(&info, &mut pixel, 4, (5, 5)));
+        assert_eq!(pixel, [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn modify_nodes() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+            <rect id="background" width="10" height="10" fill="red"/>
+            <g id="label"/>
+            </svg>"##;
+        let mut dom = str::parse::<Dom>(svg).unwrap();
+        assert_eq!(dom.root().unwrap().tag(), NodeTag::Svg);
+        assert!(dom.find_node_by_id("missing").is_none());
+
+        let mut background = dom.find_node_by_id("background").unwrap();
+        assert_eq!(background.tag(), NodeTag::Rect);
+        assert!(background.set_fill(Color::BLUE));
+
+        let mut text = Node::new_text();
+        assert_eq!(text.tag(), NodeTag::Text);
+        assert!(text.set_attribute("y", "8"));
+        let mut literal = Node::new_text_literal("Hello").unwrap();
+        text.append_child(literal.clone());
+        let mut label = dom.find_node_by_id("label").unwrap();
+        label.append_child(text);
+        assert!(label.set_visible(false));
+
+        assert_eq!(literal.text().unwrap(), "Hello");
+        assert!(literal.set_text("World"));
+        assert_eq!(literal.text().unwrap(), "World");
+        assert!(!background.set_text("World"));
+        assert!(background.text().is_none());
+
+        let mut surface = Surface::new_raster_n32_premul((10, 10)).unwrap();
+        dom.render(surface.canvas());
+        let mut pixel = [0u8; 4];
+        let info = ImageInfo::new((1, 1), ColorType::RGBA8888, AlphaType::Premul, None);
+        assert!(surface.read_pixels(&info, &mut pixel, 4, (0, 0)));
+        assert_eq!(pixel, [0, 0, 255, 255]);
+    }
 }
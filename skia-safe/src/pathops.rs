@@ -1,5 +1,5 @@
 //! Wrapper for pathops/SkPathOps.h
-use crate::{prelude::*, Path, Rect};
+use crate::{paint, path, path_utils, prelude::*, scalar, Paint, Path, Rect};
 use skia_bindings::{self as sb, SkOpBuilder};
 use std::fmt;
 
@@ -18,6 +18,11 @@ pub fn simplify(path: &Path) -> Option<Path> {
     unsafe { sb::Simplify(path.native(), result.native_mut()) }.if_true_some(result)
 }
 
+/// Returns the union of all `paths`, computed in one pass with an [`OpBuilder`].
+pub fn union(paths: impl IntoIterator<Item = impl AsRef<Path>>) -> Option<Path> {
+    OpBuilder::default().add_all(paths, PathOp::Union).resolve()
+}
+
 pub fn tight_bounds(path: &Path) -> Option<Rect> {
     let mut result = Rect::default();
     unsafe { sb::TightBounds(path.native(), result.native_mut()) }.if_true_some(result)
@@ -28,6 +33,76 @@ pub fn as_winding(path: &Path) -> Option<Path> {
     unsafe { sb::AsWinding(path.native(), result.native_mut()) }.if_true_some(result)
 }
 
+/// Grows (`distance` > 0) or shrinks (`distance` < 0) the area that `path` fills by `distance`.
+///
+/// Open contours are closed first. `join` specifies how the corners of the outline are
+/// continued, `miter_limit` limits the length of [`paint::Join::Miter`] joins (defaults to `4`).
+///
+/// The outline is computed by stroking the path with twice the distance and combining the stroke
+/// with the path, so the result is only as precise as the stroker.
+pub fn offset_outline(
+    path: &Path,
+    distance: scalar,
+    join: paint::Join,
+    miter_limit: impl Into<Option<scalar>>,
+) -> Option<Path> {
+    let closed = close_contours(path);
+    if distance == 0.0 {
+        return simplify(&closed);
+    }
+    let mut paint = Paint::default();
+    paint
+        .set_style(paint::Style::Stroke)
+        .set_stroke_width(distance.abs() * 2.0)
+        .set_stroke_join(join);
+    if let Some(miter_limit) = miter_limit.into() {
+        paint.set_stroke_miter(miter_limit);
+    }
+    let stroke = path_utils::fill_path(&closed, &paint, None, None)?;
+    let path_op = if distance > 0.0 {
+        PathOp::Union
+    } else {
+        PathOp::Difference
+    };
+    op(&closed, &stroke, path_op)
+}
+
+fn close_contours(path: &Path) -> Path {
+    let mut closed = Path::default();
+    closed.set_fill_type(path.fill_type());
+    let mut iter = path::Iter::new(path, true);
+    while let Some((verb, points)) = iter.next() {
+        match verb {
+            path::Verb::Move => {
+                closed.move_to(points[0]);
+            }
+            path::Verb::Line => {
+                closed.line_to(points[1]);
+            }
+            path::Verb::Quad => {
+                closed.quad_to(points[1], points[2]);
+            }
+            path::Verb::Conic => {
+                closed.conic_to(points[1], points[2], iter.conic_weight().unwrap_or(1.0));
+            }
+            path::Verb::Cubic => {
+                closed.cubic_to(points[1], points[2], points[3]);
+            }
+            path::Verb::Close => {
+                closed.close();
+            }
+            path::Verb::Done => {}
+        }
+    }
+    closed
+}
+
+/// Perform a series of path operations, optimized for unioning many paths together.
+///
+/// Paths are added with [`OpBuilder::add()`], and combined with [`OpBuilder::resolve()`]. The
+/// first path is combined with an empty path. The operations are evaluated in the order they were
+/// added, and all paths that are unioned are combined in a single pass, which is much faster than
+/// calling [`op()`] for each pair.
 pub type OpBuilder = Handle<SkOpBuilder>;
 unsafe_send_sync!(OpBuilder);
 
@@ -50,6 +125,8 @@ impl fmt::Debug for OpBuilder {
 }
 
 impl OpBuilder {
+    /// Add one or more paths and their operand. The builder is empty before the first path is
+    /// added, so the result of a single add is (empty-path OP path).
     pub fn add(&mut self, path: &Path, operator: PathOp) -> &mut Self {
         unsafe {
             self.native_mut().add(path.native(), operator);
@@ -57,6 +134,20 @@ impl OpBuilder {
         self
     }
 
+    /// Adds all `paths` with the same `operator`.
+    pub fn add_all(
+        &mut self,
+        paths: impl IntoIterator<Item = impl AsRef<Path>>,
+        operator: PathOp,
+    ) -> &mut Self {
+        for path in paths {
+            self.add(path.as_ref(), operator);
+        }
+        self
+    }
+
+    /// Computes the sum of all paths and operands, and resets the builder to its initial state.
+    /// Returns `None` if the operations could not be performed.
     pub fn resolve(&mut self) -> Option<Path> {
         let mut path = Path::default();
         unsafe { self.native_mut().resolve(path.native_mut()) }.if_true_some(path)
@@ -79,6 +170,16 @@ impl Path {
     pub fn as_winding(&self) -> Option<Path> {
         as_winding(self)
     }
+
+    /// See [`offset_outline()`].
+    pub fn offset_outline(
+        &self,
+        distance: scalar,
+        join: paint::Join,
+        miter_limit: impl Into<Option<scalar>>,
+    ) -> Option<Path> {
+        offset_outline(self, distance, join, miter_limit)
+    }
 }
 
 #[test]
@@ -111,3 +212,59 @@ fn test_intersect() {
     let expected: Rect = Rect::from_point_and_size((15.0, 15.0), (5.0, 5.0));
     assert_eq!(intersected.tight_bounds().unwrap(), expected);
 }
+
+#[test]
+fn test_offset_outline() {
+    let mut path = Path::new();
+    path.add_rect(Rect::from_xywh(0.0, 0.0, 100.0, 100.0), None);
+
+    let outset = path.offset_outline(10.0, paint::Join::Miter, None).unwrap();
+    assert_eq!(
+        *outset.bounds(),
+        Rect::from_xywh(-10.0, -10.0, 120.0, 120.0)
+    );
+    assert!(outset.contains((-9.0, -9.0)));
+
+    let rounded = path.offset_outline(10.0, paint::Join::Round, None).unwrap();
+    assert!(rounded.contains((-5.0, 50.0)));
+    assert!(!rounded.contains((-9.0, -9.0)));
+
+    let inset = path
+        .offset_outline(-10.0, paint::Join::Miter, None)
+        .unwrap();
+    assert_eq!(*inset.bounds(), Rect::from_xywh(10.0, 10.0, 80.0, 80.0));
+
+    let mut open = Path::new();
+    open.move_to((0.0, 0.0))
+        .line_to((100.0, 0.0))
+        .line_to((100.0, 100.0));
+    let outset = open.offset_outline(10.0, paint::Join::Miter, None).unwrap();
+    assert!(outset.contains((60.0, 30.0)));
+}
+
+#[test]
+fn test_op_builder() {
+    let rects = (0..10).map(|i| {
+        let mut path = Path::new();
+        path.add_rect(
+            Rect::from_point_and_size((i as f32 * 5.0, 0.0), (10.0, 10.0)),
+            None,
+        );
+        path
+    });
+    let union = union(rects).unwrap();
+    assert_eq!(*union.bounds(), Rect::from_xywh(0.0, 0.0, 55.0, 10.0));
+
+    let mut hole = Path::new();
+    hole.add_rect(Rect::from_xywh(20.0, 2.0, 10.0, 6.0), None);
+    let mut builder = OpBuilder::default();
+    builder
+        .add(&union, PathOp::Union)
+        .add(&hole, PathOp::Difference);
+    let result = builder.resolve().unwrap();
+    assert!(result.contains((10.0, 5.0)));
+    assert!(!result.contains((25.0, 5.0)));
+
+    // resolve() resets the builder.
+    assert!(builder.resolve().unwrap().is_empty());
+}
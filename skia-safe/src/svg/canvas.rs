@@ -1,6 +1,7 @@
 use crate::{interop::DynamicMemoryWStream, prelude::*, Data, Rect};
 use skia_bindings::{self as sb, SkCanvas};
 use std::{
+    collections::HashMap,
     fmt,
     ops::{Deref, DerefMut},
     pin::Pin,
@@ -10,6 +11,7 @@ use std::{
 pub struct Canvas {
     canvas: *mut SkCanvas,
     stream: Pin<Box<DynamicMemoryWStream>>,
+    font_families: HashMap<String, String>,
 }
 
 impl Drop for Canvas {
@@ -35,6 +37,11 @@ impl DerefMut for Canvas {
 }
 
 bitflags! {
+    /// Text is written as `<text>` elements with the `font-family`, `font-size`, `font-weight`,
+    /// and `font-style` of the typeface by default. Fonts are not embedded, so viewers render the
+    /// text with the fonts they have installed. With [`Flags::CONVERT_TEXT_TO_PATHS`], the glyph
+    /// outlines are written as paths instead, which renders identically everywhere, but the text
+    /// can not be selected or searched anymore.
     #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
     pub struct Flags : u32 {
         const CONVERT_TEXT_TO_PATHS = sb::SkSVGCanvas_kConvertTextToPaths_Flag as _;
@@ -51,6 +58,7 @@ impl fmt::Debug for Canvas {
                 crate::Canvas::borrow_from_native(unsafe { &*self.canvas }),
             )
             .field("stream", &self.stream)
+            .field("font_families", &self.font_families)
             .finish()
     }
 }
@@ -68,7 +76,25 @@ impl Canvas {
                 flags.bits(),
             )
         };
-        Canvas { canvas, stream }
+        Canvas {
+            canvas,
+            stream,
+            font_families: HashMap::new(),
+        }
+    }
+
+    /// Writes `svg_family` instead of the family name `family` into the `font-family`
+    /// attributes of `<text>` elements, for example to add the names that browsers know a font
+    /// by, or generic fallbacks like `sans-serif`.
+    ///
+    /// Has no effect on text that is converted to paths.
+    pub fn register_font_family(
+        &mut self,
+        family: impl Into<String>,
+        svg_family: impl Into<String>,
+    ) -> &mut Self {
+        self.font_families.insert(family.into(), svg_family.into());
+        self
     }
 
     /// Ends the Canvas drawing and returns the resulting SVG.
@@ -81,8 +107,42 @@ impl Canvas {
             sb::C_SkCanvas_delete(self.canvas);
         }
         self.canvas = ptr::null_mut();
-        self.stream.detach_as_data()
+        let data = self.stream.detach_as_data();
+        if self.font_families.is_empty() {
+            return data;
+        }
+        let svg = String::from_utf8_lossy(data.as_bytes());
+        Data::new_copy(replace_font_families(&svg, &self.font_families).as_bytes())
+    }
+}
+
+fn replace_font_families(svg: &str, font_families: &HashMap<String, String>) -> String {
+    const ATTRIBUTE: &str = "font-family=\"";
+    let mut result = String::with_capacity(svg.len());
+    let mut rest = svg;
+    while let Some(start) = rest.find(ATTRIBUTE) {
+        let (before, after) = rest.split_at(start + ATTRIBUTE.len());
+        result.push_str(before);
+        let end = after.find('"').unwrap_or(after.len());
+        let families: Vec<_> = after[..end]
+            .split(", ")
+            .map(|family| match font_families.get(family) {
+                Some(svg_family) => escape_attribute(svg_family),
+                None => family.to_string(),
+            })
+            .collect();
+        result.push_str(&families.join(", "));
+        rest = &after[end..];
     }
+    result.push_str(rest);
+    result
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
 }
 
 #[cfg(test)]
@@ -104,6 +164,20 @@ mod tests {
         assert!(contents.contains(r#"</svg>"#));
     }
 
+    #[test]
+    fn replace_registered_font_families() {
+        use super::replace_font_families;
+        use std::collections::HashMap;
+
+        let font_families = HashMap::from([("Roboto".to_string(), "Roboto, \"Noto Sans\"".into())]);
+        let svg =
+            r#"<text font-family="Roboto, Arial">a</text><text font-family="Roboto">b</text>"#;
+        assert_eq!(
+            replace_font_families(svg, &font_families),
+            r#"<text font-family="Roboto, &quot;Noto Sans&quot;, Arial">a</text><text font-family="Roboto, &quot;Noto Sans&quot;">b</text>"#
+        );
+    }
+
     #[test]
     fn test_svg_without_ending() {
         use crate::Paint;
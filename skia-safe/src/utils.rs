@@ -1,12 +1,18 @@
 mod camera;
 mod custom_typeface;
+mod font_fallback;
+pub mod glyph_image;
 mod null_canvas;
 mod ordered_font_mgr;
 pub mod parse_path;
+pub mod path_distance;
+pub mod path_editing;
 pub mod shadow_utils;
+pub mod simplify_path;
 pub mod text_utils;
 
 pub use camera::*;
 pub use custom_typeface::*;
+pub use font_fallback::*;
 pub use null_canvas::*;
 pub use ordered_font_mgr::*;
@@ -5,6 +5,12 @@ use crate::{
 use skia_bindings::{self as sb, SkCustomTypefaceBuilder};
 use std::fmt;
 
+/// Builds a [`Typeface`] from glyphs that are defined by paths or drawables, for example icon fonts
+/// that are generated at runtime.
+///
+/// The typeface has no character map, so [`Typeface::unichar_to_glyph()`] returns `0` for all
+/// characters. Draw its glyphs with glyph ids, for example with [`crate::TextEncoding::GlyphId`]
+/// or with the glyph buffers of [`crate::TextBlobBuilder`].
 pub type CustomTypefaceBuilder = Handle<SkCustomTypefaceBuilder>;
 unsafe_send_sync!(CustomTypefaceBuilder);
 
@@ -25,6 +31,8 @@ impl CustomTypefaceBuilder {
         Self::from_native_c(unsafe { SkCustomTypefaceBuilder::new() })
     }
 
+    /// Defines the glyph `glyph_id`. Paths, drawables, and `advance` are specified for a font of
+    /// size `1`, with the origin on the baseline.
     pub fn set_glyph<'a>(
         &mut self,
         glyph_id: GlyphId,
@@ -64,6 +72,7 @@ impl CustomTypefaceBuilder {
         self
     }
 
+    /// Creates the typeface and resets the builder.
     pub fn detach(&mut self) -> Option<Typeface> {
         Typeface::from_ptr(unsafe { sb::C_SkCustomTypefaceBuilder_detach(self.native_mut()) })
     }
@@ -98,12 +107,44 @@ impl From<(Drawable, Rect)> for TypefaceGlyph<'_> {
     }
 }
 
-#[test]
-fn build_custom_typeface() {
-    let mut builder = CustomTypefaceBuilder::new();
-    let path = Path::new();
-    builder.set_glyph(10u16, 0.0, &path);
-    builder.set_glyph(11u16, 0.0, &path);
-    let typeface = builder.detach().unwrap();
-    assert_eq!(typeface.native().ref_counted_base()._ref_cnt(), 1);
+#[cfg(test)]
+mod tests {
+    use super::CustomTypefaceBuilder;
+    use crate::{prelude::*, Color, Font, Paint, Path, Rect, Surface, TextBlob, TextEncoding};
+
+    #[test]
+    fn build_custom_typeface() {
+        let mut builder = CustomTypefaceBuilder::new();
+        let path = Path::new();
+        builder.set_glyph(10u16, 0.0, &path);
+        builder.set_glyph(11u16, 0.0, &path);
+        let typeface = builder.detach().unwrap();
+        assert_eq!(typeface.native().ref_counted_base()._ref_cnt(), 1);
+    }
+
+    #[test]
+    fn draw_glyphs_of_custom_typeface() {
+        let mut builder = CustomTypefaceBuilder::new();
+        // A square that fills the em box above the baseline.
+        let square = Path::rect(Rect::new(0.0, -1.0, 1.0, 0.0), None);
+        builder.set_glyph(1u16, 1.0, &square);
+        let typeface = builder.detach().unwrap();
+        assert_eq!(typeface.unichar_to_glyph('a' as _), 0);
+
+        let font = Font::new(typeface, 10.0);
+        let glyphs: Vec<u8> = [1u16, 1].iter().flat_map(|g| g.to_ne_bytes()).collect();
+        let blob = TextBlob::from_text(&glyphs, TextEncoding::GlyphId, &font).unwrap();
+
+        let mut surface = Surface::new_raster_n32_premul((40, 20)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+        let mut paint = Paint::default();
+        paint.set_color(Color::BLACK);
+        surface.canvas().draw_text_blob(&blob, (10, 15), &paint);
+
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((15, 10)), Color::BLACK);
+        assert_eq!(pixmap.get_color((25, 10)), Color::BLACK);
+        assert_eq!(pixmap.get_color((35, 10)), Color::WHITE);
+        assert_eq!(pixmap.get_color((15, 2)), Color::WHITE);
+    }
 }
@@ -0,0 +1,221 @@
+use crate::{prelude::*, FontMgr, FontStyle, Typeface, Unichar};
+use skia_bindings as sb;
+
+/// Chooses the typeface for characters the requested font family does not cover.
+///
+/// Used by [`FontMgr::new_with_fallback()`]. Font managers are shared between threads, so the
+/// fallback must be [`Send`] and [`Sync`].
+pub trait FontFallback: Send + Sync + 'static {
+    /// Returns a typeface that can render `character`, or `None` to let the wrapped font manager
+    /// decide.
+    ///
+    /// `bcp_47` contains the language tags of the text, the last one being the most significant.
+    fn match_family_style_character(
+        &self,
+        family_name: Option<&str>,
+        style: FontStyle,
+        bcp_47: &[&str],
+        character: Unichar,
+    ) -> Option<Typeface>;
+}
+
+impl<F> FontFallback for F
+where
+    F: Fn(Option<&str>, FontStyle, &[&str], Unichar) -> Option<Typeface> + Send + Sync + 'static,
+{
+    fn match_family_style_character(
+        &self,
+        family_name: Option<&str>,
+        style: FontStyle,
+        bcp_47: &[&str],
+        character: Unichar,
+    ) -> Option<Typeface> {
+        self(family_name, style, bcp_47, character)
+    }
+}
+
+/// An ordered list of fallback typefaces per language.
+///
+/// For each character, the typefaces registered for the most significant matching language tag
+/// are tried first, then the ones registered for other matching tags, and then the ones added with
+/// [`FallbackChain::add()`]. The first typeface that has a glyph for the character wins.
+#[derive(Clone, Default, Debug)]
+pub struct FallbackChain {
+    languages: Vec<(String, Vec<Typeface>)>,
+    any_language: Vec<Typeface>,
+}
+
+impl FallbackChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds typefaces for text whose language tag is or starts with `language`, for example `ja`
+    /// or `zh-Hant`. Tags are compared case-insensitively.
+    pub fn add_for_language(
+        &mut self,
+        language: impl Into<String>,
+        typefaces: impl IntoIterator<Item = Typeface>,
+    ) -> &mut Self {
+        let language = language.into();
+        let typefaces = typefaces.into_iter();
+        match self
+            .languages
+            .iter_mut()
+            .find(|(l, _)| l.eq_ignore_ascii_case(&language))
+        {
+            Some((_, existing)) => existing.extend(typefaces),
+            None => self.languages.push((language, typefaces.collect())),
+        }
+        self
+    }
+
+    /// Adds typefaces that are tried for all languages after the language specific ones.
+    pub fn add(&mut self, typefaces: impl IntoIterator<Item = Typeface>) -> &mut Self {
+        self.any_language.extend(typefaces);
+        self
+    }
+
+    /// Returns the typefaces in the order they are tried for text with the language tags
+    /// `bcp_47`.
+    pub fn typefaces<'a>(&'a self, bcp_47: &'a [&'a str]) -> impl Iterator<Item = &'a Typeface> {
+        bcp_47
+            .iter()
+            .rev()
+            .flat_map(move |tag| {
+                self.languages
+                    .iter()
+                    .filter(move |(language, _)| language_matches(language, tag))
+                    .flat_map(|(_, typefaces)| typefaces)
+            })
+            .chain(&self.any_language)
+    }
+}
+
+impl FontFallback for FallbackChain {
+    fn match_family_style_character(
+        &self,
+        _family_name: Option<&str>,
+        _style: FontStyle,
+        bcp_47: &[&str],
+        character: Unichar,
+    ) -> Option<Typeface> {
+        self.typefaces(bcp_47)
+            .find(|typeface| typeface.unichar_to_glyph(character) != 0)
+            .cloned()
+    }
+}
+
+/// Returns `true` if `tag` is `language` or a more specific tag of `language`.
+fn language_matches(language: &str, tag: &str) -> bool {
+    tag.len() >= language.len()
+        && tag.is_char_boundary(language.len())
+        && tag[..language.len()].eq_ignore_ascii_case(language)
+        && matches!(tag.as_bytes().get(language.len()), None | Some(b'-'))
+}
+
+impl FontMgr {
+    /// Creates a font manager that asks `fallback` for typefaces of characters the requested
+    /// family does not support, and otherwise behaves like `base`.
+    ///
+    /// This customizes the fallback of text layout, too, when the font manager is set with
+    /// `FontCollection::set_default_font_manager()`.
+    pub fn new_with_fallback(base: impl Into<FontMgr>, fallback: impl FontFallback) -> FontMgr {
+        let param = rust_font_fallback::new_param(Box::new(fallback));
+        FontMgr::from_ptr(unsafe { sb::C_RustFontFallback_new(base.into().into_ptr(), &param) })
+            .unwrap()
+    }
+}
+
+mod rust_font_fallback {
+    use super::FontFallback;
+    use crate::{prelude::*, FontStyle};
+    use skia_bindings::{RustFontFallback_Param, SkFontStyle, SkTypeface, SkUnichar, TraitObject};
+    use std::{
+        ffi::CStr,
+        mem,
+        os::raw::c_char,
+        panic::{self, AssertUnwindSafe},
+    };
+
+    pub fn new_param(fallback: Box<dyn FontFallback>) -> RustFontFallback_Param {
+        RustFontFallback_Param {
+            trait_: unsafe { mem::transmute(Box::into_raw(fallback)) },
+            matchFamilyStyleCharacter: Some(match_family_style_character),
+            drop: Some(drop),
+        }
+    }
+
+    unsafe extern "C" fn match_family_style_character(
+        to: TraitObject,
+        family_name: *const c_char,
+        style: *const SkFontStyle,
+        bcp_47: *mut *const c_char,
+        bcp_47_count: i32,
+        character: SkUnichar,
+    ) -> *mut SkTypeface {
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            let family_name = family_name
+                .as_ref()
+                .and_then(|name| CStr::from_ptr(name).to_str().ok());
+            let style = *FontStyle::from_native_ref(&*style);
+            let bcp_47_count = usize::try_from(bcp_47_count).unwrap_or(0);
+            let bcp_47: Vec<&str> = safer::from_raw_parts(bcp_47, bcp_47_count)
+                .iter()
+                .filter_map(|tag| CStr::from_ptr(*tag).to_str().ok())
+                .collect();
+            to_font_fallback(to).match_family_style_character(
+                family_name,
+                style,
+                &bcp_47,
+                character,
+            )
+        }))
+        .ok()
+        .flatten()
+        .into_ptr_or_null()
+    }
+
+    unsafe extern "C" fn drop(to: TraitObject) {
+        mem::drop(Box::from_raw(mem::transmute::<
+            TraitObject,
+            *mut dyn FontFallback,
+        >(to)))
+    }
+
+    unsafe fn to_font_fallback<'a>(to: TraitObject) -> &'a dyn FontFallback {
+        &*mem::transmute::<TraitObject, *const dyn FontFallback>(to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{language_matches, FallbackChain};
+    use crate::{FontMgr, FontStyle, Typeface};
+
+    #[test]
+    fn language_tags() {
+        assert!(language_matches("zh", "zh"));
+        assert!(language_matches("zh", "ZH-Hant"));
+        assert!(language_matches("zh-Hant", "zh-Hant-TW"));
+        assert!(!language_matches("zh-Hant", "zh-Hans"));
+        assert!(!language_matches("ja", "jav"));
+    }
+
+    #[test]
+    fn fallback_is_asked_first() {
+        let typeface = Typeface::default();
+        if typeface.unichar_to_glyph('a' as _) == 0 {
+            // No fonts available.
+            return;
+        }
+        let mut chain = FallbackChain::new();
+        chain.add_for_language("ja", [typeface.clone()]);
+        let font_mgr = FontMgr::new_with_fallback(FontMgr::default(), chain);
+
+        let matched = font_mgr
+            .match_family_style_character("", FontStyle::default(), &["en", "ja"], 'a' as _)
+            .unwrap();
+        assert_eq!(matched.unique_id(), typeface.unique_id());
+    }
+}
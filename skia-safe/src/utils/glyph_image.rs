@@ -0,0 +1,109 @@
+//! Rasterized glyphs for text renderers that cache glyphs in their own atlases.
+use crate::{
+    scalar, AlphaType, ColorType, Font, GlyphId, IPoint, IRect, Image, ImageInfo, Paint, Point,
+    Rect, RoundOut, Surface,
+};
+
+/// A glyph rasterized by [`rasterize_glyph()`].
+#[derive(Clone, Debug)]
+pub struct GlyphImage {
+    pub image: Image,
+    /// The position of the top left pixel of `image` relative to the glyph's origin on the
+    /// baseline.
+    pub offset: IPoint,
+    /// The horizontal advance of the glyph.
+    pub advance: scalar,
+}
+
+impl GlyphImage {
+    /// The area the image covers when the glyph is drawn at `origin`.
+    pub fn bounds(&self, origin: impl Into<IPoint>) -> IRect {
+        IRect::from_pt_size(origin.into() + self.offset, self.image.dimensions())
+    }
+}
+
+impl Font {
+    /// See [`rasterize_glyph()`].
+    pub fn rasterize_glyph(
+        &self,
+        glyph: GlyphId,
+        color_type: ColorType,
+        paint: Option<&Paint>,
+    ) -> Option<GlyphImage> {
+        rasterize_glyph(self, glyph, color_type, paint)
+    }
+}
+
+/// Rasterizes `glyph` with the size, hinting, and edging of `font` into an image that tightly
+/// covers the glyph.
+///
+/// With [`ColorType::Alpha8`] the image is the coverage mask of the glyph. With a color type that
+/// has color channels, outlines are filled with the color of `paint` and color glyphs, like emoji,
+/// keep their colors. `paint` defaults to opaque black.
+///
+/// Returns `None` if the glyph has no visible pixels, like a space, or `color_type` is not
+/// supported for raster surfaces.
+pub fn rasterize_glyph(
+    font: &Font,
+    glyph: GlyphId,
+    color_type: ColorType,
+    paint: Option<&Paint>,
+) -> Option<GlyphImage> {
+    let mut advances = [0.0];
+    let mut bounds = [Rect::default()];
+    font.get_widths_bounds(&[glyph], Some(&mut advances), Some(&mut bounds), paint);
+    if bounds[0].is_empty() {
+        return None;
+    }
+    // Anti-aliasing may touch the pixels next to the glyph's bounds.
+    let ibounds: IRect = bounds[0].round_out();
+    let ibounds = ibounds.with_outset((1, 1));
+
+    let info = ImageInfo::new(ibounds.size(), color_type, AlphaType::Premul, None);
+    let mut surface = Surface::new_raster(&info, None, None)?;
+    let default_paint = Paint::default();
+    surface.canvas().draw_glyphs_at(
+        &[glyph],
+        [Point::default()].as_slice(),
+        (-ibounds.left, -ibounds.top),
+        font,
+        paint.unwrap_or(&default_paint),
+    );
+
+    Some(GlyphImage {
+        image: surface.image_snapshot(),
+        offset: IPoint::new(ibounds.left, ibounds.top),
+        advance: advances[0],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ColorType, Font, Typeface};
+
+    #[test]
+    fn rasterize_glyph_mask() {
+        let font = Font::new(Typeface::default(), 32.0);
+        let glyph = font.unichar_to_glyph('H' as _);
+        if glyph == 0 {
+            // No fonts available.
+            return;
+        }
+
+        let glyph_image = font
+            .rasterize_glyph(glyph, ColorType::Alpha8, None)
+            .unwrap();
+        assert_eq!(glyph_image.image.color_type(), ColorType::Alpha8);
+        // The glyph is above the baseline and to the right of the origin.
+        assert!(glyph_image.offset.y < 0);
+        assert!(glyph_image.offset.y + glyph_image.image.height() <= 2);
+        assert!(glyph_image.advance > 0.0);
+
+        let pixmap = glyph_image.image.peek_pixels().unwrap();
+        let pixels = pixmap.bytes().unwrap();
+        assert!(pixels.iter().any(|&coverage| coverage == 0xff));
+
+        let space = font.unichar_to_glyph(' ' as _);
+        assert!(font.rasterize_glyph(space, ColorType::N32, None).is_none());
+    }
+}
@@ -0,0 +1,214 @@
+//! Nearest point queries against the outline of a path, for hit-testing strokes and snapping.
+use crate::{scalar, ContourMeasure, ContourMeasureIter, Path, Point, Vector};
+
+/// The number of intervals each contour is divided into before they are refined.
+const INITIAL_INTERVALS: usize = 16;
+
+/// Intervals shorter than this fraction of the contour's length are not subdivided further.
+const TOLERANCE: scalar = 1e-5;
+
+/// Number of golden-section iterations. Each iteration shrinks the search interval by ~0.618.
+const REFINE_ITERATIONS: usize = 32;
+
+/// The point on a path's outline that is closest to a query point.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ClosestPoint {
+    /// The point on the path.
+    pub point: Point,
+    /// The unit tangent of the path at `point`.
+    pub tangent: Vector,
+    /// The distance from the query point to `point`.
+    pub distance: scalar,
+    /// The index of the contour `point` lies on, in the order of [`ContourMeasureIter`].
+    pub contour: usize,
+    /// The position of `point` on the contour, from `0.0` at its start to `1.0` at its end,
+    /// proportional to the distance along the contour.
+    pub t: scalar,
+    /// The distance along the contour from its start to `point`. Pass it to
+    /// [`ContourMeasure::pos_tan()`] or [`ContourMeasure::segment()`] to continue from here.
+    pub offset: scalar,
+}
+
+impl Path {
+    /// See [`closest_point()`].
+    pub fn closest_point(&self, p: impl Into<Point>) -> Option<ClosestPoint> {
+        closest_point(self, p)
+    }
+
+    /// See [`distance_to_point()`].
+    pub fn distance_to_point(&self, p: impl Into<Point>) -> Option<scalar> {
+        distance_to_point(self, p)
+    }
+}
+
+/// Returns the point on the outline of `path` that is closest to `p`, or `None` if the path has
+/// no contours with a length.
+///
+/// The outline is measured with [`ContourMeasureIter`], so curves are approximated the same way
+/// they are by [`crate::PathMeasure`]. The path's fill type and whether `p` lies inside the path
+/// are ignored, use [`Path::contains()`] for that.
+///
+pub fn closest_point(path: &Path, p: impl Into<Point>) -> Option<ClosestPoint> {
+    let p = p.into();
+    ContourMeasureIter::new(path, false, None)
+        .enumerate()
+        .filter_map(|(index, contour)| closest_point_on_contour(&contour, index, p))
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))
+}
+
+/// Returns the distance from `p` to the outline of `path`, see [`closest_point()`].
+pub fn distance_to_point(path: &Path, p: impl Into<Point>) -> Option<scalar> {
+    closest_point(path, p).map(|c| c.distance)
+}
+
+fn closest_point_on_contour(
+    contour: &ContourMeasure,
+    index: usize,
+    p: Point,
+) -> Option<ClosestPoint> {
+    let length = contour.length();
+    let tolerance = length * TOLERANCE;
+    let distance_at = |offset: scalar| {
+        contour
+            .pos_tan(offset)
+            .map(|(pos, _)| Point::distance(pos, p))
+            .unwrap_or(scalar::INFINITY)
+    };
+
+    let step = length / INITIAL_INTERVALS as scalar;
+    let ends: Vec<(scalar, scalar)> = (0..=INITIAL_INTERVALS)
+        .map(|i| (i as scalar * step).min(length))
+        .map(|offset| (offset, distance_at(offset)))
+        .collect();
+    let (mut offset, mut distance) = ends
+        .iter()
+        .copied()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+    // Branch and bound: every point of an interval is at most half its length away from one of
+    // its ends, so intervals that can not contain a closer point than the closest one found so
+    // far are dropped, and the others are halved until they are shorter than the tolerance.
+    let mut intervals: Vec<_> = ends.windows(2).map(|w| (w[0], w[1])).collect();
+    while let Some(((a, da), (b, db))) = intervals.pop() {
+        let half = (b - a) / 2.0;
+        if half <= tolerance || da.min(db) - half >= distance - tolerance {
+            continue;
+        }
+        let m = a + half;
+        let dm = distance_at(m);
+        if dm < distance {
+            offset = m;
+            distance = dm;
+        }
+        // Continue with the closer half, so that the bound tightens early.
+        let (left, right) = (((a, da), (m, dm)), ((m, dm), (b, db)));
+        if da < db {
+            intervals.extend([right, left]);
+        } else {
+            intervals.extend([left, right]);
+        }
+    }
+
+    // The distance is unimodal close to the closest end found, so a golden-section search around
+    // it converges to the minimum.
+    let ratio = (5.0f32.sqrt() - 1.0) / 2.0;
+    let (mut a, mut b) = (
+        (offset - 2.0 * tolerance).max(0.0),
+        (offset + 2.0 * tolerance).min(length),
+    );
+    let mut c = b - ratio * (b - a);
+    let mut d = a + ratio * (b - a);
+    let (mut fc, mut fd) = (distance_at(c), distance_at(d));
+    for _ in 0..REFINE_ITERATIONS {
+        if fc < fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - ratio * (b - a);
+            fc = distance_at(c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + ratio * (b - a);
+            fd = distance_at(d);
+        }
+    }
+    let refined = (a + b) / 2.0;
+    let refined_distance = distance_at(refined);
+    if refined_distance < distance {
+        offset = refined;
+        distance = refined_distance;
+    }
+
+    let (point, tangent) = contour.pos_tan(offset)?;
+    Some(ClosestPoint {
+        point,
+        tangent,
+        distance,
+        contour: index,
+        t: offset / length,
+        offset,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Path, Point, Rect};
+
+    #[test]
+    fn closest_point_on_rect() {
+        let path = Path::rect(Rect::new(0.0, 0.0, 100.0, 50.0), None);
+
+        let closest = path.closest_point((40.0, -10.0)).unwrap();
+        assert!(Point::distance(closest.point, (40.0, 0.0).into()) < 0.01);
+        assert!((closest.distance - 10.0).abs() < 0.01);
+        assert!((closest.offset - 40.0).abs() < 0.01);
+        assert!((closest.t - 40.0 / 300.0).abs() < 0.001);
+        assert_eq!(closest.contour, 0);
+
+        // Inside points measure to the nearest edge.
+        let distance = path.distance_to_point((90.0, 25.0)).unwrap();
+        assert!((distance - 10.0).abs() < 0.01);
+
+        // Corners
+        let closest = path.closest_point((110.0, 60.0)).unwrap();
+        assert!(Point::distance(closest.point, (100.0, 50.0).into()) < 0.01);
+
+        assert!(Path::new().closest_point((0.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn closest_point_on_circle_and_second_contour() {
+        let mut path = Path::circle((0.0, 0.0), 50.0, None);
+        path.move_to((200.0, 0.0)).line_to((200.0, 100.0));
+
+        let closest = path.closest_point((30.0, 40.0)).unwrap();
+        assert!(closest.distance < 0.1);
+        assert_eq!(closest.contour, 0);
+
+        let closest = path.closest_point((0.0, 100.0)).unwrap();
+        assert!((closest.distance - 50.0).abs() < 0.1);
+        assert!(Point::distance(closest.point, (0.0, 50.0).into()) < 0.1);
+
+        let closest = path.closest_point((210.0, 120.0)).unwrap();
+        assert_eq!(closest.contour, 1);
+        assert!(Point::distance(closest.point, (200.0, 100.0).into()) < 0.01);
+        assert!((closest.offset - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn closest_point_on_a_small_feature_of_a_long_contour() {
+        let mut path = Path::new();
+        path.move_to((0.0, 0.0))
+            .line_to((49999.0, 0.0))
+            .line_to((50000.0, 5.0))
+            .line_to((50001.0, 0.0))
+            .line_to((100000.0, 0.0));
+
+        let closest = path.closest_point((50000.0, 6.0)).unwrap();
+        assert!((closest.distance - 1.0).abs() < 0.01);
+        assert!(Point::distance(closest.point, (50000.0, 5.0).into()) < 0.01);
+        assert!((closest.t - 0.5).abs() < 0.001);
+    }
+}
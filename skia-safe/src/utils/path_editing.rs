@@ -0,0 +1,276 @@
+//! An editable representation of a [`Path`] as contours of segments, for vector editors that
+//! move individual points or remove contours and then convert back to a [`Path`].
+use crate::{path::Verb, scalar, Path, PathFillType, Point};
+
+/// A [`Path`] decomposed into contours of segments that can be modified in place.
+///
+/// Points are indexed in the same order as by [`Path::get_point()`]: the start point of each
+/// contour, followed by the points of its segments.
+#[derive(Clone, PartialEq, Debug)]
+pub struct EditablePath {
+    pub fill_type: PathFillType,
+    pub contours: Vec<Contour>,
+}
+
+/// A contour that starts at `start` and continues with `segments`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Contour {
+    pub start: Point,
+    pub segments: Vec<Segment>,
+    pub closed: bool,
+}
+
+/// A segment of a [`Contour`]. It starts at the end point of the previous segment, or at the start
+/// point of the contour, and ends at its last point.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Segment {
+    Line([Point; 1]),
+    Quad([Point; 2]),
+    /// The control and end point, and the weight.
+    Conic([Point; 2], scalar),
+    Cubic([Point; 3]),
+}
+
+impl Segment {
+    pub fn verb(&self) -> Verb {
+        match self {
+            Segment::Line(_) => Verb::Line,
+            Segment::Quad(_) => Verb::Quad,
+            Segment::Conic(..) => Verb::Conic,
+            Segment::Cubic(_) => Verb::Cubic,
+        }
+    }
+
+    /// The control points and the end point of the segment.
+    pub fn points(&self) -> &[Point] {
+        match self {
+            Segment::Line(points) => points,
+            Segment::Quad(points) | Segment::Conic(points, _) => points,
+            Segment::Cubic(points) => points,
+        }
+    }
+
+    pub fn points_mut(&mut self) -> &mut [Point] {
+        match self {
+            Segment::Line(points) => points,
+            Segment::Quad(points) | Segment::Conic(points, _) => points,
+            Segment::Cubic(points) => points,
+        }
+    }
+
+    pub fn end(&self) -> Point {
+        *self.points().last().unwrap()
+    }
+}
+
+impl Contour {
+    pub fn new(start: impl Into<Point>) -> Self {
+        Self {
+            start: start.into(),
+            segments: Vec::new(),
+            closed: false,
+        }
+    }
+
+    /// The number of points, including the start point.
+    pub fn point_count(&self) -> usize {
+        1 + self
+            .segments
+            .iter()
+            .map(|s| s.points().len())
+            .sum::<usize>()
+    }
+
+    /// Returns the point at `index` in this contour, `0` being the start point.
+    pub fn point(&self, index: usize) -> Option<Point> {
+        self.points().nth(index)
+    }
+
+    /// Returns a mutable reference to the point at `index` in this contour, `0` being the start
+    /// point.
+    pub fn point_mut(&mut self, index: usize) -> Option<&mut Point> {
+        if index == 0 {
+            return Some(&mut self.start);
+        }
+        self.segments
+            .iter_mut()
+            .flat_map(|s| s.points_mut())
+            .nth(index - 1)
+    }
+
+    /// Returns an iterator over all points, starting with the start point.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        std::iter::once(self.start).chain(self.segments.iter().flat_map(|s| s.points()).copied())
+    }
+}
+
+impl From<&Path> for EditablePath {
+    fn from(path: &Path) -> Self {
+        Self::new(path)
+    }
+}
+
+impl From<&EditablePath> for Path {
+    fn from(path: &EditablePath) -> Self {
+        path.to_path()
+    }
+}
+
+impl EditablePath {
+    pub fn new(path: &Path) -> Self {
+        let mut contours: Vec<Contour> = Vec::new();
+        crate::path::visit_verbs(path, |verb, points, weight| {
+            let segment = match verb {
+                Verb::Move => {
+                    contours.push(Contour::new(points[0]));
+                    return;
+                }
+                Verb::Close => {
+                    if let Some(contour) = contours.last_mut() {
+                        contour.closed = true;
+                    }
+                    return;
+                }
+                Verb::Line => Segment::Line([points[0]]),
+                Verb::Quad => Segment::Quad([points[0], points[1]]),
+                Verb::Conic => Segment::Conic([points[0], points[1]], weight),
+                Verb::Cubic => Segment::Cubic([points[0], points[1], points[2]]),
+                Verb::Done => return,
+            };
+            if let Some(contour) = contours.last_mut() {
+                contour.segments.push(segment);
+            }
+        });
+        Self {
+            fill_type: path.fill_type(),
+            contours,
+        }
+    }
+
+    /// Creates a [`Path`] from the contours with a single call to [`Path::new_from()`].
+    pub fn to_path(&self) -> Path {
+        let mut points = Vec::with_capacity(self.point_count());
+        let mut verbs = Vec::new();
+        let mut weights = Vec::new();
+        for contour in &self.contours {
+            points.extend(contour.points());
+            verbs.push(Verb::Move as u8);
+            for segment in &contour.segments {
+                verbs.push(segment.verb() as u8);
+                if let Segment::Conic(_, weight) = segment {
+                    weights.push(*weight);
+                }
+            }
+            if contour.closed {
+                verbs.push(Verb::Close as u8);
+            }
+        }
+        Path::new_from(&points, &verbs, &weights, self.fill_type, None)
+    }
+
+    /// The number of points in all contours.
+    pub fn point_count(&self) -> usize {
+        self.contours.iter().map(|c| c.point_count()).sum()
+    }
+
+    /// Returns the point at `index`, see [`Path::get_point()`].
+    pub fn point(&self, index: usize) -> Option<Point> {
+        let (contour, index) = self.locate(index)?;
+        self.contours[contour].point(index)
+    }
+
+    /// Returns a mutable reference to the point at `index`, see [`Path::get_point()`].
+    pub fn point_mut(&mut self, index: usize) -> Option<&mut Point> {
+        let (contour, index) = self.locate(index)?;
+        self.contours[contour].point_mut(index)
+    }
+
+    /// Moves the point at `index` to `p`. Returns `false` if there is no such point.
+    pub fn set_point(&mut self, index: usize, p: impl Into<Point>) -> bool {
+        self.point_mut(index)
+            .map(|point| *point = p.into())
+            .is_some()
+    }
+
+    /// Removes and returns the contour at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove_contour(&mut self, index: usize) -> Contour {
+        self.contours.remove(index)
+    }
+
+    /// Returns the index of the contour and the index of the point in that contour.
+    fn locate(&self, mut index: usize) -> Option<(usize, usize)> {
+        for (i, contour) in self.contours.iter().enumerate() {
+            let count = contour.point_count();
+            if index < count {
+                return Some((i, index));
+            }
+            index -= count;
+        }
+        None
+    }
+}
+
+impl Path {
+    /// Returns an editable copy of the path, see [`EditablePath`].
+    pub fn to_editable(&self) -> EditablePath {
+        EditablePath::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EditablePath, Segment};
+    use crate::{Path, PathFillType, Point};
+
+    fn test_path() -> Path {
+        let mut path = Path::new();
+        path.set_fill_type(PathFillType::EvenOdd);
+        path.move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .conic_to((15.0, 5.0), (10.0, 10.0), 0.5)
+            .close()
+            .move_to((20.0, 20.0))
+            .cubic_to((30.0, 20.0), (30.0, 30.0), (20.0, 30.0));
+        path
+    }
+
+    #[test]
+    fn round_trip() {
+        let path = test_path();
+        let editable = path.to_editable();
+        assert_eq!(editable.contours.len(), 2);
+        assert!(editable.contours[0].closed);
+        assert!(!editable.contours[1].closed);
+        assert_eq!(
+            editable.contours[0].segments[1],
+            Segment::Conic([Point::new(15.0, 5.0), Point::new(10.0, 10.0)], 0.5)
+        );
+        assert_eq!(editable.point_count(), path.count_points());
+        assert_eq!(editable.to_path(), path);
+    }
+
+    #[test]
+    fn edit_points_and_contours() {
+        let path = test_path();
+        let mut editable = EditablePath::from(&path);
+        for i in 0..path.count_points() {
+            assert_eq!(editable.point(i), path.get_point(i));
+        }
+
+        assert!(editable.set_point(5, (25.0, 15.0)));
+        assert!(!editable.set_point(8, (0.0, 0.0)));
+        let edited = editable.to_path();
+        assert_eq!(edited.get_point(5), Some(Point::new(25.0, 15.0)));
+        assert_eq!(edited.count_verbs(), path.count_verbs());
+
+        let removed = editable.remove_contour(0);
+        assert_eq!(removed.start, Point::new(0.0, 0.0));
+        let edited = Path::from(&editable);
+        assert_eq!(edited.count_points(), 4);
+        assert_eq!(edited.get_point(1), Some(Point::new(25.0, 15.0)));
+    }
+}
@@ -0,0 +1,471 @@
+//! Reduces the number of verbs of paths with many short segments, like freehand input.
+use crate::{path, scalar, Path, Point, Vector};
+
+/// Turns that are sharper than this angle (in radians) are kept as corners.
+const CORNER_ANGLE: scalar = std::f32::consts::FRAC_PI_3;
+
+/// The maximum number of Newton-Raphson reparameterizations before a run is split.
+const MAX_REPARAMETERIZATIONS: usize = 4;
+
+impl Path {
+    /// See [`simplified_with_tolerance()`].
+    pub fn simplified_with_tolerance(&self, tolerance: scalar) -> Path {
+        simplified_with_tolerance(self, tolerance)
+    }
+}
+
+/// Returns a path with fewer points and verbs that deviates from `path` by at most about
+/// `tolerance`.
+///
+/// The contours are flattened to polylines. The Ramer–Douglas–Peucker algorithm finds the
+/// vertices that are needed to stay within `tolerance`: runs between them that are straight
+/// become lines, sharp turns are kept as corners, and the remaining runs are re-fitted with
+/// cubic Béziers (Schneider's algorithm).
+///
+/// Closed contours stay closed and the fill type is preserved.
+pub fn simplified_with_tolerance(path: &Path, tolerance: scalar) -> Path {
+    let mut result = Path::default();
+    result.set_fill_type(path.fill_type());
+    for contour in flatten(path, tolerance / 4.0) {
+        simplify_contour(contour, tolerance, &mut result);
+    }
+    result
+}
+
+struct Contour {
+    points: Vec<Point>,
+    closed: bool,
+}
+
+impl Contour {
+    fn push(&mut self, p: Point) {
+        if self.points.last() != Some(&p) {
+            self.points.push(p);
+        }
+    }
+
+    fn push_curve(&mut self, segments: usize, eval: impl Fn(scalar) -> Point) {
+        for i in 1..=segments {
+            self.push(eval(i as scalar / segments as scalar));
+        }
+    }
+}
+
+fn flatten(path: &Path, tolerance: scalar) -> Vec<Contour> {
+    let mut contours = Vec::new();
+    let mut current: Option<Contour> = None;
+    let mut iter = path::Iter::new(path, false);
+    while let Some((verb, p)) = iter.next() {
+        if verb == path::Verb::Move {
+            contours.extend(current.take());
+            current = Some(Contour {
+                points: vec![p[0]],
+                closed: false,
+            });
+            continue;
+        }
+        let contour = match current.as_mut() {
+            Some(contour) => contour,
+            None => continue,
+        };
+        match verb {
+            path::Verb::Line => contour.push(p[1]),
+            path::Verb::Quad => {
+                let segments = segment_count(&p, tolerance);
+                contour.push_curve(segments, |t| {
+                    let mt = 1.0 - t;
+                    p[0] * (mt * mt) + p[1] * (2.0 * t * mt) + p[2] * (t * t)
+                });
+            }
+            path::Verb::Conic => {
+                let w = iter.conic_weight().unwrap_or(1.0);
+                let segments = segment_count(&p, tolerance);
+                contour.push_curve(segments, |t| {
+                    let mt = 1.0 - t;
+                    let (b0, b1, b2) = (mt * mt, 2.0 * w * t * mt, t * t);
+                    (p[0] * b0 + p[1] * b1 + p[2] * b2) * (1.0 / (b0 + b1 + b2))
+                });
+            }
+            path::Verb::Cubic => {
+                let segments = segment_count(&p, tolerance);
+                let bezier = [p[0], p[1], p[2], p[3]];
+                contour.push_curve(segments, |t| eval_cubic(&bezier, t));
+            }
+            path::Verb::Close => {
+                contour.closed = true;
+                contours.extend(current.take());
+            }
+            path::Verb::Move | path::Verb::Done => {}
+        }
+    }
+    contours.extend(current);
+    contours
+}
+
+/// The number of line segments that approximate a curve with the control points `p` within
+/// `tolerance`, estimated from the second differences of the control points.
+fn segment_count(p: &[Point], tolerance: scalar) -> usize {
+    let curvature = p
+        .windows(3)
+        .map(|w| (w[0] - w[1] * 2.0 + w[2]).length())
+        .fold(0.0, scalar::max);
+    let segments = (curvature / tolerance.max(scalar::EPSILON)).sqrt().ceil();
+    (segments as usize).clamp(1, 100)
+}
+
+fn simplify_contour(contour: Contour, tolerance: scalar, out: &mut Path) {
+    let Contour { mut points, closed } = contour;
+    if closed && points.len() > 1 && points.last() == points.first() {
+        points.pop();
+    }
+    out.move_to(points[0]);
+    if closed && points.len() > 1 {
+        points.push(points[0]);
+    }
+
+    if points.len() > 1 {
+        let kept = ramer_douglas_peucker(&points, tolerance);
+        let corners = corners(&points, &kept);
+        // A run is straight if no vertex between its ends had to be kept.
+        let straight =
+            |start: usize, end: usize| kept.binary_search(&start).map(|i| kept[i + 1]) == Ok(end);
+        let seam = if closed {
+            seam_tangent(&points, &kept, &corners, &straight)
+        } else {
+            None
+        };
+        let last = points.len() - 1;
+        for run in corners.windows(2) {
+            let (start, end) = (run[0], run[1]);
+            if straight(start, end) {
+                out.line_to(points[end]);
+            } else {
+                let run = &points[start..=end];
+                let n = run.len();
+                let t1 = match seam {
+                    Some(tangent) if start == 0 => tangent,
+                    _ => normalized(run[1] - run[0]),
+                };
+                let t2 = match seam {
+                    Some(tangent) if end == last => -tangent,
+                    _ => normalized(run[n - 2] - run[n - 1]),
+                };
+                fit_cubic(run, t1, t2, tolerance, out);
+            }
+        }
+    }
+
+    if closed {
+        out.close();
+    }
+}
+
+/// Returns the sorted indices of the vertices that approximate `points` within `tolerance`.
+fn ramer_douglas_peucker(points: &[Point], tolerance: scalar) -> Vec<usize> {
+    let last = points.len() - 1;
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[last] = true;
+    let mut stack = vec![(0, last)];
+    while let Some((start, end)) = stack.pop() {
+        let mut max_distance = 0.0;
+        let mut index = start;
+        for (i, p) in points.iter().enumerate().take(end).skip(start + 1) {
+            let distance = distance_to_segment(*p, points[start], points[end]);
+            if distance > max_distance {
+                max_distance = distance;
+                index = i;
+            }
+        }
+        if max_distance > tolerance {
+            keep[index] = true;
+            stack.push((start, index));
+            stack.push((index, end));
+        }
+    }
+    (0..points.len()).filter(|i| keep[*i]).collect()
+}
+
+/// Returns the kept vertices at which the polyline turns sharply, including both ends.
+fn corners(points: &[Point], kept: &[usize]) -> Vec<usize> {
+    let mut corners = vec![kept[0]];
+    for w in kept.windows(3) {
+        let incoming = normalized(points[w[1]] - points[w[0]]);
+        let outgoing = normalized(points[w[2]] - points[w[1]]);
+        if dot(incoming, outgoing) < CORNER_ANGLE.cos() {
+            corners.push(w[1]);
+        }
+    }
+    corners.push(kept[kept.len() - 1]);
+    corners
+}
+
+/// Returns the tangent at the start point of a closed contour, if the contour does not turn
+/// sharply there. The runs that meet at the start point must both follow it, otherwise the
+/// simplified contour has a kink there.
+fn seam_tangent(
+    points: &[Point],
+    kept: &[usize],
+    corners: &[usize],
+    straight: impl Fn(usize, usize) -> bool,
+) -> Option<Vector> {
+    if kept.len() < 3 {
+        return None;
+    }
+    let last = points.len() - 1;
+    let incoming = normalized(points[last] - points[kept[kept.len() - 2]]);
+    let outgoing = normalized(points[kept[1]] - points[0]);
+    if dot(incoming, outgoing) < CORNER_ANGLE.cos() {
+        return None;
+    }
+    // A straight run can not bend towards the other run, so the other run follows it.
+    let tangent = if straight(corners[0], corners[1]) {
+        outgoing
+    } else if straight(corners[corners.len() - 2], last) {
+        incoming
+    } else {
+        normalized(points[1] - points[last - 1])
+    };
+    Some(tangent)
+}
+
+/// Fits cubic Béziers to `d` and appends them to `out`. `t1` is the tangent at the start, and
+/// `t2` the reversed tangent at the end.
+fn fit_cubic(d: &[Point], t1: Vector, t2: Vector, tolerance: scalar, out: &mut Path) {
+    let last = d.len() - 1;
+    if d.len() == 2 {
+        let distance = Point::distance(d[0], d[1]) / 3.0;
+        out.cubic_to(d[0] + t1 * distance, d[1] + t2 * distance, d[1]);
+        return;
+    }
+
+    let max_error = tolerance * tolerance;
+    let mut u = chord_length_parameterize(d);
+    let mut bezier = generate_bezier(d, &u, t1, t2);
+    let (mut error, mut split) = compute_max_error(d, &bezier, &u);
+    if error <= max_error {
+        out.cubic_to(bezier[1], bezier[2], bezier[3]);
+        return;
+    }
+
+    if error <= max_error * 4.0 {
+        for _ in 0..MAX_REPARAMETERIZATIONS {
+            u = reparameterize(d, &u, &bezier);
+            bezier = generate_bezier(d, &u, t1, t2);
+            (error, split) = compute_max_error(d, &bezier, &u);
+            if error <= max_error {
+                out.cubic_to(bezier[1], bezier[2], bezier[3]);
+                return;
+            }
+        }
+    }
+
+    let mut center = d[split - 1] - d[split + 1];
+    if !center.normalize() {
+        center = normalized(d[split - 1] - d[split]);
+    }
+    fit_cubic(&d[..=split], t1, center, tolerance, out);
+    fit_cubic(&d[split..=last], -center, t2, tolerance, out);
+}
+
+fn chord_length_parameterize(d: &[Point]) -> Vec<scalar> {
+    let mut u = Vec::with_capacity(d.len());
+    u.push(0.0);
+    for w in d.windows(2) {
+        let length = u[u.len() - 1];
+        u.push(length + Point::distance(w[0], w[1]));
+    }
+    let total = u[u.len() - 1];
+    if total > 0.0 {
+        u.iter_mut().for_each(|u| *u /= total);
+    }
+    u
+}
+
+/// Computes the control points of the Bézier that fits `d` at the parameters `u` with the least
+/// squared error.
+fn generate_bezier(d: &[Point], u: &[scalar], t1: Vector, t2: Vector) -> [Point; 4] {
+    let (first, last) = (d[0], d[d.len() - 1]);
+    let mut c = [[0.0; 2]; 2];
+    let mut x = [0.0; 2];
+    for (p, &t) in d.iter().zip(u) {
+        let mt = 1.0 - t;
+        let (b0, b1, b2, b3) = (mt * mt * mt, 3.0 * t * mt * mt, 3.0 * t * t * mt, t * t * t);
+        let a1 = t1 * b1;
+        let a2 = t2 * b2;
+        c[0][0] += dot(a1, a1);
+        c[0][1] += dot(a1, a2);
+        c[1][1] += dot(a2, a2);
+        let tmp = *p - (first * (b0 + b1) + last * (b2 + b3));
+        x[0] += dot(a1, tmp);
+        x[1] += dot(a2, tmp);
+    }
+    c[1][0] = c[0][1];
+
+    let det_c0_c1 = c[0][0] * c[1][1] - c[1][0] * c[0][1];
+    let det_c0_x = c[0][0] * x[1] - c[1][0] * x[0];
+    let det_x_c1 = x[0] * c[1][1] - x[1] * c[0][1];
+    let (alpha_l, alpha_r) = if det_c0_c1 == 0.0 {
+        (0.0, 0.0)
+    } else {
+        (det_x_c1 / det_c0_c1, det_c0_x / det_c0_c1)
+    };
+
+    // Fall back to a heuristic if the least squares solution is degenerate.
+    let segment_length = Point::distance(first, last);
+    let epsilon = 1.0e-6 * segment_length;
+    let (alpha_l, alpha_r) = if alpha_l < epsilon || alpha_r < epsilon {
+        (segment_length / 3.0, segment_length / 3.0)
+    } else {
+        (alpha_l, alpha_r)
+    };
+    [first, first + t1 * alpha_l, last + t2 * alpha_r, last]
+}
+
+/// Returns the maximum squared distance of `d` to the Bézier and the index of that point.
+fn compute_max_error(d: &[Point], bezier: &[Point; 4], u: &[scalar]) -> (scalar, usize) {
+    let mut max_error = 0.0;
+    let mut split = d.len() / 2;
+    let interior = d.iter().zip(u).enumerate().take(d.len() - 1).skip(1);
+    for (i, (p, &t)) in interior {
+        let v = eval_cubic(bezier, t) - *p;
+        let error = dot(v, v);
+        if error >= max_error {
+            max_error = error;
+            split = i;
+        }
+    }
+    (max_error, split)
+}
+
+/// Improves the parameters `u` with a Newton-Raphson step towards the closest points on the
+/// Bézier.
+fn reparameterize(d: &[Point], u: &[scalar], bezier: &[Point; 4]) -> Vec<scalar> {
+    let [p0, p1, p2, p3] = *bezier;
+    d.iter()
+        .zip(u)
+        .map(|(p, &t)| {
+            let mt = 1.0 - t;
+            let q = eval_cubic(bezier, t);
+            let q1 =
+                ((p1 - p0) * (mt * mt) + (p2 - p1) * (2.0 * t * mt) + (p3 - p2) * (t * t)) * 3.0;
+            let q2 = ((p2 - p1 * 2.0 + p0) * mt + (p3 - p2 * 2.0 + p1) * t) * 6.0;
+            let numerator = dot(q - *p, q1);
+            let denominator = dot(q1, q1) + dot(q - *p, q2);
+            if denominator == 0.0 {
+                t
+            } else {
+                (t - numerator / denominator).clamp(0.0, 1.0)
+            }
+        })
+        .collect()
+}
+
+fn eval_cubic(p: &[Point; 4], t: scalar) -> Point {
+    let mt = 1.0 - t;
+    p[0] * (mt * mt * mt)
+        + p[1] * (3.0 * t * mt * mt)
+        + p[2] * (3.0 * t * t * mt)
+        + p[3] * (t * t * t)
+}
+
+fn distance_to_segment(p: Point, a: Point, b: Point) -> scalar {
+    let ab = b - a;
+    let length_squared = dot(ab, ab);
+    let t = if length_squared == 0.0 {
+        0.0
+    } else {
+        (dot(p - a, ab) / length_squared).clamp(0.0, 1.0)
+    };
+    Point::distance(p, a + ab * t)
+}
+
+fn normalized(mut v: Vector) -> Vector {
+    v.normalize();
+    v
+}
+
+fn dot(a: Vector, b: Vector) -> scalar {
+    a.x * b.x + a.y * b.y
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Path, Point, Rect};
+
+    #[test]
+    fn straight_runs_become_lines() {
+        let mut path = Path::default();
+        path.move_to((0.0, 0.0));
+        for i in 1..=100 {
+            path.line_to((i as f32, 0.0));
+        }
+        for i in 1..=100 {
+            path.line_to((100.0, i as f32));
+        }
+
+        let simplified = path.simplified_with_tolerance(0.5);
+        let mut points = [Point::default(); 3];
+        assert_eq!(simplified.count_points(), 3);
+        simplified.get_points(&mut points);
+        assert_eq!(
+            points,
+            [
+                Point::new(0.0, 0.0),
+                Point::new(100.0, 0.0),
+                Point::new(100.0, 100.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn curves_are_refitted() {
+        let mut path = Path::default();
+        for i in 0..360 {
+            let angle = (i as f32).to_radians();
+            let p = (100.0 * angle.cos(), 100.0 * angle.sin());
+            if i == 0 {
+                path.move_to(p);
+            } else {
+                path.line_to(p);
+            }
+        }
+        path.close();
+
+        let simplified = path.simplified_with_tolerance(0.5);
+        assert!(simplified.count_verbs() < 20);
+        assert!(simplified.is_last_contour_closed());
+        let bounds = simplified.compute_tight_bounds();
+        let expected = Rect::new(-100.0, -100.0, 100.0, 100.0);
+        assert!((bounds.left - expected.left).abs() < 1.0);
+        assert!((bounds.top - expected.top).abs() < 1.0);
+        assert!((bounds.right - expected.right).abs() < 1.0);
+        assert!((bounds.bottom - expected.bottom).abs() < 1.0);
+    }
+
+    #[test]
+    fn closed_contours_are_smooth_at_the_start_point() {
+        let mut path = Path::default();
+        for i in 0..36 {
+            let angle = (i as f32 * 10.0).to_radians();
+            let p = (100.0 * angle.cos(), 100.0 * angle.sin());
+            if i == 0 {
+                path.move_to(p);
+            } else {
+                path.line_to(p);
+            }
+        }
+        path.close();
+
+        let simplified = path.simplified_with_tolerance(0.5);
+        let mut points = vec![Point::default(); simplified.count_points()];
+        simplified.get_points(&mut points);
+        let n = points.len();
+        let start = points[0];
+        assert_eq!(points[n - 1], start);
+        let mut outgoing = points[1] - start;
+        let mut incoming = start - points[n - 2];
+        assert!(outgoing.normalize() && incoming.normalize());
+        assert!(outgoing.x * incoming.x + outgoing.y * incoming.y > 0.9999);
+    }
+}
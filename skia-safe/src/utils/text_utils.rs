@@ -1,3 +1,5 @@
+//! Helpers to draw a single line of text and to convert it to a path without building a
+//! [`crate::TextBlob`].
 use crate::{prelude::*, Canvas, Font, Paint, Path, Point, TextEncoding};
 use core::borrow::BorrowMut;
 use skia_bindings::SkTextUtils;
@@ -5,6 +7,7 @@ use skia_bindings::SkTextUtils;
 pub use skia_bindings::SkTextUtils_Align as Align;
 variant_name!(Align::Center);
 
+/// Draws `text` so that it is aligned to `p` as specified by `align`. `p.y` is the baseline.
 pub fn draw_str(
     canvas: &mut Canvas,
     text: impl AsRef<str>,
@@ -13,14 +16,34 @@ pub fn draw_str(
     paint: &Paint,
     align: Align,
 ) {
-    let text = text.as_ref().as_bytes();
+    draw_text(
+        canvas,
+        text.as_ref().as_bytes(),
+        TextEncoding::UTF8,
+        p,
+        font,
+        paint,
+        align,
+    )
+}
+
+/// Draws `text` of the given `encoding`, see [`draw_str()`].
+pub fn draw_text(
+    canvas: &mut Canvas,
+    text: &[u8],
+    encoding: TextEncoding,
+    p: impl Into<Point>,
+    font: &Font,
+    paint: &Paint,
+    align: Align,
+) {
     let p = p.into();
     unsafe {
         SkTextUtils::Draw(
             canvas.native_mut(),
             text.as_ptr() as _,
             text.len(),
-            TextEncoding::UTF8.into_native(),
+            encoding.into_native(),
             p.x,
             p.y,
             font.native(),
@@ -44,15 +67,25 @@ impl Canvas {
     }
 }
 
+/// Returns the outlines of the glyphs of `text`, positioned with the baseline starting at `p`.
 pub fn get_path(text: impl AsRef<str>, p: impl Into<Point>, font: &Font) -> Path {
-    let text = text.as_ref().as_bytes();
+    get_text_path(text.as_ref().as_bytes(), TextEncoding::UTF8, p, font)
+}
+
+/// Returns the outlines of the glyphs of `text` of the given `encoding`, see [`get_path()`].
+pub fn get_text_path(
+    text: &[u8],
+    encoding: TextEncoding,
+    p: impl Into<Point>,
+    font: &Font,
+) -> Path {
     let p = p.into();
     let mut path = Path::default();
     unsafe {
         SkTextUtils::GetPath(
             text.as_ptr() as _,
             text.len(),
-            TextEncoding::UTF8.into_native(),
+            encoding.into_native(),
             p.x,
             p.y,
             font.native(),
@@ -67,3 +100,65 @@ impl Path {
         get_path(text, p, font)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{draw_str, get_path, get_text_path, Align};
+    use crate::{Color, Font, Paint, Surface, TextEncoding, Typeface};
+
+    #[test]
+    fn path_of_glyphs_and_str_are_equal() {
+        let font = Font::new(Typeface::default(), 24.0);
+        let text = "Skia";
+        let glyphs = font.str_to_glyphs_vec(text);
+        let glyph_bytes: Vec<u8> = glyphs.iter().flat_map(|g| g.to_ne_bytes()).collect();
+
+        let path = get_path(text, (10, 30), &font);
+        let glyph_path = get_text_path(&glyph_bytes, TextEncoding::GlyphId, (10, 30), &font);
+        assert_eq!(path, glyph_path);
+        if !path.is_empty() {
+            assert!(path.bounds().left >= 10.0);
+        }
+    }
+
+    /// Draws `text` aligned at x = 100 and returns the horizontal range of the columns that
+    /// contain ink, or `None` if nothing was drawn (no fonts available).
+    fn ink_columns(text: &str, font: &Font, align: Align) -> Option<(i32, i32)> {
+        let mut surface = Surface::new_raster_n32_premul((200, 40)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+        let mut paint = Paint::default();
+        paint.set_color(Color::BLACK);
+        draw_str(surface.canvas(), text, (100, 30), font, &paint, align);
+
+        let pixmap = surface.peek_pixels().unwrap();
+        let inked = |x: i32| (0..40).any(|y: i32| pixmap.get_color((x, y)) != Color::WHITE);
+        let left = (0..200).find(|&x| inked(x))?;
+        let right = (0..200).rev().find(|&x| inked(x))?;
+        Some((left, right))
+    }
+
+    #[test]
+    fn draw_aligned() {
+        let font = Font::new(Typeface::default(), 20.0);
+        let text = "Skia";
+        let (left, center, right) = match (
+            ink_columns(text, &font, Align::Left),
+            ink_columns(text, &font, Align::Center),
+            ink_columns(text, &font, Align::Right),
+        ) {
+            (Some(left), Some(center), Some(right)) => (left, center, right),
+            _ => return,
+        };
+        let (advance, _) = font.measure_str(text, None);
+
+        // The same glyphs are drawn in every case, only shifted by the alignment.
+        assert!(left.0 >= 99);
+        assert!(right.1 <= 101);
+        assert!(center.0 < 100 && center.1 > 100);
+        for (shifted, by) in [(center, advance / 2.0), (right, advance)] {
+            let by = by.round() as i32;
+            assert!((left.0 - by - shifted.0).abs() <= 2);
+            assert!((left.1 - by - shifted.1).abs() <= 2);
+        }
+    }
+}
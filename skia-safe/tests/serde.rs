@@ -0,0 +1,68 @@
+#![cfg(feature = "serde")]
+use skia_safe::{Color, Color4f, Matrix, Path, PathFillType, Point, RRect, Rect, Vector, M44};
+
+fn round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(value: &T) -> T {
+    serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+}
+
+#[test]
+fn geometry_and_colors() {
+    let rect = Rect::new(1.0, 2.0, 3.0, 4.0);
+    assert_eq!(
+        serde_json::to_string(&rect).unwrap(),
+        r#"{"left":1.0,"top":2.0,"right":3.0,"bottom":4.0}"#
+    );
+    assert_eq!(round_trip(&rect), rect);
+    assert_eq!(round_trip(&Point::new(1.5, -2.0)), Point::new(1.5, -2.0));
+    assert_eq!(round_trip(&Color::CYAN), Color::CYAN);
+    let color = Color4f::new(0.25, 0.5, 0.75, 1.0);
+    assert_eq!(round_trip(&color), color);
+
+    let matrix = Matrix::new_all(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 0.0, 0.0, 1.0);
+    assert_eq!(
+        serde_json::to_string(&matrix).unwrap(),
+        "[1.0,2.0,3.0,4.0,5.0,6.0,0.0,0.0,1.0]"
+    );
+    assert_eq!(round_trip(&matrix), matrix);
+    let m44 = M44::translate(1.0, 2.0, 3.0);
+    assert_eq!(round_trip(&m44), m44);
+
+    let radii = [
+        Vector::new(1.0, 2.0),
+        Vector::new(3.0, 4.0),
+        Vector::new(5.0, 6.0),
+        Vector::new(7.0, 8.0),
+    ];
+    let rrect = RRect::new_rect_radii(Rect::new(0.0, 0.0, 100.0, 100.0), &radii);
+    assert_eq!(round_trip(&rrect), rrect);
+}
+
+#[test]
+fn path_verbs() {
+    let mut path = Path::new();
+    path.set_fill_type(PathFillType::EvenOdd);
+    path.move_to((0.0, 0.0))
+        .line_to((10.0, 0.0))
+        .quad_to((15.0, 5.0), (10.0, 10.0))
+        .conic_to((5.0, 15.0), (0.0, 10.0), 0.5)
+        .close()
+        .move_to((20.0, 20.0))
+        .cubic_to((30.0, 20.0), (30.0, 30.0), (20.0, 30.0));
+
+    let json = serde_json::to_string(&path).unwrap();
+    assert_eq!(
+        json,
+        concat!(
+            r#"{"fill_type":"EvenOdd","verbs":[{"Move":{"x":0.0,"y":0.0}},"#,
+            r#"{"Line":{"x":10.0,"y":0.0}},"#,
+            r#"{"Quad":[{"x":15.0,"y":5.0},{"x":10.0,"y":10.0}]},"#,
+            r#"{"Conic":[{"x":5.0,"y":15.0},{"x":0.0,"y":10.0},0.5]},"Close","#,
+            r#"{"Move":{"x":20.0,"y":20.0}},"#,
+            r#"{"Cubic":[{"x":30.0,"y":20.0},{"x":30.0,"y":30.0},{"x":20.0,"y":30.0}]}]}"#
+        )
+    );
+    let restored: Path = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, path);
+
+    assert!(serde_json::from_str::<Path>(r#"{"fill_type":"Nonzero","verbs":[]}"#).is_err());
+}
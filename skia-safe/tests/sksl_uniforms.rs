@@ -0,0 +1,122 @@
+#![cfg(feature = "derive")]
+use skia_safe::{
+    runtime_effect::{uniform, UniformError, UniformField},
+    Color4f, RuntimeEffect, RuntimeEffectBuilder, SkslUniforms,
+};
+
+#[derive(SkslUniforms)]
+#[repr(C)]
+struct Uniforms {
+    offset: [f32; 2],
+    #[sksl(array)]
+    count: [i32; 2],
+    transform: [[f32; 3]; 3],
+    #[sksl(name = "tint")]
+    color: Color4f,
+}
+
+const SKSL: &str = r#"
+    layout(color) uniform half4 tint;
+    uniform float2 offset;
+    uniform int count[2];
+    uniform float3x3 transform;
+
+    half4 main(float2 p) {
+        return tint;
+    }
+"#;
+
+#[test]
+fn derived_fields() {
+    assert_eq!(
+        Uniforms::FIELDS,
+        &[
+            UniformField {
+                name: "offset",
+                ty: uniform::Type::Float2,
+                count: 1,
+                offset: 0
+            },
+            UniformField {
+                name: "count",
+                ty: uniform::Type::Int,
+                count: 2,
+                offset: 8
+            },
+            UniformField {
+                name: "transform",
+                ty: uniform::Type::Float3x3,
+                count: 1,
+                offset: 16
+            },
+            UniformField {
+                name: "tint",
+                ty: uniform::Type::Float4,
+                count: 1,
+                offset: 52
+            },
+        ]
+    );
+}
+
+#[test]
+fn set_derived_uniforms() {
+    let uniforms = Uniforms {
+        offset: [1.0, 2.0],
+        count: [3, 4],
+        transform: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [5.0, 6.0, 1.0]],
+        color: Color4f::new(0.0, 0.0, 1.0, 1.0),
+    };
+
+    let effect = RuntimeEffect::make_for_shader(SKSL, None).unwrap();
+    let mut builder = RuntimeEffectBuilder::new(effect);
+    builder.set_uniforms(&uniforms).unwrap();
+
+    let effect = builder.effect();
+    let bytes = builder.uniforms();
+    let bytes = bytes.as_bytes();
+    let offset = effect.find_uniform("count").unwrap().offset();
+    assert_eq!(&bytes[offset..offset + 4], 3i32.to_ne_bytes());
+    let offset = effect.find_uniform("transform").unwrap().offset();
+    assert_eq!(&bytes[offset + 24..offset + 28], 5.0f32.to_ne_bytes());
+}
+
+#[test]
+fn reject_mismatched_uniforms() {
+    #[derive(SkslUniforms)]
+    #[repr(C)]
+    struct Mismatched {
+        offset: [f32; 3],
+    }
+
+    let effect = RuntimeEffect::make_for_shader(SKSL, None).unwrap();
+    let mut builder = RuntimeEffectBuilder::new(effect);
+    assert!(matches!(
+        builder.set_uniforms(&Mismatched { offset: [0.0; 3] }),
+        Err(UniformError::TypeMismatch { .. })
+    ));
+}
+
+mod reexported {
+    pub use skia_safe as skia;
+}
+
+#[test]
+fn derive_with_crate_path() {
+    #[derive(SkslUniforms)]
+    #[sksl(crate = "reexported::skia")]
+    #[repr(C)]
+    struct Offset {
+        offset: [f32; 2],
+    }
+
+    assert_eq!(
+        Offset::FIELDS,
+        &[UniformField {
+            name: "offset",
+            ty: uniform::Type::Float2,
+            count: 1,
+            offset: 0
+        }]
+    );
+}